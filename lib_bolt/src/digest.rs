@@ -0,0 +1,189 @@
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DigestCredentials {
+    username: String,
+    password: String,
+}
+
+// the parts of a `WWW-Authenticate: Digest ...` challenge the response is built from
+pub struct Challenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: String,
+    qop: Option<String>,
+}
+
+// the Digest challenge among the response's WWW-Authenticate headers, if the server sent one
+pub fn find_challenge(headers: &[Vec<String>]) -> Option<Challenge> {
+    headers
+        .iter()
+        .filter(|header| header[0].eq_ignore_ascii_case("www-authenticate"))
+        .find_map(|header| parse_challenge(&header[1]))
+}
+
+fn parse_challenge(value: &str) -> Option<Challenge> {
+    let value = value.trim();
+
+    // a server can offer several schemes in one header, e.g. "Negotiate, Digest realm=..."
+    let start = value.to_lowercase().find("digest ")?;
+    let params = parse_params(&value[start + "digest ".len()..]);
+
+    let qop = params.get("qop").and_then(|qop| {
+        let offered: Vec<&str> = qop.split(',').map(str::trim).collect();
+
+        // plain auth is enough and doesn't need the body hashed
+        if offered.contains(&"auth") {
+            Some("auth".to_string())
+        } else if offered.contains(&"auth-int") {
+            Some("auth-int".to_string())
+        } else {
+            None
+        }
+    });
+
+    Some(Challenge {
+        realm: params.get("realm").cloned().unwrap_or_default(),
+        nonce: params.get("nonce").cloned()?,
+        opaque: params.get("opaque").cloned(),
+        algorithm: params.get("algorithm").cloned().unwrap_or_else(|| "MD5".to_string()),
+        qop,
+    })
+}
+
+// key=value pairs, values optionally quoted and free to contain commas inside the quotes
+fn parse_params(text: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while matches!(chars.peek(), Some(c) if *c == ',' || c.is_whitespace()) {
+            chars.next();
+        }
+
+        let key: String = chars.by_ref().take_while(|c| *c != '=').collect();
+
+        if key.trim().is_empty() {
+            break;
+        }
+
+        let mut value = String::new();
+
+        if chars.peek() == Some(&'"') {
+            chars.next();
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value = chars.by_ref().take_while(|c| *c != ',').collect();
+        }
+
+        params.insert(key.trim().to_lowercase(), value.trim().to_string());
+    }
+
+    params
+}
+
+fn hash(algorithm: &str, data: impl AsRef<[u8]>) -> String {
+    let bytes = if algorithm.to_uppercase().starts_with("SHA-256") {
+        Sha256::digest(data).to_vec()
+    } else {
+        Md5::digest(data).to_vec()
+    };
+
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// the Authorization header answering the challenge, RFC 7616 with RFC 2069 as the fallback when no qop is offered
+pub fn authorization(
+    challenge: &Challenge,
+    credentials: &DigestCredentials,
+    method: &str,
+    url: &str,
+    body: &[u8],
+    cnonce: &str,
+) -> Result<String, String> {
+    let algorithm = challenge.algorithm.to_uppercase();
+
+    if !matches!(algorithm.as_str(), "MD5" | "MD5-SESS" | "SHA-256" | "SHA-256-SESS") {
+        return Err(format!("the server asked for {} digest auth, which bolt can't answer", challenge.algorithm));
+    }
+
+    let url = reqwest::Url::parse(url).map_err(|err| format!("invalid url {}: {}", url, err))?;
+    let uri = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+
+    // every challenge gets a fresh nonce from the server, so this is always its first use
+    let nc = "00000001";
+
+    let mut ha1 = hash(
+        &algorithm,
+        format!("{}:{}:{}", credentials.username, challenge.realm, credentials.password),
+    );
+
+    if algorithm.ends_with("-SESS") {
+        ha1 = hash(&algorithm, format!("{}:{}:{}", ha1, challenge.nonce, cnonce));
+    }
+
+    let ha2 = match challenge.qop.as_deref() {
+        Some("auth-int") => {
+            let body_hash = hash(&algorithm, body);
+            hash(&algorithm, format!("{}:{}:{}", method, uri, body_hash))
+        }
+        _ => hash(&algorithm, format!("{}:{}", method, uri)),
+    };
+
+    let response = match &challenge.qop {
+        Some(qop) => hash(
+            &algorithm,
+            format!("{}:{}:{}:{}:{}:{}", ha1, challenge.nonce, nc, cnonce, qop, ha2),
+        ),
+        None => hash(&algorithm, format!("{}:{}:{}", ha1, challenge.nonce, ha2)),
+    };
+
+    let mut header = format!(
+        r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", algorithm={}, response="{}""#,
+        quote(&credentials.username),
+        quote(&challenge.realm),
+        quote(&challenge.nonce),
+        quote(&uri),
+        challenge.algorithm,
+        response
+    );
+
+    if let Some(qop) = &challenge.qop {
+        header.push_str(&format!(r#", qop={}, nc={}, cnonce="{}""#, qop, nc, cnonce));
+    }
+
+    if let Some(opaque) = &challenge.opaque {
+        header.push_str(&format!(r#", opaque="{}""#, quote(opaque)));
+    }
+
+    Ok(header)
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// not a secret, only has to differ between requests
+pub fn new_cnonce() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+
+    hash("MD5", nanos.to_string())[..16].to_string()
+}
@@ -1,6 +1,9 @@
+mod digest;
+mod tls;
 mod utils;
 use actix_web::{body, http, web, App, HttpRequest, HttpResponse, HttpServer};
 use serde::{Deserialize, Serialize};
+use digest::DigestCredentials;
 use utils::*;
 
 #[derive(Serialize, Deserialize)]
@@ -9,6 +12,9 @@ struct Ping {
 }
 
 static VERSION: &str = "0.11.8";
+
+// upper bound on per-request retries, whatever the frontend sends
+static MAX_RETRIES: u8 = 10;
 static HELP: &str = r#"
 Bolt CLI (Build and test APIs)
 
@@ -22,7 +28,7 @@ Options:
   --reset        Reset dist
     "#;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum Method {
     GET,
     POST,
@@ -32,6 +38,15 @@ pub enum Method {
     PATCH,
     OPTIONS,
     CONNECT,
+    COPY,
+    // anything else the user typed, PURGE, PROPFIND and the like
+    Custom(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HttpVersion {
+    Http1,
+    Http2,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -40,16 +55,50 @@ pub enum ResponseType {
     JSON,
 }
 
+// why a request got no response, so the frontend can say what to check
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    DnsResolution,
+    ConnectionRefused,
+    ConnectionTimeout,
+    TlsHandshake,
+    ProtocolError,
+    Cancelled,
+    Other,
+}
+
 #[derive(Clone, Serialize)]
-struct Response {
+pub struct Response {
     status: u16,
     body: String,
     headers: Vec<Vec<String>>,
+    // same as time_total, kept for frontends that only know this one
     time: u32,
+    // until the status line and headers arrived, then until the last byte of the body
+    time_to_first_byte: u32,
+    time_total: u32,
     size: u64,
     response_type: ResponseType,
     request_index: usize,
     failed: bool,
+    attempts: u8,
+    // what actually went out, after the client's defaults were added
+    sent_method: String,
+    sent_url: String,
+    sent_headers: Vec<Vec<String>>,
+    sent_body: String,
+    // the Content-Encoding the body arrived with and its size before decoding, size is after
+    content_encoding: String,
+    encoded_size: u64,
+    // the decoded body still starts like a gzip stream
+    double_compressed: bool,
+    // both messages in wire format, the response body as it arrived before decoding
+    raw_request: String,
+    raw_response: String,
+    // the size limit the body was cut off at, None when all of it was read
+    truncated_at: Option<u64>,
+    // set on failures that happened on the way to or from the server
+    error_kind: Option<ErrorKind>,
 }
 
 impl Response {
@@ -59,14 +108,44 @@ impl Response {
             body: String::new(),
             headers: Vec::new(),
             time: 0,
+            time_to_first_byte: 0,
+            time_total: 0,
             size: 0,
             response_type: ResponseType::TEXT,
             request_index: 0,
             failed: false,
+            attempts: 1,
+            sent_method: String::new(),
+            sent_url: String::new(),
+            sent_headers: Vec::new(),
+            sent_body: String::new(),
+            content_encoding: String::new(),
+            encoded_size: 0,
+            double_compressed: false,
+            raw_request: String::new(),
+            raw_response: String::new(),
+            truncated_at: None,
+            error_kind: None,
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TlsConfig {
+    insecure_skip_verify: bool,
+    ca_cert_path: Option<String>,
+    // (certificate, private key), both PEM files
+    client_cert: Option<(String, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryPolicy {
+    #[default]
+    NetworkErrors,
+    NetworkAndServerErrors,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Request {
     url: String,
@@ -74,6 +153,49 @@ pub struct Request {
     body: String,
     headers: Vec<Vec<String>>,
     request_index: usize,
+    // sent instead of body, read here so the frontend never holds the bytes
+    body_file: Option<String>,
+    // answers a 401 Digest challenge with one more request
+    digest_auth: Option<DigestCredentials>,
+    options: ExecOptions,
+}
+
+// how a request is carried out rather than what it says. every caller of execute hands these
+// over in one piece so redirects, timeouts, tls and retries can't differ between them
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ExecOptions {
+    proxy: String,
+    no_proxy: Vec<String>,
+    tls: TlsConfig,
+    retries: u8,
+    retry_on: RetryPolicy,
+    // 0 waits for as long as the server takes
+    timeout_ms: u64,
+    follow_redirects: bool,
+    // empty keeps reqwest's default, a User-Agent header on the request still wins
+    user_agent: String,
+    // bytes of body read before giving up on the rest, 0 reads everything
+    max_response_size: u64,
+    // None lets the client negotiate
+    http_version: Option<HttpVersion>,
+}
+
+impl Default for ExecOptions {
+    fn default() -> Self {
+        ExecOptions {
+            proxy: String::new(),
+            no_proxy: Vec::new(),
+            tls: TlsConfig::default(),
+            retries: 0,
+            retry_on: RetryPolicy::default(),
+            timeout_ms: 0,
+            follow_redirects: true,
+            user_agent: String::new(),
+            max_response_size: 0,
+            http_version: None,
+        }
+    }
 }
 
 #[actix_web::get("/ping")]
@@ -109,6 +231,15 @@ pub async fn send_request(_req: HttpRequest, body: String) -> HttpResponse {
         body: String,
         headers: Vec<Vec<String>>,
         index: usize,
+
+        #[serde(default)]
+        body_file: Option<String>,
+        #[serde(default)]
+        digest_auth: Option<DigestCredentials>,
+
+        // the options sit next to the other fields on the wire
+        #[serde(flatten)]
+        options: ExecOptions,
     }
 
     let payload: SendPayload = serde_json::from_str(&body).unwrap();
@@ -119,9 +250,12 @@ pub async fn send_request(_req: HttpRequest, body: String) -> HttpResponse {
         body: payload.body,
         headers: payload.headers,
         request_index: payload.index,
+        body_file: payload.body_file,
+        digest_auth: payload.digest_auth,
+        options: payload.options,
     };
 
-    let resp = http_send(request).await;
+    let resp = execute(request).await;
 
     let response_body = serde_json::to_string(&resp).unwrap();
 
@@ -132,34 +266,216 @@ pub async fn send_request(_req: HttpRequest, body: String) -> HttpResponse {
     return response;
 }
 
-async fn http_send(mut req: Request) -> Response {
+// the one place a request goes out, retries and digest auth included
+pub async fn execute(mut req: Request) -> Response {
+    req.options.retries = req.options.retries.min(MAX_RETRIES);
+
     if !req.url.contains("http") {
         let new_url = "http://".to_string() + &req.url;
 
         req.url = new_url;
     }
 
-    let mut request = prepare_request(req.clone());
+    let proxy = active_proxy(&req);
+
+    let request = match prepare_request(req.clone()) {
+        Ok(request) => with_headers(request, &req.headers),
+        Err(err) => {
+            let mut err_resp = Response::new();
+
+            err_resp.failed = true;
+            err_resp.body = err;
+            err_resp.request_index = req.request_index;
+
+            return err_resp;
+        }
+    };
+
+    let mut sent = request.try_clone().and_then(|request| request.build().ok());
+
+    let mut attempts = 1;
+
+    let mut http_response = loop {
+        let retry = if attempts <= req.options.retries {
+            request.try_clone()
+        } else {
+            None
+        };
 
-    for h in req.headers {
-        if h[0] != "" && h[1] != "" {
+        // last attempt, or a body that can't be replayed
+        let Some(next) = retry else {
+            break execute_request(request, &proxy, req.options.max_response_size).await;
+        };
+
+        let response = execute_request(next, &proxy, req.options.max_response_size).await;
+
+        if !should_retry(&response, req.options.retry_on) {
+            break response;
+        }
+
+        actix_web::rt::time::sleep(retry_delay(attempts)).await;
+
+        attempts += 1;
+    };
+
+    if http_response.status == 401 {
+        if let Some(credentials) = &req.digest_auth {
+            if let Some(challenge) = digest::find_challenge(&http_response.headers) {
+                match answer_challenge(&req, &challenge, credentials) {
+                    Ok(request) => {
+                        sent = request.try_clone().and_then(|request| request.build().ok());
+                        http_response = execute_request(request, &proxy, req.options.max_response_size).await;
+                    }
+                    Err(err) => {
+                        http_response.failed = true;
+                        http_response.body = err;
+                    }
+                }
+            }
+        }
+    }
+
+    if http_response.failed && attempts > 1 {
+        http_response.body = format!("gave up after {} attempts: {}", attempts, http_response.body);
+    }
+
+    http_response.attempts = attempts;
+    http_response.request_index = req.request_index;
+
+    if let Some(sent) = sent {
+        http_response.sent_method = sent.method().to_string();
+        http_response.sent_url = sent.url().to_string();
+        http_response.sent_headers = sent_headers(&sent, &req);
+        http_response.sent_body = match &req.body_file {
+            Some(path) => format!("< {}", path),
+            None => req.body.clone(),
+        };
+        http_response.raw_request = raw_request_text(&sent, &http_response.sent_headers, &http_response.sent_body);
+    }
+
+    return http_response;
+}
+
+fn with_headers(mut request: reqwest::RequestBuilder, headers: &[Vec<String>]) -> reqwest::RequestBuilder {
+    for h in headers {
+        if h[0] != "" && h[1] != "" && !is_client_managed_header(&h[0]) {
             request = request.header(h[0].clone(), h[1].clone());
         }
     }
 
+    request
+}
+
+// the same request again, carrying the Authorization the challenge asked for
+fn answer_challenge(
+    req: &Request,
+    challenge: &digest::Challenge,
+    credentials: &DigestCredentials,
+) -> Result<reqwest::RequestBuilder, String> {
+    let request = with_headers(prepare_request(req.clone())?, &req.headers);
+
+    let built = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .ok_or_else(|| "could not repeat the request for digest auth".to_string())?;
+
+    let body = built.body().and_then(|body| body.as_bytes()).unwrap_or_default();
+
+    let authorization = digest::authorization(
+        challenge,
+        credentials,
+        built.method().as_str(),
+        built.url().as_str(),
+        body,
+        &digest::new_cnonce(),
+    )?;
+
+    Ok(request.header(reqwest::header::AUTHORIZATION, authorization))
+}
+
+async fn execute_request(request: reqwest::RequestBuilder, proxy: &Option<String>, max_size: u64) -> Response {
     let start = get_timestamp();
     let response = request.send().await;
     let end = get_timestamp();
 
-    let mut http_response = match response {
-        Ok(resp) => {
+    match response {
+        Ok(mut resp) => {
             let mut new_response = Response::new();
 
+            // kept before the body is read, which consumes the response
+            let version = resp.version();
+            let status = resp.status();
+            let raw_headers = resp.headers().clone();
+
             new_response.headers = extract_headers(resp.headers());
             new_response.status = resp.status().as_u16();
-            new_response.time = (end - start) as u32;
-            new_response.body = resp.text().await.unwrap();
-            new_response.size = new_response.body.len() as u64;
+            new_response.time_to_first_byte = (end - start) as u32;
+            new_response.content_encoding = resp
+                .headers()
+                .get(reqwest::header::CONTENT_ENCODING)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+
+            let limit = if max_size == 0 { usize::MAX } else { max_size as usize };
+
+            // read chunk by chunk, a server streaming gigabytes would otherwise fill the memory
+            let mut bytes = Vec::new();
+            let mut cut = false;
+
+            loop {
+                match resp.chunk().await {
+                    Ok(Some(chunk)) => {
+                        let room = limit - bytes.len();
+
+                        if chunk.len() > room {
+                            bytes.extend_from_slice(&chunk[..room]);
+                            cut = true;
+                            break;
+                        }
+
+                        bytes.extend_from_slice(&chunk);
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        new_response.failed = true;
+                        new_response.body = format!("could not read the response body: {}", describe_error(&err));
+                        new_response.error_kind = Some(classify_error(&err));
+
+                        return new_response;
+                    }
+                }
+            }
+
+            new_response.time_total = (get_timestamp() - start) as u32;
+            new_response.time = new_response.time_total;
+
+            new_response.encoded_size = bytes.len() as u64;
+            new_response.raw_response = raw_response_text(version, status, &raw_headers, &bytes);
+
+            // a compressed body can grow past the limit again while it is decoded
+            let mut body = match decode_body(&new_response.content_encoding, &bytes, limit, cut) {
+                Ok(body) => body,
+                Err(err) => {
+                    new_response.failed = true;
+                    new_response.body = err;
+
+                    return new_response;
+                }
+            };
+
+            if body.len() > limit {
+                body.truncate(limit);
+                cut = true;
+            }
+
+            if cut {
+                new_response.truncated_at = Some(max_size);
+            }
+
+            new_response.double_compressed = !new_response.content_encoding.is_empty() && looks_gzipped(&body);
+            new_response.body = String::from_utf8_lossy(&body).into_owned();
+            new_response.size = body.len() as u64;
 
             if new_response.headers.contains(&vec![
                 "content-type".to_string(),
@@ -175,21 +491,113 @@ async fn http_send(mut req: Request) -> Response {
             let mut err_resp = Response::new();
 
             err_resp.failed = true;
+            err_resp.error_kind = Some(classify_error(&err));
 
-            err_resp.body = err.to_string();
+            err_resp.body = match proxy {
+                Some(proxy) => format!("request through proxy {} failed: {}", proxy, describe_error(&err)),
+                None => describe_error(&err),
+            };
 
             err_resp
         }
+    }
+}
+
+// the TLS version, cipher and certificate of the url's host, from a connection of its own made
+// when the user asks, never on the way of a send
+#[actix_web::post("/inspect_tls")]
+pub async fn inspect_tls(_req: HttpRequest, body: String) -> HttpResponse {
+    #[derive(Serialize)]
+    struct TlsInspection {
+        details: Option<tls::TlsDetails>,
+        error: String,
+    }
+
+    let inspection = match tls::inspect(body.trim()).await {
+        Ok(details) => TlsInspection {
+            details: Some(details),
+            error: String::new(),
+        },
+        Err(error) => TlsInspection { details: None, error },
     };
 
-    http_response.request_index = req.request_index;
+    HttpResponse::Ok()
+        .insert_header(("Access-Control-Allow-Origin", "*"))
+        .json(inspection)
+}
 
-    return http_response;
+// whether anything answers on the url's host and port, without sending the request. a plain TCP
+// connect, so it says nothing about a proxy the request would go through
+#[actix_web::post("/check_host")]
+pub async fn check_host(_req: HttpRequest, body: String) -> HttpResponse {
+    #[derive(Serialize)]
+    struct HostCheck {
+        reachable: bool,
+        time: u32,
+        error_kind: Option<ErrorKind>,
+        error: String,
+    }
+
+    let start = get_timestamp();
+    let result = connect_to_host(body.trim()).await;
+    let time = (get_timestamp() - start) as u32;
+
+    let check = match result {
+        Ok(()) => HostCheck {
+            reachable: true,
+            time,
+            error_kind: None,
+            error: String::new(),
+        },
+        Err((kind, error)) => HostCheck {
+            reachable: false,
+            time,
+            error_kind: Some(kind),
+            error,
+        },
+    };
+
+    HttpResponse::Ok()
+        .insert_header(("Access-Control-Allow-Origin", "*"))
+        .json(check)
+}
+
+#[actix_web::post("/file_info")]
+pub async fn file_info(_req: HttpRequest, body: String) -> HttpResponse {
+    #[derive(Serialize)]
+    struct FileInfo {
+        size: Option<u64>,
+        error: String,
+    }
+
+    let info = match std::fs::metadata(body.trim()) {
+        Ok(metadata) if metadata.is_file() => FileInfo {
+            size: Some(metadata.len()),
+            error: String::new(),
+        },
+        Ok(_) => FileInfo {
+            size: None,
+            error: "not a file".to_string(),
+        },
+        Err(err) => FileInfo {
+            size: None,
+            error: err.to_string(),
+        },
+    };
+
+    HttpResponse::Ok()
+        .insert_header(("Access-Control-Allow-Origin", "*"))
+        .json(info)
 }
 
 #[actix_web::post("/save_state")]
 pub async fn save_state(_req: HttpRequest, body: String) -> HttpResponse {
-    std::fs::write(get_home() + "state.json", body).unwrap();
+    // the frontend keeps its unsaved changes and tells the user when this fails
+    if let Err(err) = std::fs::write(get_home() + "state.json", body) {
+        return HttpResponse::InternalServerError()
+            .insert_header(("Access-Control-Allow-Origin", "*"))
+            .body(format!("could not write {}state.json: {}", get_home(), err));
+    }
 
     let response = HttpResponse::Ok()
         .insert_header(("Access-Control-Allow-Origin", "*"))
@@ -200,7 +608,15 @@ pub async fn save_state(_req: HttpRequest, body: String) -> HttpResponse {
 
 #[actix_web::post("/restore_state")]
 pub async fn restore_state(_req: HttpRequest) -> HttpResponse {
-    let state = std::fs::read_to_string(get_home() + "state.json").unwrap();
+    // an empty body tells the frontend to start from a fresh state
+    let state = match std::fs::read_to_string(get_home() + "state.json") {
+        Ok(state) if looks_like_state(&state) => state,
+        Ok(_) => {
+            set_aside_state("state.json is not a valid Bolt state");
+            String::new()
+        }
+        Err(_) => String::new(),
+    };
 
     let response = HttpResponse::Ok()
         .insert_header(("Access-Control-Allow-Origin", "*"))
@@ -227,6 +643,9 @@ pub async fn launch_server(port: u16) {
             .service(save_state)
             .service(send_request)
             .service(open_link)
+            .service(file_info)
+            .service(check_host)
+            .service(inspect_tls)
             .default_service(web::post().to(e404))
     });
 
@@ -309,8 +728,8 @@ pub fn start(args: Vec<String>, port: u16) {
     }
 
     if launch {
+        // no state.json is left to the frontend, which starts a first run with the sample collection
         verify_home();
-        verify_state();
 
         if !is_tauri {
             verify_dist();
@@ -325,3 +744,191 @@ pub fn start(args: Vec<String>, port: u16) {
         launch_server(port);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, body: &str, headers: &[(&str, &str)]) -> Request {
+        Request {
+            url: "http://localhost/".to_string(),
+            method,
+            body: body.to_string(),
+            headers: headers.iter().map(|(name, value)| vec![name.to_string(), value.to_string()]).collect(),
+            request_index: 0,
+            body_file: None,
+            digest_auth: None,
+            options: ExecOptions::default(),
+        }
+    }
+
+    fn built(req: &Request) -> reqwest::Request {
+        with_headers(prepare_request(req.clone()).unwrap(), &req.headers).build().unwrap()
+    }
+
+    #[test]
+    fn body_and_client_managed_headers_for_every_method() {
+        let methods = [Method::GET, Method::HEAD, Method::POST, Method::PUT, Method::DELETE];
+        let user_headers: [&[(&str, &str)]; 4] = [
+            &[],
+            &[("Content-Length", "999")],
+            &[("transfer-encoding", "chunked")],
+            &[(" CONTENT-LENGTH ", "0"), ("Transfer-Encoding", "gzip"), ("X-Kept", "yes")],
+        ];
+
+        for method in methods {
+            for body in ["", "{\"a\":1}"] {
+                for headers in user_headers {
+                    let req = request(method.clone(), body, headers);
+                    let sent = built(&req);
+                    let case = format!("{:?} body={:?} headers={:?}", method, body, headers);
+
+                    let sent_body = sent.body().and_then(|body| body.as_bytes());
+                    assert_eq!(sent_body, (!body.is_empty()).then_some(body.as_bytes()), "{}", case);
+
+                    assert!(sent.headers().get("content-length").is_none(), "{}", case);
+                    assert!(sent.headers().get("transfer-encoding").is_none(), "{}", case);
+
+                    let kept = headers.iter().any(|(name, _)| *name == "X-Kept");
+                    assert_eq!(sent.headers().get("x-kept").is_some(), kept, "{}", case);
+                }
+            }
+        }
+    }
+
+    // a local server answering every request with whatever `answer` makes of its path and head,
+    // one connection per request
+    fn mock_server(answer: impl Fn(&str, &str) -> String + Send + Sync + 'static) -> String {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let answer = std::sync::Arc::new(answer);
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let answer = answer.clone();
+
+                std::thread::spawn(move || {
+                    let mut head = String::new();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    while reader.read_line(&mut head).map(|read| read > 2).unwrap_or(false) {}
+
+                    let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let _ = stream.write_all(answer(&path, &head.to_lowercase()).as_bytes());
+                });
+            }
+        });
+
+        base
+    }
+
+    fn reply(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+            status,
+            body.len(),
+            headers,
+            body
+        )
+    }
+
+    fn get(url: String, options: ExecOptions) -> Request {
+        Request {
+            url,
+            options,
+            ..request(Method::GET, "", &[])
+        }
+    }
+
+    #[actix_web::test]
+    async fn execute_follows_redirects_unless_told_not_to() {
+        let base = mock_server(|path, _| match path {
+            "/old" => reply("302 Found", "Location: /new\r\n", ""),
+            _ => reply("200 OK", "", "moved here"),
+        });
+
+        let followed = execute(get(format!("{}/old", base), ExecOptions::default())).await;
+        assert_eq!((followed.status, followed.body.as_str()), (200, "moved here"));
+
+        let options = ExecOptions {
+            follow_redirects: false,
+            ..ExecOptions::default()
+        };
+        let stopped = execute(get(format!("{}/old", base), options)).await;
+        assert_eq!(stopped.status, 302);
+    }
+
+    #[actix_web::test]
+    async fn execute_retries_server_errors_only_when_asked() {
+        // busy on the first call, fine from then on
+        let flaky = || {
+            let calls = std::sync::atomic::AtomicUsize::new(0);
+
+            mock_server(move |_, _| match calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                0 => reply("503 Service Unavailable", "", "busy"),
+                _ => reply("200 OK", "", "ok"),
+            })
+        };
+
+        let options = ExecOptions {
+            retries: 2,
+            ..ExecOptions::default()
+        };
+        let network_only = execute(get(flaky(), options.clone())).await;
+        assert_eq!((network_only.status, network_only.attempts), (503, 1));
+
+        let options = ExecOptions {
+            retry_on: RetryPolicy::NetworkAndServerErrors,
+            ..options
+        };
+        let retried = execute(get(flaky(), options)).await;
+        assert_eq!((retried.status, retried.attempts, retried.body.as_str()), (200, 2, "ok"));
+    }
+
+    #[actix_web::test]
+    async fn execute_gives_up_after_the_timeout() {
+        let base = mock_server(|_, _| {
+            std::thread::sleep(std::time::Duration::from_secs(3));
+            reply("200 OK", "", "late")
+        });
+
+        let options = ExecOptions {
+            timeout_ms: 200,
+            ..ExecOptions::default()
+        };
+        let response = execute(get(base, options)).await;
+
+        assert!(response.failed);
+        assert_eq!(response.error_kind, Some(ErrorKind::ConnectionTimeout));
+    }
+
+    #[actix_web::test]
+    async fn execute_sends_the_user_agent_and_cuts_large_bodies() {
+        // the body is the User-Agent the request arrived with, padded past the limit
+        let base = mock_server(|_, head| {
+            let agent = head.lines().find_map(|line| line.strip_prefix("user-agent: ")).unwrap_or("").trim();
+            reply("200 OK", "", &format!("{}{}", agent, "x".repeat(4096)))
+        });
+
+        let options = ExecOptions {
+            user_agent: "bolt-test".to_string(),
+            max_response_size: 1024,
+            ..ExecOptions::default()
+        };
+        let response = execute(get(base, options)).await;
+
+        assert!(response.body.starts_with("bolt-test"));
+        assert_eq!(response.truncated_at, Some(1024));
+        assert!(response.body.len() <= 1024);
+    }
+
+    #[test]
+    fn client_managed_header_names() {
+        assert!(is_client_managed_header("Content-Length"));
+        assert!(is_client_managed_header(" transfer-encoding "));
+        assert!(!is_client_managed_header("Content-Type"));
+        assert!(!is_client_managed_header("X-Content-Length"));
+    }
+}
@@ -0,0 +1,134 @@
+use serde::Serialize;
+use std::time::Duration;
+
+// what the server's end of an HTTPS connection looked like
+#[derive(Debug, Serialize, Clone)]
+pub struct TlsDetails {
+    version: String,
+    cipher: String,
+    subject: String,
+    issuer: String,
+    not_before: String,
+    not_after: String,
+    // negative once the certificate has expired
+    days_left: i32,
+}
+
+// for each step of the handshake, the whole inspection gets twice that
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// reqwest doesn't say how it negotiated, so this is a connection of its own to the same host,
+// made when the user asks for it. it never verifies, a certificate that failed the real request is
+// the one worth looking at, and it goes direct without the request's client certificate or CA
+pub async fn inspect(url: &str) -> Result<TlsDetails, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|err| format!("invalid url {}: {}", url, err))?;
+
+    if parsed.scheme() != "https" {
+        return Err(format!("{} is not an https url", url));
+    }
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| format!("{} has no host", url))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    // the lookup can't be given a timeout of its own, this bounds it along with the rest
+    let handshake = actix_web::rt::task::spawn_blocking(move || handshake(&host, port));
+
+    match actix_web::rt::time::timeout(PROBE_TIMEOUT * 2, handshake).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err(format!("no TLS handshake within {} s", (PROBE_TIMEOUT * 2).as_secs())),
+    }
+}
+
+// native-tls only sits on openssl where the system has no TLS library of its own
+#[cfg(not(any(target_os = "macos", target_os = "ios", target_os = "windows")))]
+fn handshake(host: &str, port: u16) -> Result<TlsDetails, String> {
+    use openssl::asn1::Asn1Time;
+    use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
+    use openssl::x509::X509NameRef;
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    fn name_text(name: &X509NameRef) -> String {
+        name.entries()
+            .map(|entry| {
+                let key = entry.object().nid().short_name().unwrap_or("?");
+                let value = entry.data().to_string().unwrap_or_default();
+
+                format!("{}={}", key, value)
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    let address = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| err.to_string())?
+        .next()
+        .ok_or_else(|| format!("{} has no address", host))?;
+
+    let stream = TcpStream::connect_timeout(&address, PROBE_TIMEOUT).map_err(|err| err.to_string())?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).map_err(|err| err.to_string())?;
+    stream.set_write_timeout(Some(PROBE_TIMEOUT)).map_err(|err| err.to_string())?;
+
+    let mut builder = SslConnector::builder(SslMethod::tls()).map_err(|err| err.to_string())?;
+    builder.set_verify(SslVerifyMode::NONE);
+
+    let stream = builder
+        .build()
+        .configure()
+        .map_err(|err| err.to_string())?
+        .verify_hostname(false)
+        .connect(host, stream)
+        .map_err(|err| err.to_string())?;
+
+    let ssl = stream.ssl();
+    let cert = ssl.peer_certificate().ok_or_else(|| "the server sent no certificate".to_string())?;
+
+    let days_left = Asn1Time::days_from_now(0)
+        .and_then(|now| now.diff(cert.not_after()))
+        .map(|diff| diff.days)
+        .unwrap_or(0);
+
+    Ok(TlsDetails {
+        version: ssl.version_str().to_string(),
+        cipher: ssl.current_cipher().map(|cipher| cipher.name().to_string()).unwrap_or_default(),
+        subject: name_text(cert.subject_name()),
+        issuer: name_text(cert.issuer_name()),
+        not_before: cert.not_before().to_string(),
+        not_after: cert.not_after().to_string(),
+        days_left,
+    })
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "windows"))]
+fn handshake(_host: &str, _port: u16) -> Result<TlsDetails, String> {
+    Err("this build reads TLS details through openssl, which macOS and Windows builds don't include".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_web::test]
+    async fn only_https_is_inspected() {
+        assert!(inspect("http://localhost/").await.unwrap_err().contains("not an https url"));
+        assert!(inspect("not a url").await.unwrap_err().starts_with("invalid url"));
+    }
+
+    #[actix_web::test]
+    async fn closed_port_fails_without_hanging() {
+        // bound and dropped, nothing listens there anymore
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let started = std::time::Instant::now();
+        let result = inspect(&format!("https://127.0.0.1:{}/", port)).await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < PROBE_TIMEOUT * 2);
+    }
+}
@@ -1,8 +1,16 @@
+use crate::ErrorKind;
+use crate::HttpVersion;
 use crate::Method;
 use crate::Request;
+use crate::Response;
+use crate::RetryPolicy;
+use crate::TlsConfig;
 use std::path::Path;
-use std::process::Command;
-use std::time::SystemTime;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 pub fn extract_headers(map: &reqwest::header::HeaderMap) -> Vec<Vec<String>> {
     let mut headers: Vec<Vec<String>> = Vec::new();
@@ -19,6 +27,115 @@ pub fn extract_headers(map: &reqwest::header::HeaderMap) -> Vec<Vec<String>> {
     return headers;
 }
 
+// the request's own headers plus the ones the client adds on its way out
+pub fn sent_headers(sent: &reqwest::Request, req: &Request) -> Vec<Vec<String>> {
+    let mut headers: Vec<Vec<String>> = sent
+        .headers()
+        .iter()
+        .map(|(key, value)| vec![key.to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()])
+        .collect();
+
+    let missing = |headers: &Vec<Vec<String>>, name: &str| !headers.iter().any(|header| header[0] == name);
+
+    if let Some(host) = sent.url().host_str() {
+        let host = match sent.url().port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+
+        headers.insert(0, vec!["host".to_string(), host]);
+    }
+
+    if missing(&headers, "accept") {
+        headers.push(vec!["accept".to_string(), "*/*".to_string()]);
+    }
+
+    if !req.options.user_agent.trim().is_empty() && missing(&headers, "user-agent") {
+        headers.push(vec!["user-agent".to_string(), req.options.user_agent.trim().to_string()]);
+    }
+
+    let body_length = sent.body().and_then(|body| body.as_bytes()).map_or(0, |body| body.len());
+
+    if body_length > 0 && missing(&headers, "content-length") {
+        headers.push(vec!["content-length".to_string(), body_length.to_string()]);
+    }
+
+    headers
+}
+
+// raw views show at most this much of a body, the full one is in the regular views
+const RAW_BODY_LIMIT: usize = 64 * 1024;
+
+// the request line, headers and body the way they go over an HTTP/1.x connection. header names
+// are lowercase, that is how the client stores and sends them
+pub fn raw_request_text(sent: &reqwest::Request, headers: &[Vec<String>], body: &str) -> String {
+    let url = sent.url();
+    let target = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+
+    let mut raw = format!("{} {} {:?}\r\n", sent.method(), target, sent.version());
+
+    for header in headers {
+        raw.push_str(&format!("{}: {}\r\n", header[0], header[1]));
+    }
+
+    raw.push_str("\r\n");
+    raw.push_str(&raw_body(body.as_bytes()));
+
+    raw
+}
+
+// status line, headers in the order and with the duplicates they arrived in, and the body before
+// any Content-Encoding is undone. chunked framing is already gone at this point
+pub fn raw_response_text(
+    version: reqwest::Version,
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+    body: &[u8],
+) -> String {
+    let mut raw = format!(
+        "{:?} {} {}\r\n",
+        version,
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    );
+
+    for (key, value) in headers.iter() {
+        raw.push_str(&format!("{}: {}\r\n", key, String::from_utf8_lossy(value.as_bytes())));
+    }
+
+    raw.push_str("\r\n");
+    raw.push_str(&raw_body(body));
+
+    raw
+}
+
+// printable text as is, every other byte as \xNN so binary and compressed bodies stay readable
+fn raw_body(body: &[u8]) -> String {
+    let shown = &body[..body.len().min(RAW_BODY_LIMIT)];
+
+    let mut raw = match std::str::from_utf8(shown) {
+        Ok(text) => text.to_string(),
+        // text cut off by the limit in the middle of a character
+        Err(err) if err.error_len().is_none() => String::from_utf8_lossy(&shown[..err.valid_up_to()]).into_owned(),
+        Err(_) => shown
+            .iter()
+            .map(|byte| match byte {
+                b'\n' | b'\r' | b'\t' | 0x20..=0x7e => (*byte as char).to_string(),
+                _ => format!("\\x{:02x}", byte),
+            })
+            .collect(),
+    };
+
+    if body.len() > shown.len() {
+        raw.push_str(&format!("\n... {} more bytes", body.len() - shown.len()));
+    }
+
+    raw
+}
+
 pub fn get_timestamp() -> u128 {
     return SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -26,25 +143,320 @@ pub fn get_timestamp() -> u128 {
         .as_millis();
 }
 
-pub fn prepare_request(req: Request) -> reqwest::RequestBuilder {
-    let client = reqwest::Client::new();
+// proxies get the same treatment as request urls: a missing scheme means plain http
+pub fn normalize_proxy_url(url: &str) -> String {
+    let url = url.trim();
+
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        "http://".to_string() + url
+    }
+}
+
+// no-proxy entries match a host exactly or as a domain suffix,
+// so both "internal.corp" and ".internal.corp" cover "api.internal.corp"
+pub fn bypasses_proxy(url: &str, no_proxy: &[String]) -> bool {
+    let host = match reqwest::Url::parse(url) {
+        Ok(url) => match url.host_str() {
+            Some(host) => host.to_lowercase(),
+            None => return false,
+        },
+        Err(_) => return false,
+    };
+
+    no_proxy
+        .iter()
+        .map(|entry| entry.trim().to_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            if entry == "*" {
+                return true;
+            }
+
+            let domain = entry.trim_start_matches('.');
+
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+}
+
+// the proxy a request will go through, if any
+pub fn active_proxy(req: &Request) -> Option<String> {
+    if req.options.proxy.trim().is_empty() || bypasses_proxy(&req.url, &req.options.no_proxy) {
+        return None;
+    }
+
+    Some(normalize_proxy_url(&req.options.proxy))
+}
+
+pub fn build_client(req: &Request) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder();
+
+    match active_proxy(req) {
+        Some(proxy_url) => {
+            let proxy = reqwest::Proxy::all(&proxy_url)
+                .map_err(|err| format!("invalid proxy url {}: {}", proxy_url, err))?;
+
+            builder = builder.proxy(proxy);
+        }
+        // don't fall back to the system proxy variables when the user bypassed it
+        None if !req.options.proxy.trim().is_empty() => builder = builder.no_proxy(),
+        None => {}
+    }
+
+    builder = apply_tls(builder, &req.options.tls)?;
+
+    if req.options.timeout_ms > 0 {
+        builder = builder.timeout(Duration::from_millis(req.options.timeout_ms));
+    }
+
+    if !req.options.follow_redirects {
+        builder = builder.redirect(reqwest::redirect::Policy::none());
+    }
+
+    if !req.options.user_agent.trim().is_empty() {
+        builder = builder.user_agent(req.options.user_agent.trim());
+    }
+
+    match req.options.http_version {
+        Some(HttpVersion::Http1) => builder = builder.http1_only(),
+        // no upgrade dance, the server has to speak HTTP/2 from the first byte
+        Some(HttpVersion::Http2) => builder = builder.http2_prior_knowledge(),
+        None => {}
+    }
+
+    builder.build().map_err(|err| describe_error(&err))
+}
+
+fn read_pem(path: &str, what: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|err| format!("could not read {} {}: {}", what, path, err))
+}
+
+pub fn apply_tls(
+    mut builder: reqwest::ClientBuilder,
+    tls: &TlsConfig,
+) -> Result<reqwest::ClientBuilder, String> {
+    if tls.insecure_skip_verify {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    if let Some(path) = &tls.ca_cert_path {
+        let pem = read_pem(path, "CA certificate")?;
+        let cert = reqwest::Certificate::from_pem(&pem)
+            .map_err(|err| format!("invalid CA certificate {}: {}", path, err))?;
+
+        builder = builder.add_root_certificate(cert);
+    }
+
+    if let Some((cert_path, key_path)) = &tls.client_cert {
+        let cert = read_pem(cert_path, "client certificate")?;
+        let key = read_pem(key_path, "client key")?;
+
+        let identity = reqwest::Identity::from_pkcs8_pem(&cert, &key)
+            .map_err(|err| format!("invalid client certificate {}: {}", cert_path, err))?;
+
+        builder = builder.identity(identity);
+    }
+
+    Ok(builder)
+}
+
+// whether a failed attempt is worth another try under the request's retry policy
+pub fn should_retry(response: &Response, policy: RetryPolicy) -> bool {
+    match policy {
+        // a status means the server answered, a body that didn't decode won't on the next try either
+        RetryPolicy::NetworkErrors => response.failed && response.status == 0,
+        RetryPolicy::NetworkAndServerErrors => {
+            (response.failed && response.status == 0) || response.status >= 500
+        }
+    }
+}
+
+// undoes each Content-Encoding, the last one listed was applied last. stops a byte past `limit`
+// so the caller can tell there was more, and keeps what decoded of a body that was `cut` short
+pub fn decode_body(encoding: &str, bytes: &[u8], limit: usize, cut: bool) -> Result<Vec<u8>, String> {
+    let mut body = bytes.to_vec();
+
+    let codings = encoding
+        .split(',')
+        .map(|coding| coding.trim().to_lowercase())
+        .filter(|coding| !coding.is_empty());
+
+    for coding in codings.rev() {
+        let decoded = match coding.as_str() {
+            "identity" => continue,
+            "gzip" | "x-gzip" => read_all(flate2::read::MultiGzDecoder::new(&body[..]), limit, cut),
+            // meant to be zlib wrapped, some servers send the raw stream
+            "deflate" => read_all(flate2::read::ZlibDecoder::new(&body[..]), limit, cut)
+                .or_else(|_| read_all(flate2::read::DeflateDecoder::new(&body[..]), limit, cut)),
+            "br" => read_all(brotli_decompressor::Decompressor::new(&body[..], 4096), limit, cut),
+            other => return Err(format!("the body is {} encoded, which bolt can't decode", other)),
+        };
+
+        body = decoded.map_err(|err| format!("could not decode the {} body: {}", coding, err))?;
+    }
+
+    Ok(body)
+}
+
+fn read_all<R: Read>(reader: R, limit: usize, cut: bool) -> std::io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+
+    match reader.take((limit as u64).saturating_add(1)).read_to_end(&mut buffer) {
+        Ok(_) => Ok(buffer),
+        // the stream ends early because the body was cut, not because it is broken
+        Err(err) if cut && err.kind() == std::io::ErrorKind::UnexpectedEof => Ok(buffer),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn looks_gzipped(body: &[u8]) -> bool {
+    body.starts_with(&[0x1f, 0x8b])
+}
+
+// exponential backoff: 250ms, 500ms, 1s, ... capped at 8s
+pub fn retry_delay(attempt: u8) -> Duration {
+    let exponent = u32::from(attempt.saturating_sub(1)).min(5);
+
+    Duration::from_millis(250 * 2u64.pow(exponent))
+}
+
+// reqwest hides the interesting part of TLS failures (which certificate,
+// who issued it) in the error's sources, so print the whole chain
+pub fn describe_error(err: &dyn std::error::Error) -> String {
+    let mut description = err.to_string();
+    let mut source = err.source();
+
+    while let Some(cause) = source {
+        let cause_text = cause.to_string();
+
+        if !description.contains(&cause_text) {
+            description.push_str(": ");
+            description.push_str(&cause_text);
+        }
+
+        source = cause.source();
+    }
+
+    description
+}
+
+// reqwest only tells timeouts and connect errors apart, the rest is in the error's sources: an io
+// error kind where there is one, otherwise the wording of the resolver, hyper or the tls library
+pub fn classify_error(err: &(dyn std::error::Error + 'static)) -> ErrorKind {
+    if err.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_timeout) {
+        return ErrorKind::ConnectionTimeout;
+    }
+
+    let mut source = Some(err);
+
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            match io.kind() {
+                std::io::ErrorKind::ConnectionRefused => return ErrorKind::ConnectionRefused,
+                std::io::ErrorKind::TimedOut => return ErrorKind::ConnectionTimeout,
+                std::io::ErrorKind::Interrupted => return ErrorKind::Cancelled,
+                _ => {}
+            }
+        }
+
+        source = cause.source();
+    }
+
+    let text = describe_error(err).to_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+
+    if mentions(&[
+        "dns error",
+        "failed to lookup address",
+        "name or service not known",
+        "no such host",
+        "nodename nor servname",
+        "name resolution",
+    ]) {
+        ErrorKind::DnsResolution
+    } else if mentions(&["connection refused"]) {
+        ErrorKind::ConnectionRefused
+    } else if mentions(&["timed out", "deadline has elapsed"]) {
+        ErrorKind::ConnectionTimeout
+    } else if mentions(&["certificate", "handshake", "tls", "ssl"]) {
+        ErrorKind::TlsHandshake
+    } else if mentions(&["canceled", "cancelled"]) {
+        ErrorKind::Cancelled
+    } else if mentions(&["connection closed before message completed", "incomplete message", "invalid http", "parse error", "protocol"]) {
+        ErrorKind::ProtocolError
+    } else {
+        ErrorKind::Other
+    }
+}
+
+const HOST_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// a TCP connect to the url's host and port, given up on after HOST_CHECK_TIMEOUT
+pub async fn connect_to_host(url: &str) -> Result<(), (ErrorKind, String)> {
+    let url = if url.contains("://") { url.to_string() } else { format!("http://{}", url) };
+
+    let parsed = reqwest::Url::parse(&url).map_err(|err| (ErrorKind::Other, format!("invalid url {}: {}", url, err)))?;
+
+    // host_str keeps the brackets around an ipv6 address
+    let host = match parsed.host_str() {
+        Some(host) => host.trim_start_matches('[').trim_end_matches(']').to_string(),
+        None => return Err((ErrorKind::Other, format!("{} has no host", url))),
+    };
+    let port = parsed.port_or_known_default().unwrap_or(80);
+
+    let connect = actix_web::rt::net::TcpStream::connect((host.as_str(), port));
+
+    match actix_web::rt::time::timeout(HOST_CHECK_TIMEOUT, connect).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(err)) => Err((classify_error(&err), format!("{}:{}: {}", host, port, describe_error(&err)))),
+        Err(_) => Err((
+            ErrorKind::ConnectionTimeout,
+            format!("{}:{} did not answer within {} s", host, port, HOST_CHECK_TIMEOUT.as_secs()),
+        )),
+    }
+}
+
+pub fn prepare_request(req: Request) -> Result<reqwest::RequestBuilder, String> {
+    let client = build_client(&req)?;
+
+    let body = match &req.body_file {
+        Some(path) => std::fs::read(path)
+            .map_err(|err| format!("could not read the body file {}: {}", path, err))?,
+        None => req.body.into_bytes(),
+    };
 
     let builder = match req.method {
-        Method::GET => client.get(req.url).body(req.body),
-        Method::POST => client.post(req.url).body(req.body),
-        Method::PUT => client.put(req.url).body(req.body),
-        Method::DELETE => client.delete(req.url).body(req.body),
-        Method::HEAD => client.head(req.url).body(req.body),
-        Method::PATCH => client.patch(req.url).body(req.body),
-        Method::OPTIONS => client
-            .request(reqwest::Method::OPTIONS, req.url)
-            .body(req.body),
-        Method::CONNECT => client
-            .request(reqwest::Method::CONNECT, req.url)
-            .body(req.body),
+        Method::GET => client.get(req.url),
+        Method::POST => client.post(req.url),
+        Method::PUT => client.put(req.url),
+        Method::DELETE => client.delete(req.url),
+        Method::HEAD => client.head(req.url),
+        Method::PATCH => client.patch(req.url),
+        Method::OPTIONS => client.request(reqwest::Method::OPTIONS, req.url),
+        Method::CONNECT => client.request(reqwest::Method::CONNECT, req.url),
+        Method::COPY => client.request(custom_method("COPY")?, req.url),
+        Method::Custom(name) => client.request(custom_method(&name)?, req.url),
     };
 
-    return builder;
+    // any method carries a body that was written, an empty one is left off instead of going out as
+    // a Content-Length: 0 on a GET
+    let builder = if body.is_empty() { builder } else { builder.body(body) };
+
+    Ok(builder)
+}
+
+// headers the client works out from the body, a hand written one could disagree with what is sent
+const CLIENT_MANAGED_HEADERS: [&str; 2] = ["content-length", "transfer-encoding"];
+
+// the frontend strips these too and tells the user, this keeps old saves and other callers honest
+pub fn is_client_managed_header(name: &str) -> bool {
+    CLIENT_MANAGED_HEADERS.iter().any(|managed| name.trim().eq_ignore_ascii_case(managed))
+}
+
+// the frontend checks the name too, this keeps old saves and other callers honest
+fn custom_method(name: &str) -> Result<reqwest::Method, String> {
+    reqwest::Method::from_bytes(name.as_bytes()).map_err(|_| format!("invalid method {:?}", name))
 }
 
 // downloads the dist from github
@@ -70,7 +482,7 @@ pub fn _clone_repo_dev() {
         get_home() + "bolt/"
     );
 
-    run_command(shell_command, "../".to_string());
+    report_command(run_command(shell_command, "../".to_string()));
 }
 
 pub fn _clone_repo_release() {
@@ -78,7 +490,15 @@ pub fn _clone_repo_release() {
 
     let shell_command = format!("git clone {url} --depth 1");
 
-    run_command(shell_command, get_home());
+    report_command(run_command(shell_command, get_home()));
+}
+
+fn report_command(result: std::io::Result<ExitStatus>) {
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("command exited with {}", status),
+        Err(err) => panic!("failed to execute command: {}", err),
+    }
 }
 
 fn copy_dir(src: &str, dst: &str) -> std::io::Result<()> {
@@ -106,45 +526,55 @@ fn copy_dir(src: &str, dst: &str) -> std::io::Result<()> {
     Ok(())
 }
 
-pub fn run_command(shell_command: String, dir: String) {
-    let _output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", &shell_command])
-            .current_dir(dir)
-            .output()
-            .expect(&format!("failed to execute command: {}", &shell_command))
+pub fn run_command(shell_command: String, dir: String) -> std::io::Result<ExitStatus> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", &shell_command]);
+        command
     } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&shell_command)
-            .current_dir(dir)
-            .output()
-            .expect(&format!("failed to execute command: {}", &shell_command))
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(&shell_command);
+        command
     };
-}
 
-pub fn verify_state() {
-    let path = get_home() + "state.json";
-    if !file_exists(&path) {
-        println!("Creating state file");
+    let mut child = command
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
 
-        create_state(&path);
-    }
-}
+    let stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
 
-pub fn file_exists(path: &String) -> bool {
-    if Path::new(&path).exists() {
-        true
-    } else {
-        println!("File {} does not exist", path);
-        false
+    // read both pipes at once so neither can fill up and stall the child,
+    // and print lines in the order they arrive
+    let (sender, receiver) = mpsc::channel::<String>();
+
+    let stdout_sender = sender.clone();
+    let stdout_reader = thread::spawn(move || forward_lines(stdout, stdout_sender));
+    let stderr_reader = thread::spawn(move || forward_lines(stderr, sender));
+
+    for line in receiver {
+        println!("{}", line);
     }
-}
 
-pub fn create_state(path: &String) {
-    let new_state = r#"{"page":"Home","main_current":0,"col_current":[0,0],"main_col":{"name":"New Collection ","requests":[{"url":"","body":"","headers":[["",""]],"params":[["",""]],"method":"GET","response":{"status":0,"body":"","headers":[],"time":0,"size":0,"response_type":"TEXT","request_index":0,"failed":false},"name":"New Request 1","req_tab":1,"resp_tab":1,"loading":false}],"collapsed":false},"collections":[]}"#;
+    stdout_reader.join().unwrap();
+    stderr_reader.join().unwrap();
+
+    child.wait()
+}
 
-    std::fs::write(path, new_state).unwrap();
+fn forward_lines<R: Read>(stream: R, sender: Sender<String>) {
+    for line in BufReader::new(stream).lines() {
+        match line {
+            Ok(line) => {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    }
 }
 
 pub fn open_browser(link: String) {
@@ -178,6 +608,24 @@ pub fn get_home() -> String {
     path
 }
 
+pub fn looks_like_state(state: &str) -> bool {
+    match serde_json::from_str::<serde_json::Value>(state) {
+        Ok(value) => value.get("main_col").is_some() && value.get("collections").is_some(),
+        Err(_) => false,
+    }
+}
+
+// moves a broken state file out of the way so the next save doesn't overwrite it
+pub fn set_aside_state(reason: &str) {
+    let path = get_home() + "state.json";
+    let backup = format!("{}state.json.broken-{}", get_home(), get_timestamp());
+
+    match std::fs::rename(&path, &backup) {
+        Ok(_) => println!("{}, moved it to {}", reason, backup),
+        Err(err) => println!("{}, could not move it aside: {}", reason, err),
+    }
+}
+
 pub fn verify_home() {
     let path = get_home();
     if !dir_exists(&path) {
@@ -206,3 +654,86 @@ pub fn dir_exists(path: &String) -> bool {
 pub fn create_home(path: &String) {
     std::fs::create_dir(path).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    // whatever reqwest makes of a GET to `url`, which has to fail
+    async fn failure(url: &str, timeout: Option<Duration>) -> ErrorKind {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let err = builder.build().unwrap().get(url).send().await.expect_err("the request should fail");
+
+        classify_error(&err)
+    }
+
+    // a port nothing listens on, the listener is dropped before it's used
+    fn closed_port() -> u16 {
+        TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+    }
+
+    // accepts connections and hands each one to `answer` on its own thread
+    fn serve(answer: fn(std::net::TcpStream)) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                thread::spawn(move || answer(stream));
+            }
+        });
+
+        port
+    }
+
+    #[actix_web::test]
+    async fn unknown_host_is_dns() {
+        // .invalid never resolves, RFC 6761
+        assert_eq!(failure("http://bolt-test.invalid/", None).await, ErrorKind::DnsResolution);
+    }
+
+    #[actix_web::test]
+    async fn closed_port_is_refused() {
+        let url = format!("http://127.0.0.1:{}/", closed_port());
+
+        assert_eq!(failure(&url, None).await, ErrorKind::ConnectionRefused);
+    }
+
+    #[actix_web::test]
+    async fn silent_server_is_timeout() {
+        let port = serve(|stream| {
+            thread::sleep(Duration::from_secs(5));
+            drop(stream);
+        });
+        let url = format!("http://127.0.0.1:{}/", port);
+
+        assert_eq!(failure(&url, Some(Duration::from_millis(200))).await, ErrorKind::ConnectionTimeout);
+    }
+
+    #[actix_web::test]
+    async fn plain_http_answer_to_https_is_tls() {
+        let port = serve(|mut stream| {
+            let _ = stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n");
+        });
+        let url = format!("https://127.0.0.1:{}/", port);
+
+        assert_eq!(failure(&url, None).await, ErrorKind::TlsHandshake);
+    }
+
+    #[test]
+    fn io_error_kinds() {
+        let io = |kind| std::io::Error::new(kind, "from the socket");
+
+        assert_eq!(classify_error(&io(std::io::ErrorKind::ConnectionRefused)), ErrorKind::ConnectionRefused);
+        assert_eq!(classify_error(&io(std::io::ErrorKind::TimedOut)), ErrorKind::ConnectionTimeout);
+        assert_eq!(classify_error(&io(std::io::ErrorKind::Interrupted)), ErrorKind::Cancelled);
+        assert_eq!(classify_error(&io(std::io::ErrorKind::Other)), ErrorKind::Other);
+    }
+}
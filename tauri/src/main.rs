@@ -3,8 +3,14 @@
 
 mod utils;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Manager;
+
 static PORT: u16 = 3344;
 
+// set once the frontend has saved, so the next close request goes through
+static CLOSING: AtomicBool = AtomicBool::new(false);
+
 fn launch_cli() {
     std::thread::spawn(|| {
         let args = vec!["bolt".to_string(), "--tauri".to_string()];
@@ -13,10 +19,37 @@ fn launch_cli() {
     });
 }
 
+#[tauri::command]
+fn close_window(window: tauri::Window) {
+    CLOSING.store(true, Ordering::SeqCst);
+
+    let _ = window.close();
+}
+
 fn main() {
     launch_cli();
 
-    let app = tauri::Builder::default().invoke_handler(tauri::generate_handler![]);
+    let app = tauri::Builder::default()
+        .invoke_handler(tauri::generate_handler![close_window])
+        .setup(|app| {
+            if let Some(window) = app.get_window("main") {
+                utils::restore_geometry(&window);
+            }
+
+            Ok(())
+        })
+        // the frontend saves unsaved changes first, then calls close_window
+        .on_window_event(|event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event.event() {
+                // read while the window is still there, the frontend's save can take a moment
+                utils::save_geometry(event.window());
+
+                if !CLOSING.load(Ordering::SeqCst) {
+                    api.prevent_close();
+                    let _ = event.window().emit("bolt://close-requested", ());
+                }
+            }
+        });
 
     app.run(tauri::generate_context!()).unwrap();
 }
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use tauri::{PhysicalPosition, PhysicalSize, Position, Size, Window};
+
+// smaller than this and the layout falls apart, e.g. a geometry saved while minimized
+const MIN_WIDTH: u32 = 400;
+const MIN_HEIGHT: u32 = 300;
+
+// part of the title bar that has to stay on a monitor so the window can still be dragged back
+const VISIBLE_MARGIN: i32 = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct WindowGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+}
+
+// next to state.json, the frontend never needs it
+fn geometry_path() -> Option<std::path::PathBuf> {
+    dirs::home_dir().map(|home| home.join("bolt").join("window.json"))
+}
+
+pub fn save_geometry(window: &Window) {
+    let (Ok(position), Ok(size), Ok(maximized)) = (window.outer_position(), window.outer_size(), window.is_maximized()) else {
+        return;
+    };
+
+    // a minimized window reports a bogus position on some platforms, keep the last good one
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        return;
+    }
+
+    let Some(path) = geometry_path() else {
+        return;
+    };
+
+    // a maximized window fills the screen, remember the size it goes back to instead
+    let geometry = match (maximized, load_geometry(&path)) {
+        (true, Some(saved)) => WindowGeometry { maximized, ..saved },
+        _ => WindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized,
+        },
+    };
+
+    match serde_json::to_string(&geometry) {
+        Ok(json) => {
+            if let Err(err) = std::fs::write(&path, json) {
+                println!("could not save the window geometry to {}: {}", path.display(), err);
+            }
+        }
+        Err(err) => println!("could not serialize the window geometry: {}", err),
+    }
+}
+
+fn load_geometry(path: &std::path::Path) -> Option<WindowGeometry> {
+    let json = std::fs::read_to_string(path).ok()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+pub fn restore_geometry(window: &Window) {
+    let Some(geometry) = geometry_path().and_then(|path| load_geometry(&path)) else {
+        return;
+    };
+
+    let geometry = clamp_to_monitors(window, geometry);
+
+    let _ = window.set_size(Size::Physical(PhysicalSize {
+        width: geometry.width,
+        height: geometry.height,
+    }));
+    let _ = window.set_position(Position::Physical(PhysicalPosition {
+        x: geometry.x,
+        y: geometry.y,
+    }));
+
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+// the monitor the window was on may be gone, so pull it back onto one that is still connected
+fn clamp_to_monitors(window: &Window, mut geometry: WindowGeometry) -> WindowGeometry {
+    let monitors = window.available_monitors().unwrap_or_default();
+
+    let visible = monitors.iter().any(|monitor| {
+        let position = monitor.position();
+        let size = monitor.size();
+
+        geometry.x + VISIBLE_MARGIN > position.x
+            && geometry.x < position.x + size.width as i32 - VISIBLE_MARGIN
+            && geometry.y >= position.y
+            && geometry.y < position.y + size.height as i32 - VISIBLE_MARGIN
+    });
+
+    if visible {
+        return geometry;
+    }
+
+    let Some(monitor) = window.primary_monitor().ok().flatten().or_else(|| monitors.into_iter().next()) else {
+        return geometry;
+    };
+
+    let position = monitor.position();
+    let size = monitor.size();
+
+    geometry.width = geometry.width.clamp(MIN_WIDTH, size.width.max(MIN_WIDTH));
+    geometry.height = geometry.height.clamp(MIN_HEIGHT, size.height.max(MIN_HEIGHT));
+    geometry.x = position.x + (size.width.saturating_sub(geometry.width) / 2) as i32;
+    geometry.y = position.y + (size.height.saturating_sub(geometry.height) / 2) as i32;
+
+    geometry
+}
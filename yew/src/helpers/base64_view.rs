@@ -0,0 +1,75 @@
+// the response body with base64 undone, for the decoded view. a body that is one base64 blob
+// (optionally a quoted json string) is decoded whole, otherwise every json string that decodes
+// to readable text is replaced by that text. binary results are left encoded, there is nothing
+// to show for them.
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use serde_json::Value;
+
+// short strings like "test" or "user" happen to be valid base64 too
+const MIN_FIELD_LENGTH: usize = 8;
+
+pub fn decode_base64_body(body: &str) -> Result<String, String> {
+    let trimmed = body.trim();
+    let unquoted = trimmed
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(trimmed);
+
+    if let Some(text) = decode_text(unquoted) {
+        return Ok(text);
+    }
+
+    let mut value: Value = match serde_json::from_str(trimmed) {
+        Ok(value) => value,
+        Err(_) => return Err("the body is neither base64 nor json with base64 fields".to_string()),
+    };
+
+    if decode_fields(&mut value) == 0 {
+        return Err("no field of the body decodes to text".to_string());
+    }
+
+    serde_json::to_string_pretty(&value).map_err(|err| err.to_string())
+}
+
+// how many strings were replaced
+fn decode_fields(value: &mut Value) -> usize {
+    match value {
+        Value::String(text) if text.len() >= MIN_FIELD_LENGTH => match decode_text(text) {
+            Some(decoded) => {
+                *text = decoded;
+                1
+            }
+            None => 0,
+        },
+        Value::Array(items) => items.iter_mut().map(decode_fields).sum(),
+        Value::Object(fields) => fields.values_mut().map(decode_fields).sum(),
+        _ => 0,
+    }
+}
+
+// standard or url safe alphabet, padded or not, ignoring line breaks. None unless the bytes are
+// printable utf-8
+fn decode_text(text: &str) -> Option<String> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    if compact.is_empty() {
+        return None;
+    }
+
+    let bytes = [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(&compact).ok())?;
+
+    let decoded = String::from_utf8(bytes).ok()?;
+
+    let printable = decoded
+        .chars()
+        .all(|c| !c.is_control() || c.is_whitespace());
+
+    if printable {
+        Some(decoded)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,266 @@
+// "copy as code" snippets for the active request
+use crate::helpers::enums::HttpMethod;
+use crate::helpers::enums::HttpVersion;
+use crate::helpers::enums::Language;
+use crate::utils::{outgoing_headers, parse_url};
+use crate::Request;
+
+pub fn to_snippet(req: &Request, language: Language) -> String {
+    match language {
+        Language::Curl => to_curl(req),
+        Language::FetchJs => to_fetch_js(req),
+        Language::PythonRequests => to_python_requests(req),
+        Language::RustReqwest => to_rust_reqwest(req),
+    }
+}
+
+fn resolved_url(req: &Request) -> String {
+    parse_url(req.url.clone(), &req.params, req.raw_query)
+}
+
+pub fn to_curl(req: &Request) -> String {
+    let method = match &req.method {
+        // tokens may carry shell characters like & or |, plain names stay readable
+        HttpMethod::Custom(name) if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') => shell_quote(name),
+        method => method.to_string(),
+    };
+
+    let mut lines = vec![format!("curl -X {} {}", method, shell_quote(&resolved_url(req)))];
+
+    match req.http_version {
+        Some(HttpVersion::Http1) => lines.push("  --http1.1".to_string()),
+        Some(HttpVersion::Http2) => lines.push("  --http2".to_string()),
+        None => {}
+    }
+
+    for header in outgoing_headers(req) {
+        lines.push(format!(
+            "  -H {}",
+            shell_quote(&format!("{}: {}", header[0], header[1]))
+        ));
+    }
+
+    if let Some(path) = &req.body_file {
+        lines.push(format!("  --data-binary {}", shell_quote(&format!("@{path}"))));
+    } else if !req.body.is_empty() {
+        lines.push(format!("  --data-raw {}", shell_quote(&req.body)));
+    }
+
+    lines.join(" \\\n")
+}
+
+pub fn to_fetch_js(req: &Request) -> String {
+    // a file body only makes sense under node, where fs is there to read it
+    let mut snippet = match req.body_file {
+        Some(_) => "import { readFile } from \"node:fs/promises\";\n\n".to_string(),
+        None => String::new(),
+    };
+
+    snippet.push_str(&format!(
+        "const response = await fetch({}, {{\n  method: {},\n",
+        js_string(&resolved_url(req)),
+        js_string(&req.method.to_string())
+    ));
+
+    let headers = outgoing_headers(req);
+
+    if !headers.is_empty() {
+        snippet.push_str("  headers: {\n");
+
+        for header in headers {
+            snippet.push_str(&format!(
+                "    {}: {},\n",
+                js_string(&header[0]),
+                js_string(&header[1])
+            ));
+        }
+
+        snippet.push_str("  },\n");
+    }
+
+    if let Some(path) = &req.body_file {
+        snippet.push_str(&format!("  body: await readFile({}),\n", js_string(path)));
+    } else if !req.body.is_empty() {
+        snippet.push_str(&format!("  body: {},\n", js_string(&req.body)));
+    }
+
+    snippet.push_str("});\n\nconsole.log(response.status, await response.text());\n");
+
+    snippet
+}
+
+pub fn to_python_requests(req: &Request) -> String {
+    let mut snippet = format!(
+        "import requests\n\nresponse = requests.request(\n    {},\n    {},\n",
+        python_string(&req.method.to_string()),
+        python_string(&resolved_url(req))
+    );
+
+    let headers = outgoing_headers(req);
+
+    if !headers.is_empty() {
+        snippet.push_str("    headers={\n");
+
+        for header in headers {
+            snippet.push_str(&format!(
+                "        {}: {},\n",
+                python_string(&header[0]),
+                python_string(&header[1])
+            ));
+        }
+
+        snippet.push_str("    },\n");
+    }
+
+    if let Some(path) = &req.body_file {
+        snippet.push_str(&format!("    data=open({}, \"rb\"),\n", python_string(path)));
+    } else if !req.body.is_empty() {
+        snippet.push_str(&format!(
+            "    data={}.encode(\"utf-8\"),\n",
+            python_string(&req.body)
+        ));
+    }
+
+    snippet.push_str(")\n\nprint(response.status_code, response.text)\n");
+
+    snippet
+}
+
+pub fn to_rust_reqwest(req: &Request) -> String {
+    let method = match &req.method {
+        // COPY is a WebDAV verb reqwest has no constant for, neither are the ones typed in
+        HttpMethod::COPY | HttpMethod::Custom(_) => {
+            format!("reqwest::Method::from_bytes({}.as_bytes())?", rust_string(&req.method.to_string()))
+        }
+        method => format!("reqwest::Method::{method}"),
+    };
+
+    let mut snippet = format!(
+        "let client = reqwest::blocking::Client::new();\n\nlet response = client\n    .request({}, {})\n",
+        method,
+        rust_string(&resolved_url(req))
+    );
+
+    for header in outgoing_headers(req) {
+        snippet.push_str(&format!(
+            "    .header({}, {})\n",
+            rust_string(&header[0]),
+            rust_string(&header[1])
+        ));
+    }
+
+    if let Some(path) = &req.body_file {
+        snippet.push_str(&format!("    .body(std::fs::read({})?)\n", rust_string(path)));
+    } else if !req.body.is_empty() {
+        snippet.push_str(&format!("    .body({})\n", rust_string(&req.body)));
+    }
+
+    snippet.push_str("    .send()?;\n\nprintln!(\"{} {}\", response.status(), response.text()?);\n");
+
+    snippet
+}
+
+// single quotes, with embedded quotes closed, escaped and reopened
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// json string literals are valid javascript, unicode passes through untouched
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+fn python_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+// escape_debug already produces rust escape syntax
+fn rust_string(value: &str) -> String {
+    format!("\"{}\"", value.escape_debug())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // quotes of both kinds, a backslash, line breaks, a tab and some non-ascii text
+    const AWKWARD: &str = "it's \"quoted\" \\ a\r\nb\tc ünï 😀";
+
+    #[test]
+    fn shell_quotes_close_around_single_quotes() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        // everything else, newlines included, is literal inside single quotes
+        assert_eq!(shell_quote("a\nb \"c\" $HOME ü"), "'a\nb \"c\" $HOME ü'");
+    }
+
+    #[test]
+    fn js_strings_escape_quotes_and_line_breaks() {
+        assert_eq!(js_string(AWKWARD), "\"it's \\\"quoted\\\" \\\\ a\\r\\nb\\tc ünï 😀\"");
+        // valid in json, but they end a line inside a javascript string
+        assert_eq!(js_string("a\u{2028}b\u{2029}"), "\"a\\u2028b\\u2029\"");
+        assert_eq!(js_string("\u{7}"), "\"\\u0007\"");
+    }
+
+    #[test]
+    fn python_strings_escape_quotes_and_controls() {
+        assert_eq!(python_string(AWKWARD), "\"it's \\\"quoted\\\" \\\\ a\\r\\nb\\tc ünï 😀\"");
+        assert_eq!(python_string("\u{7}\u{1b}"), "\"\\u0007\\u001b\"");
+    }
+
+    #[test]
+    fn rust_strings_escape_quotes_and_line_breaks() {
+        assert_eq!(rust_string("it's \"quoted\" \\ a\r\nb\tc"), "\"it\\'s \\\"quoted\\\" \\\\ a\\r\\nb\\tc\"");
+        // printable unicode is left as is, controls become \u{..}
+        assert_eq!(rust_string("ünï 😀\u{7}"), "\"ünï 😀\\u{7}\"");
+    }
+
+    #[test]
+    fn snippets_carry_the_escaped_request() {
+        let mut req = Request::new();
+        req.method = HttpMethod::POST;
+        req.url = "https://api.test/it's".to_string();
+        req.headers = vec![vec!["X-Note".to_string(), "say \"hi\"".to_string()]];
+        req.body = "{\"name\": \"zoë\"}\n".to_string();
+
+        assert_eq!(
+            to_curl(&req),
+            "curl -X POST 'https://api.test/it'\\''s' \\\n  -H 'X-Note: say \"hi\"' \\\n  --data-raw '{\"name\": \"zoë\"}\n'"
+        );
+
+        let python = to_python_requests(&req);
+        assert!(python.contains("    \"https://api.test/it's\",\n"));
+        assert!(python.contains("        \"X-Note\": \"say \\\"hi\\\"\",\n"));
+        assert!(python.contains("    data=\"{\\\"name\\\": \\\"zoë\\\"}\\n\".encode(\"utf-8\"),\n"));
+    }
+
+    #[test]
+    fn custom_methods_are_quoted_when_they_need_it() {
+        let mut req = Request::new();
+        req.url = "http://a.test".to_string();
+
+        req.method = HttpMethod::Custom("PURGE".to_string());
+        assert!(to_curl(&req).starts_with("curl -X PURGE 'http://a.test'"));
+
+        req.method = HttpMethod::Custom("A&B".to_string());
+        assert!(to_curl(&req).starts_with("curl -X 'A&B' 'http://a.test'"));
+        assert!(to_rust_reqwest(&req).contains(".request(reqwest::Method::from_bytes(\"A&B\".as_bytes())?, \"http://a.test\")"));
+    }
+}
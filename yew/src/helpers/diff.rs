@@ -0,0 +1,206 @@
+// line and header diffs between two responses, used by the compare view
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderChange {
+    Added(String, String),
+    Removed(String, String),
+    // (name, old value, new value)
+    Changed(String, String, String),
+}
+
+// the lcs table is lines(old) * lines(new) cells, anything above this is not diffed
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+// diffs `old` against `new` line by line, None when the bodies are too large to compare
+pub fn diff_lines(old: &str, new: &str) -> Option<Vec<DiffLine>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lines shared at both ends don't need to go through the table
+    let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    if (old_mid.len() + 1).saturating_mul(new_mid.len() + 1) > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    let mut lines: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line.to_string()))
+        .collect();
+
+    lines.extend(lcs_diff(old_mid, new_mid));
+
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line.to_string())),
+    );
+
+    Some(lines)
+}
+
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let width = new.len() + 1;
+
+    // table[i * width + j] is the lcs length of old[i..] and new[j..]
+    let mut table = vec![0u32; (old.len() + 1) * width];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+
+    lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line.to_string())));
+    lines.extend(new[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+
+    lines
+}
+
+// header names compare case insensitively, unchanged headers are left out
+pub fn diff_headers(old: &[Vec<String>], new: &[Vec<String>]) -> Vec<HeaderChange> {
+    let find = |headers: &[Vec<String>], name: &str| {
+        headers
+            .iter()
+            .find(|header| header[0].eq_ignore_ascii_case(name))
+            .map(|header| header[1].clone())
+    };
+
+    let mut changes = Vec::new();
+
+    for header in old {
+        match find(new, &header[0]) {
+            None => changes.push(HeaderChange::Removed(header[0].clone(), header[1].clone())),
+            Some(value) if value != header[1] => {
+                changes.push(HeaderChange::Changed(header[0].clone(), header[1].clone(), value))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for header in new {
+        if find(old, &header[0]).is_none() {
+            changes.push(HeaderChange::Added(header[0].clone(), header[1].clone()));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(line: &str) -> DiffLine {
+        DiffLine::Same(line.to_string())
+    }
+
+    fn added(line: &str) -> DiffLine {
+        DiffLine::Added(line.to_string())
+    }
+
+    fn removed(line: &str) -> DiffLine {
+        DiffLine::Removed(line.to_string())
+    }
+
+    fn header(name: &str, value: &str) -> Vec<String> {
+        vec![name.to_string(), value.to_string()]
+    }
+
+    #[test]
+    fn equal_bodies_are_all_the_same() {
+        assert_eq!(diff_lines("a\nb", "a\nb"), Some(vec![same("a"), same("b")]));
+        assert_eq!(diff_lines("", ""), Some(vec![]));
+    }
+
+    #[test]
+    fn changed_lines_keep_their_place() {
+        let diff = diff_lines("{\n  \"id\": 1,\n  \"ok\": true\n}", "{\n  \"id\": 2,\n  \"ok\": true\n}");
+
+        assert_eq!(
+            diff,
+            Some(vec![
+                same("{"),
+                removed("  \"id\": 1,"),
+                added("  \"id\": 2,"),
+                same("  \"ok\": true"),
+                same("}"),
+            ])
+        );
+    }
+
+    #[test]
+    fn lines_only_on_one_side() {
+        assert_eq!(diff_lines("a\nc", "a\nb\nc"), Some(vec![same("a"), added("b"), same("c")]));
+        assert_eq!(diff_lines("a\nb\nc", "c"), Some(vec![removed("a"), removed("b"), same("c")]));
+        assert_eq!(diff_lines("", "x"), Some(vec![added("x")]));
+    }
+
+    #[test]
+    fn bodies_too_large_are_not_diffed() {
+        let old: String = (0..2001).map(|n| format!("old {n}\n")).collect();
+        let new: String = (0..2001).map(|n| format!("new {n}\n")).collect();
+
+        assert_eq!(diff_lines(&old, &new), None);
+
+        // shared ends are left out of the table, so a small change in a large body still diffs
+        let edited = old.replacen("old 1000\n", "changed\n", 1);
+        let diff = diff_lines(&old, &edited).unwrap();
+
+        assert_eq!(diff.len(), 2002);
+        assert_eq!(diff[1000], removed("old 1000"));
+        assert_eq!(diff[1001], added("changed"));
+    }
+
+    #[test]
+    fn header_names_ignore_case() {
+        let old = [header("Content-Type", "text/plain"), header("ETag", "1"), header("X-Old", "a")];
+        let new = [header("content-type", "text/plain"), header("etag", "2"), header("X-New", "b")];
+
+        assert_eq!(
+            diff_headers(&old, &new),
+            vec![
+                HeaderChange::Changed("ETag".to_string(), "1".to_string(), "2".to_string()),
+                HeaderChange::Removed("X-Old".to_string(), "a".to_string()),
+                HeaderChange::Added("X-New".to_string(), "b".to_string()),
+            ]
+        );
+    }
+}
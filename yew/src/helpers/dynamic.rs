@@ -0,0 +1,326 @@
+// built-in {{$...}} tokens filled in when a request is sent
+//
+//   {{$timestamp}}          unix time in seconds
+//   {{$isoTimestamp}}       current time as ISO 8601, in UTC
+//   {{$uuid}}               a random v4 uuid
+//   {{$randomInt}}          a random integer in 0..1000
+//   {{$randomInt min max}}  a random integer in min..max
+//
+// every occurrence gets its own value, except the time which is the same for the whole send.
+// unknown tokens are left as they are.
+
+use std::collections::BTreeMap;
+
+const DEFAULT_RANDOM_MAX: i64 = 1000;
+
+// {{name}} from the captured variables, names that aren't set stay as they are so they are easy to spot
+pub fn resolve_variables(text: &str, variables: &BTreeMap<String, String>) -> String {
+    if variables.is_empty() {
+        return text.to_string();
+    }
+
+    let mut resolved = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let token = &rest[start + 2..];
+        let end = match token.find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+
+        resolved.push_str(&rest[..start]);
+
+        match variables.get(token[..end].trim()) {
+            Some(value) => resolved.push_str(value),
+            None => resolved.push_str(&rest[start..start + 2 + end + 2]),
+        }
+
+        rest = &token[end + 2..];
+    }
+
+    resolved.push_str(rest);
+
+    resolved
+}
+
+// names of the {{...}} tokens still in a resolved text
+pub fn unresolved_tokens(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let token = &rest[start + 2..];
+        let end = match token.find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+
+        names.push(token[..end].trim().to_string());
+        rest = &token[end + 2..];
+    }
+
+    names
+}
+
+// the {{ being typed at the caret: where it starts and the part of the name typed after it so
+// far. None once the token is closed or the caret is past a space
+pub fn open_token(text: &str, caret: usize) -> Option<(usize, &str)> {
+    let before = text.get(..caret)?;
+    let start = before.rfind("{{")?;
+    let typed = &before[start + 2..];
+
+    if typed.chars().any(|c| c.is_whitespace() || c == '{' || c == '}') {
+        return None;
+    }
+
+    Some((start, typed))
+}
+
+// names starting with what was typed come first, then the ones merely containing it, shorter
+// before longer within each. case doesn't matter
+pub fn rank_variables<'a>(names: impl IntoIterator<Item = &'a str>, typed: &str, limit: usize) -> Vec<String> {
+    let typed = typed.to_lowercase();
+
+    let mut ranked: Vec<(bool, usize, &str)> = names
+        .into_iter()
+        .filter_map(|name| {
+            let lower = name.to_lowercase();
+
+            if lower.starts_with(&typed) {
+                Some((false, name.len(), name))
+            } else if lower.contains(&typed) {
+                Some((true, name.len(), name))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    ranked.sort();
+    ranked.dedup_by(|a, b| a.2 == b.2);
+
+    ranked.into_iter().take(limit).map(|(_, _, name)| name.to_string()).collect()
+}
+
+// swaps the token being typed for the whole {{name}}, reusing a }} already right after the caret.
+// also returns where the caret goes, just past the token
+pub fn complete_token(text: &str, start: usize, caret: usize, name: &str) -> (String, usize) {
+    let rest = &text[caret..];
+    let rest = rest.strip_prefix("}}").unwrap_or(rest);
+
+    let token = format!("{{{{{name}}}}}");
+    let caret = start + token.len();
+
+    (format!("{}{}{}", &text[..start], token, rest), caret)
+}
+
+// the text with its {{name}} tokens filled in, in pieces so the ones without a value can be told
+// apart (true). {{$...}} tokens get their value at send time and count as known
+pub fn resolved_parts(text: &str, variables: &BTreeMap<String, String>) -> Vec<(String, bool)> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let token = &rest[start + 2..];
+        let end = match token.find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+
+        if start > 0 {
+            parts.push((rest[..start].to_string(), false));
+        }
+
+        let name = token[..end].trim();
+
+        match variables.get(name) {
+            Some(value) => parts.push((value.clone(), false)),
+            None => parts.push((rest[start..start + 2 + end + 2].to_string(), !name.starts_with('$'))),
+        }
+
+        rest = &token[end + 2..];
+    }
+
+    if !rest.is_empty() {
+        parts.push((rest.to_string(), false));
+    }
+
+    parts
+}
+
+// what {{name}} means for one request: its own variables win over its collection's, which win
+// over the values captured at runtime. rows without a name are skipped
+pub fn layered_variables(
+    runtime: &BTreeMap<String, String>,
+    collection: &[Vec<String>],
+    local: &[Vec<String>],
+) -> BTreeMap<String, String> {
+    let mut merged = runtime.clone();
+
+    for row in collection.iter().chain(local) {
+        let name = row[0].trim();
+
+        if !name.is_empty() {
+            merged.insert(name.to_string(), row[1].clone());
+        }
+    }
+
+    merged
+}
+
+pub fn resolve_dynamic(text: &str, now_ms: f64) -> String {
+    let mut resolved = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{$") {
+        let token = &rest[start + 2..];
+        let end = match token.find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+
+        resolved.push_str(&rest[..start]);
+
+        match generate(token[..end].trim(), now_ms) {
+            Some(value) => resolved.push_str(&value),
+            None => resolved.push_str(&rest[start..start + 2 + end + 2]),
+        }
+
+        rest = &token[end + 2..];
+    }
+
+    resolved.push_str(rest);
+
+    resolved
+}
+
+fn generate(token: &str, now_ms: f64) -> Option<String> {
+    let mut parts = token.split_whitespace();
+
+    match parts.next()? {
+        "$timestamp" => Some(((now_ms / 1000.0) as u64).to_string()),
+        "$isoTimestamp" => Some(String::from(js_sys::Date::new(&now_ms.into()).to_iso_string())),
+        "$uuid" => Some(uuid_v4()),
+        "$randomInt" => {
+            let bounds: Vec<i64> = parts.map(|part| part.parse().ok()).collect::<Option<_>>()?;
+
+            let (min, max) = match bounds[..] {
+                [] => (0, DEFAULT_RANDOM_MAX),
+                [min, max] if min < max => (min, max),
+                _ => return None,
+            };
+
+            Some((min + (js_sys::Math::random() * (max - min) as f64) as i64).to_string())
+        }
+        _ => None,
+    }
+}
+
+fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+
+    for byte in bytes.iter_mut() {
+        *byte = (js_sys::Math::random() * 256.0) as u8;
+    }
+
+    // version 4, variant 10xx
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn rows(pairs: &[(&str, &str)]) -> Vec<Vec<String>> {
+        pairs.iter().map(|(name, value)| vec![name.to_string(), value.to_string()]).collect()
+    }
+
+    #[test]
+    fn nearer_layers_shadow_the_outer_ones() {
+        let runtime = map(&[("host", "runtime"), ("token", "captured"), ("id", "1")]);
+        let collection = rows(&[("host", "collection"), ("tenant", "acme")]);
+        let local = rows(&[("host", "local"), (" id ", "2")]);
+
+        assert_eq!(
+            layered_variables(&runtime, &collection, &local),
+            map(&[("host", "local"), ("id", "2"), ("tenant", "acme"), ("token", "captured")])
+        );
+
+        assert_eq!(
+            layered_variables(&runtime, &collection, &[]).get("host").map(String::as_str),
+            Some("collection")
+        );
+    }
+
+    #[test]
+    fn rows_without_a_name_are_skipped() {
+        let layered = layered_variables(&BTreeMap::new(), &rows(&[("", "x"), ("  ", "y")]), &rows(&[("a", "")]));
+
+        // an empty value is still a value
+        assert_eq!(layered, map(&[("a", "")]));
+    }
+
+    #[test]
+    fn undefined_names_stay_as_typed() {
+        let variables = map(&[("host", "a.test"), ("id", "7")]);
+
+        assert_eq!(
+            resolve_variables("https://{{host}}/users/{{ id }}?t={{token}}", &variables),
+            "https://a.test/users/7?t={{token}}"
+        );
+        assert_eq!(resolve_variables("{{ missing }} and {{host", &variables), "{{ missing }} and {{host");
+        assert_eq!(resolve_variables("{{host}}", &BTreeMap::new()), "{{host}}");
+
+        assert_eq!(
+            unresolved_tokens(&resolve_variables("{{host}}/{{ token }}/{{$uuid}}", &variables)),
+            vec!["token", "$uuid"]
+        );
+    }
+
+    #[test]
+    fn values_are_not_resolved_again() {
+        let variables = map(&[("a", "{{b}}"), ("b", "nested")]);
+
+        assert_eq!(resolve_variables("{{a}}", &variables), "{{b}}");
+    }
+
+    #[test]
+    fn parts_flag_the_names_without_a_value() {
+        let variables = map(&[("host", "a.test")]);
+
+        assert_eq!(
+            resolved_parts("http://{{host}}/{{id}}/{{$uuid}}", &variables),
+            vec![
+                ("http://".to_string(), false),
+                ("a.test".to_string(), false),
+                ("/".to_string(), false),
+                ("{{id}}".to_string(), true),
+                ("/".to_string(), false),
+                ("{{$uuid}}".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_dynamic_tokens_stay_as_typed() {
+        assert_eq!(resolve_dynamic("t={{$timestamp}}", 1_700_000_000_500.0), "t=1700000000");
+        assert_eq!(resolve_dynamic("{{$nope}} {{ $randomInt 5 1 }} {{name}}", 0.0), "{{$nope}} {{ $randomInt 5 1 }} {{name}}");
+    }
+}
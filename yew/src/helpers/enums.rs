@@ -2,7 +2,7 @@ use std::fmt;
 use serde::{Deserialize, Serialize};
 
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum HttpMethod {
     GET,
     POST,
@@ -13,6 +13,8 @@ pub enum HttpMethod {
     OPTIONS,
     CONNECT,
     COPY,
+    // typed in under "Other…", like PURGE or PROPFIND. not counted, the dropdown lists it separately
+    Custom(String),
 }
 
 impl From<usize> for HttpMethod {
@@ -36,6 +38,13 @@ impl HttpMethod {
     pub fn count() -> usize {
         9
     }
+
+    // dropdown order, custom methods after every known one
+    pub fn rank(&self) -> usize {
+        (0..HttpMethod::count())
+            .position(|index| HttpMethod::from(index) == *self)
+            .unwrap_or(HttpMethod::count())
+    }
 }
 
 impl fmt::Display for HttpMethod {
@@ -50,6 +59,7 @@ impl fmt::Display for HttpMethod {
             HttpMethod::OPTIONS => write!(f, "OPTIONS"),
             HttpMethod::COPY => write!(f, "COPY"),
             HttpMethod::CONNECT => write!(f, "CONNECT"),
+            HttpMethod::Custom(name) => write!(f, "{}", name),
         }
     }
 }
@@ -66,7 +76,7 @@ impl From<String> for HttpMethod {
             "options" => HttpMethod::OPTIONS,
             "copy" => HttpMethod::COPY,
             "connect" => HttpMethod::CONNECT,
-            _ => panic!("Invalid value for HttpMethod"),
+            _ => HttpMethod::Custom(string),
         }
     }
 }
@@ -83,14 +93,21 @@ impl From<HttpMethod> for String {
             HttpMethod::OPTIONS => "OPTIONS".to_string(),
             HttpMethod::COPY => "COPY".to_string(),
             HttpMethod::CONNECT => "CONNECT".to_string(),
+            HttpMethod::Custom(name) => name,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RequestTabs {
     Body,
     Params,
     Headers,
+    Docs,
+    Settings,
+    Auth,
+    Tests,
+    Preview,
 }
 
 impl From<u8> for RequestTabs {
@@ -99,24 +116,65 @@ impl From<u8> for RequestTabs {
             1 => RequestTabs::Body,
             2 => RequestTabs::Params,
             3 => RequestTabs::Headers,
+            4 => RequestTabs::Docs,
+            5 => RequestTabs::Settings,
+            6 => RequestTabs::Auth,
+            7 => RequestTabs::Tests,
+            8 => RequestTabs::Preview,
             _ => panic!("Invalid value for RequestTabs"),
         }
     }
 }
 
+impl RequestTabs {
+    // in the order the tab strip shows them
+    pub const ORDER: [RequestTabs; 8] = [
+        RequestTabs::Body,
+        RequestTabs::Params,
+        RequestTabs::Headers,
+        RequestTabs::Docs,
+        RequestTabs::Auth,
+        RequestTabs::Tests,
+        RequestTabs::Settings,
+        RequestTabs::Preview,
+    ];
+
+    pub fn element_id(self) -> &'static str {
+        match self {
+            RequestTabs::Body => "req_body_tab",
+            RequestTabs::Params => "req_params_tab",
+            RequestTabs::Headers => "req_headers_tab",
+            RequestTabs::Docs => "req_docs_tab",
+            RequestTabs::Settings => "req_settings_tab",
+            RequestTabs::Auth => "req_auth_tab",
+            RequestTabs::Tests => "req_tests_tab",
+            RequestTabs::Preview => "req_preview_tab",
+        }
+    }
+}
+
 impl From<RequestTabs> for u8 {
     fn from(tab: RequestTabs) -> Self {
         match tab {
             RequestTabs::Body => 1,
             RequestTabs::Params => 2,
             RequestTabs::Headers => 3,
+            RequestTabs::Docs => 4,
+            RequestTabs::Settings => 5,
+            RequestTabs::Auth => 6,
+            RequestTabs::Tests => 7,
+            RequestTabs::Preview => 8,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResponseTabs {
     Body,
     Headers,
+    Sent,
+    Raw,
+    Tls,
 }
 
 impl From<u8> for ResponseTabs {
@@ -124,16 +182,585 @@ impl From<u8> for ResponseTabs {
         match value {
             1 => ResponseTabs::Body,
             2 => ResponseTabs::Headers,
+            3 => ResponseTabs::Sent,
+            4 => ResponseTabs::Raw,
+            5 => ResponseTabs::Tls,
             _ => panic!("Invalid value for ResponseTabs"),
         }
     }
 }
 
+impl ResponseTabs {
+    // Tls stays last, the tab is only there for responses that came over TLS
+    pub const ORDER: [ResponseTabs; 5] = [ResponseTabs::Body, ResponseTabs::Headers, ResponseTabs::Sent, ResponseTabs::Raw, ResponseTabs::Tls];
+
+    pub fn element_id(self) -> &'static str {
+        match self {
+            ResponseTabs::Body => "resp_body_tab",
+            ResponseTabs::Headers => "resp_headers_tab",
+            ResponseTabs::Sent => "resp_sent_tab",
+            ResponseTabs::Raw => "resp_raw_tab",
+            ResponseTabs::Tls => "resp_tls_tab",
+        }
+    }
+}
+
 impl From<ResponseTabs> for u8 {
     fn from(tab: ResponseTabs) -> Self {
         match tab {
             ResponseTabs::Body => 1,
             ResponseTabs::Headers => 2,
+            ResponseTabs::Sent => 3,
+            ResponseTabs::Raw => 4,
+            ResponseTabs::Tls => 5,
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    Replace,
+    Merge,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    System,
+}
+
+impl Theme {
+    // the theme the navbar toggle switches to next
+    pub fn next(self) -> Self {
+        match self {
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::System,
+            Theme::System => Theme::Dark,
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Theme::Dark => write!(f, "Dark"),
+            Theme::Light => write!(f, "Light"),
+            Theme::System => write!(f, "System"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeHandle {
+    Sidebar,
+    Request,
+}
+
+// errors stay until dismissed, the rest go away after a few seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    pub fn class(&self) -> &'static str {
+        match self {
+            ToastLevel::Info => "toast toastinfo",
+            ToastLevel::Warning => "toast toastwarning",
+            ToastLevel::Error => "toast toasterror",
+        }
+    }
+}
+
+// whether the backend has the latest state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveStatus {
+    Saved,
+    Saving,
+    Failed(String),
+}
+
+// scrollable panels whose offset each request remembers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollPanel {
+    RequestBody,
+    ResponseBody,
+}
+
+impl ScrollPanel {
+    pub fn element_id(self) -> &'static str {
+        match self {
+            ScrollPanel::RequestBody => "reqbody",
+            ScrollPanel::ResponseBody => "respbody",
+        }
+    }
+}
+
+// by id, the list may be sorted or moved while the confirmation is up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteTarget {
+    Request(u64),
+    CollectionRequest(u64, u64),
+    Collection(u64),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthMode {
+    #[default]
+    None,
+    OAuth2ClientCredentials,
+    Digest,
+}
+
+impl AuthMode {
+    pub fn count() -> usize {
+        3
+    }
+}
+
+impl From<usize> for AuthMode {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => AuthMode::None,
+            1 => AuthMode::OAuth2ClientCredentials,
+            2 => AuthMode::Digest,
+            _ => panic!("Invalid index for AuthMode"),
+        }
+    }
+}
+
+impl fmt::Display for AuthMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthMode::None => write!(f, "No auth"),
+            AuthMode::OAuth2ClientCredentials => write!(f, "OAuth 2.0 client credentials"),
+            AuthMode::Digest => write!(f, "Digest"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryPolicy {
+    #[default]
+    NetworkErrors,
+    NetworkAndServerErrors,
+}
+
+impl RetryPolicy {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for RetryPolicy {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => RetryPolicy::NetworkErrors,
+            1 => RetryPolicy::NetworkAndServerErrors,
+            _ => panic!("Invalid index for RetryPolicy"),
+        }
+    }
+}
+
+impl fmt::Display for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryPolicy::NetworkErrors => write!(f, "Network errors"),
+            RetryPolicy::NetworkAndServerErrors => write!(f, "Network and 5xx errors"),
+        }
+    }
+}
+
+// how the response body is shown. Auto trusts the Content-Type, the others override a server
+// that labels its output wrong
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyFormat {
+    #[default]
+    Auto,
+    Json,
+    Xml,
+    Text,
+}
+
+impl BodyFormat {
+    pub fn count() -> usize {
+        4
+    }
+}
+
+impl From<usize> for BodyFormat {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => BodyFormat::Auto,
+            1 => BodyFormat::Json,
+            2 => BodyFormat::Xml,
+            3 => BodyFormat::Text,
+            _ => panic!("Invalid index for BodyFormat"),
+        }
+    }
+}
+
+impl fmt::Display for BodyFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodyFormat::Auto => write!(f, "Auto"),
+            BodyFormat::Json => write!(f, "JSON"),
+            BodyFormat::Xml => write!(f, "XML"),
+            BodyFormat::Text => write!(f, "Text"),
+        }
+    }
+}
+
+// which side makes the http call. the backend has no CORS and honours the proxy, tls and retry
+// settings, the browser sees exactly what a web client of the api would
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transport {
+    #[default]
+    Auto,
+    Backend,
+    BrowserFetch,
+}
+
+impl Transport {
+    pub fn count() -> usize {
+        3
+    }
+}
+
+impl From<usize> for Transport {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => Transport::Auto,
+            1 => Transport::Backend,
+            2 => Transport::BrowserFetch,
+            _ => panic!("Invalid index for Transport"),
+        }
+    }
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Transport::Auto => write!(f, "Auto"),
+            Transport::Backend => write!(f, "Backend"),
+            Transport::BrowserFetch => write!(f, "Browser fetch"),
+        }
+    }
+}
+
+// pinned per request, a request without one lets the client negotiate
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpVersion {
+    Http1,
+    Http2,
+}
+
+impl HttpVersion {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for HttpVersion {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => HttpVersion::Http1,
+            1 => HttpVersion::Http2,
+            _ => panic!("Invalid index for HttpVersion"),
+        }
+    }
+}
+
+impl fmt::Display for HttpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpVersion::Http1 => write!(f, "HTTP/1.1"),
+            HttpVersion::Http2 => write!(f, "HTTP/2"),
         }
     }
-}
\ No newline at end of file
+}
+
+// what sorting a collection orders its requests by. unlike SidebarSort this rewrites the stored order
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RequestOrder {
+    #[default]
+    Name,
+    Method,
+    Url,
+}
+
+impl RequestOrder {
+    pub fn count() -> usize {
+        3
+    }
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            RequestOrder::Name => "order.name",
+            RequestOrder::Method => "order.method",
+            RequestOrder::Url => "order.url",
+        }
+    }
+}
+
+impl From<usize> for RequestOrder {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => RequestOrder::Name,
+            1 => RequestOrder::Method,
+            2 => RequestOrder::Url,
+            _ => panic!("Invalid index for RequestOrder"),
+        }
+    }
+}
+
+// how the sidebars order requests, Manual is the order they are stored in
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SidebarSort {
+    #[default]
+    Manual,
+    Alphabetical,
+    ByMethod,
+    ByHost,
+    RecentlySent,
+    RecentlyModified,
+}
+
+impl SidebarSort {
+    pub fn count() -> usize {
+        6
+    }
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            SidebarSort::Manual => "sort.manual",
+            SidebarSort::Alphabetical => "sort.alphabetical",
+            SidebarSort::ByMethod => "sort.by_method",
+            SidebarSort::ByHost => "sort.by_host",
+            SidebarSort::RecentlySent => "sort.recently_sent",
+            SidebarSort::RecentlyModified => "sort.recently_modified",
+        }
+    }
+
+    // the sorts that put a heading over each group of requests
+    pub fn groups(self) -> bool {
+        matches!(self, SidebarSort::ByMethod | SidebarSort::ByHost)
+    }
+}
+
+impl From<usize> for SidebarSort {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => SidebarSort::Manual,
+            1 => SidebarSort::Alphabetical,
+            2 => SidebarSort::ByMethod,
+            3 => SidebarSort::ByHost,
+            4 => SidebarSort::RecentlySent,
+            5 => SidebarSort::RecentlyModified,
+            _ => panic!("Invalid index for SidebarSort"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Curl,
+    FetchJs,
+    PythonRequests,
+    RustReqwest,
+}
+
+impl Language {
+    pub fn count() -> usize {
+        4
+    }
+}
+
+impl From<usize> for Language {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => Language::Curl,
+            1 => Language::FetchJs,
+            2 => Language::PythonRequests,
+            3 => Language::RustReqwest,
+            _ => panic!("Invalid index for Language"),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::Curl => write!(f, "curl"),
+            Language::FetchJs => write!(f, "JavaScript fetch"),
+            Language::PythonRequests => write!(f, "Python requests"),
+            Language::RustReqwest => write!(f, "Rust reqwest"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SigningAlgorithm {
+    #[default]
+    Off,
+    HmacSha256,
+}
+
+impl SigningAlgorithm {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for SigningAlgorithm {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => SigningAlgorithm::Off,
+            1 => SigningAlgorithm::HmacSha256,
+            _ => panic!("Invalid index for SigningAlgorithm"),
+        }
+    }
+}
+
+impl fmt::Display for SigningAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningAlgorithm::Off => write!(f, "No signing"),
+            SigningAlgorithm::HmacSha256 => write!(f, "HMAC-SHA256"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignedContent {
+    #[default]
+    CanonicalRequest,
+    Body,
+}
+
+impl SignedContent {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for SignedContent {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => SignedContent::CanonicalRequest,
+            1 => SignedContent::Body,
+            _ => panic!("Invalid index for SignedContent"),
+        }
+    }
+}
+
+impl fmt::Display for SignedContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignedContent::CanonicalRequest => write!(f, "Canonical request"),
+            SignedContent::Body => write!(f, "Body only"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+impl SignatureEncoding {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for SignatureEncoding {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => SignatureEncoding::Hex,
+            1 => SignatureEncoding::Base64,
+            _ => panic!("Invalid index for SignatureEncoding"),
+        }
+    }
+}
+
+impl fmt::Display for SignatureEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureEncoding::Hex => write!(f, "Hex"),
+            SignatureEncoding::Base64 => write!(f, "Base64"),
+        }
+    }
+}
+
+// the language of the interface, not to be confused with the snippet Language
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    English,
+    Arabic,
+}
+
+impl Lang {
+    pub fn count() -> usize {
+        2
+    }
+
+    pub fn is_rtl(self) -> bool {
+        matches!(self, Lang::Arabic)
+    }
+}
+
+impl From<usize> for Lang {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => Lang::English,
+            1 => Lang::Arabic,
+            _ => panic!("Invalid index for Lang"),
+        }
+    }
+}
+
+// each language by its own name, so it can be found without reading the current one
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lang::English => write!(f, "English"),
+            Lang::Arabic => write!(f, "العربية"),
+        }
+    }
+}
+
+// the inputs that suggest {{variables}} while typing in them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocompleteField {
+    Url,
+    HeaderValue(usize),
+    ParamValue(usize),
+}
+
+impl AutocompleteField {
+    pub fn input_id(&self) -> String {
+        match self {
+            AutocompleteField::Url => "urlinput".to_string(),
+            AutocompleteField::HeaderValue(index) => format!("headervalue{index}"),
+            AutocompleteField::ParamValue(index) => format!("paramvalue{index}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocompleteAction {
+    // the input changed, what is around its caret decides the suggestions
+    Typed(AutocompleteField),
+    Next,
+    Previous,
+    // the highlighted suggestion, or the one clicked
+    Accept(Option<usize>),
+    Dismiss,
+}
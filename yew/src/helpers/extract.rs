@@ -0,0 +1,144 @@
+// pulls values out of a response for capture rules, the source of a rule can be:
+//
+//   data.items[0].id       a path like the ones the tree view copies
+//   /data/items/0/id       a JSON pointer
+//   header:X-Request-Id    a response header, the name is case insensitive
+//
+// strings come out as they are, numbers, booleans and objects as their json text.
+use crate::helpers::json_path::{lookup, value_text};
+use serde_json::Value;
+
+const HEADER_PREFIX: &str = "header:";
+
+// [variable, value] for every rule whose source is present in the response
+pub fn extract_all(rules: &[Vec<String>], body: &str, headers: &[Vec<String>]) -> Vec<(String, String)> {
+    let rules: Vec<&Vec<String>> = rules
+        .iter()
+        .filter(|rule| !rule[0].trim().is_empty() && !rule[1].trim().is_empty())
+        .collect();
+
+    if rules.is_empty() {
+        return Vec::new();
+    }
+
+    // only parsed when a rule reads the body
+    let mut parsed: Option<Option<Value>> = None;
+
+    rules
+        .into_iter()
+        .filter_map(|rule| {
+            let source = rule[1].trim();
+
+            let value = match source.strip_prefix(HEADER_PREFIX) {
+                Some(name) => header_value(headers, name.trim()),
+                None => {
+                    let body = parsed.get_or_insert_with(|| serde_json::from_str(body).ok());
+                    body.as_ref().and_then(|body| body_value(body, source))
+                }
+            };
+
+            value.map(|value| (rule[0].trim().to_string(), value))
+        })
+        .collect()
+}
+
+pub fn body_value(body: &Value, source: &str) -> Option<String> {
+    let value = if source.starts_with('/') {
+        body.pointer(source)
+    } else {
+        lookup(body, source)
+    };
+
+    value.map(value_text)
+}
+
+// the first header with that name
+pub fn header_value(headers: &[Vec<String>], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|header| header.len() == 2 && header[0].eq_ignore_ascii_case(name))
+        .map(|header| header[1].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{"data": {"items": [{"id": 7, "name": "first"}], "ok": true, "ratio": 0.5, "next": null}}"#;
+
+    fn rule(variable: &str, source: &str) -> Vec<String> {
+        vec![variable.to_string(), source.to_string()]
+    }
+
+    fn headers() -> Vec<Vec<String>> {
+        vec![
+            vec!["X-Request-Id".to_string(), "abc".to_string()],
+            vec!["x-request-id".to_string(), "second".to_string()],
+        ]
+    }
+
+    fn extract(source: &str) -> Option<String> {
+        extract_all(&[rule("var", source)], BODY, &headers())
+            .pop()
+            .map(|(_, value)| value)
+    }
+
+    #[test]
+    fn paths_and_pointers_find_the_same_values() {
+        assert_eq!(extract("data.items[0].name").as_deref(), Some("first"));
+        assert_eq!(extract("$.data.items[0].name").as_deref(), Some("first"));
+        assert_eq!(extract("/data/items/0/name").as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn numbers_and_other_values_come_out_as_json() {
+        assert_eq!(extract("data.items[0].id").as_deref(), Some("7"));
+        assert_eq!(extract("/data/ratio").as_deref(), Some("0.5"));
+        assert_eq!(extract("data.ok").as_deref(), Some("true"));
+        assert_eq!(extract("data.next").as_deref(), Some("null"));
+        assert_eq!(extract("data.items[0]").as_deref(), Some(r#"{"id":7,"name":"first"}"#));
+    }
+
+    #[test]
+    fn missing_paths_capture_nothing() {
+        assert_eq!(extract("data.items[1].id"), None);
+        assert_eq!(extract("data.missing"), None);
+        assert_eq!(extract("/data/items/x"), None);
+        // malformed paths, and a header that isn't there
+        assert_eq!(extract("data..id"), None);
+        assert_eq!(extract("data.items[x]"), None);
+        assert_eq!(extract("header:X-Missing"), None);
+    }
+
+    #[test]
+    fn headers_match_the_first_of_that_name() {
+        assert_eq!(extract("header:x-request-id").as_deref(), Some("abc"));
+        assert_eq!(extract("header: X-REQUEST-ID ").as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn only_complete_rules_run() {
+        let rules = [
+            rule(" id ", " data.items[0].id "),
+            rule("", "data.ok"),
+            rule("ok", "  "),
+            rule("missing", "data.missing"),
+            rule("request", "header:X-Request-Id"),
+        ];
+
+        assert_eq!(
+            extract_all(&rules, BODY, &headers()),
+            vec![("id".to_string(), "7".to_string()), ("request".to_string(), "abc".to_string())]
+        );
+    }
+
+    #[test]
+    fn a_body_that_isnt_json_still_gives_headers() {
+        let rules = [rule("id", "data.id"), rule("request", "header:X-Request-Id")];
+
+        assert_eq!(
+            extract_all(&rules, "<html>not json</html>", &headers()),
+            vec![("request".to_string(), "abc".to_string())]
+        );
+    }
+}
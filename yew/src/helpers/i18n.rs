@@ -0,0 +1,543 @@
+// interface strings by key, one row per key with the text of every language, so a language can't
+// lack a key. an empty translation falls back to English, "{}" marks where tr_args puts its values.
+use crate::helpers::enums::Lang;
+use std::cell::Cell;
+
+thread_local! {
+    // views have no context to read it from, it follows the settings
+    static CURRENT: Cell<Lang> = const { Cell::new(Lang::English) };
+}
+
+// key, English, Arabic
+static STRINGS: &[(&str, &str, &str)] = &[
+    ("nav.switch_theme", "Switch theme", "تبديل المظهر"),
+    ("nav.find_replace", "Find and replace", "بحث واستبدال"),
+    ("nav.export", "Export workspace", "تصدير مساحة العمل"),
+    ("nav.import", "Import workspace", "استيراد مساحة العمل"),
+    ("nav.import_http", "Import .http file", "استيراد ملف http."),
+    ("nav.help", "Help", "مساعدة"),
+    ("nav.settings", "Settings", "الإعدادات"),
+
+    ("save.saved", "Saved", "محفوظ"),
+    ("save.unsaved", "Unsaved changes", "تغييرات غير محفوظة"),
+    ("save.now", "Save now", "حفظ الآن"),
+    ("save.failed", "Could not save: {}", "تعذر الحفظ: {}"),
+    ("save.retry", "Retry", "إعادة المحاولة"),
+    ("save.leave_unsaved", "Your latest changes could not be saved", "تعذر حفظ آخر تغييراتك"),
+    ("save.close_unsaved", "Your latest changes could not be saved. Close anyway?", "تعذر حفظ آخر تغييراتك. الإغلاق على أي حال؟"),
+
+    ("toast.dismiss", "Dismiss", "إغلاق"),
+    ("toast.copied", "Copied to the clipboard", "تم النسخ إلى الحافظة"),
+    ("toast.save_failed", "Failed to save state: {}", "تعذر حفظ الحالة: {}"),
+    ("toast.send_failed", "Could not reach the backend: {}", "تعذر الوصول إلى الخادم الخلفي: {}"),
+    ("toast.dropped_headers", "{} removed, the client sets it from the body", "تمت إزالة {}، يحدده العميل من المتن"),
+    ("toast.bad_response", "Dropped a response that could not be read: {}", "تم تجاهل استجابة تعذرت قراءتها: {}"),
+    ("toast.bad_token_response", "Could not read the token response: {}", "تعذرت قراءة استجابة الرمز: {}"),
+    ("toast.invalid_url", "Not sent, the URL is invalid: {}", "لم يُرسل الطلب، العنوان غير صالح: {}"),
+    ("toast.invalid_method", "Not sent, the method is invalid: {}", "لم يُرسل الطلب، الطريقة غير صالحة: {}"),
+    ("toast.host_reachable", "The host answered in {} ms", "أجاب المضيف خلال {} مللي ثانية"),
+    ("toast.host_unreachable", "The host is not reachable: {}", "لا يمكن الوصول إلى المضيف: {}"),
+    ("toast.transport_fallback", "The backend could not be reached, sent from the browser instead: {}", "تعذر الوصول إلى الخادم الخلفي، أُرسل الطلب من المتصفح بدلاً منه: {}"),
+    ("transport.fetch_failed", "The browser could not complete the request, a CORS block is the usual cause: {}", "تعذر على المتصفح إكمال الطلب، والسبب المعتاد هو حظر CORS: {}"),
+    ("transport.no_body_file", "A body file can only be sent through the backend", "لا يمكن إرسال ملف المحتوى إلا عبر الخادم الخلفي"),
+    ("toast.imported_workspace", "Imported {} collections and {} requests", "تم استيراد {} مجموعات و{} طلبات"),
+    ("toast.imported_collection", "Imported {} with {} requests", "تم استيراد {} مع {} طلبات"),
+    ("toast.import_failed", "Could not import: {}", "تعذر الاستيراد: {}"),
+    ("toast.import_fetch_failed", "Could not fetch the document ({}): {}", "تعذر جلب المستند ({}): {}"),
+    ("import.no_status", "no response", "لا استجابة"),
+    ("import.replace_prompt", "Replace the current workspace with the imported file?", "هل تريد استبدال مساحة العمل الحالية بالملف المستورد؟"),
+    ("import.merge_prompt", "Merge the imported file into the current workspace instead?\n\nPress Cancel to leave the workspace as it is.", "هل تريد دمج الملف المستورد في مساحة العمل الحالية بدلا من ذلك؟\n\nاضغط إلغاء لترك مساحة العمل كما هي."),
+    ("import.workspace_failed", "Could not import workspace: {}", "تعذر استيراد مساحة العمل: {}"),
+    ("import.not_workspace", "not a valid Bolt workspace file", "ليس ملف مساحة عمل Bolt صالحا"),
+    ("import.no_version", "workspace file has no version", "ملف مساحة العمل بلا إصدار"),
+    ("import.unsupported_version", "workspace version {} is not supported (expected {})", "إصدار مساحة العمل {} غير مدعوم (المتوقع {})"),
+    ("import.invalid_state", "invalid state file: {}", "ملف حالة غير صالح: {}"),
+    ("import.invalid_workspace", "invalid workspace file: {}", "ملف مساحة عمل غير صالح: {}"),
+    ("import.url_prompt", "URL of a Bolt workspace, Postman collection or OpenAPI spec", "عنوان مساحة عمل Bolt أو مجموعة Postman أو مواصفة OpenAPI"),
+    ("import.openapi_prompt", "OpenAPI spec URL (leave empty to choose a file)", "عنوان مواصفة OpenAPI (اتركه فارغا لاختيار ملف)"),
+    ("import.openapi_failed", "Could not import the OpenAPI spec: {}", "تعذر استيراد مواصفة OpenAPI: {}"),
+    ("import.spec_fetch_failed", "Could not fetch the spec ({}): {}", "تعذر جلب المواصفة ({}): {}"),
+    ("toast.restore_failed", "Could not restore the saved state, starting fresh: {}", "تعذرت استعادة الحالة المحفوظة، البدء من جديد: {}"),
+
+    ("sidebar.requests", "Requests", "الطلبات"),
+    ("sidebar.collections", "Collections", "المجموعات"),
+    ("sidebar.pinned", "Pinned", "المثبتة"),
+    ("sidebar.modified", "Edited since the last save", "عُدّل منذ آخر حفظ"),
+    ("sidebar.new_request", "New request", "طلب جديد"),
+    ("sidebar.new_collection", "New collection", "مجموعة جديدة"),
+    ("sidebar.add_request", "Add a request", "إضافة طلب"),
+    ("sidebar.import_url", "Import from URL", "استيراد من عنوان"),
+    ("sidebar.import_openapi", "Import an OpenAPI spec", "استيراد مواصفات OpenAPI"),
+    ("sidebar.run", "Run every request in order", "تشغيل كل الطلبات بالترتيب"),
+    ("sidebar.run_parallel", "Run every request at once", "تشغيل كل الطلبات معاً"),
+    ("sidebar.tag_filter", "Only show requests with this tag", "إظهار الطلبات التي تحمل هذا الوسم فقط"),
+    ("sidebar.all_tags", "All tags", "كل الوسوم"),
+    ("tags.add", "Add tags: smoke, wip", "إضافة وسوم: smoke, wip"),
+    ("tags.remove", "Remove the {} tag", "حذف الوسم {}"),
+    ("sidebar.sort_collection", "Sort the requests by {}", "ترتيب الطلبات حسب {}"),
+    ("sidebar.duplicate", "Duplicate the collection", "نسخ المجموعة"),
+    ("sidebar.no_host", "No host", "بلا مضيف"),
+    ("sidebar.sort", "Sort requests", "ترتيب الطلبات"),
+    ("sidebar.dismiss_samples", "Remove the example requests", "إزالة الطلبات التوضيحية"),
+    ("sidebar.move_up", "Move the collection up", "نقل المجموعة للأعلى"),
+    ("sidebar.move_down", "Move the collection down", "نقل المجموعة للأسفل"),
+    ("sidebar.add_folder", "Add a folder", "إضافة مجلد"),
+    ("sidebar.add_subfolder", "Add a subfolder", "إضافة مجلد فرعي"),
+    ("sidebar.rename", "Rename", "إعادة تسمية"),
+    ("sidebar.remove_folder", "Remove the folder, keeping its requests", "حذف المجلد مع الإبقاء على طلباته"),
+    ("sidebar.move_folder", "Move into a folder", "نقل إلى مجلد"),
+    ("sidebar.pin", "Pin to the top", "تثبيت في الأعلى"),
+    ("sidebar.unpin", "Unpin", "إلغاء التثبيت"),
+    ("sidebar.send_failed", "The last send failed", "فشل آخر إرسال"),
+
+    ("folder.name_prompt", "Folder name", "اسم المجلد"),
+    ("folder.default_name", "New Folder", "مجلد جديد"),
+    ("folder.none_yet", "This collection has no folders yet, add one from the collection's folder icon", "لا توجد مجلدات في هذه المجموعة بعد، أضف واحدا من أيقونة المجلد في المجموعة"),
+    ("folder.move_prompt", "Move into which folder? Leave empty for the top level.\n\n{}", "إلى أي مجلد تريد النقل؟ اتركه فارغا للمستوى الأعلى.\n\n{}"),
+    ("folder.not_found", "There is no folder called \"{}\"", "لا يوجد مجلد باسم \"{}\""),
+
+    ("req.send", "Send", "إرسال"),
+    ("req.repeat", "Repeat", "تكرار"),
+    ("req.repeat_title", "Send this request repeatedly", "إرسال هذا الطلب بشكل متكرر"),
+    ("req.stop_polling", "Stop polling", "إيقاف التكرار"),
+    ("req.stop_interval", "Stop ({}s)", "إيقاف ({} ث)"),
+    ("req.polling_interval", "Polling interval in ms", "الفاصل بين الإرسالات بالمللي ثانية"),
+    ("req.copy_as", "Copy as...", "نسخ كـ..."),
+    ("req.copy_as_title", "Copy this request as code", "نسخ هذا الطلب كشيفرة"),
+    ("req.base_url", "Base URL for /paths", "العنوان الأساسي للمسارات /"),
+    ("req.collection_base_url", "Collection base URL for /paths", "العنوان الأساسي للمجموعة للمسارات /"),
+    ("req.effective_url", "The URL this request is sent to", "العنوان الذي يُرسل إليه هذا الطلب"),
+    ("req.insecure", "insecure", "غير آمن"),
+    ("req.created", "Created {}", "أُنشئ {}"),
+    ("req.modified", "Modified {}", "عُدّل {}"),
+    ("req.last_sent", "Last sent {}", "أُرسل آخر مرة {}"),
+    ("req.invalid_url", "invalid URL", "عنوان غير صالح"),
+    ("req.check_host", "Ping", "فحص"),
+    ("req.check_host_title", "Check that the host answers, without sending the request", "التحقق من أن المضيف يستجيب دون إرسال الطلب"),
+
+    ("url.empty", "the URL is empty", "العنوان فارغ"),
+    ("url.missing_host", "missing host", "المضيف مفقود"),
+    ("url.unsupported_scheme", "unsupported scheme {}", "المخطط {} غير مدعوم"),
+    ("url.invalid_port", "invalid port {}", "المنفذ {} غير صالح"),
+    ("url.invalid_host", "invalid host {}", "المضيف {} غير صالح"),
+    ("req.other_method", "Other…", "أخرى…"),
+    ("req.custom_method", "Method name", "اسم الطريقة"),
+    ("req.invalid_method", "invalid method", "طريقة غير صالحة"),
+    ("method.empty", "enter a method name", "أدخل اسم الطريقة"),
+    ("method.invalid_char", "{} is not allowed in a method name", "{} غير مسموح في اسم الطريقة"),
+    ("req.insecure_title", "TLS certificate verification is disabled for this request", "التحقق من شهادة TLS معطل لهذا الطلب"),
+
+    ("tab.body", "Body", "المحتوى"),
+    ("tab.params", "Params", "المعاملات"),
+    ("tab.headers", "Headers", "الترويسات"),
+    ("tab.docs", "Docs", "التوثيق"),
+    ("tab.auth", "Auth", "المصادقة"),
+    ("tab.tests", "Tests", "الاختبارات"),
+    ("tab.settings", "Settings", "الإعدادات"),
+    ("tab.preview", "Preview", "معاينة"),
+
+    ("body.missing_content_type", "The body looks like {} but no Content-Type header is set", "يبدو المحتوى من نوع {} لكن لم تُضبط ترويسة Content-Type"),
+    ("body.add_header", "Add header", "إضافة الترويسة"),
+    ("body.dismiss", "Dismiss", "تجاهل"),
+    ("body.ignored_on_method", "Most servers ignore a body on {} requests, it is still sent", "تتجاهل معظم الخوادم المحتوى في طلبات {}، لكنه يُرسل مع ذلك"),
+    ("body.file_unreadable", "Can't read this file", "تتعذر قراءة هذا الملف"),
+    ("body.check_size", "Check size", "فحص الحجم"),
+    ("body.clear_file", "Clear", "مسح"),
+    ("body.clear_file_title", "Go back to the inline body", "العودة إلى المحتوى المكتوب"),
+    ("body.file_note", "The file is read from disk when the request is sent", "يُقرأ الملف من القرص عند إرسال الطلب"),
+    ("body.file_placeholder", "Send a file: /path/to/payload.bin", "إرسال ملف: /path/to/payload.bin"),
+    ("body.load_file", "Load file...", "تحميل ملف..."),
+    ("body.load_file_title", "Load a file into the editor", "تحميل ملف إلى المحرر"),
+    ("body.placeholder", "Request body", "محتوى الطلب"),
+    ("body.large_file", "This file is {}, loading it into the editor may freeze Bolt. Sending it from disk avoids that. Load it anyway?", "حجم هذا الملف {}، وقد يؤدي تحميله إلى المحرر إلى تجميد Bolt. إرساله من القرص يتجنب ذلك. هل تريد تحميله على أي حال؟"),
+
+    ("params.key", "Key", "المفتاح"),
+    ("params.query_title", "Everything after the ?, edits replace the rows below", "كل ما بعد ?، التعديلات تستبدل الصفوف أدناه"),
+    ("params.raw_query", "Don't re-encode", "دون إعادة ترميز"),
+    ("params.raw_query_title", "Send the params exactly as typed, for values that are already percent-encoded", "إرسال المعاملات كما كُتبت تماما، للقيم المرمّزة مسبقا"),
+
+    ("docs.edit", "Edit", "تحرير"),
+    ("docs.preview", "Preview", "معاينة"),
+    ("docs.placeholder", "Describe this request (markdown)", "صف هذا الطلب (markdown)"),
+
+    ("secret.reveal", "Reveal secrets", "إظهار القيم السرية"),
+    ("secret.hide", "Hide secrets", "إخفاء القيم السرية"),
+    ("secret.reveal_title", "Secret values are hidden on screen and left out of exports until revealed", "تُخفى القيم السرية على الشاشة وتُستبعد من التصدير حتى يتم إظهارها"),
+    ("secret.mark", "Mark as secret", "تعيين كقيمة سرية"),
+    ("secret.plain_variable", "Secret, click to make it a plain variable", "سري، انقر لجعله متغيراً عادياً"),
+    ("secret.plain_header", "Secret, click to make it a plain header", "سرية، انقر لجعلها ترويسة عادية"),
+    ("secret.plain_param", "Secret, click to make it a plain param", "سري، انقر لجعله معاملاً عادياً"),
+
+    ("auth.field", "Field", "الحقل"),
+    ("auth.fetch_token", "Fetch token", "جلب الرمز"),
+    ("auth.token_url", "Token URL", "عنوان الرمز"),
+    ("auth.client_id", "Client ID", "معرّف العميل"),
+    ("auth.client_secret", "Client secret", "سر العميل"),
+    ("auth.scopes", "Scopes", "النطاقات"),
+    ("auth.remember_secret", "Remember secret", "تذكر السر"),
+    ("auth.username", "Username", "اسم المستخدم"),
+    ("auth.password", "Password", "كلمة المرور"),
+    ("auth.remember_password", "Remember password", "تذكر كلمة المرور"),
+    ("auth.fetching", "Fetching token...", "جارٍ جلب الرمز..."),
+    ("auth.token_failed", "Token request failed", "فشل طلب الرمز"),
+    ("auth.no_token", "No token yet, one is fetched on the next send", "لا يوجد رمز بعد، سيُجلب عند الإرسال التالي"),
+    ("auth.token_valid_for", "Token valid for {}s", "الرمز صالح لمدة {} ث"),
+    ("auth.token_expired", "Token expired, it is refreshed on the next send", "انتهت صلاحية الرمز، سيُجدد عند الإرسال التالي"),
+    ("auth.token_valid", "Token valid", "الرمز صالح"),
+    ("auth.sign_secret", "Secret", "السر"),
+    ("auth.sign_header", "Header", "الترويسة"),
+    ("auth.sign_content", "Sign", "التوقيع على"),
+    ("auth.sign_encoding", "Encoding", "الترميز"),
+    ("auth.canonical_note", "Signs the method, the path with its query and the SHA-256 of the body, one per line", "يوقّع الطريقة والمسار مع الاستعلام وبصمة SHA-256 للمحتوى، كل منها في سطر"),
+
+    ("tests.variable", "Variable", "المتغير"),
+    ("tests.local_title", "Variables for this request only, they win over captured ones", "متغيرات لهذا الطلب فقط، وتتقدم على القيم الملتقطة"),
+    ("tests.collection_title", "Variables for every request in this collection", "متغيرات لكل طلبات هذه المجموعة"),
+    ("tests.precedence", "A {{name}} is looked up in this request's variables first, then the collection's, then the captured values", "يُبحث عن {{name}} في متغيرات هذا الطلب أولاً، ثم متغيرات المجموعة، ثم القيم الملتقطة"),
+    ("tests.capture_title", "Capture into variables, use them as {{name}} in other requests", "التقط قيماً في متغيرات واستخدمها كـ {{name}} في طلبات أخرى"),
+    ("tests.clear_values", "Clear values", "مسح القيم"),
+    ("tests.clear_values_title", "Forget every captured value", "نسيان كل القيم الملتقطة"),
+    ("tests.source", "JSON path", "مسار JSON"),
+    ("tests.source_placeholder", "data.token, /data/token or header:X-Token", "data.token أو /data/token أو header:X-Token"),
+    ("tests.current_value", "Current value", "القيمة الحالية"),
+    ("tests.captured", "Variables captured so far", "المتغيرات الملتقطة حتى الآن"),
+    ("tests.runtime", "runtime", "وقت التشغيل"),
+    ("tests.overridden", "overridden", "مُتجاوَز"),
+    ("tests.overridden_local", "A variable of this request takes its place", "يحل محله متغير من هذا الطلب"),
+    ("tests.overridden_collection", "A variable of the collection takes its place", "يحل محله متغير من المجموعة"),
+    ("tests.schema_placeholder", "JSON Schema the response body should match", "مخطط JSON الذي يجب أن يطابقه محتوى الاستجابة"),
+    ("tests.schema_pass", "The last response matches the schema", "آخر استجابة تطابق المخطط"),
+
+    ("reqsettings.proxy_placeholder", "Use global proxy", "استخدام الوكيل العام"),
+    ("reqsettings.user_agent", "User-Agent", "User-Agent"),
+    ("reqsettings.user_agent_placeholder", "Use global User-Agent", "استخدام User-Agent العام"),
+    ("reqsettings.no_user_agent", "No User-Agent", "بدون User-Agent"),
+    ("reqsettings.no_user_agent_title", "Not even a User-Agent header row, requests sent through the browser still carry its own", "ولا حتى صف ترويسة User-Agent، والطلبات المرسلة عبر المتصفح تحمل ترويسته الخاصة"),
+    ("reqsettings.retries", "Retries", "عدد المحاولات الإضافية"),
+    ("reqsettings.retry_on", "Retry on", "إعادة المحاولة عند"),
+    ("reqsettings.transport", "Send through", "الإرسال عبر"),
+    ("reqsettings.transport_title", "Auto uses the backend and only falls back to the browser when the backend can't be reached. The browser path is subject to CORS and ignores the proxy, TLS and retry settings", "الوضع التلقائي يستخدم الخادم الخلفي ولا يلجأ إلى المتصفح إلا إذا تعذر الوصول إليه. الإرسال عبر المتصفح يخضع لقيود CORS ويتجاهل إعدادات الوكيل وTLS وإعادة المحاولة"),
+    ("reqsettings.http_version", "HTTP version", "إصدار HTTP"),
+    ("reqsettings.auto", "Auto", "تلقائي"),
+    ("reqsettings.conditional", "Conditional requests", "الطلبات المشروطة"),
+    ("reqsettings.conditional_title", "Sends If-None-Match and If-Modified-Since from the last response's ETag and Last-Modified", "يرسل If-None-Match وIf-Modified-Since من ETag وLast-Modified في آخر استجابة"),
+    ("reqsettings.validators", "Validators", "قيم التحقق"),
+    ("reqsettings.insecure", "Skip TLS verification", "تخطي التحقق من TLS"),
+    ("reqsettings.ca_cert", "CA certificate", "شهادة الجهة المصدقة"),
+    ("reqsettings.client_cert", "Client certificate", "شهادة العميل"),
+    ("reqsettings.client_key", "Client key", "مفتاح العميل"),
+
+    ("a11y.request_list", "Requests, use the arrow keys to move and Enter to send", "الطلبات، استخدم الأسهم للتنقل و Enter للإرسال"),
+    ("a11y.request_tabs", "Request sections", "أقسام الطلب"),
+    ("a11y.response_tabs", "Response sections", "أقسام الاستجابة"),
+    ("a11y.add_header", "Add a header", "إضافة ترويسة"),
+    ("a11y.remove_header", "Remove this header", "حذف هذه الترويسة"),
+    ("a11y.add_param", "Add a parameter", "إضافة معامل"),
+    ("a11y.remove_param", "Remove this parameter", "حذف هذا المعامل"),
+    ("a11y.add_capture", "Add a capture", "إضافة التقاط"),
+    ("a11y.remove_capture", "Remove this capture", "حذف هذا الالتقاط"),
+    ("a11y.add_variable", "Add a variable", "إضافة متغير"),
+    ("a11y.remove_variable", "Remove this variable", "حذف هذا المتغير"),
+    ("a11y.delete_collection", "Delete this collection", "حذف هذه المجموعة"),
+    ("a11y.delete_request", "Delete this request", "حذف هذا الطلب"),
+    ("a11y.toggle_collection", "Show or hide the requests", "إظهار الطلبات أو إخفاؤها"),
+    ("a11y.close_tab", "Close this tab", "إغلاق هذا التبويب"),
+
+    ("preview.title", "What a send would post right now", "ما سيُرسل الآن"),
+    ("req.resolved_url", "The URL with its variables filled in", "العنوان بعد تعويض المتغيرات"),
+    ("req.unresolved_variable", "No variable has this name, it is sent as written", "لا يوجد متغير بهذا الاسم، سيُرسل كما هو"),
+    ("preview.unresolved", "No value for {}, sent as written", "لا توجد قيمة لـ {}، سيُرسل كما هو"),
+    ("preview.note", "Values like {{$uuid}} and {{$timestamp}} are generated again on every send. An OAuth token that still has to be fetched is not shown.", "القيم مثل {{$uuid}} و {{$timestamp}} تتولد من جديد مع كل إرسال. رمز OAuth الذي لم يُجلب بعد لا يظهر هنا."),
+
+    ("resp.sent", "Request sent", "الطلب المرسل"),
+    ("resp.sent_title", "The request as it went over the wire", "الطلب كما أُرسل فعلاً"),
+    ("resp.raw_tab", "Raw", "خام"),
+    ("resp.raw_title", "Both messages in wire format, headers in the order they arrived and the body before decoding", "الرسالتان بصيغة النقل، الترويسات بترتيب وصولها والمحتوى قبل فك ترميزه"),
+    ("resp.raw_empty", "Send the request again to see the raw messages", "أرسل الطلب مجدداً لرؤية الرسائل الخام"),
+    ("resp.raw_request", "Request", "الطلب"),
+    ("resp.raw_response", "Response", "الاستجابة"),
+    ("resp.tls_tab", "TLS", "TLS"),
+    ("resp.tls_title", "The negotiated TLS and the server's certificate", "إعدادات TLS المتفق عليها وشهادة الخادم"),
+    ("resp.tls_inspect", "Inspect TLS", "فحص TLS"),
+    ("resp.tls_inspecting", "Inspecting…", "جارٍ الفحص…"),
+    ("resp.tls_inspect_title", "Open a new connection to the host and show what it negotiates", "فتح اتصال جديد بالمضيف وعرض ما يتم الاتفاق عليه"),
+    ("resp.tls_failed", "Couldn't inspect {}: {}", "تعذر فحص {}: {}"),
+    ("resp.tls_field", "Field", "الحقل"),
+    ("resp.tls_version", "Version", "الإصدار"),
+    ("resp.tls_cipher", "Cipher", "خوارزمية التشفير"),
+    ("resp.tls_subject", "Subject", "الموضوع"),
+    ("resp.tls_issuer", "Issuer", "المُصدر"),
+    ("resp.tls_not_before", "Valid from", "صالحة من"),
+    ("resp.tls_not_after", "Valid until", "صالحة حتى"),
+    ("resp.tls_days_left", "{} days left", "متبقٍ {} يوماً"),
+    ("resp.tls_expired", "expired", "منتهية الصلاحية"),
+    ("resp.tls_note", "A separate connection from the backend, not the request's: the certificate isn't verified and the request's proxy, client certificate and CA aren't used", "اتصال منفصل من الخادم الخلفي وليس اتصال الطلب: لا يتم التحقق من الشهادة ولا تُستخدم إعدادات الوكيل وشهادة العميل والجهة المصدقة الخاصة بالطلب"),
+    ("resp.sent_empty", "Send the request again to see what went out", "أرسل الطلب مجدداً لرؤية ما أُرسل"),
+    ("resp.tree", "Tree", "شجرة"),
+    ("resp.raw", "Raw", "خام"),
+    ("resp.tree_title", "Switch between the raw body and a collapsible tree", "التبديل بين المحتوى الخام وشجرة قابلة للطي"),
+    ("resp.base64", "Base64", "Base64"),
+    ("resp.base64_title", "Show the body with base64 decoded", "عرض المحتوى بعد فك ترميز base64"),
+    ("resp.base64_failed", "Could not decode the body: {}", "تعذر فك ترميز المحتوى: {}"),
+    ("resp.compare", "Compare", "مقارنة"),
+    ("resp.compare_title", "Compare with the previous response", "المقارنة مع الاستجابة السابقة"),
+    ("resp.header_filter", "Filter headers", "تصفية الترويسات"),
+    ("resp.sort_headers", "Sort A-Z", "ترتيب أبجدي"),
+    ("resp.sort_headers_title", "Sort the headers by name, the response keeps the order they arrived in", "ترتيب الترويسات حسب الاسم، تحتفظ الاستجابة بترتيب وصولها"),
+    ("resp.no_headers_match", "No header matches the filter", "لا توجد ترويسة تطابق التصفية"),
+    ("resp.status", "Status: ", "الحالة: "),
+    ("resp.time", "Time: ", "الوقت: "),
+    ("resp.time_title", "Until the last byte of the body arrived", "حتى وصول آخر بايت من المحتوى"),
+    ("resp.ttfb", "First byte: ", "أول بايت: "),
+    ("resp.ttfb_title", "Until the status line and headers arrived", "حتى وصول سطر الحالة والترويسات"),
+    ("resp.size", "Size: ", "الحجم: "),
+    ("resp.encoding", "{}: {} → {}", "{}: {} ← {}"),
+    ("resp.encoding_title", "Content-Encoding, size on the wire and after decoding", "ترميز المحتوى، الحجم المنقول وبعد فك الضغط"),
+    ("resp.double_compressed", "The body is still gzip after decoding, the server probably compressed it twice", "المحتوى ما زال مضغوطاً بـ gzip بعد فك الضغط، غالباً ضغطه الخادم مرتين"),
+    ("resp.sparkline", "Last {} sends: fastest {} ms, average {} ms, slowest {} ms", "آخر {} إرسالات: الأسرع {} مللي ثانية، المتوسط {} مللي ثانية، الأبطأ {} مللي ثانية"),
+    ("resp.format_title", "Show the body as, whatever its Content-Type says", "عرض المحتوى كـ، بغض النظر عن Content-Type"),
+    ("resp.via_backend", "Via backend", "عبر الخادم الخلفي"),
+    ("resp.via_backend_title", "Sent by the backend, CORS didn't apply", "أرسله الخادم الخلفي، لم تنطبق قيود CORS"),
+    ("resp.via_browser", "Via browser", "عبر المتصفح"),
+    ("resp.via_browser_title", "Sent with the browser's fetch, only the headers the server exposes are shown", "أُرسل عبر fetch في المتصفح، تظهر فقط الترويسات التي يكشفها الخادم"),
+    ("resp.attempts", "Succeeded on attempt {}", "نجح في المحاولة {}"),
+    ("resp.export_http", "Export .http", "تصدير http."),
+    ("resp.export_http_title", "Save the request as a .http file", "حفظ الطلب كملف http."),
+    ("resp.cut_off", "Response truncated at {}, the limit set in settings. The rest was never read.", "اقتُطعت الاستجابة عند {}، وهو الحد المضبوط في الإعدادات. لم يُقرأ الباقي."),
+    ("resp.not_modified", "Not modified, showing the cached body", "لم يتغير، يُعرض المحتوى المحفوظ"),
+    ("resp.truncated", "Showing the first {} KB of a {} KB response.", "عرض أول {} كيلوبايت من استجابة حجمها {} كيلوبايت."),
+    ("resp.show_full", "Show full", "عرض الكل"),
+    ("resp.download", "Download", "تنزيل"),
+    ("resp.schema_ok", "Schema ✓", "المخطط ✓"),
+    ("resp.schema_ok_title", "The body matches the schema", "المحتوى يطابق المخطط"),
+    ("resp.copy_path", "Copy {}", "نسخ {}"),
+    ("resp.diff_summary", "Previous: status {}, {} B. Current: status {}, {} B.", "السابقة: الحالة {}، {} بايت. الحالية: الحالة {}، {} بايت."),
+    ("resp.headers_identical", "Headers are identical", "الترويسات متطابقة"),
+    ("resp.previous", "Previous", "السابقة"),
+    ("resp.current", "Current", "الحالية"),
+    ("resp.bodies_identical", "Bodies are identical", "المحتويات متطابقة"),
+    ("resp.bodies_differ", "Bodies differ (sizes {} B vs {} B)", "المحتويات مختلفة (الحجمان {} بايت و{} بايت)"),
+    ("error.dns", "Could not resolve the host, check the hostname or your network", "تعذر العثور على المضيف، تحقق من اسم المضيف أو من اتصالك بالشبكة"),
+    ("error.refused", "The host refused the connection, check that the server is running on that port", "رفض المضيف الاتصال، تحقق من أن الخادم يعمل على هذا المنفذ"),
+    ("error.timeout", "The host did not answer in time, it may be down or only reachable through a VPN", "لم يستجب المضيف في الوقت المحدد، ربما هو متوقف أو لا يمكن الوصول إليه إلا عبر VPN"),
+    ("error.tls", "The TLS handshake failed, check the certificate settings of this request", "فشلت مصافحة TLS، تحقق من إعدادات الشهادات لهذا الطلب"),
+    ("error.protocol", "The server answered with something that isn't valid HTTP", "أجاب الخادم بشيء ليس HTTP صالحاً"),
+    ("error.cancelled", "The request was cancelled before it finished", "أُلغي الطلب قبل أن يكتمل"),
+    ("header.header", "Header", "الترويسة"),
+    ("header.value", "Value", "القيمة"),
+
+    ("notice.delete_request", "Delete request \"{}\"?", "حذف الطلب \"{}\"؟"),
+    ("notice.delete_collection", "Delete collection \"{}\"?", "حذف المجموعة \"{}\"؟"),
+    ("notice.deleted", "Deleted \"{}\"", "تم حذف \"{}\""),
+    ("notice.replaced", "Replaced text in {} fields", "تم الاستبدال في {} حقول"),
+    ("replace.title", "Find and replace in all requests", "بحث واستبدال في كل الطلبات"),
+    ("replace.close", "Close", "إغلاق"),
+    ("replace.find", "Find", "بحث"),
+    ("replace.replace", "Replace with", "استبدال بـ"),
+    ("replace.case", "Match case", "مطابقة حالة الأحرف"),
+    ("replace.whole_word", "Whole word", "كلمة كاملة"),
+    ("replace.no_matches", "No matches", "لا توجد نتائج"),
+    ("replace.apply", "Replace {} matches", "استبدال {} نتيجة"),
+    ("replace.main_list", "Requests", "الطلبات"),
+    ("replace.field_url", "URL", "الرابط"),
+    ("replace.field_header", "Header {}", "الترويسة {}"),
+    ("replace.field_param", "Param {}", "المعامل {}"),
+    ("replace.field_body", "Body", "المحتوى"),
+    ("replace.done", "Replaced {} matches in {} requests", "تم استبدال {} نتيجة في {} طلبات"),
+    ("replace.undo_skipped", "{} fields changed since the replace and were left as they are", "تغيرت {} حقول منذ الاستبدال فتُركت كما هي"),
+    ("notice.delete", "Delete", "حذف"),
+    ("notice.cancel", "Cancel", "إلغاء"),
+    ("notice.undo", "Undo", "تراجع"),
+    ("trash.title", "Trash", "سلة المحذوفات"),
+    ("trash.restore", "Restore", "استعادة"),
+    ("trash.purge", "Delete everything in the trash for good", "حذف كل ما في السلة نهائياً"),
+    ("trash.purge_confirm", "Delete everything in the trash for good? This can't be undone.", "حذف كل ما في السلة نهائياً؟ لا يمكن التراجع عن ذلك."),
+
+    ("run.running", "Running \"{}\": {}/{}", "تشغيل \"{}\": {}/{}"),
+    ("run.passed", "\"{}\": {} of {} passed", "\"{}\": نجح {} من {}"),
+    ("run.running_parallel", "Running \"{}\", {} at a time: {}/{}", "تشغيل \"{}\"، {} في كل مرة: {}/{}"),
+    ("run.total", "{} ms in total", "{} مللي ثانية إجمالاً"),
+    ("run.stop", "Stop", "إيقاف"),
+    ("run.close", "Close", "إغلاق"),
+    ("run.sending", "sending…", "جارٍ الإرسال…"),
+    ("run.schema_errors", "{} schema errors", "{} أخطاء في المخطط"),
+    ("run.schema_errors_title", "The body does not match the response schema", "المحتوى لا يطابق مخطط الاستجابة"),
+
+    ("settings.setting", "Setting", "الإعداد"),
+    ("settings.value", "Value", "القيمة"),
+    ("settings.appearance", "Appearance", "المظهر"),
+    ("settings.language", "Language", "اللغة"),
+    ("settings.network", "Network", "الشبكة"),
+    ("settings.proxy", "Proxy", "الوكيل"),
+    ("settings.no_proxy", "No proxy for", "بدون وكيل لـ"),
+    ("settings.requests", "Requests", "الطلبات"),
+    ("settings.restore", "Restore defaults", "استعادة الافتراضيات"),
+    ("settings.restore_title", "Reset the request defaults, the proxy, storage settings and default headers stay", "إعادة ضبط افتراضيات الطلبات، تبقى إعدادات الوكيل والتخزين والترويسات الافتراضية"),
+    ("settings.timeout", "Timeout (ms, 0 for none)", "المهلة (مللي ثانية، 0 بلا مهلة)"),
+    ("settings.collection_order", "Sort collections by", "ترتيب المجموعات حسب"),
+    ("settings.run_concurrency", "Requests in flight during a parallel run", "الطلبات المتزامنة أثناء التشغيل المتوازي"),
+    ("settings.follow_redirects", "Follow redirects", "اتباع إعادة التوجيه"),
+    ("settings.double_click_send", "Send on double-click in the sidebar", "الإرسال عند النقر المزدوج في الشريط الجانبي"),
+    ("settings.user_agent_hint", "the backend's default, a User-Agent header on a request wins", "افتراضي الخادم، وترويسة User-Agent في الطلب لها الأولوية"),
+    ("settings.default_headers", "Default headers", "الترويسات الافتراضية"),
+    ("settings.default_headers_hint", "New requests start with these headers", "تبدأ الطلبات الجديدة بهذه الترويسات"),
+    ("settings.responses", "Responses", "الاستجابات"),
+    ("settings.status_log", "Statuses kept per request", "عدد الحالات المحفوظة لكل طلب"),
+    ("settings.max_response", "Stop reading responses above (MB, 0 for no limit)", "إيقاف قراءة الاستجابات الأكبر من (ميغابايت، 0 بلا حد)"),
+    ("settings.max_response_title", "0 reads every response to the end, however large", "0 يقرأ كل استجابة حتى نهايتها مهما كان حجمها"),
+    ("settings.truncate", "Truncate bodies above (KB)", "اقتطاع المحتوى الأكبر من (كيلوبايت)"),
+    ("settings.storage", "Storage", "التخزين"),
+    ("settings.recent_urls", "Remembered URLs ({})", "الروابط المحفوظة ({})"),
+    ("settings.clear_recent_urls", "Clear", "مسح"),
+    ("settings.persist", "Save response bodies to disk", "حفظ محتوى الاستجابات على القرص"),
+
+    // names given to new items, stored as plain text once created
+    ("name.new_request", "New Request", "طلب جديد"),
+    ("name.new_collection", "New Collection", "مجموعة جديدة"),
+    ("name.copy", "{} copy", "{} (نسخة)"),
+    ("name.untitled", "Untitled", "بلا عنوان"),
+
+    ("empty.no_requests", "No requests yet", "لا توجد طلبات بعد"),
+    ("empty.create_request", "Create your first request", "أنشئ طلبك الأول"),
+    ("empty.no_collections", "No collections yet", "لا توجد مجموعات بعد"),
+    ("empty.create_collection", "Create a collection", "أنشئ مجموعة"),
+    ("empty.empty_collection", "This collection has no requests", "هذه المجموعة لا تحتوي على طلبات"),
+    ("empty.open_request", "Open a request from the sidebar", "افتح طلبًا من الشريط الجانبي"),
+
+    ("order.name", "Name", "الاسم"),
+    ("order.method", "Method", "الطريقة"),
+    ("order.url", "URL", "العنوان"),
+    ("sort.manual", "Manual order", "الترتيب اليدوي"),
+    ("sort.alphabetical", "Alphabetical", "أبجدياً"),
+    ("sort.by_method", "By method", "حسب الطريقة"),
+    ("sort.by_host", "By host", "حسب المضيف"),
+    ("sort.recently_sent", "Recently sent", "المرسلة مؤخراً"),
+    ("sort.recently_modified", "Recently modified", "المعدلة مؤخراً"),
+
+    // the first-run collection
+    ("sample.collection", "Examples", "أمثلة"),
+    ("sample.get", "GET with query params", "GET مع معاملات الاستعلام"),
+    ("sample.get_description", "The echo service answers with the query params it received.", "تعيد خدمة الصدى معاملات الاستعلام التي استلمتها."),
+    ("sample.post", "POST a JSON body", "إرسال محتوى JSON عبر POST"),
+    ("sample.post_description", "The parsed body comes back under `json`.", "يعود المحتوى المحلل تحت `json`."),
+    ("sample.headers", "Custom headers", "ترويسات مخصصة"),
+    ("sample.headers_description", "Sends a fresh `{{$uuid}}` as a header and shows every header the server saw.", "يرسل `{{$uuid}}` جديدًا كترويسة ويعرض كل الترويسات التي استلمها الخادم."),
+];
+
+pub fn set_language(lang: Lang) {
+    CURRENT.with(|current| current.set(lang));
+}
+
+pub fn language() -> Lang {
+    CURRENT.with(|current| current.get())
+}
+
+// the key itself when it is missing, so a typo shows up in the interface
+pub fn tr(key: &str) -> String {
+    let row = match STRINGS.iter().find(|row| row.0 == key) {
+        Some(row) => row,
+        None => return key.to_string(),
+    };
+
+    let text = match language() {
+        Lang::English => row.1,
+        Lang::Arabic => row.2,
+    };
+
+    if text.is_empty() { row.1 } else { text }.to_string()
+}
+
+// fills each "{}" with the next value, in order
+pub fn tr_args(key: &str, values: &[&str]) -> String {
+    let text = tr(key);
+    let mut filled = String::with_capacity(text.len());
+    let mut values = values.iter();
+
+    for (index, part) in text.split("{}").enumerate() {
+        if index > 0 {
+            filled.push_str(values.next().copied().unwrap_or_default());
+        }
+
+        filled.push_str(part);
+    }
+
+    filled
+}
+
+// for the dir attribute of the page
+pub fn text_direction() -> &'static str {
+    if language().is_rtl() {
+        "rtl"
+    } else {
+        "ltr"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    #[test]
+    fn every_language_has_every_key() {
+        let mut keys = HashSet::new();
+
+        for (key, english, arabic) in STRINGS {
+            assert!(keys.insert(key), "{key} is listed twice");
+            assert!(!english.is_empty(), "{key} has no English text");
+            assert!(!arabic.is_empty(), "{key} has no Arabic text");
+
+            // tr_args fills the same values into either language
+            assert_eq!(english.matches("{}").count(), arabic.matches("{}").count(), "{key} takes a different number of values");
+        }
+    }
+
+    #[test]
+    fn every_key_in_the_source_is_listed() {
+        let mut missing = Vec::new();
+
+        visit(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src")), &mut |path, text| {
+            for call in ["tr(\"", "tr_args(\""] {
+                for (start, _) in text.match_indices(call) {
+                    // not a longer name ending in tr, like str(
+                    if text[..start].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+
+                    let rest = &text[start + call.len()..];
+                    let key = &rest[..rest.find('"').unwrap_or(rest.len())];
+
+                    if !STRINGS.iter().any(|row| row.0 == key) {
+                        missing.push(format!("{key} in {}", path.display()));
+                    }
+                }
+            }
+        });
+
+        assert!(missing.is_empty(), "keys without text: {missing:?}");
+    }
+
+    #[test]
+    fn values_fill_the_placeholders_in_order() {
+        set_language(Lang::English);
+        assert_eq!(tr_args("toast.imported_workspace", &["2", "5"]), "Imported 2 collections and 5 requests");
+
+        set_language(Lang::Arabic);
+        assert_eq!(tr_args("auth.token_valid_for", &["30"]), "الرمز صالح لمدة 30 ث");
+
+        set_language(Lang::English);
+    }
+
+    fn visit(dir: &Path, check: &mut dyn FnMut(&Path, &str)) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                visit(&path, check);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                check(&path, &std::fs::read_to_string(&path).unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,628 @@
+// builds collections from API descriptions
+use crate::helpers::enums::HttpMethod;
+use crate::Collection;
+use crate::Folder;
+use crate::Request;
+use serde_json::{Map, Value};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ImportError {
+    InvalidJson(String),
+    UnsupportedVersion(String),
+    MissingPaths,
+    MissingItems,
+    UnknownFormat,
+}
+
+// the kinds of document an import from a url can be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Workspace,
+    Postman,
+    OpenApi,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::InvalidJson(err) => write!(f, "the spec is not valid JSON: {err}"),
+            ImportError::UnsupportedVersion(version) => {
+                write!(f, "OpenAPI version {version} is not supported, only 3.x is")
+            }
+            ImportError::MissingPaths => write!(f, "the spec has no paths"),
+            ImportError::MissingItems => write!(f, "the Postman collection has no items"),
+            ImportError::UnknownFormat => {
+                write!(f, "not a Bolt workspace, Postman collection or OpenAPI spec")
+            }
+        }
+    }
+}
+
+// guards against deep or self referencing schemas
+const MAX_REF_DEPTH: usize = 16;
+const MAX_SCHEMA_DEPTH: usize = 6;
+
+// http methods an openapi path item can hold, in the order they are imported
+const OPERATIONS: [(&str, HttpMethod); 7] = [
+    ("get", HttpMethod::GET),
+    ("post", HttpMethod::POST),
+    ("put", HttpMethod::PUT),
+    ("patch", HttpMethod::PATCH),
+    ("delete", HttpMethod::DELETE),
+    ("head", HttpMethod::HEAD),
+    ("options", HttpMethod::OPTIONS),
+];
+
+// told apart by their top level keys, none of the formats says what it is in one place
+pub fn detect_format(text: &str) -> Result<ImportFormat, ImportError> {
+    let root: Value =
+        serde_json::from_str(text).map_err(|err| ImportError::InvalidJson(err.to_string()))?;
+
+    let is_postman = root.pointer("/info/_postman_id").is_some()
+        || root
+            .pointer("/info/schema")
+            .and_then(Value::as_str)
+            .is_some_and(|schema| schema.contains("getpostman.com"));
+
+    if root.get("openapi").is_some() || root.get("swagger").is_some() {
+        Ok(ImportFormat::OpenApi)
+    } else if is_postman || (root.get("info").is_some() && root.get("item").is_some()) {
+        Ok(ImportFormat::Postman)
+    } else if root.get("main_col").is_some() || (root.get("version").is_some() && root.get("state").is_some()) {
+        Ok(ImportFormat::Workspace)
+    } else {
+        Err(ImportError::UnknownFormat)
+    }
+}
+
+// a Postman v2 collection, its folders become folders and its variables the collection's
+pub fn import_postman(text: &str) -> Result<Collection, ImportError> {
+    let root: Value =
+        serde_json::from_str(text).map_err(|err| ImportError::InvalidJson(err.to_string()))?;
+
+    let items = root
+        .get("item")
+        .and_then(Value::as_array)
+        .ok_or(ImportError::MissingItems)?;
+
+    let mut collection = Collection::new();
+
+    collection.name = root
+        .pointer("/info/name")
+        .and_then(Value::as_str)
+        .unwrap_or("Imported collection")
+        .to_string();
+
+    let mut variables = key_value_rows(root.get("variable"));
+    variables.push(vec![String::new(), String::new()]);
+    collection.variables = variables;
+
+    add_postman_items(&mut collection, items, None, 0);
+
+    Ok(collection)
+}
+
+fn add_postman_items(collection: &mut Collection, items: &[Value], parent: Option<u64>, depth: usize) {
+    if depth > MAX_SCHEMA_DEPTH * 2 {
+        return;
+    }
+
+    for item in items {
+        let name = item.get("name").and_then(Value::as_str).unwrap_or_default();
+
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            let id = collection.folders.len() as u64;
+
+            collection.folders.push(Folder {
+                id,
+                name: name.to_string(),
+                parent,
+                collapsed: false,
+            });
+
+            add_postman_items(collection, children, Some(id), depth + 1);
+        } else if let Some(request) = item.get("request") {
+            let mut request = postman_request(request);
+
+            request.name = name.to_string();
+            request.folder = parent;
+
+            collection.requests.push(request);
+        }
+    }
+}
+
+fn postman_request(source: &Value) -> Request {
+    let mut request = Request::new();
+
+    // a request can be given as nothing but its url
+    if let Some(url) = source.as_str() {
+        request.url = url.to_string();
+        return request;
+    }
+
+    let method = source.get("method").and_then(Value::as_str).unwrap_or("GET");
+    request.method = (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|known| known.to_string().eq_ignore_ascii_case(method))
+        .unwrap_or(HttpMethod::GET);
+
+    // the raw url already carries the query, the split out parts are only a convenience
+    request.url = match source.get("url") {
+        Some(Value::String(url)) => url.clone(),
+        Some(url) => url.get("raw").and_then(Value::as_str).unwrap_or_default().to_string(),
+        None => String::new(),
+    };
+
+    request.description = match source.get("description") {
+        Some(Value::String(description)) => description.clone(),
+        Some(description) => description
+            .get("content")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        None => String::new(),
+    };
+
+    let mut headers = key_value_rows(source.get("header"));
+
+    if let Some(body) = source.get("body") {
+        match body.get("mode").and_then(Value::as_str) {
+            Some("raw") => {
+                request.body = body.get("raw").and_then(Value::as_str).unwrap_or_default().to_string();
+            }
+            Some("urlencoded") => {
+                request.body = key_value_rows(body.get("urlencoded"))
+                    .iter()
+                    .map(|row| format!("{}={}", row[0], row[1]))
+                    .collect::<Vec<String>>()
+                    .join("&");
+
+                if !headers.iter().any(|header| header[0].eq_ignore_ascii_case("content-type")) {
+                    headers.push(vec![
+                        "Content-Type".to_string(),
+                        "application/x-www-form-urlencoded".to_string(),
+                    ]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    headers.push(vec![String::new(), String::new()]);
+    request.headers = headers;
+
+    request
+}
+
+// Postman's [{ "key": ..., "value": ... }] lists, leaving out the disabled entries
+fn key_value_rows(list: Option<&Value>) -> Vec<Vec<String>> {
+    list.and_then(Value::as_array)
+        .map(|list| {
+            list.iter()
+                .filter(|entry| !entry.get("disabled").and_then(Value::as_bool).unwrap_or(false))
+                .filter_map(|entry| {
+                    let key = entry.get("key").and_then(Value::as_str)?;
+                    let value = match entry.get("value") {
+                        Some(Value::String(value)) => value.clone(),
+                        Some(Value::Null) | None => String::new(),
+                        Some(value) => value.to_string(),
+                    };
+
+                    Some(vec![key.to_string(), value])
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn import_openapi(spec: &str, base_override: Option<String>) -> Result<Collection, ImportError> {
+    let root: Value =
+        serde_json::from_str(spec).map_err(|err| ImportError::InvalidJson(err.to_string()))?;
+
+    let version = root
+        .get("openapi")
+        .or_else(|| root.get("swagger"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+
+    if !version.starts_with("3.") {
+        return Err(ImportError::UnsupportedVersion(version.to_string()));
+    }
+
+    let paths = root
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or(ImportError::MissingPaths)?;
+
+    let base = base_override.unwrap_or_else(|| server_url(&root));
+    let base = base.trim_end_matches('/');
+
+    let mut collection = Collection::new();
+
+    // requests keep their paths and inherit the server from the collection
+    if !base.is_empty() {
+        collection.base_url = Some(base.to_string());
+    }
+
+    collection.name = root
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("Imported API")
+        .to_string();
+
+    for (path, item) in paths {
+        let item = resolve(&root, item);
+
+        let shared_params = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for (key, method) in OPERATIONS {
+            if let Some(operation) = item.get(key) {
+                collection.requests.push(build_request(
+                    &root,
+                    path,
+                    method,
+                    resolve(&root, operation),
+                    &shared_params,
+                ));
+            }
+        }
+    }
+
+    Ok(collection)
+}
+
+// first server entry, with its variables filled in from their defaults
+fn server_url(root: &Value) -> String {
+    let server = match root.pointer("/servers/0") {
+        Some(server) => server,
+        None => return String::new(),
+    };
+
+    let mut url = server
+        .get("url")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+        for (name, variable) in variables {
+            if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                url = url.replace(&format!("{{{name}}}"), default);
+            }
+        }
+    }
+
+    url
+}
+
+fn build_request(
+    root: &Value,
+    path: &str,
+    method: HttpMethod,
+    operation: &Value,
+    shared_params: &[Value],
+) -> Request {
+    let mut request = Request::new();
+
+    // {id} becomes {{id}} so it reads as a placeholder to fill in
+    request.url = path.replace('{', "{{").replace('}', "}}");
+
+    let name = operation
+        .get("summary")
+        .or_else(|| operation.get("operationId"))
+        .and_then(Value::as_str)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{method} {path}"));
+
+    // after the name, which falls back to the method
+    request.method = method;
+
+    request.name = match operation.pointer("/tags/0").and_then(Value::as_str) {
+        Some(tag) => format!("{tag} / {name}"),
+        None => name,
+    };
+
+    request.tags = operation
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|tags| tags.iter().filter_map(Value::as_str).map(|tag| tag.to_string()).collect())
+        .unwrap_or_default();
+
+    if let Some(description) = operation.get("description").and_then(Value::as_str) {
+        request.description = description.to_string();
+    }
+
+    let mut params = Vec::new();
+    let mut headers = Vec::new();
+
+    let operation_params = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    for param in shared_params.iter().chain(operation_params.iter()) {
+        let param = resolve(root, param);
+
+        if !param.get("required").and_then(Value::as_bool).unwrap_or(false) {
+            continue;
+        }
+
+        let name = match param.get("name").and_then(Value::as_str) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        match param.get("in").and_then(Value::as_str) {
+            Some("query") => params.push(vec![name, String::new()]),
+            Some("header") => headers.push(vec![name, String::new()]),
+            _ => {}
+        }
+    }
+
+    if let Some(content) = resolve(root, operation.get("requestBody").unwrap_or(&Value::Null))
+        .get("content")
+        .and_then(Value::as_object)
+    {
+        let json = content
+            .iter()
+            .find(|(media_type, _)| media_type.contains("json"));
+
+        if let Some((media_type, media)) = json {
+            headers.push(vec!["Content-Type".to_string(), media_type.clone()]);
+
+            let body = example_body(root, media);
+            request.body = serde_json::to_string_pretty(&body).unwrap_or_default();
+        }
+    }
+
+    params.push(vec![String::new(), String::new()]);
+    headers.push(vec![String::new(), String::new()]);
+
+    request.params = params;
+    request.headers = headers;
+
+    request
+}
+
+// the media type's own example wins, then named examples, then a skeleton from the schema
+fn example_body(root: &Value, media: &Value) -> Value {
+    if let Some(example) = media.get("example") {
+        return example.clone();
+    }
+
+    let named = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next())
+        .and_then(|example| resolve(root, example).get("value"));
+
+    if let Some(example) = named {
+        return example.clone();
+    }
+
+    match media.get("schema") {
+        Some(schema) => skeleton(root, schema, 0, &mut Vec::new()),
+        None => Value::Object(Map::new()),
+    }
+}
+
+// `expanding` holds the refs on the current path, a schema that refers back to itself stops there
+fn skeleton<'a>(root: &'a Value, schema: &'a Value, depth: usize, expanding: &mut Vec<&'a str>) -> Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if expanding.contains(&reference) {
+            return Value::Null;
+        }
+
+        expanding.push(reference);
+        let value = skeleton(root, resolve(root, schema), depth, expanding);
+        expanding.pop();
+
+        return value;
+    }
+
+    if let Some(example) = schema.get("example") {
+        return example.clone();
+    }
+
+    if depth >= MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+
+    // use the first variant of a composed schema
+    for key in ["allOf", "oneOf", "anyOf"] {
+        if let Some(first) = schema.get(key).and_then(|variants| variants.get(0)) {
+            return skeleton(root, first, depth + 1, expanding);
+        }
+    }
+
+    match schema.get("type").and_then(Value::as_str) {
+        Some("object") => object_skeleton(root, schema, depth, expanding),
+        None if schema.get("properties").is_some() => object_skeleton(root, schema, depth, expanding),
+        Some("array") => match schema.get("items") {
+            Some(items) => Value::Array(vec![skeleton(root, items, depth + 1, expanding)]),
+            None => Value::Array(Vec::new()),
+        },
+        Some("string") => Value::String(String::new()),
+        Some("integer") | Some("number") => Value::from(0),
+        Some("boolean") => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
+fn object_skeleton<'a>(root: &'a Value, schema: &'a Value, depth: usize, expanding: &mut Vec<&'a str>) -> Value {
+    let mut object = Map::new();
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (name, property) in properties {
+            object.insert(name.clone(), skeleton(root, property, depth + 1, expanding));
+        }
+    }
+
+    Value::Object(object)
+}
+
+// follows local "#/..." references, leaving anything else as it is
+fn resolve<'a>(root: &'a Value, mut value: &'a Value) -> &'a Value {
+    for _ in 0..MAX_REF_DEPTH {
+        let target = match value.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference,
+            None => return value,
+        };
+
+        match target.strip_prefix('#').and_then(|pointer| root.pointer(pointer)) {
+            Some(resolved) => value = resolved,
+            None => return value,
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(text: &str) -> Option<ImportFormat> {
+        detect_format(text).ok()
+    }
+
+    #[test]
+    fn workspace_exports() {
+        // what export writes, and the bare state older exports held
+        assert_eq!(
+            format(r#"{"version": 1, "timestamp": 0, "state": {"main_col": {"requests": []}}}"#),
+            Some(ImportFormat::Workspace)
+        );
+        assert_eq!(format(r#"{"main_col": {"requests": []}, "collections": []}"#), Some(ImportFormat::Workspace));
+    }
+
+    #[test]
+    fn postman_collections() {
+        assert_eq!(
+            format(r#"{"info": {"_postman_id": "1", "name": "Api"}, "item": []}"#),
+            Some(ImportFormat::Postman)
+        );
+        assert_eq!(
+            format(r#"{"info": {"schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"}}"#),
+            Some(ImportFormat::Postman)
+        );
+        // exports without an id or schema still have info and item
+        assert_eq!(format(r#"{"info": {"name": "Api"}, "item": [{"name": "a"}]}"#), Some(ImportFormat::Postman));
+    }
+
+    #[test]
+    fn openapi_specs() {
+        assert_eq!(
+            format(r#"{"openapi": "3.0.3", "info": {"title": "Api", "version": "1"}, "paths": {}}"#),
+            Some(ImportFormat::OpenApi)
+        );
+        // swagger 2 is recognized, the importer reports it as unsupported
+        assert_eq!(format(r#"{"swagger": "2.0", "info": {}, "paths": {}}"#), Some(ImportFormat::OpenApi));
+    }
+
+    // a trimmed down petstore, with an operation named by its operationId and one named by nothing
+    const PETSTORE: &str = r##"{
+        "openapi": "3.0.3",
+        "info": {"title": "Swagger Petstore", "version": "1.0.0"},
+        "servers": [{"url": "https://petstore3.swagger.io/api/v3/"}],
+        "paths": {
+            "/pet": {
+                "put": {
+                    "tags": ["pet"],
+                    "summary": "Update an existing pet",
+                    "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}}
+                },
+                "post": {
+                    "tags": ["pet"],
+                    "summary": "Add a new pet to the store",
+                    "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}}
+                }
+            },
+            "/pet/findByStatus": {
+                "get": {
+                    "tags": ["pet"],
+                    "summary": "Finds Pets by status",
+                    "parameters": [{"name": "status", "in": "query", "required": true}]
+                }
+            },
+            "/pet/{petId}": {
+                "parameters": [{"name": "petId", "in": "path", "required": true}],
+                "get": {"tags": ["pet"], "summary": "Find pet by ID"},
+                "delete": {
+                    "tags": ["pet"],
+                    "summary": "Deletes a pet",
+                    "parameters": [{"name": "api_key", "in": "header", "required": true}]
+                }
+            },
+            "/store/order/{orderId}": {
+                "get": {"tags": ["store", "orders"], "operationId": "getOrderById"}
+            },
+            "/user/login": {
+                "get": {}
+            }
+        },
+        "components": {
+            "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "status": {"type": "string"}}
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn petstore_operations_become_requests() {
+        let collection = import_openapi(PETSTORE, None).unwrap();
+
+        assert_eq!(collection.name, "Swagger Petstore");
+        assert_eq!(collection.base_url.as_deref(), Some("https://petstore3.swagger.io/api/v3"));
+
+        let imported: Vec<(String, &str, &str, Vec<String>)> = collection
+            .requests
+            .iter()
+            .map(|req| (req.method.to_string(), req.url.as_str(), req.name.as_str(), req.tags.clone()))
+            .collect();
+
+        let tags = |tags: &[&str]| tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>();
+
+        // paths in order, and each path's operations in the order of OPERATIONS
+        assert_eq!(
+            imported,
+            [
+                ("POST".to_string(), "/pet", "pet / Add a new pet to the store", tags(&["pet"])),
+                ("PUT".to_string(), "/pet", "pet / Update an existing pet", tags(&["pet"])),
+                ("GET".to_string(), "/pet/findByStatus", "pet / Finds Pets by status", tags(&["pet"])),
+                ("GET".to_string(), "/pet/{{petId}}", "pet / Find pet by ID", tags(&["pet"])),
+                ("DELETE".to_string(), "/pet/{{petId}}", "pet / Deletes a pet", tags(&["pet"])),
+                ("GET".to_string(), "/store/order/{{orderId}}", "store / getOrderById", tags(&["store", "orders"])),
+                ("GET".to_string(), "/user/login", "GET /user/login", tags(&[])),
+            ]
+        );
+
+        let requests = &collection.requests;
+
+        assert_eq!(requests[0].headers[0], ["Content-Type", "application/json"]);
+        assert_eq!(serde_json::from_str::<Value>(&requests[0].body).unwrap(), serde_json::json!({"name": "", "status": ""}));
+        assert_eq!(requests[2].params[0], ["status", ""]);
+        assert_eq!(requests[4].headers[0], ["api_key", ""]);
+    }
+
+    #[test]
+    fn other_documents_are_rejected() {
+        for text in [r#"{"info": {"name": "Api"}}"#, r#"{"version": 1}"#, "[]", "{}", "null"] {
+            assert!(matches!(detect_format(text), Err(ImportError::UnknownFormat)), "{text}");
+        }
+
+        for text in ["", "openapi: 3.0.0", "{\"openapi\": "] {
+            assert!(matches!(detect_format(text), Err(ImportError::InvalidJson(_))), "{text}");
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// looks up values by the paths the tree view copies: data.items[0].id, ["odd key"].value
+use serde_json::Value;
+
+pub fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+
+    for segment in parse_path(path)? {
+        current = match segment {
+            Segment::Key(key) => current.get(key.as_str())?,
+            Segment::Index(index) => current.get(index)?,
+        };
+    }
+
+    Some(current)
+}
+
+// strings come out without quotes, everything else as json
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+// None when the path is malformed
+fn parse_path(path: &str) -> Option<Vec<Segment>> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+
+    // the tree view copies the root itself as "."
+    let path = if path == "." { "" } else { path };
+
+    let mut segments = Vec::new();
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = closing_bracket(after)?;
+            let inner = after[..end].trim();
+
+            let segment = if inner.starts_with('"') {
+                Segment::Key(serde_json::from_str(inner).ok()?)
+            } else {
+                Segment::Index(inner.parse().ok()?)
+            };
+
+            segments.push(segment);
+            rest = &after[end + 1..];
+        } else {
+            let name = rest.strip_prefix('.').unwrap_or(rest);
+            let end = name.find(['.', '[']).unwrap_or(name.len());
+
+            if end == 0 {
+                return None;
+            }
+
+            segments.push(Segment::Key(name[..end].to_string()));
+            rest = &name[end..];
+        }
+    }
+
+    Some(segments)
+}
+
+// skips over brackets inside a quoted key
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ']' if !in_string => return Some(index),
+            _ => {}
+        }
+    }
+
+    None
+}
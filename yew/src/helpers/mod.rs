@@ -1 +1,14 @@
-pub mod enums;
\ No newline at end of file
+pub mod enums;
+pub mod diff;
+pub mod codegen;
+pub mod import;
+pub mod schema;
+pub mod dynamic;
+pub mod signing;
+pub mod json_path;
+pub mod extract;
+pub mod i18n;
+pub mod base64_view;
+pub mod url;
+pub mod samples;
+pub mod replace;
@@ -0,0 +1,235 @@
+// find & replace across the urls, header and param values and bodies of every request
+
+use crate::Collection;
+use crate::Request;
+
+// where in a request a match is, header and param rows by position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchField {
+    Url,
+    Header(usize),
+    Param(usize),
+    Body,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FindOptions {
+    pub case_sensitive: bool,
+    // the match can't have a letter, digit or _ right before or after it
+    pub whole_word: bool,
+}
+
+// one field holding the searched text, all of its occurrences are replaced together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMatch {
+    // None for the main list
+    pub collection: Option<u64>,
+    pub request: u64,
+    pub field: MatchField,
+    pub count: usize,
+}
+
+// a field a replace changed, enough to put it back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldEdit {
+    pub collection: Option<u64>,
+    pub request: u64,
+    pub field: MatchField,
+    pub before: String,
+    pub after: String,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn same_char(a: char, b: char, case_sensitive: bool) -> bool {
+    a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+// byte length of the text `query` matches at the start of `text`, lowercasing can change
+// lengths so the query's own length can't be used
+fn match_len(text: &str, query: &str, case_sensitive: bool) -> Option<usize> {
+    let mut chars = text.char_indices();
+
+    for q in query.chars() {
+        let (_, c) = chars.next()?;
+
+        if !same_char(c, q, case_sensitive) {
+            return None;
+        }
+    }
+
+    Some(chars.next().map(|(index, _)| index).unwrap_or(text.len()))
+}
+
+// byte ranges of the non-overlapping occurrences of `query`, left to right
+pub fn find_ranges(text: &str, query: &str, options: FindOptions) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+
+    if query.is_empty() {
+        return ranges;
+    }
+
+    let mut start = 0;
+
+    while start < text.len() {
+        let found = match_len(&text[start..], query, options.case_sensitive)
+            .map(|len| start + len)
+            .filter(|&end| {
+                !options.whole_word
+                    || (!text[..start].chars().next_back().is_some_and(is_word_char)
+                        && !text[end..].chars().next().is_some_and(is_word_char))
+            });
+
+        match found {
+            Some(end) => {
+                ranges.push((start, end));
+                start = end;
+            }
+            None => start += text[start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+
+    ranges
+}
+
+// None when there is nothing to replace
+pub fn replace_text(text: &str, query: &str, replacement: &str, options: FindOptions) -> Option<String> {
+    let ranges = find_ranges(text, query, options);
+
+    if ranges.is_empty() {
+        return None;
+    }
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for (start, end) in ranges {
+        replaced.push_str(&text[last..start]);
+        replaced.push_str(replacement);
+        last = end;
+    }
+
+    replaced.push_str(&text[last..]);
+
+    Some(replaced)
+}
+
+pub fn field_text(request: &Request, field: MatchField) -> Option<&String> {
+    match field {
+        MatchField::Url => Some(&request.url),
+        MatchField::Header(index) => request.headers.get(index)?.get(1),
+        MatchField::Param(index) => request.params.get(index)?.get(1),
+        MatchField::Body => Some(&request.body),
+    }
+}
+
+fn field_text_mut(request: &mut Request, field: MatchField) -> Option<&mut String> {
+    match field {
+        MatchField::Url => Some(&mut request.url),
+        MatchField::Header(index) => request.headers.get_mut(index)?.get_mut(1),
+        MatchField::Param(index) => request.params.get_mut(index)?.get_mut(1),
+        MatchField::Body => Some(&mut request.body),
+    }
+}
+
+fn request_fields(request: &Request) -> Vec<MatchField> {
+    let mut fields = vec![MatchField::Url];
+    fields.extend((0..request.headers.len()).map(MatchField::Header));
+    fields.extend((0..request.params.len()).map(MatchField::Param));
+    fields.push(MatchField::Body);
+
+    fields
+}
+
+fn find_in_request(request: &Request, collection: Option<u64>, query: &str, options: FindOptions) -> Vec<FieldMatch> {
+    request_fields(request)
+        .into_iter()
+        .filter_map(|field| {
+            let count = find_ranges(field_text(request, field)?, query, options).len();
+
+            (count > 0).then_some(FieldMatch {
+                collection,
+                request: request.id,
+                field,
+                count,
+            })
+        })
+        .collect()
+}
+
+// the main list first, then the collections in order, a request's matches stay together
+pub fn find_matches(main: &Collection, collections: &[Collection], query: &str, options: FindOptions) -> Vec<FieldMatch> {
+    let main_matches = main.requests.iter().flat_map(|request| find_in_request(request, None, query, options));
+
+    let col_matches = collections.iter().flat_map(|col| {
+        col.requests
+            .iter()
+            .flat_map(move |request| find_in_request(request, Some(col.id), query, options))
+    });
+
+    main_matches.chain(col_matches).collect()
+}
+
+fn find_request<'a>(main: &'a mut Collection, collections: &'a mut [Collection], collection: Option<u64>, id: u64) -> Option<&'a mut Request> {
+    let col = match collection {
+        Some(col_id) => collections.iter_mut().find(|col| col.id == col_id)?,
+        None => main,
+    };
+
+    col.requests.iter_mut().find(|request| request.id == id)
+}
+
+// replaces in the given fields and returns what changed, a field that no longer holds the
+// text is left alone
+pub fn apply_replace(
+    main: &mut Collection,
+    collections: &mut [Collection],
+    matches: &[FieldMatch],
+    query: &str,
+    replacement: &str,
+    options: FindOptions,
+) -> Vec<FieldEdit> {
+    let mut edits = Vec::new();
+
+    for found in matches {
+        let text = match find_request(main, collections, found.collection, found.request)
+            .and_then(|request| field_text_mut(request, found.field))
+        {
+            Some(text) => text,
+            None => continue,
+        };
+
+        if let Some(after) = replace_text(text, query, replacement, options) {
+            let before = std::mem::replace(text, after.clone());
+
+            edits.push(FieldEdit {
+                collection: found.collection,
+                request: found.request,
+                field: found.field,
+                before,
+                after,
+            });
+        }
+    }
+
+    edits
+}
+
+// puts back what a replace changed and returns how many fields were left as they are, because
+// they were edited again or went away since
+pub fn revert_edits(main: &mut Collection, collections: &mut [Collection], edits: &[FieldEdit]) -> usize {
+    let mut skipped = 0;
+
+    for edit in edits {
+        match find_request(main, collections, edit.collection, edit.request)
+            .and_then(|request| field_text_mut(request, edit.field))
+        {
+            Some(text) if *text == edit.after => *text = edit.before.clone(),
+            _ => skipped += 1,
+        }
+    }
+
+    skipped
+}
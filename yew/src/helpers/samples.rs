@@ -0,0 +1,80 @@
+// the collection a first run starts with, a few requests that work as they are against a
+// public echo service
+use crate::helpers::enums::HttpMethod;
+use crate::helpers::i18n::tr;
+use crate::Collection;
+use crate::Request;
+
+const ECHO_SERVICE: &str = "https://httpbin.org";
+
+pub fn sample_collection() -> Collection {
+    let mut collection = Collection::new();
+
+    collection.name = tr("sample.collection");
+    collection.base_url = Some(ECHO_SERVICE.to_string());
+    collection.sample = true;
+
+    collection.requests = vec![
+        sample_request(
+            tr("sample.get"),
+            HttpMethod::GET,
+            "/get",
+            &[("page", "1"), ("search", "bolt")],
+            &[],
+            "",
+            tr("sample.get_description"),
+        ),
+        sample_request(
+            tr("sample.post"),
+            HttpMethod::POST,
+            "/post",
+            &[],
+            &[("Content-Type", "application/json")],
+            "{\n    \"name\": \"Bolt\",\n    \"tags\": [\"api\", \"client\"]\n}",
+            tr("sample.post_description"),
+        ),
+        sample_request(
+            tr("sample.headers"),
+            HttpMethod::GET,
+            "/headers",
+            &[],
+            &[("X-Request-Id", "{{$uuid}}"), ("Accept", "application/json")],
+            "",
+            tr("sample.headers_description"),
+        ),
+    ];
+
+    collection
+}
+
+fn sample_request(
+    name: String,
+    method: HttpMethod,
+    path: &str,
+    params: &[(&str, &str)],
+    headers: &[(&str, &str)],
+    body: &str,
+    description: String,
+) -> Request {
+    let mut request = Request::new();
+
+    request.name = name;
+    request.method = method;
+    request.url = path.to_string();
+    request.body = body.to_string();
+    request.description = description;
+
+    // the tables always end with an empty row to type into
+    request.params = rows(params);
+    request.headers = rows(headers);
+
+    request
+}
+
+fn rows(pairs: &[(&str, &str)]) -> Vec<Vec<String>> {
+    pairs
+        .iter()
+        .map(|(name, value)| vec![name.to_string(), value.to_string()])
+        .chain(std::iter::once(vec![String::new(), String::new()]))
+        .collect()
+}
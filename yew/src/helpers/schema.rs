@@ -0,0 +1,384 @@
+// a json schema checker covering the draft-07 keywords responses commonly use
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    // json pointer to the offending value, "" for the document itself
+    pub pointer: String,
+    pub message: String,
+}
+
+// nested schemas past this are not followed, so recursive refs can't loop forever
+const MAX_DEPTH: usize = 64;
+
+pub fn validate(schema: &Value, instance: &Value) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    check(schema, schema, instance, "", 0, &mut violations);
+
+    violations
+}
+
+fn check(
+    root: &Value,
+    schema: &Value,
+    instance: &Value,
+    pointer: &str,
+    depth: usize,
+    violations: &mut Vec<Violation>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+
+    // `true` accepts anything, `false` nothing
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => return push(violations, pointer, "no value is allowed here".to_string()),
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match reference.strip_prefix('#').and_then(|target| root.pointer(target)) {
+            Some(target) => check(root, target, instance, pointer, depth + 1, violations),
+            None => push(violations, pointer, format!("unresolved reference {reference}")),
+        }
+
+        // draft-07 ignores the siblings of $ref
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+
+        if !allowed.is_empty() && !allowed.iter().any(|kind| is_type(instance, kind)) {
+            push(violations, pointer, format!(
+                "expected {}, found {}",
+                allowed.join(" or "),
+                type_name(instance)
+            ));
+
+            // the remaining keywords would only repeat the mismatch
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(instance) {
+            push(violations, pointer, format!("{} is not one of the allowed values", short(instance)));
+        }
+    }
+
+    if let Some(constant) = schema.get("const") {
+        if constant != instance {
+            push(violations, pointer, format!("expected {}", short(constant)));
+        }
+    }
+
+    match instance {
+        Value::Object(object) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        push(violations, pointer, format!("missing required property \"{name}\""));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let additional = schema.get("additionalProperties");
+
+            for (name, value) in object {
+                let child = format!("{}/{}", pointer, escape_pointer(name));
+
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(property) => check(root, property, value, &child, depth + 1, violations),
+                    None => {
+                        if let Some(additional) = additional {
+                            check(root, additional, value, &child, depth + 1, violations)
+                        }
+                    }
+                }
+            }
+        }
+
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    push(violations, pointer, format!("expected at least {min} items, found {}", items.len()));
+                }
+            }
+
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    push(violations, pointer, format!("expected at most {max} items, found {}", items.len()));
+                }
+            }
+
+            match schema.get("items") {
+                // tuple form, one schema per position
+                Some(Value::Array(tuple)) => {
+                    for (index, (item, item_schema)) in items.iter().zip(tuple).enumerate() {
+                        let child = format!("{pointer}/{index}");
+                        check(root, item_schema, item, &child, depth + 1, violations);
+                    }
+                }
+                Some(item_schema) => {
+                    for (index, item) in items.iter().enumerate() {
+                        let child = format!("{pointer}/{index}");
+                        check(root, item_schema, item, &child, depth + 1, violations);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min {
+                    push(violations, pointer, format!("expected at least {min} characters, found {length}"));
+                }
+            }
+
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max {
+                    push(violations, pointer, format!("expected at most {max} characters, found {length}"));
+                }
+            }
+        }
+
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    push(violations, pointer, format!("{number} is below the minimum of {min}"));
+                }
+            }
+
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    push(violations, pointer, format!("{number} is above the maximum of {max}"));
+                }
+            }
+        }
+
+        _ => {}
+    }
+
+    if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+        for sub in all {
+            check(root, sub, instance, pointer, depth + 1, violations);
+        }
+    }
+
+    if let Some(any) = schema.get("anyOf").and_then(Value::as_array) {
+        let matches = any.iter().any(|sub| passes(root, sub, instance, pointer, depth));
+
+        if !matches {
+            push(violations, pointer, "does not match any of the anyOf schemas".to_string());
+        }
+    }
+
+    if let Some(one) = schema.get("oneOf").and_then(Value::as_array) {
+        let matches = one
+            .iter()
+            .filter(|sub| passes(root, sub, instance, pointer, depth))
+            .count();
+
+        if matches != 1 {
+            push(
+                violations,
+                pointer,
+                format!("matches {matches} of the oneOf schemas, expected exactly one"),
+            );
+        }
+    }
+}
+
+fn push(violations: &mut Vec<Violation>, pointer: &str, message: String) {
+    violations.push(Violation {
+        pointer: pointer.to_string(),
+        message,
+    });
+}
+
+fn passes(root: &Value, schema: &Value, instance: &Value, pointer: &str, depth: usize) -> bool {
+    let mut violations = Vec::new();
+    check(root, schema, instance, pointer, depth + 1, &mut violations);
+
+    violations.is_empty()
+}
+
+fn is_type(instance: &Value, kind: &str) -> bool {
+    match kind {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        // 1.0 counts as an integer, like the spec says
+        "integer" => matches!(instance.as_f64(), Some(n) if n.fract() == 0.0),
+        _ => false,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+    }
+}
+
+// keeps messages readable when the value is a large object
+fn short(value: &Value) -> String {
+    let text = value.to_string();
+
+    if text.chars().count() > 40 {
+        format!("{}...", text.chars().take(40).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // (pointer, message) pairs, easier to compare than Violation values
+    fn errors(schema: Value, instance: Value) -> Vec<(String, String)> {
+        validate(&schema, &instance)
+            .into_iter()
+            .map(|violation| (violation.pointer, violation.message))
+            .collect()
+    }
+
+    fn error(pointer: &str, message: &str) -> (String, String) {
+        (pointer.to_string(), message.to_string())
+    }
+
+    #[test]
+    fn valid_documents_have_no_violations() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "tags"],
+            "properties": {
+                "id": {"type": "integer", "minimum": 1},
+                "tags": {"type": "array", "items": {"type": "string", "minLength": 1}, "maxItems": 3}
+            }
+        });
+
+        assert!(errors(schema, json!({"id": 1.0, "tags": ["a", "b"], "extra": null})).is_empty());
+        assert!(errors(json!(true), json!([1, 2])).is_empty());
+    }
+
+    #[test]
+    fn violations_point_at_the_offending_value() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {
+                "items": {"type": "array", "items": {"type": "integer"}},
+                "a/b": {"enum": ["x", "y"]}
+            },
+            "additionalProperties": false
+        });
+
+        assert_eq!(
+            errors(schema, json!({"items": [1, "2"], "a/b": "z", "c~d": 0})),
+            vec![
+                error("", "missing required property \"id\""),
+                error("/a~1b", "\"z\" is not one of the allowed values"),
+                error("/c~0d", "no value is allowed here"),
+                error("/items/1", "expected integer, found string"),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_type_mismatch_is_reported_once() {
+        let schema = json!({"type": ["string", "null"], "minLength": 2, "enum": ["ab"]});
+
+        assert_eq!(errors(schema, json!(5)), vec![error("", "expected string or null, found number")]);
+    }
+
+    #[test]
+    fn bounds_on_strings_arrays_and_numbers() {
+        assert_eq!(
+            errors(json!({"minLength": 3, "maxLength": 3}), json!("éé")),
+            vec![error("", "expected at least 3 characters, found 2")]
+        );
+        assert_eq!(
+            errors(json!({"minItems": 1, "maxItems": 1}), json!([1, 2])),
+            vec![error("", "expected at most 1 items, found 2")]
+        );
+        assert_eq!(
+            errors(json!({"minimum": 0, "maximum": 10}), json!(10.5)),
+            vec![error("", "10.5 is above the maximum of 10")]
+        );
+    }
+
+    #[test]
+    fn references_resolve_against_the_root() {
+        let schema = json!({
+            "definitions": {"id": {"type": "string"}},
+            "properties": {
+                "id": {"$ref": "#/definitions/id"},
+                "other": {"$ref": "#/definitions/missing"}
+            }
+        });
+
+        assert_eq!(
+            errors(schema, json!({"id": 1, "other": 1})),
+            vec![
+                error("/id", "expected string, found number"),
+                error("/other", "unresolved reference #/definitions/missing"),
+            ]
+        );
+    }
+
+    #[test]
+    fn recursive_references_stop_at_the_depth_limit() {
+        let schema = json!({"$ref": "#"});
+
+        assert!(errors(schema, json!({"a": 1})).is_empty());
+    }
+
+    #[test]
+    fn combinators() {
+        let schema = json!({"oneOf": [{"type": "integer"}, {"minimum": 0}]});
+
+        assert!(errors(schema.clone(), json!(-1)).is_empty());
+        assert_eq!(
+            errors(schema, json!(1)),
+            vec![error("", "matches 2 of the oneOf schemas, expected exactly one")]
+        );
+
+        assert_eq!(
+            errors(json!({"anyOf": [{"type": "string"}, {"type": "null"}]}), json!(1)),
+            vec![error("", "does not match any of the anyOf schemas")]
+        );
+        assert_eq!(
+            errors(json!({"allOf": [{"const": 1}, {"maximum": 0}]}), json!(1)),
+            vec![error("", "1 is above the maximum of 0")]
+        );
+    }
+}
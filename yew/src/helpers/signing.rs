@@ -0,0 +1,65 @@
+// request signatures, computed on the final payload right before it is sent
+use crate::helpers::enums::{SignatureEncoding, SignedContent, SigningAlgorithm};
+use crate::SigningConfig;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+// None when signing is off or there is no secret to sign with
+pub fn sign_request(config: &SigningConfig, method: &str, url: &str, body: &str) -> Option<String> {
+    if config.algorithm == SigningAlgorithm::Off || config.secret.is_empty() {
+        return None;
+    }
+
+    let message = match config.content {
+        SignedContent::CanonicalRequest => canonical_request(method, url, body),
+        SignedContent::Body => body.to_string(),
+    };
+
+    let signature = match config.algorithm {
+        SigningAlgorithm::HmacSha256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(config.secret.as_bytes()).ok()?;
+            mac.update(message.as_bytes());
+
+            mac.finalize().into_bytes().to_vec()
+        }
+        SigningAlgorithm::Off => return None,
+    };
+
+    Some(match config.encoding {
+        SignatureEncoding::Hex => to_hex(&signature),
+        SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(signature),
+    })
+}
+
+// the method, the path with its query, and the hex sha256 of the body, one per line:
+//
+//   POST
+//   /v1/orders?dry_run=true
+//   e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+pub fn canonical_request(method: &str, url: &str, body: &str) -> String {
+    format!(
+        "{}\n{}\n{}",
+        method.to_uppercase(),
+        path_and_query(url),
+        to_hex(&Sha256::digest(body.as_bytes()))
+    )
+}
+
+// everything after the host, "/" when the url has no path
+fn path_and_query(url: &str) -> String {
+    let without_scheme = match url.find("://") {
+        Some(index) => &url[index + 3..],
+        None => url,
+    };
+
+    match without_scheme.find(['/', '?']) {
+        Some(index) if without_scheme[index..].starts_with('/') => without_scheme[index..].to_string(),
+        Some(index) => format!("/{}", &without_scheme[index..]),
+        None => "/".to_string(),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -1,8 +1,32 @@
 use crate::helpers::enums::HttpMethod as Method;
+use crate::helpers::enums::AuthMode;
+use crate::helpers::enums::{AutocompleteAction, AutocompleteField};
+use crate::helpers::enums::DeleteTarget;
+use crate::helpers::enums::ImportMode;
+use crate::helpers::enums::Lang;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::helpers::enums::Language;
+use crate::helpers::enums::ResizeHandle;
+use crate::helpers::enums::SaveStatus;
+use crate::helpers::enums::ScrollPanel;
+use crate::helpers::enums::RetryPolicy;
+use crate::helpers::enums::Transport;
+use crate::helpers::enums::HttpVersion;
+use crate::helpers::enums::BodyFormat;
+use crate::helpers::enums::Theme;
+use crate::helpers::enums::ToastLevel;
+use crate::helpers::enums::SidebarSort;
+use crate::helpers::enums::RequestOrder;
+use crate::helpers::enums::{SignatureEncoding, SignedContent, SigningAlgorithm};
+use crate::helpers::schema::Violation;
+use crate::helpers::replace::{FieldEdit, FieldMatch, FindOptions};
 use crate::utils::*;
+use crate::process::runner::{advance_run, is_running};
+use crate::process::tabs::{collection_base_url, collection_requests_mut, collection_variables, request_mut};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
-use yew::{html::Scope, Component, Context, Html};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use yew::{html, html::Scope, Component, Context, Html};
 
 mod helpers;
 mod process;
@@ -20,53 +44,269 @@ mod view;
 // Define the possible messages which can be sent to the component
 #[derive(Clone)]
 pub enum Msg {
-    SelectedMethod(Method),
-    SendPressed,
-    ReqBodyPressed,
-    ReqHeadersPressed,
-    ReqParamsPressed,
+    // the messages each reducer in process/ handles, see there
+    Edit(EditMsg),
+    Navigation(NavigationMsg),
+    Send(SendMsg),
+    Collections(CollectionsMsg),
+
+    VariableAutocomplete(AutocompleteAction),
+
+    OpenLink(String),
+
+    TagFilterChanged,
+    SaveState,
+    SaveFinished(u32, Result<(), String>),
+    CloseRequested,
+
+    ShowToast(ToastLevel, String),
+    DismissToast(u32),
+
+    SetSidebarSort(SidebarSort),
+
+    OpenFindReplace,
+    CloseFindReplace,
+    FindReplaceChanged,
+    ToggleFindCase,
+    ToggleFindWholeWord,
+    // position in the dialog's match list
+    ToggleReplaceMatch(usize),
+    ApplyReplace,
+    UndoReplace,
+
+    ToggleRevealSecrets,
+    ClearVariables,
+    CopyJsonPath(String),
+    PickBodyFile,
+    RefreshBodyFileInfo,
+    BodyFileInfo(RequestRef, Result<u64, String>),
+
+    ProxyChanged,
+    NoProxyChanged,
+    TogglePersistResponses,
+    TimeoutChanged,
+    ToggleFollowRedirects,
+    ToggleDoubleClickSend,
+    UserAgentChanged,
+    StatusLogLengthChanged,
+    TruncateSizeChanged,
+    MaxResponseSizeChanged,
+    RestoreDefaultSettings,
+    RunConcurrencyChanged,
+    CollectionOrderChanged,
+    AddDefaultHeader,
+    RemoveDefaultHeader(usize),
+    DefaultHeaderChanged(usize),
+    LanguageChanged,
+    ClearRecentUrls,
+
+    SetTheme(Theme),
+
+    PickHttpFile,
+    PickOpenApiSpec,
+    ImportOpenApi(String),
+    ImportFromUrl(String),
+    ImportDocument(String),
+    ImportHttpFile(String),
+
+    ResizeStart(ResizeHandle),
+    ResizeDrag(i32, i32),
+    ResizeStop,
+    ResetLayout(ResizeHandle),
+
+    ExportWorkspace,
+    ImportWorkspace(ImportMode),
+    WorkspaceImported(SaveState, ImportMode),
+    // None when nothing was saved yet
+    StateRestored(Option<SaveState>),
 
-    RespBodyPressed,
-    RespHeadersPressed,
-
-    AddHeader,
-    RemoveHeader(usize),
-
-    AddParam,
-    RemoveParam(usize),
+    Update,
+    HelpPressed,
 
-    ReceivedResponse,
+    Nothing,
+}
 
+// edits to the request on screen, whether it sits in the main list or a collection
+#[derive(Clone)]
+pub enum EditMsg {
+    SelectedMethod(Method),
     MethodChanged,
     UrlChanged,
     BodyChanged,
+    // a file picked for the body, read into it
+    BodyLoaded(String),
+    BodyFileChanged,
+    ClearBodyFile,
+
+    AddHeader,
+    RemoveHeader(usize),
     HeaderChanged(usize),
+    AddParam,
+    RemoveParam(usize),
     ParamChanged(usize),
+    ToggleSecretHeader(usize),
+    ToggleSecretParam(usize),
+    QueryChanged,
+    ToggleRawQuery,
+
+    AddLocalVariable,
+    RemoveLocalVariable(usize),
+    LocalVariableChanged(usize),
+    AddCapture,
+    RemoveCapture(usize),
+    CaptureChanged(usize),
+    AcceptContentType,
+    DescriptionChanged,
+
+    ReqProxyChanged,
+    ReqUserAgentChanged,
+    ToggleOmitUserAgent,
+    RetriesChanged,
+    RetryPolicyChanged,
+    TransportChanged,
+    HttpVersionChanged,
+    ToggleConditional,
+    ToggleInsecureTls,
+    CaCertChanged,
+    ClientCertChanged,
+    SchemaChanged,
+
+    AuthModeChanged,
+    AuthFieldsChanged,
+    DigestFieldsChanged,
+    SigningAlgorithmChanged,
+    SigningFieldsChanged,
+    ToggleRememberSigningSecret,
+    ToggleRememberSecret,
+
+    AddTag,
+    RemoveTag(usize),
+}
 
-    AddRequest,
-    RemoveRequest(usize),
-    SelectRequest(usize),
+// what is on screen without touching what the request sends: its tabs, how the response is
+// shown and which request is selected
+#[derive(Clone)]
+pub enum NavigationMsg {
+    ReqBodyPressed,
+    ReqParamsPressed,
+    ReqHeadersPressed,
+    ReqDocsPressed,
+    ReqSettingsPressed,
+    ReqAuthPressed,
+    ReqTestsPressed,
+    ReqPreviewPressed,
+    // arrow keys on the tab strips, with the tab to move to
+    ReqTabKey(u8),
+    ToggleDocsPreview,
 
-    AddCollection,
-    RemoveCollection(usize),
-    AddToCollection(usize),
+    RespBodyPressed,
+    RespHeadersPressed,
+    RespSentPressed,
+    RespRawPressed,
+    RespTlsPressed,
+    RespTabKey(u8),
+
+    // sent once a panel stops scrolling, for the request that was shown then
+    PanelScrolled(RequestRef, ScrollPanel, i32),
+    BodySelectionChanged(RequestRef, u32, u32),
+    DismissContentType,
+    DismissBodyNote,
+    BodyFormatChanged,
+    ToggleJsonTree,
+    FilterRespHeaders(String),
+    ToggleSortRespHeaders,
+    ToggleJsonNode(String),
+    ToggleDiffView,
+    ToggleBase64Decode,
+    ShowFullBody,
+
+    // by id, a message sent after the list moved still finds its request
+    SelectRequest(u64),
+    SelectFromCollection(u64, u64),
+    SelectTab(usize),
+    CloseTab(usize),
+    SwitchPage(Page),
+}
 
-    SelectFromCollection(usize, usize),
-    RemoveFromCollection(usize, usize),
+// a request going out and what comes back, polling, collection runs and copies of the request
+#[derive(Clone)]
+pub enum SendMsg {
+    SendPressed,
+    // double-click on a sidebar row, the collection id is None for the home list
+    SendFromSidebar(Option<u64>, u64),
+
+    // what the backend answered, delivered as messages so only update touches the state
+    ReceivedResponse(RequestRef, String),
+    ReceivedToken(RequestRef, bool, String),
+    SendFailed(RequestRef, String),
+    SendBlocked(RequestRef, String),
+    FetchToken,
+
+    // a connect to the url's host, answered without touching the response
+    CheckHost,
+    HostChecked(Result<HostCheck, String>),
+    InspectTls,
+    TlsInspected(RequestRef, String, Result<TlsDetails, String>),
+
+    StartRepeat(u32),
+    StopRepeat,
+    RepeatTick,
+
+    RunCollection(usize),
+    RunCollectionParallel(usize),
+    StopRun,
+    CloseRun,
+
+    DownloadBody,
+    CopySnippet(Language),
+    ExportHttpFile,
+}
 
+// adding, removing, reordering and restoring collections and requests, and what a collection
+// holds besides its requests
+#[derive(Clone)]
+pub enum CollectionsMsg {
+    AddCollection,
+    DuplicateCollection(usize),
+    MoveCollection { from: usize, to: usize },
+    SortCollection(usize),
+    DismissSamples,
+    // these carry request and collection ids, a message sent after the list moved still finds its item
+    RemoveCollection(u64),
     ToggleCollapsed(usize),
 
-    Update,
-    HelpPressed,
-    SwitchPage(Page),
+    AddCollectionVariable,
+    RemoveCollectionVariable(usize),
+    CollectionVariableChanged(usize),
+    ToggleSecretVariable(usize),
+    BaseUrlChanged,
 
-    Nothing,
+    AddRequest,
+    TogglePin(u64),
+    AddToCollection(usize),
+    RemoveRequest(u64),
+    RemoveFromCollection(u64, u64),
+
+    AskDelete(DeleteTarget),
+    CancelDelete,
+    ConfirmDelete,
+    UndoDelete,
+    RestoreFromTrash(usize),
+    PurgeTrash,
+    ExpireUndo(u32),
+
+    AddFolder(usize, Option<u64>),
+    RenameFolder(usize, u64),
+    RemoveFolder(usize, u64),
+    MoveIntoFolder(usize, usize),
+    ToggleFolderCollapsed(usize, u64),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum Page {
     Home,
     Collections,
+    Settings,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -83,11 +323,112 @@ struct Response {
     status: u16,
     body: String,
     headers: Vec<Vec<String>>,
+    // the total time, older backends measured it up to the headers only
     time: u32,
     size: u64,
     response_type: ResponseType,
     request_index: usize,
     failed: bool,
+
+    // 0 for responses from backends that didn't split the time, see total_time
+    #[serde(default)]
+    time_to_first_byte: u32,
+    #[serde(default)]
+    time_total: u32,
+
+    // None when the request has no schema or the body could not be checked
+    #[serde(default)]
+    schema_violations: Option<Vec<Violation>>,
+
+    // how many tries the backend needed, more than one when retries kicked in
+    #[serde(default = "default_attempts")]
+    attempts: u8,
+
+    // syntax highlighted html for JSON bodies, rebuilt when the theme changes
+    #[serde(default)]
+    highlighted: String,
+
+    // large bodies render truncated until the user asks for all of it
+    #[serde(skip)]
+    show_full: bool,
+
+    // the request as the backend sent it, empty for responses from older versions
+    #[serde(default)]
+    sent_method: String,
+    #[serde(default)]
+    sent_url: String,
+    #[serde(default)]
+    sent_headers: Vec<Vec<String>>,
+    #[serde(default)]
+    sent_body: String,
+
+    // empty when the body came uncompressed, encoded_size is what went over the wire
+    #[serde(default)]
+    content_encoding: String,
+    #[serde(default)]
+    encoded_size: u64,
+    #[serde(default)]
+    double_compressed: bool,
+
+    // both messages in wire format as the backend saw them, for the raw view
+    #[serde(default)]
+    raw_request: String,
+    #[serde(default)]
+    raw_response: String,
+
+    // the size limit the backend cut the body off at
+    #[serde(default)]
+    truncated_at: Option<u64>,
+
+    // made by the webview's fetch instead of the backend
+    #[serde(default)]
+    via_browser: bool,
+
+    // the backend's ErrorKind for a request that got no response, like "DnsResolution"
+    #[serde(default)]
+    error_kind: Option<String>,
+
+    // a 304 showing the body of the response it confirmed, see not_modified_fallback
+    #[serde(default)]
+    cached_body: bool,
+}
+
+// what the backend's /check_host answered
+#[derive(Debug, Clone, Deserialize)]
+pub struct HostCheck {
+    reachable: bool,
+    time: u32,
+    error_kind: Option<String>,
+    error: String,
+}
+
+// what the backend's /inspect_tls answered, details or the reason there are none
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsInspection {
+    details: Option<TlsDetails>,
+    error: String,
+}
+
+// the TLS version, cipher and server certificate from the backend's own handshake to the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsDetails {
+    version: String,
+    cipher: String,
+    subject: String,
+    issuer: String,
+    not_before: String,
+    not_after: String,
+    // negative once the certificate has expired
+    days_left: i32,
+}
+
+fn default_attempts() -> u8 {
+    1
+}
+
+// tables edited row by row keep a blank row at the end to type into
+fn empty_row() -> Vec<Vec<String>> {
+    vec![vec![String::new(), String::new()]]
 }
 
 impl Response {
@@ -97,77 +438,722 @@ impl Response {
             body: String::new(),
             headers: Vec::new(),
             time: 0,
+            time_to_first_byte: 0,
+            time_total: 0,
             size: 0,
             response_type: ResponseType::TEXT,
             request_index: 0,
             failed: false,
+
+            attempts: 1,
+
+            schema_violations: None,
+
+            highlighted: String::new(),
+
+            show_full: false,
+            sent_method: String::new(),
+            sent_url: String::new(),
+            sent_headers: Vec::new(),
+            sent_body: String::new(),
+            content_encoding: String::new(),
+            encoded_size: 0,
+            double_compressed: false,
+            raw_request: String::new(),
+            raw_response: String::new(),
+            truncated_at: None,
+            via_browser: false,
+            error_kind: None,
+            cached_body: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    insecure_skip_verify: bool,
+    ca_cert_path: Option<String>,
+    // (certificate, private key), both PEM files
+    client_cert: Option<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    mode: AuthMode,
+
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scopes: String,
+
+    // digest credentials, the backend answers the server's 401 challenge with them
+    username: String,
+    password: String,
+
+    // the secret, token and password only get written to disk when this is set
+    remember_secret: bool,
+
+    access_token: String,
+    // epoch millis, None when the endpoint did not send expires_in
+    expires_at: Option<f64>,
+
+    // body of the last failed token request, shown in the auth tab
+    token_error: String,
+
+    #[serde(skip)]
+    fetching: bool,
+}
+
+impl AuthConfig {
+    fn needs_token(&self) -> bool {
+        if self.mode != AuthMode::OAuth2ClientCredentials {
+            return false;
+        }
+
+        if self.access_token.is_empty() {
+            return true;
+        }
+
+        match self.expires_at {
+            Some(expires_at) => now_ms() as f64 + TOKEN_EXPIRY_MARGIN_MS >= expires_at,
+            None => false,
+        }
+    }
+
+    // the same client asking the same endpoint for the same scopes gets a token any of them can use
+    fn shares_token_with(&self, other: &AuthConfig) -> bool {
+        self.mode == AuthMode::OAuth2ClientCredentials
+            && other.mode == AuthMode::OAuth2ClientCredentials
+            && self.token_url == other.token_url
+            && self.client_id == other.client_id
+            && self.client_secret == other.client_secret
+            && self.scopes == other.scopes
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SigningConfig {
+    algorithm: SigningAlgorithm,
+    secret: String,
+
+    // header the signature is sent in
+    header: String,
+    content: SignedContent,
+    encoding: SignatureEncoding,
+
+    // the secret only gets written to disk when this is set
+    remember_secret: bool,
+}
+
+impl Default for SigningConfig {
+    fn default() -> Self {
+        SigningConfig {
+            algorithm: SigningAlgorithm::Off,
+            secret: String::new(),
+            header: "X-Signature".to_string(),
+            content: SignedContent::CanonicalRequest,
+            encoding: SignatureEncoding::Hex,
+            remember_secret: false,
         }
     }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Request {
+    // survives reordering and moves, unlike the request's position; 0 until ensure_ids runs
+    #[serde(default)]
+    id: u64,
+
     url: String,
     body: String,
     headers: Vec<Vec<String>>,
     params: Vec<Vec<String>>,
     method: Method,
 
+    // params go out exactly as typed, for pre-encoded values percent-encoding would change
+    #[serde(default)]
+    raw_query: bool,
+
+    #[serde(default)]
+    description: String,
+
+    // overrides the global proxy when set
+    #[serde(default)]
+    proxy: String,
+
+    // overrides the global User-Agent when set
+    #[serde(default)]
+    user_agent: String,
+    // no User-Agent at all, neither the global one nor a header row
+    #[serde(default)]
+    omit_user_agent: bool,
+
+    #[serde(default)]
+    tls: TlsConfig,
+
+    #[serde(default)]
+    auth: AuthConfig,
+
+    #[serde(default)]
+    signing: SigningConfig,
+
+    // [variable, json path] pairs filled in from each successful response
+    #[serde(default = "empty_row")]
+    captures: Vec<Vec<String>>,
+
+    // [name, value] pairs that win over the captured variables for this request only
+    #[serde(default = "empty_row")]
+    local_variables: Vec<Vec<String>>,
+
+    // names of the headers (lowercased) and params whose values are masked and left out of exports
+    #[serde(default)]
+    secret_headers: BTreeSet<String>,
+    #[serde(default)]
+    secret_params: BTreeSet<String>,
+
+    // show json responses as a collapsible tree instead of highlighted text
+    #[serde(default)]
+    json_tree: bool,
+    // json pointers of the tree nodes the user folded
+    #[serde(skip)]
+    collapsed_nodes: HashSet<String>,
+
+    // id of the collection folder holding the request, None at the top level
+    #[serde(default)]
+    folder: Option<u64>,
+
+    // path the backend reads the body from at send time, replaces `body` while set
+    #[serde(default)]
+    body_file: Option<String>,
+    // size of body_file, or why it can't be read, as last reported by the backend
+    #[serde(skip)]
+    body_file_info: Option<Result<u64, String>>,
+
+    // the url the TLS tab last inspected and what came back, from a connection separate from the request's
+    #[serde(skip)]
+    tls_inspection: Option<(String, Result<TlsDetails, String>)>,
+    #[serde(skip)]
+    inspecting_tls: bool,
+
+    // content type the body looks like when no Content-Type header is set, offered in the body tab
+    #[serde(skip)]
+    suggested_content_type: Option<&'static str>,
+    // the note that GET and HEAD bodies are usually ignored was closed for this request
+    #[serde(default)]
+    body_note_dismissed: bool,
+
+    #[serde(default)]
+    retries: u8,
+    #[serde(default)]
+    retry_on: RetryPolicy,
+
+    // how the response body is shown, whatever Content-Type it came with
+    #[serde(default)]
+    body_format: BodyFormat,
+
+    // Auto goes through the backend and only falls back to the browser when it can't be reached
+    #[serde(default)]
+    transport: Transport,
+
+    #[serde(default)]
+    http_version: Option<HttpVersion>,
+
+    // sends If-None-Match / If-Modified-Since from the validators below
+    #[serde(default)]
+    conditional: bool,
+    // ETag and Last-Modified of the last response that had them, cleared when the url changes
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+
+    // json schema every response is checked against
+    #[serde(default)]
+    response_schema: Option<String>,
+
     response: Response,
 
+    // the response before the current one, kept for the compare view
+    #[serde(default)]
+    last_response: Option<Response>,
+
     // META
     name: String,
 
+    #[serde(default)]
+    pinned: bool,
+
+    // labels like "smoke" or "wip", shown as chips and usable as a sidebar filter
+    #[serde(default)]
+    tags: Vec<String>,
+
     req_tab: u8,
     resp_tab: u8,
 
+    #[serde(default)]
+    docs_preview: bool,
+
+    #[serde(default)]
+    diff_view: bool,
+
+    // the response headers tab, narrowed to names or values containing the filter and
+    // optionally sorted by name, the response keeps the order they arrived in
+    #[serde(default)]
+    resp_header_filter: String,
+    #[serde(default)]
+    sort_resp_headers: bool,
+
+    // show the response body with base64 decoded
+    #[serde(default)]
+    base64_decode: bool,
+
+    // epoch millis, None for requests saved before these were tracked
+    #[serde(default)]
+    created_at: Option<u64>,
+    #[serde(default)]
+    modified_at: Option<u64>,
+    #[serde(default)]
+    last_sent_at: Option<u64>,
+
+    // the most recent statuses, drawn as a strip of dots while polling
+    #[serde(default)]
+    status_log: Vec<StatusPoint>,
+
+    // times of the latest answered sends, oldest first, for the sparkline
+    #[serde(default)]
+    time_history: Vec<u32>,
+
+    // scroll offset of the response body, restored when the request is shown again
+    #[serde(default)]
+    resp_scroll: i32,
+
+    // where the body editor was left, for this session only
+    #[serde(skip)]
+    body_scroll: i32,
+    #[serde(skip)]
+    body_selection: Option<(u32, u32)>,
+
+    // edited since the save button was last pressed, marked in the sidebar
+    #[serde(skip)]
+    modified: bool,
+
     loading: bool
 }
 
 impl Request {
     fn new() -> Request {
         Request {
+            id: new_id(),
+
             url: String::new(),
             body: String::new(),
             headers: vec![vec![String::new(), String::new()]],
             params: vec![vec![String::new(), String::new()]],
             method: Method::GET,
 
+            raw_query: false,
+
+            description: String::new(),
+
+            proxy: String::new(),
+            user_agent: String::new(),
+            omit_user_agent: false,
+
+            tls: TlsConfig::default(),
+
+            auth: AuthConfig::default(),
+            signing: SigningConfig::default(),
+            captures: empty_row(),
+            local_variables: empty_row(),
+            secret_headers: BTreeSet::new(),
+            secret_params: BTreeSet::new(),
+            json_tree: false,
+            collapsed_nodes: HashSet::new(),
+            folder: None,
+            body_file: None,
+            body_file_info: None,
+            tls_inspection: None,
+            inspecting_tls: false,
+            suggested_content_type: None,
+            body_note_dismissed: false,
+
+            retries: 0,
+            retry_on: RetryPolicy::NetworkErrors,
+            transport: Transport::Auto,
+            http_version: None,
+            conditional: false,
+            etag: None,
+            last_modified: None,
+            body_format: BodyFormat::Auto,
+
+            response_schema: None,
+
             response: Response::new(),
 
+            last_response: None,
+
             // META
-            name: "New Request ".to_string(),
+            name: tr("name.new_request"),
+
+            pinned: false,
+            tags: Vec::new(),
 
             req_tab: 1,
             resp_tab: 1,
 
+            docs_preview: false,
+
+            diff_view: false,
+            resp_header_filter: String::new(),
+            sort_resp_headers: false,
+            base64_decode: false,
+
+            created_at: Some(now_ms()),
+            modified_at: Some(now_ms()),
+            last_sent_at: None,
+
+            status_log: Vec::new(),
+            time_history: Vec::new(),
+
+            resp_scroll: 0,
+            body_scroll: 0,
+            body_selection: None,
+            modified: false,
+
             loading: false
         }
     }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
-struct Collection {
+pub struct Collection {
+    #[serde(default)]
+    id: u64,
+
     name: String,
     requests: Vec<Request>,
     collapsed: bool,
+
+    // prepended to request urls that start with "/"
+    #[serde(default)]
+    base_url: Option<String>,
+
+    // requests stay in the flat list above and point at their folder, so indices into it don't move
+    #[serde(default)]
+    folders: Vec<Folder>,
+
+    // [name, value] pairs every request of the collection sees, over the captured values
+    #[serde(default = "empty_row")]
+    variables: Vec<Vec<String>>,
+    // names of the variables above whose values are masked on screen and left out of exports
+    #[serde(default)]
+    secret_variables: BTreeSet<String>,
+
+    // seeded on first run, removed together by Msg::Collections(CollectionsMsg::DismissSamples)
+    #[serde(default)]
+    sample: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Folder {
+    id: u64,
+    name: String,
+    // None for a folder at the top of the collection
+    parent: Option<u64>,
+    collapsed: bool,
 }
 
 impl Collection {
     fn new() -> Collection {
         Collection {
-            name: "New Collection ".to_string(),
+            id: new_id(),
+            name: tr("name.new_collection"),
             requests: vec![],
             collapsed: false,
+            base_url: None,
+            folders: vec![],
+            variables: empty_row(),
+            secret_variables: BTreeSet::new(),
+            sample: false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    proxy: String,
+    no_proxy: Vec<String>,
+
+    // when off, response bodies only live for the current session
+    persist_responses: bool,
+
+    // applied to every send, 0 means no timeout
+    timeout_ms: u64,
+    follow_redirects: bool,
+    // double-clicking a sidebar request selects and sends it
+    double_click_send: bool,
+    // empty keeps the backend's default, a User-Agent header on the request wins over it
+    user_agent: String,
+    // statuses kept per request for the polling strip
+    status_log_length: usize,
+    // bodies above this render truncated until the user asks for all of it
+    truncate_size: u64,
+    // bytes the backend reads of a response body at most, 0 reads everything
+    max_response_size: u64,
+    // copied into every request created from the sidebar
+    default_headers: Vec<Vec<String>>,
+    // requests a parallel collection run keeps in flight
+    run_concurrency: usize,
+    // what the sort button of a collection orders its requests by
+    collection_order: RequestOrder,
+
+    language: Lang,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            proxy: String::new(),
+            no_proxy: Vec::new(),
+
+            persist_responses: true,
+
+            timeout_ms: 0,
+            follow_redirects: true,
+            double_click_send: true,
+            user_agent: String::new(),
+            status_log_length: STATUS_LOG_LENGTH,
+            truncate_size: LARGE_BODY_LIMIT,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            default_headers: empty_row(),
+            run_concurrency: DEFAULT_RUN_CONCURRENCY,
+            collection_order: RequestOrder::default(),
+
+            language: Lang::English,
+        }
+    }
+}
+
+// what a send would post right now, for the preview tab
+pub struct RequestPreview {
+    method: String,
+    url: String,
+    headers: Vec<Vec<String>>,
+    body: String,
+    // {{names}} that no variable filled in
+    unresolved: Vec<String>,
+}
+
+// a removed item kept around for a few seconds so the removal can be undone, with where it was to
+// put it back there. a request's collection is kept by id, its position can change before the undo
+#[derive(Clone)]
+pub enum DeletedItem {
+    Request(usize, Request),
+    CollectionRequest(u64, usize, Request),
+    // and the request that was selected in it, None when another collection was
+    Collection(usize, Collection, Option<usize>),
+}
+
+impl DeletedItem {
+    fn id(&self) -> u64 {
+        match self {
+            DeletedItem::Request(_, request) | DeletedItem::CollectionRequest(_, _, request) => request.id,
+            DeletedItem::Collection(_, collection, _) => collection.id,
+        }
+    }
+}
+
+// a removed request or collection, kept past the session until restored or purged
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TrashedItem {
+    // epoch millis
+    deleted_at: f64,
+    content: TrashedContent,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum TrashedContent {
+    // id of the collection it was in, None for the main list
+    // boxed, a request is many times the size of a collection's own fields
+    Request(Option<u64>, Box<Request>),
+    Collection(Collection),
+}
+
+impl TrashedItem {
+    fn id(&self) -> u64 {
+        match &self.content {
+            TrashedContent::Request(_, request) => request.id,
+            TrashedContent::Collection(collection) => collection.id,
+        }
+    }
+
+    fn name(&self) -> &str {
+        match &self.content {
+            TrashedContent::Request(_, request) => &request.name,
+            TrashedContent::Collection(collection) => &collection.name,
+        }
+    }
+
+    fn requests_mut(&mut self) -> &mut [Request] {
+        match &mut self.content {
+            TrashedContent::Request(_, request) => std::slice::from_mut(&mut **request),
+            TrashedContent::Collection(collection) => &mut collection.requests,
         }
     }
 }
 
+// the {{variable}} suggestions open under the input being typed in
+#[derive(Clone)]
+pub struct Autocomplete {
+    field: AutocompleteField,
+    // byte offset of the {{ being completed
+    start: usize,
+    matches: Vec<String>,
+    selected: usize,
+    // the matches are whole urls that replace the value, not {{variables}} for the token at the caret
+    whole_value: bool,
+    // the input's bottom left corner and width in viewport pixels, the popup is placed there
+    left: f64,
+    top: f64,
+    width: f64,
+}
+
+// the find & replace dialog, its matches are looked up again on every change to the search
+#[derive(Clone, Default)]
+pub struct FindReplace {
+    query: String,
+    replacement: String,
+    options: FindOptions,
+    matches: Vec<FieldMatch>,
+    // positions in matches the user unticked
+    skipped: HashSet<usize>,
+}
+
+// points at a request in the main list (no collection) or inside a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestRef {
+    collection: Option<usize>,
+    request: usize,
+}
+
+// the one request being re-sent on a timer
+#[derive(Debug, Clone, Copy)]
+pub struct Repeat {
+    // Request::id, the request can move while it is polled
+    request: u64,
+    interval_ms: u32,
+    // id returned by setInterval, needed to clear it
+    handle: i32,
+}
+
+// one pass over a collection. responses find their step by the id of the request they answer, so
+// parallel runs can't mix them up and sorting or deleting rows mid-run doesn't either
+#[derive(Debug, Clone)]
+pub struct CollectionRun {
+    // Collection::id
+    collection: u64,
+    steps: Vec<RunStep>,
+    // requests allowed in flight at once, 1 for an ordered run
+    concurrency: usize,
+    // the next step to send, steps.len() once every request went out
+    next: usize,
+    stopped: bool,
+    // epoch millis, for the wall clock time of the whole run
+    started_at: f64,
+    finished_at: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RunStep {
+    // Request::id, the request's position can change while the run goes
+    id: u64,
+    result: Option<RunResult>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RunResult {
+    status: u16,
+    time: u32,
+    failed: bool,
+    schema_violations: usize,
+}
+
+impl RunResult {
+    // the request never got a response: the token fetch failed or the backend answer was unreadable
+    fn unanswered() -> Self {
+        Self {
+            status: 0,
+            time: 0,
+            failed: true,
+            schema_violations: 0,
+        }
+    }
+}
+
+// a short message over every page, only kept for the session
+#[derive(Debug, Clone)]
+pub struct Toast {
+    id: u32,
+    level: ToastLevel,
+    text: String,
+    // epoch millis
+    created: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPoint {
+    status: u16,
+    failed: bool,
+    // epoch millis
+    timestamp: f64,
+}
+
+// panel sizes as fractions of the window, so they survive window resizes
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Layout {
+    sidebar_ratio: f64,
+    request_ratio: f64,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            sidebar_ratio: 0.25,
+            request_ratio: 0.55,
+        }
+    }
+}
+
+impl Layout {
+    // the bounds a drag can reach, also applied to layouts loaded from disk
+    fn clamped(self) -> Self {
+        Layout {
+            sidebar_ratio: clamp_ratio(self.sidebar_ratio, SIDEBAR_RATIO_RANGE, 0.25),
+            request_ratio: clamp_ratio(self.request_ratio, REQUEST_RATIO_RANGE, 0.55),
+        }
+    }
+}
+
+fn clamp_ratio(ratio: f64, (min, max): (f64, f64), default: f64) -> f64 {
+    if ratio.is_finite() {
+        ratio.clamp(min, max)
+    } else {
+        default
+    }
+}
+
+pub static SIDEBAR_RATIO_RANGE: (f64, f64) = (0.12, 0.5);
+pub static REQUEST_RATIO_RANGE: (f64, f64) = (0.2, 0.8);
+
 pub struct BoltState {
     bctx: BoltContext,
 }
 
-#[derive(Clone)]
+// not Clone on purpose, a copy would take every request and response body with it
 pub struct BoltContext {
     link: Option<Scope<BoltApp>>,
 
@@ -177,7 +1163,57 @@ pub struct BoltContext {
 
     main_col: Collection,
     collections: Vec<Collection>,
-    // resized: bool,
+
+    // Request::id of each open tab, a tab follows its request wherever it moves
+    open_tabs: Vec<u64>,
+    active_tab: Option<usize>,
+
+    settings: Settings,
+    theme: Theme,
+
+    // values captured from responses, used as {{name}} in later requests
+    variables: BTreeMap<String, String>,
+
+    layout: Layout,
+    resizing: Option<ResizeHandle>,
+
+    // set when the shown request changes so the next render restores its scroll
+    restore_scroll: bool,
+    // id of the element to focus after the next render
+    focus: Option<String>,
+
+    // the sidebars only list requests carrying this tag
+    tag_filter: Option<String>,
+    // order the sidebars list requests in, storage order stays as it is
+    sidebar_sort: SidebarSort,
+
+    // secret collection variables show their values and go into exports while set
+    reveal_secrets: bool,
+
+    save_status: SaveStatus,
+    // counts saves, only the answer to the latest one changes save_status
+    save_generation: u32,
+    // the desktop window asked to close, it does once the save went through
+    close_after_save: bool,
+
+    pending_delete: Option<DeleteTarget>,
+    last_deleted: Option<DeletedItem>,
+    // every field the last replace changed, undone together
+    last_replace: Option<Vec<FieldEdit>>,
+    find_replace: Option<FindReplace>,
+    autocomplete: Option<Autocomplete>,
+    // urls sent from the url bar, newest first, offered again while typing one
+    recent_urls: Vec<String>,
+    // oldest first
+    trash: Vec<TrashedItem>,
+    undo_generation: u32,
+
+    toasts: Vec<Toast>,
+    // id for the next toast
+    toast_counter: u32,
+
+    repeat: Option<Repeat>,
+    run: Option<CollectionRun>,
     // update_save: bool,
 }
 
@@ -190,6 +1226,91 @@ pub struct SaveState {
 
     main_col: Collection,
     collections: Vec<Collection>,
+
+    // ids, states that saved the tabs as positions under open_tabs open with the selected request only
+    #[serde(default)]
+    tabs: Vec<u64>,
+    #[serde(default)]
+    active_tab: Option<usize>,
+
+    #[serde(default)]
+    settings: Settings,
+
+    #[serde(default)]
+    theme: Theme,
+
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+
+    #[serde(default)]
+    layout: Layout,
+
+    #[serde(default)]
+    sidebar_sort: SidebarSort,
+
+    #[serde(default)]
+    trash: Vec<TrashedItem>,
+
+    #[serde(default)]
+    recent_urls: Vec<String>,
+}
+
+// polling faster than this would flood the backend
+pub static MIN_REPEAT_INTERVAL_MS: u32 = 500;
+pub static DEFAULT_REPEAT_INTERVAL_MS: u32 = 5000;
+
+// statuses kept per request for the polling strip, by default and at most
+pub static STATUS_LOG_LENGTH: usize = 20;
+pub static MAX_STATUS_LOG_LENGTH: usize = 500;
+
+// response times kept per request for the sparkline
+pub static TIME_HISTORY_LENGTH: usize = 30;
+
+// highest retry count the request settings accept
+pub static MAX_RETRIES: u8 = 10;
+
+// requests in flight at once during a parallel collection run
+pub static DEFAULT_RUN_CONCURRENCY: usize = 5;
+pub static MAX_RUN_CONCURRENCY: usize = 50;
+
+// oauth tokens are refreshed this long before they actually expire
+pub static TOKEN_EXPIRY_MARGIN_MS: f64 = 10_000.0;
+
+// how long a removed item can be brought back
+pub static UNDO_TIMEOUT_MS: i32 = 5000;
+
+// urls the url bar remembers
+pub static RECENT_URL_LIMIT: usize = 50;
+
+// what the trash keeps when the state loads, 30 days and the newest 100 items
+pub static TRASH_MAX_AGE_MS: f64 = 30.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+pub static TRASH_LIMIT: usize = 100;
+
+// how long info and warning toasts stay up
+pub static TOAST_TIMEOUT_MS: i32 = 4000;
+
+// quiet time after the last scroll event before the offset is saved
+pub static SCROLL_SAVE_DELAY_MS: i32 = 150;
+
+// bodies above this size skip formatting and highlighting and render truncated
+pub static LARGE_BODY_LIMIT: u64 = 1024 * 1024;
+
+// the backend stops reading a response body past this many bytes
+pub static DEFAULT_MAX_RESPONSE_SIZE: u64 = 100 * 1024 * 1024;
+pub static BODY_PREVIEW_LENGTH: usize = 64 * 1024;
+
+// files above this ask before being loaded into the body editor
+pub static BODY_FILE_WARN_SIZE: usize = 4 * 1024 * 1024;
+
+// Bumped whenever the layout of SaveState changes in a way older builds can't read
+pub static WORKSPACE_VERSION: u32 = 1;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorkspaceFile {
+    version: u32,
+    timestamp: u64,
+
+    state: SaveState,
 }
 
 impl BoltContext {
@@ -201,9 +1322,47 @@ impl BoltContext {
             collections: vec![],
             page: Page::Home,
 
+            open_tabs: vec![],
+            active_tab: None,
+
+            settings: Settings::default(),
+            // a fresh install follows the OS until the user picks a theme
+            theme: Theme::System,
+
+            variables: BTreeMap::new(),
+
             main_current: 0,
             col_current: vec![0, 0],
-            // resized: false,
+
+            layout: Layout::default(),
+            resizing: None,
+
+            restore_scroll: true,
+            focus: None,
+
+            tag_filter: None,
+            sidebar_sort: SidebarSort::Manual,
+
+            reveal_secrets: false,
+
+            save_status: SaveStatus::Saved,
+            save_generation: 0,
+            close_after_save: false,
+
+            pending_delete: None,
+            last_deleted: None,
+            last_replace: None,
+            find_replace: None,
+            autocomplete: None,
+            recent_urls: vec![],
+            trash: vec![],
+            undo_generation: 0,
+
+            toasts: vec![],
+            toast_counter: 0,
+
+            repeat: None,
+            run: None,
             // update_save: false,
         }
     }
@@ -227,14 +1386,22 @@ lazy_static::lazy_static! {
     static ref GLOBAL_STATE: Arc<Mutex<BoltState>> = Arc::new(Mutex::new(BoltState::new()));
 }
 
+// a panic while the state was held poisons the lock, keep going with the state as it was left
+// instead of failing every later event too
+pub fn global_state() -> MutexGuard<'static, BoltState> {
+    GLOBAL_STATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 impl Component for BoltApp {
     type Message = Msg;
     type Properties = ();
 
     fn create(ctx: &Context<Self>) -> Self {
         disable_text_selection();
+        enable_button_keys();
+        guard_unsaved_changes();
 
-        let mut state = GLOBAL_STATE.lock().unwrap();
+        let mut state = global_state();
         state.bctx.link = Some(ctx.link().clone());
 
         state.bctx.main_col.requests.push(Request::new());
@@ -243,65 +1410,296 @@ impl Component for BoltApp {
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
-        let mut state = GLOBAL_STATE.lock().unwrap();
+        let mut state = global_state();
+
+        // these only report on or trigger a save themselves, toasts aren't saved at all, nor are the open {{variable}} suggestions
+        let autosave = !matches!(
+            msg,
+            Msg::SaveState | Msg::SaveFinished(..) | Msg::CloseRequested | Msg::ShowToast(..) | Msg::DismissToast(_)
+                | Msg::Send(SendMsg::HostChecked(..))
+                | Msg::Send(SendMsg::InspectTls)
+                | Msg::Send(SendMsg::TlsInspected(..))
+                | Msg::OpenFindReplace | Msg::CloseFindReplace | Msg::FindReplaceChanged | Msg::ToggleFindCase
+                | Msg::ToggleFindWholeWord | Msg::ToggleReplaceMatch(_)
+                | Msg::VariableAutocomplete(
+                    AutocompleteAction::Typed(_) | AutocompleteAction::Next | AutocompleteAction::Previous | AutocompleteAction::Dismiss
+                )
+        );
+
+        let response = matches!(msg, Msg::Send(SendMsg::ReceivedResponse(..)));
 
         let should_render = process::update::process(&mut state.bctx, msg);
 
-        if should_render {
+        // a collection run saves once its last response is in rather than after every one
+        let mid_run = response && state.bctx.run.as_ref().is_some_and(is_running);
+
+        // a drag re-renders on every mouse move, only save once it ends
+        if autosave && !mid_run && should_render && state.bctx.resizing.is_none() {
             save_state(&mut state.bctx);
         }
 
         should_render
     }
 
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        let mut state = global_state();
+
+        if state.bctx.restore_scroll {
+            restore_panels(&state.bctx);
+            state.bctx.restore_scroll = false;
+        }
+
+        if let Some(id) = state.bctx.focus.take() {
+            focus_element(&id);
+        }
+    }
+
     fn view(&self, _ctx: &Context<Self>) -> Html {
-        let mut state = GLOBAL_STATE.lock().unwrap();
+        let mut state = global_state();
 
         let page = state.bctx.page;
 
-        if page == Page::Home {
+        let page_view = if page == Page::Home {
             view::home::home_view(&mut state.bctx)
         } else if page == Page::Collections {
             view::collections::collections_view(&mut state.bctx)
+        } else if page == Page::Settings {
+            view::settings::settings_view(&mut state.bctx)
         } else {
             view::home::home_view(&mut state.bctx)
+        };
+
+        html! {
+            <>
+                {page_view}
+                {view::toast::toast_stack(&state.bctx)}
+                {view::autocomplete::autocomplete_popup(&state.bctx)}
+                {view::replace::find_replace_dialog(&state.bctx)}
+            </>
         }
     }
 }
 
-fn send_request(request: &mut Request) {
+fn send_request(
+    request: &mut Request,
+    settings: &Settings,
+    base_url: Option<&str>,
+    variables: &BTreeMap<String, String>,
+    target: RequestRef,
+) {
     request.loading = true;
-    invoke_send(request);
+    request.last_sent_at = Some(now_ms());
+
+    if request.auth.needs_token() {
+        request.auth.fetching = true;
+        invoke_token_fetch(request, settings, target, true);
+    } else {
+        invoke_send(request, settings, base_url, variables, target);
+    }
+}
+
+fn fetch_token(request: &mut Request, settings: &Settings, target: RequestRef) {
+    request.auth.fetching = true;
+    invoke_token_fetch(request, settings, target, false);
 }
 
-pub fn receive_response(data: &str) {
-    let mut state = GLOBAL_STATE.lock().unwrap();
-    let bctx = &mut state.bctx;
+// runs inside update, the future that fetched the token only hands over the backend's answer
+pub fn receive_token(bctx: &mut BoltContext, target: RequestRef, then_send: bool, data: &str) {
+    let response: Response = match serde_json::from_str(data) {
+        Ok(response) => response,
+        Err(err) => {
+            push_toast(bctx, ToastLevel::Warning, tr_args("toast.bad_token_response", &[&err.to_string()]));
+
+            if let Some(request) = request_mut(bctx, &target) {
+                request.auth.fetching = false;
+                request.loading = false;
+            }
+
+            if then_send {
+                advance_run(bctx, &target, RunResult::unanswered());
+            }
+
+            return;
+        }
+    };
+
+    let settings = bctx.settings.clone();
+    let base_url = collection_base_url(bctx, &target);
+    let variables = collection_variables(bctx, &target);
+
+    let mut fetched = false;
+
+    if let Some(request) = request_mut(bctx, &target) {
+        request.auth.fetching = false;
+
+        match parse_token_response(response.status, response.failed, &response.body) {
+            Ok((token, expires_in)) => {
+                request.auth.access_token = token;
+                request.auth.expires_at = expires_in.map(|secs| now_ms() as f64 + secs * 1000.0);
+                request.auth.token_error = String::new();
+
+                fetched = true;
+
+                if then_send {
+                    invoke_send(request, &settings, base_url.as_deref(), &variables, target);
+                }
+            }
+            Err(err) => {
+                request.auth.token_error = err;
+                request.loading = false;
+
+                if then_send {
+                    advance_run(bctx, &target, RunResult::unanswered());
+                }
+            }
+        }
+    }
+
+    if fetched {
+        share_token(bctx, &target);
+    }
+}
 
+// hands a freshly fetched token to the other requests of the collection that use the same client
+fn share_token(bctx: &mut BoltContext, target: &RequestRef) {
+    let Some(requests) = collection_requests_mut(bctx, target) else {
+        return;
+    };
+
+    let Some(source) = requests.get(target.request).map(|request| request.auth.clone()) else {
+        return;
+    };
+
+    for request in requests.iter_mut() {
+        if request.auth.shares_token_with(&source) {
+            request.auth.access_token = source.access_token.clone();
+            request.auth.expires_at = source.expires_at;
+            request.auth.token_error = String::new();
+        }
+    }
+}
+
+// takes a still valid token from a request of the same collection instead of fetching another
+pub fn reuse_token(bctx: &mut BoltContext, target: &RequestRef) {
+    let Some(requests) = collection_requests_mut(bctx, target) else {
+        return;
+    };
+
+    let Some(auth) = requests.get(target.request).map(|request| &request.auth) else {
+        return;
+    };
+
+    if !auth.needs_token() {
+        return;
+    }
+
+    let token = requests
+        .iter()
+        .map(|request| &request.auth)
+        .find(|other| other.shares_token_with(auth) && !other.needs_token())
+        .map(|other| (other.access_token.clone(), other.expires_at));
+
+    if let Some((access_token, expires_at)) = token {
+        let auth = &mut requests[target.request].auth;
+
+        auth.access_token = access_token;
+        auth.expires_at = expires_at;
+    }
+}
+
+// runs inside update like every other change, so a burst of responses queues up as messages
+// instead of fighting over the state lock
+pub fn receive_response(bctx: &mut BoltContext, target: RequestRef, data: &str) {
     // bolt_log("received a response");
 
-    let mut response: Response = serde_json::from_str(data).unwrap();
+    let mut response = match parse_response(data) {
+        Ok(response) => response,
+        Err(err) => {
+            push_toast(bctx, ToastLevel::Warning, tr_args("toast.bad_response", &[&err.to_string()]));
+
+            if let Some(request) = request_mut(bctx, &target) {
+                request.loading = false;
+            }
+
+            advance_run(bctx, &target, RunResult::unanswered());
+
+            return;
+        }
+    };
 
     // _bolt_log(&format!("{:?}", response));
 
-    if response.response_type == ResponseType::JSON {
-        response.body = format_json(&response.body);
-        response.body = highlight_body(&response.body);
+    let theme = bctx.theme;
+    let status_log_length = bctx.settings.status_log_length;
+
+    // the request was removed while it was in flight
+    let Some(request) = request_mut(bctx, &target) else {
+        return;
+    };
+
+    store_validators(request, &response);
+
+    // a 304 has no body of its own, it is shown with the one it confirmed
+    if !not_modified_fallback(&mut response, &request.response) {
+        apply_body_format(&mut response, request.body_format, theme);
     }
 
-    if bctx.page == Page::Home {
-        let current = response.request_index;
-        state.bctx.main_col.requests[current].response = response;
-        state.bctx.main_col.requests[current].loading = false;
-    } else {
-        let current = &bctx.col_current;
-        bctx.collections[current[0]].requests[current[1]].response = response;
-        bctx.collections[current[0]].requests[current[1]].loading = false;
+    response.schema_violations = check_schema(&request.response_schema, &response);
+
+    let result = RunResult {
+        status: response.status,
+        time: response.time,
+        failed: response.failed,
+        schema_violations: response.schema_violations.as_ref().map_or(0, |violations| violations.len()),
+    };
+
+    let captured = capture_values(&request.captures, &response);
+
+    request.status_log.push(StatusPoint {
+        status: response.status,
+        failed: response.failed,
+        timestamp: now_ms() as f64,
+    });
+
+    // the setting may have shrunk since the last response
+    let overflow = request.status_log.len().saturating_sub(status_log_length);
+    request.status_log.drain(..overflow);
+
+    // a failed send has no server time worth charting
+    if !response.failed {
+        if request.time_history.len() == TIME_HISTORY_LENGTH {
+            request.time_history.remove(0);
+        }
+
+        request.time_history.push(response.time);
     }
 
-    let link = state.bctx.link.as_ref().unwrap();
+    let previous = std::mem::replace(&mut request.response, response);
+
+    // a request that was never sent has nothing to compare against
+    if previous.status != 0 || previous.failed {
+        request.last_response = Some(previous);
+    }
+
+    request.loading = false;
+
+    // captured before the next request of a run goes out, so it can use them
+    bctx.variables.extend(captured);
+
+    advance_run(bctx, &target, result);
+}
+
+// the backend never answered, so there is no response to show
+// `message` is the whole toast, the request either never reached the backend or wasn't sent at all
+pub fn receive_send_error(bctx: &mut BoltContext, target: RequestRef, message: String) {
+    push_toast(bctx, ToastLevel::Warning, message);
+
+    if let Some(request) = request_mut(bctx, &target) {
+        request.auth.fetching = false;
+        request.loading = false;
+    }
 
-    link.send_message(Msg::Update);
+    advance_run(bctx, &target, RunResult::unanswered());
 }
 
 fn main() {
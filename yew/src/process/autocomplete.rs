@@ -0,0 +1,153 @@
+use crate::helpers::dynamic::{complete_token, open_token, rank_variables};
+use crate::helpers::enums::{AutocompleteAction, AutocompleteField};
+use crate::helpers::url::matching_urls;
+use crate::process::tabs::{current_ref, request_variables};
+use crate::process::update::process;
+use crate::utils::{byte_to_utf16, utf16_to_byte};
+use crate::Autocomplete;
+use crate::BoltContext;
+use crate::EditMsg;
+use crate::Msg;
+use web_sys::HtmlInputElement;
+
+// more would push the popup past the bottom of the window
+const MAX_SUGGESTIONS: usize = 8;
+
+pub fn autocomplete(bctx: &mut BoltContext, action: AutocompleteAction) -> bool {
+    match action {
+        AutocompleteAction::Typed(field) => suggest(bctx, field),
+        AutocompleteAction::Next | AutocompleteAction::Previous => {
+            let popup = match bctx.autocomplete.as_mut() {
+                Some(popup) => popup,
+                None => return false,
+            };
+
+            let count = popup.matches.len();
+
+            popup.selected = if action == AutocompleteAction::Next {
+                (popup.selected + 1) % count
+            } else {
+                (popup.selected + count - 1) % count
+            };
+
+            true
+        }
+        AutocompleteAction::Accept(index) => accept(bctx, index),
+        AutocompleteAction::Dismiss => bctx.autocomplete.take().is_some(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn input_element(id: &str) -> Option<HtmlInputElement> {
+    use wasm_bindgen::JsCast;
+
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(id)?
+        .dyn_into::<HtmlInputElement>()
+        .ok()
+}
+
+// no page natively, so no popup
+#[cfg(not(target_arch = "wasm32"))]
+fn input_element(_id: &str) -> Option<HtmlInputElement> {
+    None
+}
+
+// the input's value and its caret as a byte offset, the end when it has no caret
+fn value_and_caret(input: &HtmlInputElement) -> (String, usize) {
+    let value = input.value();
+
+    let caret = match input.selection_start() {
+        Ok(Some(caret)) => utf16_to_byte(&value, caret),
+        _ => value.len(),
+    };
+
+    (value, caret)
+}
+
+fn suggest(bctx: &mut BoltContext, field: AutocompleteField) -> bool {
+    let was_open = bctx.autocomplete.take().is_some();
+
+    let (input, target) = match (input_element(&field.input_id()), current_ref(bctx)) {
+        (Some(input), Some(target)) => (input, target),
+        _ => return was_open,
+    };
+
+    let (value, caret) = value_and_caret(&input);
+
+    let (start, matches, whole_value) = match open_token(&value, caret) {
+        Some((start, typed)) => {
+            let variables = request_variables(bctx, &target);
+            (start, rank_variables(variables.keys().map(String::as_str), typed, MAX_SUGGESTIONS), false)
+        }
+        // outside a {{token}} the url bar offers the urls sent before
+        None if field == AutocompleteField::Url => (0, matching_urls(&bctx.recent_urls, &value, MAX_SUGGESTIONS), true),
+        None => return was_open,
+    };
+
+    if matches.is_empty() {
+        return was_open;
+    }
+
+    let rect = input.get_bounding_client_rect();
+
+    bctx.autocomplete = Some(Autocomplete {
+        field,
+        start,
+        matches,
+        selected: 0,
+        whole_value,
+        left: rect.left(),
+        top: rect.bottom(),
+        width: rect.width(),
+    });
+
+    true
+}
+
+fn accept(bctx: &mut BoltContext, index: Option<usize>) -> bool {
+    let popup = match bctx.autocomplete.take() {
+        Some(popup) => popup,
+        None => return false,
+    };
+
+    let name = match popup.matches.get(index.unwrap_or(popup.selected)) {
+        Some(name) => name,
+        None => return true,
+    };
+
+    let input = match input_element(&popup.field.input_id()) {
+        Some(input) => input,
+        None => return true,
+    };
+
+    let (completed, caret) = if popup.whole_value {
+        (name.clone(), name.len())
+    } else {
+        let (value, caret) = value_and_caret(&input);
+
+        // the caret left the token since the list was shown
+        if open_token(&value, caret).map(|(start, _)| start) != Some(popup.start) {
+            return true;
+        }
+
+        complete_token(&value, popup.start, caret, name)
+    };
+
+    input.set_value(&completed);
+
+    let caret = byte_to_utf16(&completed, caret);
+    let _ = input.set_selection_range(caret, caret);
+
+    // stored the same way typing it would have
+    let changed = match popup.field {
+        AutocompleteField::Url => Msg::Edit(EditMsg::UrlChanged),
+        AutocompleteField::HeaderValue(index) => Msg::Edit(EditMsg::HeaderChanged(index)),
+        AutocompleteField::ParamValue(index) => Msg::Edit(EditMsg::ParamChanged(index)),
+    };
+
+    process(bctx, changed);
+
+    true
+}
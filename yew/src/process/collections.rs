@@ -0,0 +1,1056 @@
+use crate::helpers::enums::DeleteTarget;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::process::folders::*;
+use crate::process::runner::forget_run_request;
+use crate::process::send::stop_repeat;
+use crate::process::tabs::*;
+use crate::utils::*;
+use crate::BoltContext;
+use crate::Collection;
+use crate::DeletedItem;
+use crate::Folder;
+use crate::CollectionsMsg;
+use crate::Request;
+use crate::RequestRef;
+use crate::TrashedContent;
+
+// the messages that add, remove, reorder or restore collections and requests, and edit what a
+// collection holds besides its requests: variables, base url and folders
+pub fn collections(bctx: &mut BoltContext, msg: CollectionsMsg) -> bool {
+    match msg {
+        CollectionsMsg::AddCollection => {
+            let mut new_collection = Collection::new();
+
+            new_collection.name = format!("{} {}", new_collection.name, bctx.collections.len() + 1);
+            bctx.collections.push(new_collection);
+
+            true
+        }
+
+        CollectionsMsg::DuplicateCollection(index) => {
+            let mut copy = match bctx.collections.get(index) {
+                Some(col) => col.clone(),
+                None => return false,
+            };
+
+            copy.name = tr_args("name.copy", &[&copy.name]);
+            copy.sample = false;
+            copy.id = new_id();
+
+            // the copy starts without the original's responses
+            for request in copy.requests.iter_mut() {
+                request.id = new_id();
+                clear_responses(request);
+            }
+
+            // appended, so col_current and the open tabs still point at the same requests
+            bctx.collections.push(copy);
+
+            true
+        }
+
+        CollectionsMsg::MoveCollection { from, to } => {
+            if from == to || from >= bctx.collections.len() || to >= bctx.collections.len() {
+                return false;
+            }
+
+            // tabs, polling, runs and undo go by id, only the selection is a position
+            let selection = collection_selection(bctx);
+
+            let col = bctx.collections.remove(from);
+            bctx.collections.insert(to, col);
+
+            restore_collection_selection(bctx, selection);
+
+            true
+        }
+
+        CollectionsMsg::SortCollection(index) => {
+            let selection = collection_selection(bctx);
+
+            let Some(col) = bctx.collections.get_mut(index) else {
+                return false;
+            };
+
+            let order = sorted_request_order(&col.requests, bctx.settings.collection_order);
+
+            let mut requests: Vec<Option<Request>> = std::mem::take(&mut col.requests).into_iter().map(Some).collect();
+            col.requests = order.iter().filter_map(|old| requests[*old].take()).collect();
+
+            sync_request_indices(&mut col.requests);
+
+            restore_collection_selection(bctx, selection);
+
+            true
+        }
+
+        CollectionsMsg::DismissSamples => {
+            let selection = collection_selection(bctx);
+
+            bctx.collections.retain(|col| !col.sample);
+
+            restore_collection_selection(bctx, selection);
+            forget_removed(bctx);
+
+            true
+        }
+
+        CollectionsMsg::RemoveCollection(id) => {
+            let index = match collection_position(bctx, id) {
+                Some(index) => index,
+                None => return false,
+            };
+
+            let selection = collection_selection(bctx);
+
+            let collection = bctx.collections.remove(index);
+            move_to_trash(bctx, TrashedContent::Collection(collection));
+
+            // deleting another collection leaves the selection where it is
+            restore_collection_selection(bctx, selection);
+            forget_removed(bctx);
+
+            true
+        }
+
+        CollectionsMsg::ToggleCollapsed(index) => {
+            let collection = &mut bctx.collections[index];
+
+            collection.collapsed = !collection.collapsed;
+
+            true
+        }
+
+        CollectionsMsg::AddCollectionVariable => {
+            let Some(collection) = current_collection_mut(bctx) else {
+                return false;
+            };
+
+            collection.variables.push(vec![String::new(), String::new()]);
+            let focus = format!("colvarname{}", collection.variables.len() - 1);
+            bctx.focus = Some(focus);
+
+            true
+        }
+
+        CollectionsMsg::RemoveCollectionVariable(index) => {
+            let Some(collection) = current_collection_mut(bctx).filter(|col| index < col.variables.len()) else {
+                return false;
+            };
+
+            collection.variables.remove(index);
+
+            true
+        }
+
+        CollectionsMsg::CollectionVariableChanged(index) => {
+            let variable = get_collection_variable(index);
+
+            let Some(collection) = current_collection_mut(bctx).filter(|col| index < col.variables.len()) else {
+                return false;
+            };
+
+            // a renamed secret stays secret
+            let old_name = collection.variables[index][0].trim().to_string();
+
+            if old_name != variable[0].trim() && collection.secret_variables.remove(&old_name) {
+                collection.secret_variables.insert(variable[0].trim().to_string());
+            }
+
+            collection.variables[index] = variable;
+
+            true
+        }
+
+        CollectionsMsg::ToggleSecretVariable(index) => {
+            let Some(collection) = current_collection_mut(bctx).filter(|col| index < col.variables.len()) else {
+                return false;
+            };
+
+            let name = collection.variables[index][0].trim().to_string();
+
+            if name.is_empty() {
+                return false;
+            }
+
+            if !collection.secret_variables.remove(&name) {
+                collection.secret_variables.insert(name);
+            }
+
+            true
+        }
+
+        CollectionsMsg::BaseUrlChanged => {
+            let base_url = get_input_value("baseurlinput").trim().to_string();
+
+            let Some(collection) = current_collection_mut(bctx) else {
+                return false;
+            };
+
+            collection.base_url = if base_url.is_empty() { None } else { Some(base_url) };
+
+            true
+        }
+
+        CollectionsMsg::AddRequest => {
+            let mut new_request = Request::new();
+            new_request.name = format!("{} {}", new_request.name, bctx.main_col.requests.len() + 1);
+            new_request.headers = template_headers(&bctx.settings);
+
+            bctx.main_col.requests.push(new_request);
+
+            // the first request is opened right away, there was nothing to show before it
+            if bctx.main_col.requests.len() == 1 {
+                bctx.main_current = 0;
+                open_tab(bctx, RequestRef { collection: None, request: 0 });
+            }
+
+            true
+        }
+
+        CollectionsMsg::TogglePin(id) => {
+            let req = match bctx.main_col.requests.iter_mut().find(|req| req.id == id) {
+                Some(req) => req,
+                None => return false,
+            };
+
+            req.pinned = !req.pinned;
+
+            true
+        }
+
+        CollectionsMsg::AddToCollection(index) => {
+            let collection = &mut bctx.collections[index];
+
+            let mut new_request = Request::new();
+            new_request.name = format!("{} {}", new_request.name, collection.requests.len() + 1);
+            new_request.headers = template_headers(&bctx.settings);
+
+            collection.requests.push(new_request);
+
+            true
+        }
+
+        CollectionsMsg::RemoveRequest(id) => {
+            let index = match request_position(&bctx.main_col.requests, id) {
+                Some(index) => index,
+                None => return false,
+            };
+
+            let request = bctx.main_col.requests.remove(index);
+            move_to_trash(bctx, TrashedContent::Request(None, Box::new(request)));
+
+            // the selection stays on the same request when one above it goes
+            if index < bctx.main_current {
+                bctx.main_current -= 1;
+            }
+
+            // an emptied list leaves nothing to select, the editor shows its empty state
+            bctx.main_current = bctx.main_current.min(bctx.main_col.requests.len().saturating_sub(1));
+            sync_request_indices(&mut bctx.main_col.requests);
+            forget_removed(bctx);
+
+            true
+        }
+
+        CollectionsMsg::RemoveFromCollection(col_id, req_id) => {
+            let (col_index, req_index) = match collection_request_position(bctx, col_id, req_id) {
+                Some(position) => position,
+                None => return false,
+            };
+
+            let request = bctx.collections[col_index].requests.remove(req_index);
+            move_to_trash(bctx, TrashedContent::Request(Some(col_id), Box::new(request)));
+            sync_request_indices(&mut bctx.collections[col_index].requests);
+
+            // same as the main list, an emptied collection stays selected and shows its empty state
+            if bctx.col_current[0] == col_index {
+                if req_index < bctx.col_current[1] {
+                    bctx.col_current[1] -= 1;
+                }
+
+                bctx.col_current[1] = bctx.col_current[1].min(bctx.collections[col_index].requests.len().saturating_sub(1));
+            }
+
+            forget_removed(bctx);
+            forget_run_request(bctx, req_id);
+
+            true
+        }
+
+        CollectionsMsg::AskDelete(target) => {
+            bctx.pending_delete = Some(target);
+
+            true
+        }
+
+        CollectionsMsg::CancelDelete => {
+            bctx.pending_delete = None;
+
+            true
+        }
+
+        CollectionsMsg::ConfirmDelete => {
+            let target = match bctx.pending_delete.take() {
+                Some(target) => target,
+                None => return true,
+            };
+
+            // the item may be gone already, then there is nothing left to delete
+            let (deleted, remove) = match target {
+                DeleteTarget::Request(id) => match request_position(&bctx.main_col.requests, id) {
+                    Some(index) => (
+                        DeletedItem::Request(index, bctx.main_col.requests[index].clone()),
+                        CollectionsMsg::RemoveRequest(id),
+                    ),
+                    None => return true,
+                },
+                DeleteTarget::CollectionRequest(col_id, req_id) => match collection_request_position(bctx, col_id, req_id) {
+                    Some((col_index, req_index)) => (
+                        DeletedItem::CollectionRequest(
+                            col_id,
+                            req_index,
+                            bctx.collections[col_index].requests[req_index].clone(),
+                        ),
+                        CollectionsMsg::RemoveFromCollection(col_id, req_id),
+                    ),
+                    None => return true,
+                },
+                DeleteTarget::Collection(id) => match collection_position(bctx, id) {
+                    Some(index) => {
+                        let selected = (bctx.col_current[0] == index).then_some(bctx.col_current[1]);
+
+                        (
+                            DeletedItem::Collection(index, bctx.collections[index].clone(), selected),
+                            CollectionsMsg::RemoveCollection(id),
+                        )
+                    }
+                    None => return true,
+                },
+            };
+
+            collections(bctx, remove);
+
+            bctx.last_deleted = Some(deleted);
+            bctx.last_replace = None;
+            bctx.undo_generation += 1;
+            schedule_undo_expiry(bctx.undo_generation);
+
+            true
+        }
+
+        CollectionsMsg::UndoDelete => {
+            // back in place, so out of the trash again
+            if let Some(deleted) = &bctx.last_deleted {
+                let id = deleted.id();
+                bctx.trash.retain(|item| item.id() != id);
+            }
+
+            match bctx.last_deleted.take() {
+                Some(DeletedItem::Request(index, request)) => {
+                    let index = index.min(bctx.main_col.requests.len());
+                    let had_selection = !bctx.main_col.requests.is_empty();
+
+                    bctx.main_col.requests.insert(index, request);
+                    sync_request_indices(&mut bctx.main_col.requests);
+
+                    // the selection stays on the request it showed, the way RemoveRequest kept it
+                    if had_selection && index <= bctx.main_current {
+                        bctx.main_current += 1;
+                    }
+                }
+                Some(DeletedItem::CollectionRequest(col_id, req_index, request)) => {
+                    let selection = collection_selection(bctx);
+
+                    if let Some(col_index) = collection_position(bctx, col_id) {
+                        let col = &mut bctx.collections[col_index];
+                        let req_index = req_index.min(col.requests.len());
+
+                        col.requests.insert(req_index, request);
+                        sync_request_indices(&mut col.requests);
+                    }
+
+                    // an emptied collection shows the request coming back
+                    restore_collection_selection(bctx, selection);
+                }
+                Some(DeletedItem::Collection(index, collection, selected)) => {
+                    let selection = collection_selection(bctx);
+                    let index = index.min(bctx.collections.len());
+
+                    bctx.collections.insert(index, collection);
+
+                    // back to what it showed when it was deleted, otherwise the selection stays put
+                    match selected {
+                        Some(request) => bctx.col_current = vec![index, request],
+                        None => restore_collection_selection(bctx, selection),
+                    }
+                }
+                None => {}
+            }
+
+            true
+        }
+
+        CollectionsMsg::RestoreFromTrash(index) => {
+            if index >= bctx.trash.len() {
+                return false;
+            }
+
+            let item = bctx.trash.remove(index);
+
+            // undoing the same deletion now would bring it back twice
+            if bctx.last_deleted.as_ref().is_some_and(|deleted| deleted.id() == item.id()) {
+                bctx.last_deleted = None;
+            }
+
+            // appended, so nothing holding a position has to move
+            match item.content {
+                TrashedContent::Request(collection, request) => {
+                    match collection.and_then(|id| collection_position(bctx, id)) {
+                        Some(col_index) => bctx.collections[col_index].requests.push(*request),
+                        // its collection went away in the meantime
+                        None => bctx.main_col.requests.push(*request),
+                    }
+                }
+                TrashedContent::Collection(collection) => bctx.collections.push(collection),
+            }
+
+            ensure_ids(bctx);
+
+            true
+        }
+
+        CollectionsMsg::PurgeTrash => {
+            bctx.trash.clear();
+
+            true
+        }
+
+        CollectionsMsg::ExpireUndo(generation) => {
+            if generation != bctx.undo_generation || (bctx.last_deleted.is_none() && bctx.last_replace.is_none()) {
+                return false;
+            }
+
+            bctx.last_deleted = None;
+            bctx.last_replace = None;
+
+            true
+        }
+
+        CollectionsMsg::AddFolder(col_index, parent) => {
+            let name = match ask_text(&tr("folder.name_prompt"), &tr("folder.default_name")) {
+                Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                _ => return false,
+            };
+
+            let collection = &mut bctx.collections[col_index];
+
+            let folder = Folder {
+                id: next_folder_id(collection),
+                name,
+                parent: shown_in(collection, parent),
+                collapsed: false,
+            };
+
+            collection.folders.push(folder);
+            collection.collapsed = false;
+
+            // open the way down to the new folder
+            if let Some(parent) = collection.folders.iter_mut().find(|folder| Some(folder.id) == parent) {
+                parent.collapsed = false;
+            }
+
+            true
+        }
+
+        CollectionsMsg::RenameFolder(col_index, id) => {
+            let collection = &mut bctx.collections[col_index];
+
+            let folder = match collection.folders.iter_mut().find(|folder| folder.id == id) {
+                Some(folder) => folder,
+                None => return false,
+            };
+
+            match ask_text(&tr("folder.name_prompt"), &folder.name) {
+                Some(name) if !name.trim().is_empty() => {
+                    folder.name = name.trim().to_string();
+
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        CollectionsMsg::RemoveFolder(col_index, id) => {
+            remove_folder(&mut bctx.collections[col_index], id);
+
+            true
+        }
+
+        CollectionsMsg::MoveIntoFolder(col_index, req_index) => {
+            let collection = &mut bctx.collections[col_index];
+
+            if collection.folders.is_empty() {
+                show_alert(&tr("folder.none_yet"));
+
+                return false;
+            }
+
+            let mut paths: Vec<String> = collection
+                .folders
+                .iter()
+                .map(|folder| folder_path(collection, folder.id))
+                .collect();
+            paths.sort();
+
+            let current = shown_in(collection, collection.requests[req_index].folder)
+                .map(|id| folder_path(collection, id))
+                .unwrap_or_default();
+
+            let message = tr_args("folder.move_prompt", &[&paths.join("\n")]);
+
+            let answer = match ask_text(&message, &current) {
+                Some(answer) => answer,
+                None => return false,
+            };
+
+            let folder = if answer.trim().is_empty() {
+                None
+            } else {
+                match find_folder_by_path(collection, &answer) {
+                    Some(id) => Some(id),
+                    None => {
+                        show_alert(&tr_args("folder.not_found", &[answer.trim()]));
+
+                        return false;
+                    }
+                }
+            };
+
+            collection.requests[req_index].folder = folder;
+
+            true
+        }
+
+        CollectionsMsg::ToggleFolderCollapsed(col_index, id) => {
+            let collection = &mut bctx.collections[col_index];
+
+            if let Some(folder) = collection.folders.iter_mut().find(|folder| folder.id == id) {
+                folder.collapsed = !folder.collapsed;
+            }
+
+            true
+        }
+    }
+}
+
+// after a removal: closes the tabs of requests that went with it, stops polling one of them and
+// drops the run of a collection that went
+fn forget_removed(bctx: &mut BoltContext) {
+    prune_tabs(bctx);
+
+    if bctx.repeat.is_some_and(|repeat| find_request(bctx, repeat.request).is_none()) {
+        stop_repeat(bctx);
+    }
+
+    if bctx.run.as_ref().is_some_and(|run| collection_position(bctx, run.collection).is_none()) {
+        bctx.run = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Page;
+
+    // the next prompt is answered with `text`, None cancels it
+    fn answer(text: Option<&str>) {
+        ANSWERS.with(|answers| answers.borrow_mut().push_back(text.map(str::to_string)));
+    }
+
+    fn ids(requests: &[Request]) -> Vec<u64> {
+        requests.iter().map(|req| req.id).collect()
+    }
+
+    // a request on the Home page and `count` collections of two requests, named "b" and "a"
+    fn context(page: Page, count: usize) -> BoltContext {
+        let mut bctx = BoltContext::new();
+
+        bctx.main_col.requests = vec![Request::new()];
+
+        for _ in 0..count {
+            let mut collection = Collection::new();
+            collection.requests = vec![Request::new(), Request::new()];
+            collection.requests[0].name = "b".to_string();
+            collection.requests[1].name = "a".to_string();
+            bctx.collections.push(collection);
+        }
+
+        bctx.page = page;
+        bctx
+    }
+
+    fn variables(rows: &[(&str, &str)]) -> Vec<Vec<String>> {
+        rows.iter().map(|(name, value)| vec![name.to_string(), value.to_string()]).collect()
+    }
+
+    #[test]
+    fn variables_are_added_to_the_collection_on_screen() {
+        let mut bctx = context(Page::Home, 1);
+        let before = bctx.collections[0].variables.len();
+
+        assert!(collections(&mut bctx, CollectionsMsg::AddCollectionVariable));
+        assert_eq!(bctx.main_col.variables.len(), 2);
+        assert_eq!(bctx.focus.as_deref(), Some("colvarname1"));
+
+        bctx.page = Page::Collections;
+
+        assert!(collections(&mut bctx, CollectionsMsg::AddCollectionVariable));
+        assert_eq!(bctx.collections[0].variables.len(), before + 1);
+        assert_eq!(bctx.main_col.variables.len(), 2);
+
+        bctx.page = Page::Settings;
+        assert!(!collections(&mut bctx, CollectionsMsg::AddCollectionVariable));
+    }
+
+    #[test]
+    fn rows_that_are_gone_are_left_alone() {
+        let mut bctx = context(Page::Collections, 1);
+        bctx.collections[0].variables = variables(&[("token", "x")]);
+
+        assert!(!collections(&mut bctx, CollectionsMsg::RemoveCollectionVariable(1)));
+        assert!(!collections(&mut bctx, CollectionsMsg::ToggleSecretVariable(5)));
+        assert!(collections(&mut bctx, CollectionsMsg::RemoveCollectionVariable(0)));
+        assert!(bctx.collections[0].variables.is_empty());
+    }
+
+    #[test]
+    fn secret_variables_toggle_by_name() {
+        let mut bctx = context(Page::Collections, 1);
+        bctx.collections[0].variables = variables(&[(" token ", "x"), ("", "y")]);
+
+        assert!(collections(&mut bctx, CollectionsMsg::ToggleSecretVariable(0)));
+        assert!(bctx.collections[0].secret_variables.contains("token"));
+
+        assert!(collections(&mut bctx, CollectionsMsg::ToggleSecretVariable(0)));
+        assert!(bctx.collections[0].secret_variables.is_empty());
+
+        // a row without a name has nothing to keep secret
+        assert!(!collections(&mut bctx, CollectionsMsg::ToggleSecretVariable(1)));
+    }
+
+    #[test]
+    fn moving_a_collection_keeps_it_selected() {
+        let mut bctx = context(Page::Collections, 3);
+        let id = bctx.collections[0].id;
+        bctx.col_current = vec![0, 1];
+
+        assert!(collections(&mut bctx, CollectionsMsg::MoveCollection { from: 0, to: 2 }));
+        assert_eq!(bctx.collections[2].id, id);
+        assert_eq!(bctx.col_current, vec![2, 1]);
+
+        assert!(!collections(&mut bctx, CollectionsMsg::MoveCollection { from: 1, to: 3 }));
+    }
+
+    #[test]
+    fn sorting_keeps_the_selected_request() {
+        let mut bctx = context(Page::Collections, 1);
+        let selected = bctx.collections[0].requests[0].id;
+        bctx.col_current = vec![0, 0];
+
+        assert!(collections(&mut bctx, CollectionsMsg::SortCollection(0)));
+
+        let names: Vec<&str> = bctx.collections[0].requests.iter().map(|req| req.name.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(bctx.collections[0].requests[bctx.col_current[1]].id, selected);
+
+        assert!(!collections(&mut bctx, CollectionsMsg::SortCollection(1)));
+    }
+
+    #[test]
+    fn pins_toggle_on_the_main_list_only() {
+        let mut bctx = context(Page::Home, 1);
+        let id = bctx.main_col.requests[0].id;
+
+        assert!(collections(&mut bctx, CollectionsMsg::TogglePin(id)));
+        assert!(bctx.main_col.requests[0].pinned);
+
+        let in_collection = bctx.collections[0].requests[0].id;
+        assert!(!collections(&mut bctx, CollectionsMsg::TogglePin(in_collection)));
+    }
+
+    #[test]
+    fn removing_another_collection_keeps_the_selection() {
+        let mut bctx = context(Page::Collections, 3);
+        let selected = bctx.collections[2].requests[1].id;
+        bctx.col_current = vec![2, 1];
+
+        let other = bctx.collections[0].id;
+        assert!(collections(&mut bctx, CollectionsMsg::RemoveCollection(other)));
+        assert_eq!(bctx.col_current, vec![1, 1]);
+        assert_eq!(bctx.collections[1].requests[1].id, selected);
+
+        // with the selected one gone there is nothing to stay on
+        let shown = bctx.collections[1].id;
+        assert!(collections(&mut bctx, CollectionsMsg::RemoveCollection(shown)));
+        assert_eq!(bctx.col_current, vec![0, 0]);
+    }
+
+    #[test]
+    fn undoing_a_collection_delete_puts_the_selection_back() {
+        let mut bctx = context(Page::Collections, 3);
+        bctx.col_current = vec![1, 1];
+
+        let deleted = bctx.collections[1].clone();
+        bctx.last_deleted = Some(DeletedItem::Collection(1, deleted.clone(), Some(1)));
+        collections(&mut bctx, CollectionsMsg::RemoveCollection(deleted.id));
+
+        assert!(collections(&mut bctx, CollectionsMsg::UndoDelete));
+        assert_eq!(bctx.collections[1].id, deleted.id);
+        assert_eq!(bctx.col_current, vec![1, 1]);
+
+        // one before the selected collection comes back, the selection stays on its collection
+        let selected = bctx.collections[2].id;
+        bctx.col_current = vec![2, 0];
+
+        let deleted = bctx.collections[0].clone();
+        bctx.last_deleted = Some(DeletedItem::Collection(0, deleted.clone(), None));
+        collections(&mut bctx, CollectionsMsg::RemoveCollection(deleted.id));
+        assert_eq!(bctx.collections[bctx.col_current[0]].id, selected);
+
+        assert!(collections(&mut bctx, CollectionsMsg::UndoDelete));
+        assert_eq!(bctx.col_current, vec![2, 0]);
+    }
+
+    #[test]
+    fn tabs_follow_their_requests_by_id() {
+        let mut bctx = context(Page::Collections, 2);
+        let (first, second) = (bctx.collections[0].requests[0].id, bctx.collections[1].requests[1].id);
+
+        open_tab(&mut bctx, RequestRef { collection: Some(1), request: 1 });
+        open_tab(&mut bctx, RequestRef { collection: Some(0), request: 0 });
+        assert_eq!(bctx.open_tabs, vec![second, first]);
+
+        assert!(collections(&mut bctx, CollectionsMsg::MoveCollection { from: 0, to: 1 }));
+        assert!(collections(&mut bctx, CollectionsMsg::SortCollection(1)));
+        assert_eq!(bctx.open_tabs, vec![second, first]);
+
+        // the active tab still shows its request wherever the move and the sort left it
+        let shown = current_ref(&bctx).and_then(|target| request_ref(&bctx, &target)).map(|req| req.id);
+        assert_eq!((bctx.active_tab, shown), (Some(1), Some(first)));
+
+        let col_id = bctx.collections[0].id;
+        assert!(collections(&mut bctx, CollectionsMsg::RemoveFromCollection(col_id, second)));
+        assert_eq!(bctx.open_tabs, vec![first]);
+        assert_eq!(bctx.active_tab, Some(0));
+    }
+
+    #[test]
+    fn new_collections_and_requests_are_numbered() {
+        let mut bctx = context(Page::Collections, 0);
+
+        assert!(collections(&mut bctx, CollectionsMsg::AddCollection));
+        assert!(collections(&mut bctx, CollectionsMsg::AddCollection));
+        assert_eq!(bctx.collections[1].name, "New Collection 2");
+
+        assert!(collections(&mut bctx, CollectionsMsg::AddToCollection(1)));
+        assert_eq!(bctx.collections[1].requests[0].name, "New Request 1");
+
+        // the template's blank row, like a request made by hand
+        assert_eq!(bctx.collections[1].requests[0].headers, variables(&[("", "")]));
+
+        assert!(collections(&mut bctx, CollectionsMsg::AddRequest));
+        assert_eq!(bctx.main_col.requests[1].name, "New Request 2");
+        assert!(bctx.open_tabs.is_empty());
+    }
+
+    #[test]
+    fn the_first_request_on_home_is_opened() {
+        let mut bctx = context(Page::Collections, 0);
+        bctx.main_col.requests.clear();
+
+        assert!(collections(&mut bctx, CollectionsMsg::AddRequest));
+        assert_eq!(bctx.page, Page::Home);
+        assert_eq!(bctx.open_tabs, ids(&bctx.main_col.requests));
+    }
+
+    #[test]
+    fn duplicates_get_new_ids_and_no_responses() {
+        let mut bctx = context(Page::Collections, 1);
+        bctx.collections[0].name = "Users".to_string();
+        bctx.collections[0].sample = true;
+        bctx.collections[0].requests[0].response.status = 200;
+
+        assert!(collections(&mut bctx, CollectionsMsg::DuplicateCollection(0)));
+
+        let (original, copy) = (&bctx.collections[0], &bctx.collections[1]);
+        assert_eq!(copy.name, "Users copy");
+        assert!(!copy.sample);
+        assert_ne!(copy.id, original.id);
+        assert!(ids(&copy.requests).iter().all(|id| !ids(&original.requests).contains(id)));
+        assert_eq!(copy.requests[0].response.status, 0);
+        assert_eq!(original.requests[0].response.status, 200);
+
+        assert!(!collections(&mut bctx, CollectionsMsg::DuplicateCollection(5)));
+    }
+
+    #[test]
+    fn duplicating_the_selected_collection_keeps_the_shown_request() {
+        let mut bctx = context(Page::Collections, 2);
+        open_tab(&mut bctx, RequestRef { collection: Some(1), request: 1 });
+
+        let shown = |bctx: &BoltContext| current_ref(bctx).and_then(|target| request_ref(bctx, &target)).map(|req| req.id);
+        let before = shown(&bctx);
+
+        assert!(collections(&mut bctx, CollectionsMsg::DuplicateCollection(1)));
+
+        assert_eq!(bctx.collections.len(), 3);
+        assert_eq!(bctx.col_current, vec![1, 1]);
+        assert_eq!(shown(&bctx), before);
+        assert_eq!(bctx.open_tabs, before.into_iter().collect::<Vec<_>>());
+        assert_eq!(bctx.active_tab, Some(0));
+    }
+
+    #[test]
+    fn dismissing_the_samples_keeps_the_selection() {
+        let mut bctx = context(Page::Collections, 3);
+        bctx.collections[0].sample = true;
+        bctx.collections[1].sample = true;
+        bctx.col_current = vec![2, 1];
+
+        let kept = bctx.collections[2].id;
+
+        assert!(collections(&mut bctx, CollectionsMsg::DismissSamples));
+        assert_eq!(bctx.collections.len(), 1);
+        assert_eq!((bctx.collections[0].id, bctx.col_current.clone()), (kept, vec![0, 1]));
+    }
+
+    #[test]
+    fn collections_collapse_and_open_again() {
+        let mut bctx = context(Page::Collections, 2);
+
+        assert!(collections(&mut bctx, CollectionsMsg::ToggleCollapsed(1)));
+        assert!(bctx.collections[1].collapsed && !bctx.collections[0].collapsed);
+
+        assert!(collections(&mut bctx, CollectionsMsg::ToggleCollapsed(1)));
+        assert!(!bctx.collections[1].collapsed);
+    }
+
+    #[test]
+    fn renamed_secret_variables_stay_secret() {
+        let mut bctx = context(Page::Collections, 1);
+        bctx.collections[0].variables = variables(&[("token", "x")]);
+        bctx.collections[0].secret_variables.insert("token".to_string());
+
+        set_input_value("colvarname0", "api_token");
+        set_input_value("colvarvalue0", "y");
+
+        assert!(collections(&mut bctx, CollectionsMsg::CollectionVariableChanged(0)));
+        assert_eq!(bctx.collections[0].variables, variables(&[("api_token", "y")]));
+        assert!(bctx.collections[0].secret_variables.contains("api_token"));
+        assert_eq!(bctx.collections[0].secret_variables.len(), 1);
+
+        assert!(!collections(&mut bctx, CollectionsMsg::CollectionVariableChanged(1)));
+    }
+
+    #[test]
+    fn an_empty_base_url_is_unset() {
+        let mut bctx = context(Page::Collections, 1);
+
+        set_input_value("baseurlinput", " https://api.test ");
+        assert!(collections(&mut bctx, CollectionsMsg::BaseUrlChanged));
+        assert_eq!(bctx.collections[0].base_url.as_deref(), Some("https://api.test"));
+
+        set_input_value("baseurlinput", " ");
+        assert!(collections(&mut bctx, CollectionsMsg::BaseUrlChanged));
+        assert_eq!(bctx.collections[0].base_url, None);
+
+        bctx.page = Page::Settings;
+        assert!(!collections(&mut bctx, CollectionsMsg::BaseUrlChanged));
+    }
+
+    #[test]
+    fn removed_requests_go_to_the_trash_without_their_response() {
+        let mut bctx = context(Page::Home, 0);
+        bctx.main_col.requests = vec![Request::new(), Request::new(), Request::new()];
+        bctx.main_col.requests[0].response.status = 500;
+        bctx.main_current = 2;
+
+        let (removed, selected) = (bctx.main_col.requests[0].id, bctx.main_col.requests[2].id);
+
+        assert!(collections(&mut bctx, CollectionsMsg::RemoveRequest(removed)));
+        assert_eq!(bctx.main_col.requests[bctx.main_current].id, selected);
+        assert_eq!(bctx.trash.len(), 1);
+        assert!(matches!(&bctx.trash[0].content, TrashedContent::Request(None, req) if req.id == removed && req.response.status == 0));
+
+        assert!(!collections(&mut bctx, CollectionsMsg::RemoveRequest(removed)));
+    }
+
+    #[test]
+    fn deletes_wait_for_confirmation_and_can_be_undone() {
+        let mut bctx = context(Page::Home, 0);
+        bctx.main_col.requests = vec![Request::new(), Request::new()];
+        bctx.main_current = 1;
+
+        let (first, second) = (bctx.main_col.requests[0].id, bctx.main_col.requests[1].id);
+
+        assert!(collections(&mut bctx, CollectionsMsg::AskDelete(DeleteTarget::Request(first))));
+        assert!(collections(&mut bctx, CollectionsMsg::CancelDelete));
+        assert_eq!(bctx.pending_delete, None);
+        assert_eq!(bctx.main_col.requests.len(), 2);
+
+        assert!(collections(&mut bctx, CollectionsMsg::AskDelete(DeleteTarget::Request(first))));
+        assert!(collections(&mut bctx, CollectionsMsg::ConfirmDelete));
+        assert_eq!(ids(&bctx.main_col.requests), [second]);
+        assert_eq!((bctx.main_current, bctx.undo_generation), (0, 1));
+        assert!(matches!(bctx.last_deleted, Some(DeletedItem::Request(0, _))));
+
+        assert!(collections(&mut bctx, CollectionsMsg::UndoDelete));
+        assert_eq!(ids(&bctx.main_col.requests), [first, second]);
+        assert_eq!(bctx.main_current, 1);
+        assert!(bctx.trash.is_empty() && bctx.last_deleted.is_none());
+
+        // nothing was asked, or what was asked about is gone already
+        assert!(collections(&mut bctx, CollectionsMsg::ConfirmDelete));
+        assert!(collections(&mut bctx, CollectionsMsg::AskDelete(DeleteTarget::Collection(7))));
+        assert!(collections(&mut bctx, CollectionsMsg::ConfirmDelete));
+        assert!(bctx.last_deleted.is_none());
+    }
+
+    #[test]
+    fn an_undone_collection_request_goes_back_in_place() {
+        let mut bctx = context(Page::Collections, 1);
+        bctx.col_current = vec![0, 1];
+
+        let col_id = bctx.collections[0].id;
+        let (first, second) = (bctx.collections[0].requests[0].id, bctx.collections[0].requests[1].id);
+
+        collections(&mut bctx, CollectionsMsg::AskDelete(DeleteTarget::CollectionRequest(col_id, first)));
+        assert!(collections(&mut bctx, CollectionsMsg::ConfirmDelete));
+        assert_eq!(bctx.col_current, vec![0, 0]);
+
+        assert!(collections(&mut bctx, CollectionsMsg::UndoDelete));
+        assert_eq!(ids(&bctx.collections[0].requests), [first, second]);
+        assert_eq!(bctx.col_current, vec![0, 1]);
+    }
+
+    #[test]
+    fn the_undo_expires_only_for_its_own_delete() {
+        let mut bctx = context(Page::Home, 0);
+        let id = bctx.main_col.requests[0].id;
+
+        collections(&mut bctx, CollectionsMsg::AskDelete(DeleteTarget::Request(id)));
+        collections(&mut bctx, CollectionsMsg::ConfirmDelete);
+
+        let generation = bctx.undo_generation;
+
+        assert!(!collections(&mut bctx, CollectionsMsg::ExpireUndo(generation - 1)));
+        assert!(bctx.last_deleted.is_some());
+
+        assert!(collections(&mut bctx, CollectionsMsg::ExpireUndo(generation)));
+        assert!(bctx.last_deleted.is_none());
+
+        assert!(!collections(&mut bctx, CollectionsMsg::ExpireUndo(generation)));
+    }
+
+    #[test]
+    fn trashed_requests_return_to_their_collection_or_home() {
+        let mut bctx = context(Page::Collections, 2);
+        let (first_col, second_col) = (bctx.collections[0].id, bctx.collections[1].id);
+        let (kept, orphan) = (bctx.collections[0].requests[0].id, bctx.collections[1].requests[0].id);
+
+        collections(&mut bctx, CollectionsMsg::RemoveFromCollection(first_col, kept));
+        collections(&mut bctx, CollectionsMsg::RemoveFromCollection(second_col, orphan));
+        collections(&mut bctx, CollectionsMsg::RemoveCollection(second_col));
+        assert_eq!(bctx.trash.len(), 3);
+
+        assert!(collections(&mut bctx, CollectionsMsg::RestoreFromTrash(1)));
+        assert!(collections(&mut bctx, CollectionsMsg::RestoreFromTrash(0)));
+        assert!(!collections(&mut bctx, CollectionsMsg::RestoreFromTrash(1)));
+
+        assert_eq!(bctx.main_col.requests.last().map(|req| req.id), Some(orphan));
+        assert_eq!(bctx.collections[0].requests.last().map(|req| req.id), Some(kept));
+
+        assert!(collections(&mut bctx, CollectionsMsg::PurgeTrash));
+        assert!(bctx.trash.is_empty());
+    }
+
+    #[test]
+    fn a_restored_delete_cannot_be_undone_again() {
+        let mut bctx = context(Page::Home, 0);
+        let id = bctx.main_col.requests[0].id;
+
+        collections(&mut bctx, CollectionsMsg::AskDelete(DeleteTarget::Request(id)));
+        collections(&mut bctx, CollectionsMsg::ConfirmDelete);
+
+        assert!(collections(&mut bctx, CollectionsMsg::RestoreFromTrash(0)));
+        assert!(bctx.last_deleted.is_none());
+
+        assert!(collections(&mut bctx, CollectionsMsg::UndoDelete));
+        assert_eq!(ids(&bctx.main_col.requests), [id]);
+    }
+
+    #[test]
+    fn folders_are_named_nested_renamed_and_removed() {
+        let mut bctx = context(Page::Collections, 1);
+        bctx.collections[0].collapsed = true;
+
+        answer(Some(" Users "));
+        assert!(collections(&mut bctx, CollectionsMsg::AddFolder(0, None)));
+        answer(Some("Admin"));
+        assert!(collections(&mut bctx, CollectionsMsg::AddFolder(0, Some(0))));
+
+        let folders = &bctx.collections[0].folders;
+        assert_eq!((folders[0].name.as_str(), folders[0].parent), ("Users", None));
+        assert_eq!((folders[1].name.as_str(), folders[1].parent), ("Admin", Some(0)));
+        assert!(!bctx.collections[0].collapsed);
+
+        // cancelled, or nothing typed
+        assert!(!collections(&mut bctx, CollectionsMsg::AddFolder(0, None)));
+        answer(Some("  "));
+        assert!(!collections(&mut bctx, CollectionsMsg::AddFolder(0, None)));
+
+        answer(Some("Accounts"));
+        assert!(collections(&mut bctx, CollectionsMsg::RenameFolder(0, 0)));
+        assert_eq!(bctx.collections[0].folders[0].name, "Accounts");
+        assert!(!collections(&mut bctx, CollectionsMsg::RenameFolder(0, 9)));
+
+        assert!(collections(&mut bctx, CollectionsMsg::ToggleFolderCollapsed(0, 1)));
+        assert!(bctx.collections[0].folders[1].collapsed);
+
+        bctx.collections[0].requests[0].folder = Some(1);
+
+        // what was in it moves up a level
+        assert!(collections(&mut bctx, CollectionsMsg::RemoveFolder(0, 1)));
+        assert_eq!(bctx.collections[0].folders.len(), 1);
+        assert_eq!(bctx.collections[0].requests[0].folder, Some(0));
+    }
+
+    #[test]
+    fn requests_move_into_folders_by_path() {
+        let mut bctx = context(Page::Collections, 1);
+
+        assert!(!collections(&mut bctx, CollectionsMsg::MoveIntoFolder(0, 0)));
+        assert!(take_effects()[0].starts_with("alert "));
+
+        answer(Some("Users"));
+        collections(&mut bctx, CollectionsMsg::AddFolder(0, None));
+        answer(Some("Admin"));
+        collections(&mut bctx, CollectionsMsg::AddFolder(0, Some(0)));
+
+        answer(Some(" users/ADMIN "));
+        assert!(collections(&mut bctx, CollectionsMsg::MoveIntoFolder(0, 1)));
+        assert_eq!(bctx.collections[0].requests[1].folder, Some(1));
+
+        answer(Some("Groups"));
+        assert!(!collections(&mut bctx, CollectionsMsg::MoveIntoFolder(0, 1)));
+        assert_eq!(take_effects(), ["alert There is no folder called \"Groups\""]);
+        assert_eq!(bctx.collections[0].requests[1].folder, Some(1));
+
+        // cancelled, then left empty for the top level
+        assert!(!collections(&mut bctx, CollectionsMsg::MoveIntoFolder(0, 1)));
+        answer(Some(""));
+        assert!(collections(&mut bctx, CollectionsMsg::MoveIntoFolder(0, 1)));
+        assert_eq!(bctx.collections[0].requests[1].folder, None);
+    }
+}
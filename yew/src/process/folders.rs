@@ -0,0 +1,62 @@
+use crate::Collection;
+
+// folder ids only have to be unique inside their collection
+pub fn next_folder_id(col: &Collection) -> u64 {
+    col.folders.iter().map(|folder| folder.id + 1).max().unwrap_or(0)
+}
+
+pub fn folder_exists(col: &Collection, id: u64) -> bool {
+    col.folders.iter().any(|folder| folder.id == id)
+}
+
+// the folder a request or folder is shown in, a dangling id counts as the top level
+pub fn shown_in(col: &Collection, folder: Option<u64>) -> Option<u64> {
+    folder.filter(|id| folder_exists(col, *id))
+}
+
+// "outer/inner", for picking a folder by name
+pub fn folder_path(col: &Collection, id: u64) -> String {
+    let mut names = Vec::new();
+    let mut current = Some(id);
+
+    // bounded by the folder count in case a parent chain loops
+    for _ in 0..=col.folders.len() {
+        let folder = match current.and_then(|id| col.folders.iter().find(|folder| folder.id == id)) {
+            Some(folder) => folder,
+            None => break,
+        };
+
+        names.push(folder.name.as_str());
+        current = folder.parent;
+    }
+
+    names.reverse();
+    names.join("/")
+}
+
+pub fn find_folder_by_path(col: &Collection, path: &str) -> Option<u64> {
+    let path = path.trim().trim_matches('/');
+
+    col.folders
+        .iter()
+        .find(|folder| folder_path(col, folder.id).eq_ignore_ascii_case(path))
+        .map(|folder| folder.id)
+}
+
+// ungroups a folder: its requests and subfolders move up to its parent
+pub fn remove_folder(col: &mut Collection, id: u64) {
+    let parent = match col.folders.iter().find(|folder| folder.id == id) {
+        Some(folder) => folder.parent,
+        None => return,
+    };
+
+    for request in col.requests.iter_mut().filter(|request| request.folder == Some(id)) {
+        request.folder = parent;
+    }
+
+    for folder in col.folders.iter_mut().filter(|folder| folder.parent == Some(id)) {
+        folder.parent = parent;
+    }
+
+    col.folders.retain(|folder| folder.id != id);
+}
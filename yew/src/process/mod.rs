@@ -1 +1,9 @@
-pub mod update;
\ No newline at end of file
+pub mod update;
+pub mod tabs;
+pub mod folders;pub mod runner;
+pub mod autocomplete;
+pub mod replace;
+pub mod request_edit;
+pub mod navigation;
+pub mod send;
+pub mod collections;
@@ -0,0 +1,602 @@
+use crate::helpers::enums::{BodyFormat, RequestTabs, ResponseTabs, ScrollPanel};
+use crate::process::tabs::*;
+use crate::utils::*;
+use crate::BoltContext;
+use crate::NavigationMsg;
+use crate::RequestRef;
+
+// the messages that change what is on screen without touching what the request sends: the
+// request and response tabs, how the response is shown and which request is selected
+pub fn navigation(bctx: &mut BoltContext, msg: NavigationMsg) -> bool {
+    match msg {
+        NavigationMsg::ReqBodyPressed => {
+            bctx.restore_scroll = true;
+
+            show_request_tab(bctx, RequestTabs::Body)
+        }
+
+        NavigationMsg::ReqParamsPressed => show_request_tab(bctx, RequestTabs::Params),
+
+        NavigationMsg::ReqHeadersPressed => show_request_tab(bctx, RequestTabs::Headers),
+
+        NavigationMsg::ReqDocsPressed => show_request_tab(bctx, RequestTabs::Docs),
+
+        NavigationMsg::ReqSettingsPressed => show_request_tab(bctx, RequestTabs::Settings),
+
+        NavigationMsg::ReqAuthPressed => show_request_tab(bctx, RequestTabs::Auth),
+
+        NavigationMsg::ReqTestsPressed => show_request_tab(bctx, RequestTabs::Tests),
+
+        NavigationMsg::ReqPreviewPressed => show_request_tab(bctx, RequestTabs::Preview),
+
+        NavigationMsg::ReqTabKey(tab) => {
+            let tab = RequestTabs::from(tab);
+
+            bctx.focus = Some(tab.element_id().to_string());
+
+            if tab == RequestTabs::Body {
+                bctx.restore_scroll = true;
+            }
+
+            show_request_tab(bctx, tab)
+        }
+
+        NavigationMsg::ToggleDocsPreview => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.docs_preview = !req.docs_preview;
+
+            true
+        }
+
+        NavigationMsg::RespBodyPressed => {
+            bctx.restore_scroll = true;
+
+            show_response_tab(bctx, ResponseTabs::Body)
+        }
+
+        NavigationMsg::RespHeadersPressed => show_response_tab(bctx, ResponseTabs::Headers),
+
+        NavigationMsg::RespSentPressed => show_response_tab(bctx, ResponseTabs::Sent),
+
+        NavigationMsg::RespRawPressed => show_response_tab(bctx, ResponseTabs::Raw),
+
+        NavigationMsg::RespTlsPressed => show_response_tab(bctx, ResponseTabs::Tls),
+
+        NavigationMsg::RespTabKey(tab) => {
+            let tab = ResponseTabs::from(tab);
+
+            bctx.focus = Some(tab.element_id().to_string());
+
+            if tab == ResponseTabs::Body {
+                bctx.restore_scroll = true;
+            }
+
+            show_response_tab(bctx, tab)
+        }
+
+        NavigationMsg::PanelScrolled(target, panel, offset) => {
+            if let Some(req) = request_mut(bctx, &target) {
+                match panel {
+                    ScrollPanel::RequestBody => req.body_scroll = offset,
+                    ScrollPanel::ResponseBody => req.resp_scroll = offset,
+                }
+            }
+
+            // nothing to redraw, the offset is saved with the next state save
+            false
+        }
+
+        NavigationMsg::BodySelectionChanged(target, start, end) => {
+            if let Some(req) = request_mut(bctx, &target) {
+                req.body_selection = Some((start, end));
+            }
+
+            false
+        }
+
+        NavigationMsg::DismissContentType => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.suggested_content_type = None;
+
+            true
+        }
+
+        NavigationMsg::DismissBodyNote => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.body_note_dismissed = true;
+
+            true
+        }
+
+        NavigationMsg::BodyFormatChanged => {
+            let index: usize = get_select_value("respformat").parse().unwrap_or(0);
+            let format = BodyFormat::from(index.min(BodyFormat::count() - 1));
+            let theme = bctx.theme;
+
+            let Some(request) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            request.body_format = format;
+            request.json_tree = request.json_tree && effective_format(&request.response, format) == BodyFormat::Json;
+
+            apply_body_format(&mut request.response, format, theme);
+
+            true
+        }
+
+        NavigationMsg::ToggleJsonTree => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.json_tree = !req.json_tree;
+
+            true
+        }
+
+        NavigationMsg::FilterRespHeaders(filter) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.resp_header_filter = filter;
+
+            true
+        }
+
+        NavigationMsg::ToggleSortRespHeaders => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.sort_resp_headers = !req.sort_resp_headers;
+
+            true
+        }
+
+        NavigationMsg::ToggleJsonNode(pointer) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            if !req.collapsed_nodes.remove(&pointer) {
+                req.collapsed_nodes.insert(pointer);
+            }
+
+            true
+        }
+
+        NavigationMsg::ToggleDiffView => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.diff_view = !req.diff_view;
+
+            true
+        }
+
+        NavigationMsg::ToggleBase64Decode => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.base64_decode = !req.base64_decode;
+
+            true
+        }
+
+        NavigationMsg::ShowFullBody => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.response.show_full = true;
+
+            true
+        }
+
+        NavigationMsg::SelectRequest(id) => {
+            let index = match request_position(&bctx.main_col.requests, id) {
+                Some(index) => index,
+                None => return false,
+            };
+
+            bctx.main_current = index;
+            bctx.main_col.requests[index].response.request_index = index;
+
+            open_tab(
+                bctx,
+                RequestRef {
+                    collection: None,
+                    request: index,
+                },
+            );
+
+            bctx.restore_scroll = true;
+
+            true
+        }
+
+        NavigationMsg::SelectFromCollection(col_id, req_id) => {
+            let (col_index, req_index) = match collection_request_position(bctx, col_id, req_id) {
+                Some(position) => position,
+                None => return false,
+            };
+
+            bctx.col_current = vec![col_index, req_index];
+
+            bctx.collections[col_index].requests[req_index]
+                .response
+                .request_index = req_index;
+
+            open_tab(
+                bctx,
+                RequestRef {
+                    collection: Some(col_index),
+                    request: req_index,
+                },
+            );
+
+            bctx.restore_scroll = true;
+
+            true
+        }
+
+        NavigationMsg::SelectTab(index) => {
+            activate_tab(bctx, index);
+            bctx.restore_scroll = true;
+
+            true
+        }
+
+        NavigationMsg::CloseTab(index) => {
+            close_tab(bctx, index);
+            bctx.restore_scroll = true;
+
+            true
+        }
+
+        NavigationMsg::SwitchPage(page) => {
+            bctx.page = page;
+            sync_page_tab(bctx);
+            bctx.restore_scroll = true;
+
+            true
+        }
+    }
+}
+
+fn show_request_tab(bctx: &mut BoltContext, tab: RequestTabs) -> bool {
+    let Some(req) = current_request_mut(bctx) else {
+        return false;
+    };
+
+    req.req_tab = tab.into();
+
+    true
+}
+
+fn show_response_tab(bctx: &mut BoltContext, tab: ResponseTabs) -> bool {
+    let Some(req) = current_request_mut(bctx) else {
+        return false;
+    };
+
+    req.resp_tab = tab.into();
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collection, Page, Request};
+
+    // two requests on the Home page and a collection of two, the second of each selected
+    fn context(page: Page) -> BoltContext {
+        let mut bctx = BoltContext::new();
+
+        bctx.main_col.requests = vec![Request::new(), Request::new()];
+        bctx.main_current = 1;
+
+        let mut collection = Collection::new();
+        collection.requests = vec![Request::new(), Request::new()];
+        bctx.collections.push(collection);
+        bctx.col_current = vec![0, 1];
+
+        bctx.page = page;
+        bctx
+    }
+
+    #[test]
+    fn tabs_change_on_the_request_of_the_page_shown() {
+        for page in [Page::Home, Page::Collections] {
+            let mut bctx = context(page);
+
+            assert!(navigation(&mut bctx, NavigationMsg::ReqHeadersPressed));
+            assert!(navigation(&mut bctx, NavigationMsg::RespRawPressed));
+
+            let (shown, other) = match page {
+                Page::Home => (&bctx.main_col.requests[1], &bctx.collections[0].requests[1]),
+                _ => (&bctx.collections[0].requests[1], &bctx.main_col.requests[1]),
+            };
+
+            assert_eq!((shown.req_tab, shown.resp_tab), (3, 4));
+            assert_eq!((other.req_tab, other.resp_tab), (1, 1));
+            assert_eq!(bctx.main_col.requests[0].req_tab, 1);
+        }
+    }
+
+    #[test]
+    fn nothing_happens_without_a_request_on_screen() {
+        let messages = || {
+            vec![
+                NavigationMsg::ReqBodyPressed,
+                NavigationMsg::ReqParamsPressed,
+                NavigationMsg::ReqHeadersPressed,
+                NavigationMsg::ReqDocsPressed,
+                NavigationMsg::ReqSettingsPressed,
+                NavigationMsg::ReqAuthPressed,
+                NavigationMsg::ReqTestsPressed,
+                NavigationMsg::ReqPreviewPressed,
+                NavigationMsg::ReqTabKey(RequestTabs::Auth.into()),
+                NavigationMsg::ToggleDocsPreview,
+                NavigationMsg::RespBodyPressed,
+                NavigationMsg::RespHeadersPressed,
+                NavigationMsg::RespSentPressed,
+                NavigationMsg::RespRawPressed,
+                NavigationMsg::RespTlsPressed,
+                NavigationMsg::RespTabKey(ResponseTabs::Tls.into()),
+                NavigationMsg::DismissContentType,
+                NavigationMsg::DismissBodyNote,
+                NavigationMsg::BodyFormatChanged,
+                NavigationMsg::ToggleJsonTree,
+                NavigationMsg::FilterRespHeaders("x-".to_string()),
+                NavigationMsg::ToggleSortRespHeaders,
+                NavigationMsg::ToggleJsonNode("/a".to_string()),
+                NavigationMsg::ToggleDiffView,
+                NavigationMsg::ToggleBase64Decode,
+                NavigationMsg::ShowFullBody,
+            ]
+        };
+
+        set_input_value("respformat", "1");
+
+        for msg in messages() {
+            assert!(!navigation(&mut context(Page::Settings), msg));
+        }
+
+        // a selection left behind by a deleted collection
+        for msg in messages() {
+            let mut bctx = context(Page::Collections);
+            bctx.col_current = vec![3, 0];
+
+            assert!(!navigation(&mut bctx, msg));
+        }
+    }
+
+    #[test]
+    fn tab_keys_focus_the_tab_they_switch_to() {
+        let mut bctx = context(Page::Home);
+        bctx.restore_scroll = false;
+
+        assert!(navigation(&mut bctx, NavigationMsg::ReqTabKey(RequestTabs::Docs.into())));
+        assert_eq!(bctx.main_col.requests[1].req_tab, 4);
+        assert_eq!(bctx.focus.as_deref(), Some("req_docs_tab"));
+        assert!(!bctx.restore_scroll);
+
+        assert!(navigation(&mut bctx, NavigationMsg::RespTabKey(ResponseTabs::Body.into())));
+        assert_eq!(bctx.focus.as_deref(), Some("resp_body_tab"));
+        assert!(bctx.restore_scroll);
+    }
+
+    #[test]
+    fn json_nodes_collapse_and_open_again() {
+        let mut bctx = context(Page::Collections);
+
+        navigation(&mut bctx, NavigationMsg::ToggleJsonNode("/items/0".to_string()));
+        assert!(bctx.collections[0].requests[1].collapsed_nodes.contains("/items/0"));
+
+        navigation(&mut bctx, NavigationMsg::ToggleJsonNode("/items/0".to_string()));
+        assert!(bctx.collections[0].requests[1].collapsed_nodes.is_empty());
+    }
+
+    #[test]
+    fn selecting_by_id_opens_a_tab_for_the_request() {
+        let mut bctx = context(Page::Home);
+        let (col_id, req_id) = (bctx.collections[0].id, bctx.collections[0].requests[0].id);
+
+        assert!(navigation(&mut bctx, NavigationMsg::SelectFromCollection(col_id, req_id)));
+        assert_eq!(bctx.col_current, vec![0, 0]);
+        assert_eq!(bctx.page, Page::Collections);
+        assert_eq!(bctx.open_tabs, vec![req_id]);
+
+        // gone by the time the click lands
+        assert!(!navigation(&mut bctx, NavigationMsg::SelectRequest(0)));
+    }
+
+    #[test]
+    fn every_tab_button_shows_its_tab() {
+        let request_tabs = [
+            (NavigationMsg::ReqBodyPressed, RequestTabs::Body),
+            (NavigationMsg::ReqParamsPressed, RequestTabs::Params),
+            (NavigationMsg::ReqHeadersPressed, RequestTabs::Headers),
+            (NavigationMsg::ReqDocsPressed, RequestTabs::Docs),
+            (NavigationMsg::ReqSettingsPressed, RequestTabs::Settings),
+            (NavigationMsg::ReqAuthPressed, RequestTabs::Auth),
+            (NavigationMsg::ReqTestsPressed, RequestTabs::Tests),
+            (NavigationMsg::ReqPreviewPressed, RequestTabs::Preview),
+        ];
+
+        for (msg, tab) in request_tabs {
+            let mut bctx = context(Page::Home);
+            bctx.restore_scroll = false;
+
+            assert!(navigation(&mut bctx, msg));
+            assert_eq!(bctx.main_col.requests[1].req_tab, u8::from(tab));
+
+            // only the body editor has a scroll offset to put back
+            assert_eq!(bctx.restore_scroll, tab == RequestTabs::Body);
+        }
+
+        let response_tabs = [
+            (NavigationMsg::RespBodyPressed, ResponseTabs::Body),
+            (NavigationMsg::RespHeadersPressed, ResponseTabs::Headers),
+            (NavigationMsg::RespSentPressed, ResponseTabs::Sent),
+            (NavigationMsg::RespRawPressed, ResponseTabs::Raw),
+            (NavigationMsg::RespTlsPressed, ResponseTabs::Tls),
+        ];
+
+        for (msg, tab) in response_tabs {
+            let mut bctx = context(Page::Collections);
+            bctx.restore_scroll = false;
+
+            assert!(navigation(&mut bctx, msg));
+            assert_eq!(bctx.collections[0].requests[1].resp_tab, u8::from(tab));
+            assert_eq!(bctx.restore_scroll, tab == ResponseTabs::Body);
+        }
+    }
+
+    #[test]
+    fn scroll_and_selection_land_on_the_request_they_were_read_from() {
+        let mut bctx = context(Page::Home);
+        let other = RequestRef { collection: Some(0), request: 0 };
+
+        assert!(!navigation(&mut bctx, NavigationMsg::PanelScrolled(other, ScrollPanel::RequestBody, 40)));
+        assert!(!navigation(&mut bctx, NavigationMsg::PanelScrolled(other, ScrollPanel::ResponseBody, 900)));
+        assert!(!navigation(&mut bctx, NavigationMsg::BodySelectionChanged(other, 2, 5)));
+
+        let req = &bctx.collections[0].requests[0];
+        assert_eq!((req.body_scroll, req.resp_scroll, req.body_selection), (40, 900, Some((2, 5))));
+        assert_eq!(bctx.main_col.requests[1].resp_scroll, 0);
+
+        // the request went away before the timer fired
+        let gone = RequestRef { collection: None, request: 7 };
+        assert!(!navigation(&mut bctx, NavigationMsg::PanelScrolled(gone, ScrollPanel::RequestBody, 1)));
+        assert!(!navigation(&mut bctx, NavigationMsg::BodySelectionChanged(gone, 0, 1)));
+    }
+
+    #[test]
+    fn notes_are_dismissed_on_the_request_shown() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[1].suggested_content_type = Some("application/json");
+
+        assert!(navigation(&mut bctx, NavigationMsg::DismissContentType));
+        assert!(navigation(&mut bctx, NavigationMsg::DismissBodyNote));
+
+        let req = &bctx.main_col.requests[1];
+        assert_eq!(req.suggested_content_type, None);
+        assert!(req.body_note_dismissed);
+        assert!(!bctx.main_col.requests[0].body_note_dismissed);
+    }
+
+    #[test]
+    fn a_chosen_format_reformats_the_body_and_closes_the_tree_outside_json() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[1].response.body = "{\"a\":1}".to_string();
+
+        set_input_value("respformat", "1");
+        assert!(navigation(&mut bctx, NavigationMsg::BodyFormatChanged));
+        assert!(navigation(&mut bctx, NavigationMsg::ToggleJsonTree));
+
+        let req = &bctx.main_col.requests[1];
+        assert_eq!(req.body_format, BodyFormat::Json);
+        assert_eq!(req.response.body, "{\n  \"a\": 1\n}");
+        assert!(!req.response.highlighted.is_empty());
+        assert!(req.json_tree);
+
+        set_input_value("respformat", "3");
+        assert!(navigation(&mut bctx, NavigationMsg::BodyFormatChanged));
+
+        let req = &bctx.main_col.requests[1];
+        assert_eq!(req.body_format, BodyFormat::Text);
+        assert!(req.response.highlighted.is_empty());
+        assert!(!req.json_tree);
+    }
+
+    #[test]
+    fn response_views_toggle_on_the_request_shown() {
+        let mut bctx = context(Page::Collections);
+
+        assert!(navigation(&mut bctx, NavigationMsg::ToggleDocsPreview));
+        assert!(navigation(&mut bctx, NavigationMsg::FilterRespHeaders("cache".to_string())));
+        assert!(navigation(&mut bctx, NavigationMsg::ToggleSortRespHeaders));
+        assert!(navigation(&mut bctx, NavigationMsg::ToggleDiffView));
+        assert!(navigation(&mut bctx, NavigationMsg::ToggleBase64Decode));
+        assert!(navigation(&mut bctx, NavigationMsg::ShowFullBody));
+
+        let req = &bctx.collections[0].requests[1];
+        assert!(req.docs_preview && req.sort_resp_headers && req.diff_view && req.base64_decode);
+        assert!(req.response.show_full);
+        assert_eq!(req.resp_header_filter, "cache");
+
+        assert!(navigation(&mut bctx, NavigationMsg::ToggleDiffView));
+        assert!(!bctx.collections[0].requests[1].diff_view);
+        assert!(!bctx.collections[0].requests[0].docs_preview);
+    }
+
+    #[test]
+    fn selecting_from_the_home_list_opens_a_tab() {
+        let mut bctx = context(Page::Collections);
+        let id = bctx.main_col.requests[0].id;
+
+        assert!(navigation(&mut bctx, NavigationMsg::SelectRequest(id)));
+        assert_eq!((bctx.page, bctx.main_current), (Page::Home, 0));
+        assert_eq!(bctx.open_tabs, vec![id]);
+        assert_eq!(bctx.active_tab, Some(0));
+        assert!(bctx.restore_scroll);
+
+        // already open, so the tab is reused
+        assert!(navigation(&mut bctx, NavigationMsg::SelectRequest(id)));
+        assert_eq!(bctx.open_tabs, vec![id]);
+
+        let (col_id, gone) = (bctx.collections[0].id, bctx.main_col.requests[1].id);
+        assert!(!navigation(&mut bctx, NavigationMsg::SelectFromCollection(col_id, gone)));
+    }
+
+    #[test]
+    fn tabs_switch_and_close_across_pages() {
+        let mut bctx = context(Page::Home);
+        let (home, collection) = (bctx.main_col.requests[0].id, bctx.collections[0].requests[0].id);
+
+        open_tab(&mut bctx, RequestRef { collection: None, request: 0 });
+        open_tab(&mut bctx, RequestRef { collection: Some(0), request: 0 });
+        assert_eq!(bctx.page, Page::Collections);
+
+        assert!(navigation(&mut bctx, NavigationMsg::SelectTab(0)));
+        assert_eq!((bctx.page, bctx.main_current, bctx.active_tab), (Page::Home, 0, Some(0)));
+
+        assert!(navigation(&mut bctx, NavigationMsg::CloseTab(0)));
+        assert_eq!(bctx.open_tabs, vec![collection]);
+        assert_eq!((bctx.page, bctx.col_current.clone()), (Page::Collections, vec![0, 0]));
+
+        // a tab closed twice in a row
+        assert!(navigation(&mut bctx, NavigationMsg::CloseTab(3)));
+        assert!(!bctx.open_tabs.contains(&home));
+    }
+
+    #[test]
+    fn switching_pages_picks_the_tab_of_that_page() {
+        let mut bctx = context(Page::Home);
+
+        open_tab(&mut bctx, RequestRef { collection: Some(0), request: 1 });
+        open_tab(&mut bctx, RequestRef { collection: None, request: 1 });
+
+        assert!(navigation(&mut bctx, NavigationMsg::SwitchPage(Page::Collections)));
+        assert_eq!(bctx.active_tab, Some(0));
+
+        assert!(navigation(&mut bctx, NavigationMsg::SwitchPage(Page::Settings)));
+        assert_eq!(bctx.page, Page::Settings);
+        assert_eq!(bctx.active_tab, None);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::helpers::enums::ToastLevel;
+use crate::helpers::i18n::tr_args;
+use crate::helpers::replace::{apply_replace, find_matches, revert_edits, FieldMatch};
+use crate::utils::{collection_request_position, now_ms, push_toast, request_position, schedule_undo_expiry};
+use crate::BoltContext;
+use std::collections::HashSet;
+
+// looks the matches up again, every one of them ticked
+pub fn refresh_matches(bctx: &mut BoltContext) {
+    let dialog = match bctx.find_replace.as_mut() {
+        Some(dialog) => dialog,
+        None => return,
+    };
+
+    dialog.matches = find_matches(&bctx.main_col, &bctx.collections, &dialog.query, dialog.options);
+    dialog.skipped.clear();
+}
+
+pub fn replace_selected(bctx: &mut BoltContext) -> bool {
+    let dialog = match bctx.find_replace.as_ref() {
+        Some(dialog) if !dialog.query.is_empty() => dialog,
+        _ => return false,
+    };
+
+    let selected: Vec<FieldMatch> = dialog
+        .matches
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !dialog.skipped.contains(index))
+        .map(|(_, found)| found.clone())
+        .collect();
+
+    let count: usize = selected.iter().map(|found| found.count).sum();
+    let (query, replacement, options) = (dialog.query.clone(), dialog.replacement.clone(), dialog.options);
+
+    let edits = apply_replace(&mut bctx.main_col, &mut bctx.collections, &selected, &query, &replacement, options);
+
+    if edits.is_empty() {
+        return false;
+    }
+
+    let touched: HashSet<(Option<u64>, u64)> = edits.iter().map(|edit| (edit.collection, edit.request)).collect();
+
+    for (collection, id) in &touched {
+        let position = match collection {
+            Some(col_id) => collection_request_position(bctx, *col_id, *id).map(|(col, req)| (Some(col), req)),
+            None => request_position(&bctx.main_col.requests, *id).map(|req| (None, req)),
+        };
+
+        let request = match position {
+            Some((Some(col), req)) => &mut bctx.collections[col].requests[req],
+            Some((None, req)) => &mut bctx.main_col.requests[req],
+            None => continue,
+        };
+
+        request.modified = true;
+        request.modified_at = Some(now_ms());
+    }
+
+    let requests = touched.len();
+    push_toast(
+        bctx,
+        ToastLevel::Info,
+        tr_args("replace.done", &[&count.to_string(), &requests.to_string()]),
+    );
+
+    // only the latest change can be undone, whichever kind it was
+    bctx.last_deleted = None;
+    bctx.last_replace = Some(edits);
+    bctx.undo_generation += 1;
+    schedule_undo_expiry(bctx.undo_generation);
+
+    refresh_matches(bctx);
+
+    true
+}
+
+pub fn undo_replace(bctx: &mut BoltContext) -> bool {
+    let edits = match bctx.last_replace.take() {
+        Some(edits) => edits,
+        None => return false,
+    };
+
+    let skipped = revert_edits(&mut bctx.main_col, &mut bctx.collections, &edits);
+
+    if skipped > 0 {
+        push_toast(bctx, ToastLevel::Warning, tr_args("replace.undo_skipped", &[&skipped.to_string()]));
+    }
+
+    refresh_matches(bctx);
+
+    true
+}
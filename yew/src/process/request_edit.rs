@@ -0,0 +1,1219 @@
+use crate::helpers::enums::{AuthMode, HttpVersion, RetryPolicy, Transport};
+use crate::helpers::enums::{AutocompleteAction, AutocompleteField};
+use crate::helpers::enums::{SignatureEncoding, SignedContent, SigningAlgorithm};
+use crate::helpers::url::{clean_url, parse_query};
+use crate::process::autocomplete::autocomplete;
+use crate::process::tabs::{current_ref, current_request_mut};
+use crate::utils::*;
+use crate::BoltContext;
+use crate::EditMsg;
+use crate::MAX_RETRIES;
+
+// the messages from the request editor, each one changes the request on screen whether it
+// sits in the main list or a collection
+pub fn request_edit(bctx: &mut BoltContext, msg: EditMsg) -> bool {
+    match msg {
+        EditMsg::SelectedMethod(meth) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.method = meth;
+
+            true
+        }
+
+        EditMsg::MethodChanged => {
+            let method = get_method();
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.method = method;
+
+            true
+        }
+
+        EditMsg::UrlChanged => {
+            let url = clean_url(&get_url());
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            // validators belong to the resource they came from
+            if req.url != url {
+                req.etag = None;
+                req.last_modified = None;
+            }
+
+            req.url = url.clone();
+            req.name = url;
+
+            autocomplete(bctx, AutocompleteAction::Typed(AutocompleteField::Url));
+
+            true
+        }
+
+        EditMsg::BodyChanged => {
+            let body = get_body();
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            // only a suggestion, the header is added when the user accepts it
+            req.suggested_content_type = if has_header(&req.headers, "content-type") {
+                None
+            } else {
+                detect_content_type(&body)
+            };
+
+            req.body = body;
+
+            true
+        }
+
+        // a file picked for the body, read into it rather than sent from disk
+        EditMsg::BodyLoaded(body) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.suggested_content_type = if has_header(&req.headers, "content-type") {
+                None
+            } else {
+                detect_content_type(&body)
+            };
+
+            req.body = body;
+
+            true
+        }
+
+        EditMsg::BodyFileChanged => {
+            let path = get_input_value("bodyfileinput").trim().to_string();
+
+            if path.is_empty() {
+                return false;
+            }
+
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            if let Some(req) = current_request_mut(bctx) {
+                invoke_body_file_info(&path, target);
+
+                req.body_file = Some(path);
+                req.body_file_info = None;
+                req.suggested_content_type = None;
+            }
+
+            true
+        }
+
+        EditMsg::ClearBodyFile => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.body_file = None;
+            req.body_file_info = None;
+
+            true
+        }
+
+        EditMsg::AddHeader => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.headers.push(vec!["".to_string(), "".to_string()]);
+
+            // move to the key of the new row so keyboard users can type straight away
+            let row = req.headers.len() - 1;
+            bctx.focus = Some(format!("headerkey{}", row));
+
+            true
+        }
+
+        EditMsg::RemoveHeader(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.headers.remove(index);
+
+            true
+        }
+
+        EditMsg::HeaderChanged(index) => {
+            let header = get_header(index);
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            // a renamed secret stays secret
+            let old_name = req.headers[index][0].trim().to_lowercase();
+
+            if old_name != header[0].trim().to_lowercase() && req.secret_headers.remove(&old_name) {
+                req.secret_headers.insert(header[0].trim().to_lowercase());
+            }
+
+            req.headers[index] = header;
+
+            // a header typed by hand answers the suggestion
+            if has_header(&req.headers, "content-type") {
+                req.suggested_content_type = None;
+            }
+
+            true
+        }
+
+        EditMsg::AddParam => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.params.push(vec!["".to_string(), "".to_string()]);
+
+            let row = req.params.len() - 1;
+            bctx.focus = Some(format!("paramkey{}", row));
+
+            true
+        }
+
+        EditMsg::RemoveParam(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.params.remove(index);
+
+            true
+        }
+
+        EditMsg::ParamChanged(index) => {
+            let param = get_param(index);
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            // a renamed secret stays secret
+            let old_name = req.params[index][0].trim().to_string();
+
+            if old_name != param[0].trim() && req.secret_params.remove(&old_name) {
+                req.secret_params.insert(param[0].trim().to_string());
+            }
+
+            req.params[index] = param;
+
+            true
+        }
+
+        EditMsg::ToggleSecretHeader(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            let name = req.headers[index][0].trim().to_lowercase();
+
+            if name.is_empty() {
+                return false;
+            }
+
+            if !req.secret_headers.remove(&name) {
+                req.secret_headers.insert(name);
+            }
+
+            true
+        }
+
+        EditMsg::ToggleSecretParam(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            let name = req.params[index][0].trim().to_string();
+
+            if name.is_empty() {
+                return false;
+            }
+
+            if !req.secret_params.remove(&name) {
+                req.secret_params.insert(name);
+            }
+
+            true
+        }
+
+        EditMsg::QueryChanged => {
+            let query = get_input_value("queryinput");
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.params = parse_query(query.trim());
+
+            true
+        }
+
+        EditMsg::ToggleRawQuery => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.raw_query = !req.raw_query;
+
+            true
+        }
+
+        EditMsg::AddLocalVariable => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.local_variables.push(vec![String::new(), String::new()]);
+
+            let row = req.local_variables.len() - 1;
+            bctx.focus = Some(format!("localvarname{}", row));
+
+            true
+        }
+
+        EditMsg::RemoveLocalVariable(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.local_variables.remove(index);
+
+            true
+        }
+
+        EditMsg::LocalVariableChanged(index) => {
+            let variable = get_local_variable(index);
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.local_variables[index] = variable;
+
+            true
+        }
+
+        EditMsg::AddCapture => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.captures.push(vec![String::new(), String::new()]);
+
+            let row = req.captures.len() - 1;
+            bctx.focus = Some(format!("capturename{}", row));
+
+            true
+        }
+
+        EditMsg::RemoveCapture(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.captures.remove(index);
+
+            true
+        }
+
+        EditMsg::CaptureChanged(index) => {
+            let capture = get_capture(index);
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.captures[index] = capture;
+
+            true
+        }
+
+        EditMsg::AcceptContentType => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            if let Some(content_type) = req.suggested_content_type.take() {
+                let header = vec!["Content-Type".to_string(), content_type.to_string()];
+
+                // an empty Content-Type row or the blank row left for typing fills in instead of growing the table
+                let slot = req.headers.iter().position(|header| {
+                    header[0].trim().eq_ignore_ascii_case("content-type")
+                        || (header[0].is_empty() && header[1].is_empty())
+                });
+
+                match slot {
+                    Some(index) => req.headers[index] = header,
+                    None => req.headers.push(header),
+                }
+
+                if !req.headers.last().is_some_and(|header| header[0].is_empty() && header[1].is_empty()) {
+                    req.headers.push(vec![String::new(), String::new()]);
+                }
+            }
+
+            true
+        }
+
+        EditMsg::DescriptionChanged => {
+            let description = get_description();
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.description = description;
+
+            true
+        }
+
+        EditMsg::ReqProxyChanged => {
+            let proxy = get_input_value("reqproxy");
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.proxy = proxy;
+
+            true
+        }
+
+        EditMsg::ReqUserAgentChanged => {
+            let user_agent = get_input_value("requseragent").trim().to_string();
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.user_agent = user_agent;
+
+            true
+        }
+
+        EditMsg::ToggleOmitUserAgent => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.omit_user_agent = !req.omit_user_agent;
+
+            true
+        }
+
+        EditMsg::RetriesChanged => {
+            let retries = get_input_value("reqretries").trim().parse::<u8>().unwrap_or(0).min(MAX_RETRIES);
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.retries = retries;
+
+            true
+        }
+
+        EditMsg::RetryPolicyChanged => {
+            let index: usize = get_select_value("reqretryon").parse().unwrap_or(0);
+            let policy = RetryPolicy::from(index.min(RetryPolicy::count() - 1));
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.retry_on = policy;
+
+            true
+        }
+
+        EditMsg::TransportChanged => {
+            let index: usize = get_select_value("reqtransport").parse().unwrap_or(0);
+            let transport = Transport::from(index.min(Transport::count() - 1));
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.transport = transport;
+
+            true
+        }
+
+        EditMsg::HttpVersionChanged => {
+            // "" is Auto, anything else an index into HttpVersion
+            let version = get_select_value("reqhttpversion")
+                .parse::<usize>()
+                .ok()
+                .map(|index| HttpVersion::from(index.min(HttpVersion::count() - 1)));
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.http_version = version;
+
+            true
+        }
+
+        EditMsg::ToggleConditional => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.conditional = !req.conditional;
+
+            true
+        }
+
+        EditMsg::ToggleInsecureTls => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.tls.insecure_skip_verify = !req.tls.insecure_skip_verify;
+
+            true
+        }
+
+        EditMsg::CaCertChanged => {
+            let path = get_optional_input("reqcacert");
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.tls.ca_cert_path = path;
+
+            true
+        }
+
+        EditMsg::ClientCertChanged => {
+            let cert = get_input_value("reqclientcert").trim().to_string();
+            let key = get_input_value("reqclientkey").trim().to_string();
+
+            let client_cert = if cert.is_empty() && key.is_empty() {
+                None
+            } else {
+                Some((cert, key))
+            };
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.tls.client_cert = client_cert;
+
+            true
+        }
+
+        EditMsg::SchemaChanged => {
+            let schema = get_textarea_value("reqschema");
+            let schema = if schema.trim().is_empty() { None } else { Some(schema) };
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            // recheck the response on screen so the result reflects the edit
+            req.response.schema_violations = check_schema(&schema, &req.response);
+            req.response_schema = schema;
+
+            true
+        }
+
+        EditMsg::AuthModeChanged => {
+            let index: usize = get_select_value("authmode").parse().unwrap_or(0);
+            let mode = AuthMode::from(index.min(AuthMode::count() - 1));
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.auth.mode = mode;
+
+            true
+        }
+
+        EditMsg::AuthFieldsChanged => {
+            let token_url = get_input_value("authtokenurl").trim().to_string();
+            let client_id = get_input_value("authclientid").trim().to_string();
+            let client_secret = get_input_value("authclientsecret");
+            let scopes = get_input_value("authscopes").trim().to_string();
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            let auth = &mut req.auth;
+
+            // a token issued for other credentials is no longer valid
+            if auth.token_url != token_url || auth.client_id != client_id || auth.client_secret != client_secret || auth.scopes != scopes {
+                auth.access_token = String::new();
+                auth.expires_at = None;
+            }
+
+            auth.token_url = token_url;
+            auth.client_id = client_id;
+            auth.client_secret = client_secret;
+            auth.scopes = scopes;
+
+            true
+        }
+
+        EditMsg::DigestFieldsChanged => {
+            let username = get_input_value("authusername").trim().to_string();
+            let password = get_input_value("authpassword");
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.auth.username = username;
+            req.auth.password = password;
+
+            true
+        }
+
+        EditMsg::SigningAlgorithmChanged => {
+            let index: usize = get_select_value("signalgorithm").parse().unwrap_or(0);
+            let algorithm = SigningAlgorithm::from(index.min(SigningAlgorithm::count() - 1));
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.signing.algorithm = algorithm;
+
+            true
+        }
+
+        EditMsg::SigningFieldsChanged => {
+            let content: usize = get_select_value("signcontent").parse().unwrap_or(0);
+            let encoding: usize = get_select_value("signencoding").parse().unwrap_or(0);
+            let secret = get_input_value("signsecret");
+            let header = get_input_value("signheader").trim().to_string();
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            let signing = &mut req.signing;
+            signing.content = SignedContent::from(content.min(SignedContent::count() - 1));
+            signing.encoding = SignatureEncoding::from(encoding.min(SignatureEncoding::count() - 1));
+            signing.secret = secret;
+
+            if !header.is_empty() {
+                signing.header = header;
+            }
+
+            true
+        }
+
+        EditMsg::ToggleRememberSigningSecret => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.signing.remember_secret = !req.signing.remember_secret;
+
+            true
+        }
+
+        EditMsg::ToggleRememberSecret => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            req.auth.remember_secret = !req.auth.remember_secret;
+
+            true
+        }
+
+        EditMsg::AddTag => {
+            let tags = parse_tags(&get_input_value("taginput"));
+            set_input_value("taginput", "");
+
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            for tag in tags {
+                if !req.tags.iter().any(|existing| existing.eq_ignore_ascii_case(&tag)) {
+                    req.tags.push(tag);
+                }
+            }
+
+            bctx.focus = Some("taginput".to_string());
+
+            true
+        }
+
+        EditMsg::RemoveTag(index) => {
+            let Some(req) = current_request_mut(bctx) else {
+                return false;
+            };
+
+            if index < req.tags.len() {
+                req.tags.remove(index);
+            }
+
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::enums::HttpMethod as Method;
+    use crate::{Collection, Page, Request};
+
+    // one request on the Home page and one in a collection
+    fn context(page: Page) -> BoltContext {
+        let mut bctx = BoltContext::new();
+
+        bctx.main_col.requests = vec![Request::new()];
+
+        let mut collection = Collection::new();
+        collection.requests = vec![Request::new()];
+        bctx.collections.push(collection);
+        bctx.col_current = vec![0, 0];
+
+        bctx.page = page;
+        bctx
+    }
+
+    fn shown(bctx: &BoltContext) -> &Request {
+        &bctx.main_col.requests[0]
+    }
+
+    fn row(name: &str, value: &str) -> Vec<String> {
+        vec![name.to_string(), value.to_string()]
+    }
+
+    // what the editor's fields hold when the message arrives
+    fn fields(values: &[(&str, &str)]) {
+        for (id, value) in values {
+            set_input_value(id, value);
+        }
+    }
+
+    #[test]
+    fn nothing_changes_without_a_request_on_screen() {
+        let messages = vec![
+            EditMsg::SelectedMethod(Method::POST),
+            EditMsg::MethodChanged,
+            EditMsg::UrlChanged,
+            EditMsg::BodyChanged,
+            EditMsg::BodyLoaded("{}".to_string()),
+            EditMsg::BodyFileChanged,
+            EditMsg::ClearBodyFile,
+            EditMsg::AddHeader,
+            EditMsg::RemoveHeader(0),
+            EditMsg::HeaderChanged(0),
+            EditMsg::AddParam,
+            EditMsg::RemoveParam(0),
+            EditMsg::ParamChanged(0),
+            EditMsg::ToggleSecretHeader(0),
+            EditMsg::ToggleSecretParam(0),
+            EditMsg::QueryChanged,
+            EditMsg::ToggleRawQuery,
+            EditMsg::AddLocalVariable,
+            EditMsg::RemoveLocalVariable(0),
+            EditMsg::LocalVariableChanged(0),
+            EditMsg::AddCapture,
+            EditMsg::RemoveCapture(0),
+            EditMsg::CaptureChanged(0),
+            EditMsg::AcceptContentType,
+            EditMsg::DescriptionChanged,
+            EditMsg::ReqProxyChanged,
+            EditMsg::ReqUserAgentChanged,
+            EditMsg::ToggleOmitUserAgent,
+            EditMsg::RetriesChanged,
+            EditMsg::RetryPolicyChanged,
+            EditMsg::TransportChanged,
+            EditMsg::HttpVersionChanged,
+            EditMsg::ToggleConditional,
+            EditMsg::ToggleInsecureTls,
+            EditMsg::CaCertChanged,
+            EditMsg::ClientCertChanged,
+            EditMsg::SchemaChanged,
+            EditMsg::AuthModeChanged,
+            EditMsg::AuthFieldsChanged,
+            EditMsg::DigestFieldsChanged,
+            EditMsg::SigningAlgorithmChanged,
+            EditMsg::SigningFieldsChanged,
+            EditMsg::ToggleRememberSigningSecret,
+            EditMsg::ToggleRememberSecret,
+            EditMsg::AddTag,
+            EditMsg::RemoveTag(0),
+        ];
+
+        fields(&[("methodselect", "get"), ("bodyfileinput", "/tmp/body.bin"), ("taginput", "api")]);
+
+        for msg in messages {
+            let mut bctx = context(Page::Settings);
+
+            assert!(!request_edit(&mut bctx, msg));
+            assert!(bctx.focus.is_none());
+        }
+
+        assert!(take_effects().is_empty());
+    }
+
+    #[test]
+    fn methods_come_from_the_dropdown_or_the_name_typed_in() {
+        let mut bctx = context(Page::Home);
+
+        assert!(request_edit(&mut bctx, EditMsg::SelectedMethod(Method::DELETE)));
+        assert_eq!(shown(&bctx).method, Method::DELETE);
+
+        fields(&[("methodselect", "put")]);
+        assert!(request_edit(&mut bctx, EditMsg::MethodChanged));
+        assert_eq!(shown(&bctx).method, Method::PUT);
+
+        // picking "Other…" before anything is typed
+        fields(&[("methodselect", "other")]);
+        assert!(request_edit(&mut bctx, EditMsg::MethodChanged));
+        assert_eq!(shown(&bctx).method, Method::Custom(String::new()));
+
+        fields(&[("methodinput", " PURGE ")]);
+        assert!(request_edit(&mut bctx, EditMsg::MethodChanged));
+        assert_eq!(shown(&bctx).method, Method::Custom("PURGE".to_string()));
+    }
+
+    #[test]
+    fn a_new_url_names_the_request_and_drops_its_validators() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].url = "https://a.test/old".to_string();
+        bctx.main_col.requests[0].etag = Some("\"v1\"".to_string());
+        bctx.main_col.requests[0].last_modified = Some("yesterday".to_string());
+
+        fields(&[("urlinput", " 'https://a.test/old' ")]);
+        assert!(request_edit(&mut bctx, EditMsg::UrlChanged));
+        assert_eq!(shown(&bctx).etag.as_deref(), Some("\"v1\""));
+
+        fields(&[("urlinput", "https://a.test/new")]);
+        assert!(request_edit(&mut bctx, EditMsg::UrlChanged));
+
+        let req = shown(&bctx);
+        assert_eq!((req.url.as_str(), req.name.as_str()), ("https://a.test/new", "https://a.test/new"));
+        assert_eq!((req.etag.as_deref(), req.last_modified.as_deref()), (None, None));
+
+        // there is no input to anchor a popup to
+        assert!(bctx.autocomplete.is_none());
+    }
+
+    #[test]
+    fn typed_bodies_suggest_a_content_type() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[("reqbody", "a=1&b=2")]);
+        assert!(request_edit(&mut bctx, EditMsg::BodyChanged));
+        assert_eq!(shown(&bctx).body, "a=1&b=2");
+        assert_eq!(shown(&bctx).suggested_content_type, Some("application/x-www-form-urlencoded"));
+
+        bctx.main_col.requests[0].headers = vec![row("content-type", "text/plain")];
+
+        assert!(request_edit(&mut bctx, EditMsg::BodyChanged));
+        assert_eq!(shown(&bctx).suggested_content_type, None);
+    }
+
+    #[test]
+    fn loaded_bodies_suggest_a_content_type_once() {
+        let mut bctx = context(Page::Home);
+
+        assert!(request_edit(&mut bctx, EditMsg::BodyLoaded("{\"a\": 1}".to_string())));
+        assert_eq!(bctx.main_col.requests[0].suggested_content_type, Some("application/json"));
+
+        // the user already said what it is
+        bctx.main_col.requests[0].headers = vec![vec!["Content-Type".to_string(), "text/plain".to_string()]];
+
+        assert!(request_edit(&mut bctx, EditMsg::BodyLoaded("<a></a>".to_string())));
+        assert_eq!(bctx.main_col.requests[0].suggested_content_type, None);
+        assert_eq!(bctx.main_col.requests[0].body, "<a></a>");
+    }
+
+    #[test]
+    fn body_files_are_sized_by_the_backend_and_cleared() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].suggested_content_type = Some("application/json");
+
+        fields(&[("bodyfileinput", "  ")]);
+        assert!(!request_edit(&mut bctx, EditMsg::BodyFileChanged));
+        assert!(take_effects().is_empty());
+
+        fields(&[("bodyfileinput", " /tmp/upload.bin ")]);
+        assert!(request_edit(&mut bctx, EditMsg::BodyFileChanged));
+        assert_eq!(shown(&bctx).body_file.as_deref(), Some("/tmp/upload.bin"));
+        assert_eq!(shown(&bctx).suggested_content_type, None);
+        assert_eq!(take_effects(), ["file_info"]);
+
+        bctx.main_col.requests[0].body_file_info = Some(Ok(12));
+
+        assert!(request_edit(&mut bctx, EditMsg::ClearBodyFile));
+        assert_eq!((shown(&bctx).body_file.as_ref(), shown(&bctx).body_file_info.as_ref()), (None, None));
+    }
+
+    #[test]
+    fn rows_are_added_to_the_request_on_screen() {
+        let mut bctx = context(Page::Collections);
+
+        assert!(request_edit(&mut bctx, EditMsg::AddHeader));
+        assert!(request_edit(&mut bctx, EditMsg::AddHeader));
+
+        // after the blank row every request starts with
+        assert_eq!(bctx.collections[0].requests[0].headers.len(), 3);
+        assert_eq!(bctx.main_col.requests[0].headers.len(), 1);
+        assert_eq!(bctx.focus.as_deref(), Some("headerkey2"));
+
+        assert!(request_edit(&mut bctx, EditMsg::AddParam));
+        assert_eq!(bctx.collections[0].requests[0].params.len(), 2);
+        assert_eq!(bctx.focus.as_deref(), Some("paramkey1"));
+
+        assert!(request_edit(&mut bctx, EditMsg::AddLocalVariable));
+        assert_eq!(bctx.collections[0].requests[0].local_variables.len(), 2);
+        assert_eq!(bctx.focus.as_deref(), Some("localvarname1"));
+
+        assert!(request_edit(&mut bctx, EditMsg::AddCapture));
+        assert_eq!(bctx.collections[0].requests[0].captures.len(), 2);
+        assert_eq!(bctx.focus.as_deref(), Some("capturename1"));
+
+        assert!(!request_edit(&mut context(Page::Settings), EditMsg::AddHeader));
+    }
+
+    #[test]
+    fn rows_are_removed_by_position() {
+        let mut bctx = context(Page::Home);
+        let req = &mut bctx.main_col.requests[0];
+        req.headers = vec![row("a", "1"), row("b", "2")];
+        req.params = vec![row("c", "3"), row("d", "4")];
+        req.local_variables = vec![row("e", "5"), row("f", "6")];
+        req.captures = vec![row("g", "$.g"), row("h", "$.h")];
+
+        assert!(request_edit(&mut bctx, EditMsg::RemoveHeader(0)));
+        assert!(request_edit(&mut bctx, EditMsg::RemoveParam(1)));
+        assert!(request_edit(&mut bctx, EditMsg::RemoveLocalVariable(0)));
+        assert!(request_edit(&mut bctx, EditMsg::RemoveCapture(1)));
+
+        let req = shown(&bctx);
+        assert_eq!(req.headers, [row("b", "2")]);
+        assert_eq!(req.params, [row("c", "3")]);
+        assert_eq!(req.local_variables, [row("f", "6")]);
+        assert_eq!(req.captures, [row("g", "$.g")]);
+    }
+
+    #[test]
+    fn renamed_secret_rows_stay_secret() {
+        let mut bctx = context(Page::Home);
+        let req = &mut bctx.main_col.requests[0];
+        req.headers = vec![row("X-Key", "k")];
+        req.params = vec![row("key", "k")];
+        req.secret_headers.insert("x-key".to_string());
+        req.secret_params.insert("key".to_string());
+
+        fields(&[("headerkey0", "X-Api-Key"), ("headervalue0", "k2"), ("paramkey0", " api_key "), ("paramvalue0", "k2")]);
+
+        assert!(request_edit(&mut bctx, EditMsg::HeaderChanged(0)));
+        assert!(request_edit(&mut bctx, EditMsg::ParamChanged(0)));
+
+        let req = shown(&bctx);
+        assert_eq!(req.headers, [row("X-Api-Key", "k2")]);
+        assert_eq!(req.params, [row(" api_key ", "k2")]);
+        assert_eq!(req.secret_headers.iter().collect::<Vec<_>>(), ["x-api-key"]);
+        assert_eq!(req.secret_params.iter().collect::<Vec<_>>(), ["api_key"]);
+    }
+
+    #[test]
+    fn a_typed_content_type_answers_the_suggestion() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].suggested_content_type = Some("application/json");
+
+        fields(&[("headerkey0", "Content-Type"), ("headervalue0", "")]);
+        assert!(request_edit(&mut bctx, EditMsg::HeaderChanged(0)));
+        assert_eq!(shown(&bctx).suggested_content_type, Some("application/json"));
+
+        fields(&[("headervalue0", "text/csv")]);
+        assert!(request_edit(&mut bctx, EditMsg::HeaderChanged(0)));
+        assert_eq!(shown(&bctx).suggested_content_type, None);
+    }
+
+    #[test]
+    fn secret_headers_toggle_by_lowercase_name() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].headers = vec![
+            vec![" Authorization ".to_string(), "Bearer x".to_string()],
+            vec![String::new(), String::new()],
+        ];
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleSecretHeader(0)));
+        assert!(bctx.main_col.requests[0].secret_headers.contains("authorization"));
+
+        // a row without a name has nothing to hide
+        assert!(!request_edit(&mut bctx, EditMsg::ToggleSecretHeader(1)));
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleSecretHeader(0)));
+        assert!(bctx.main_col.requests[0].secret_headers.is_empty());
+    }
+
+    #[test]
+    fn secret_params_toggle_by_trimmed_name() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].params = vec![row(" Token ", "x"), row("", "")];
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleSecretParam(0)));
+        assert!(shown(&bctx).secret_params.contains("Token"));
+
+        assert!(!request_edit(&mut bctx, EditMsg::ToggleSecretParam(1)));
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleSecretParam(0)));
+        assert!(shown(&bctx).secret_params.is_empty());
+    }
+
+    #[test]
+    fn the_raw_query_replaces_the_params() {
+        let mut bctx = context(Page::Home);
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleRawQuery));
+        assert!(shown(&bctx).raw_query);
+
+        fields(&[("queryinput", " ?page=2&sort ")]);
+        assert!(request_edit(&mut bctx, EditMsg::QueryChanged));
+        assert_eq!(shown(&bctx).params, [row("page", "2"), row("sort", ""), row("", "")]);
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleRawQuery));
+        assert!(!shown(&bctx).raw_query);
+    }
+
+    #[test]
+    fn variables_and_captures_are_read_from_their_row() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[
+            ("localvarname0", "user"),
+            ("localvarvalue0", "ada"),
+            ("capturename0", "token"),
+            ("capturepath0", "$.access_token"),
+        ]);
+
+        assert!(request_edit(&mut bctx, EditMsg::LocalVariableChanged(0)));
+        assert!(request_edit(&mut bctx, EditMsg::CaptureChanged(0)));
+
+        assert_eq!(shown(&bctx).local_variables, [row("user", "ada")]);
+        assert_eq!(shown(&bctx).captures, [row("token", "$.access_token")]);
+    }
+
+    #[test]
+    fn an_accepted_content_type_fills_the_blank_row() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].suggested_content_type = Some("application/json");
+
+        assert!(request_edit(&mut bctx, EditMsg::AcceptContentType));
+        assert_eq!(shown(&bctx).headers, [row("Content-Type", "application/json"), row("", "")]);
+        assert_eq!(shown(&bctx).suggested_content_type, None);
+
+        // an emptied Content-Type row is filled in again rather than repeated
+        bctx.main_col.requests[0].headers = vec![row("content-type", ""), row("Accept", "*/*")];
+        bctx.main_col.requests[0].suggested_content_type = Some("application/xml");
+
+        assert!(request_edit(&mut bctx, EditMsg::AcceptContentType));
+        assert_eq!(
+            shown(&bctx).headers,
+            [row("Content-Type", "application/xml"), row("Accept", "*/*"), row("", "")]
+        );
+
+        // nothing was suggested
+        assert!(request_edit(&mut bctx, EditMsg::AcceptContentType));
+        assert_eq!(shown(&bctx).headers.len(), 3);
+    }
+
+    #[test]
+    fn docs_and_connection_settings_are_read_from_their_fields() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[
+            ("reqdescription", "Lists the users"),
+            ("reqproxy", "http://proxy:3128"),
+            ("requseragent", " bolt-tests "),
+        ]);
+
+        assert!(request_edit(&mut bctx, EditMsg::DescriptionChanged));
+        assert!(request_edit(&mut bctx, EditMsg::ReqProxyChanged));
+        assert!(request_edit(&mut bctx, EditMsg::ReqUserAgentChanged));
+        assert!(request_edit(&mut bctx, EditMsg::ToggleOmitUserAgent));
+
+        let req = shown(&bctx);
+        assert_eq!(req.description, "Lists the users");
+        assert_eq!(req.proxy, "http://proxy:3128");
+        assert_eq!(req.user_agent, "bolt-tests");
+        assert!(req.omit_user_agent);
+    }
+
+    #[test]
+    fn retries_are_capped_and_policies_clamped() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[("reqretries", "99"), ("reqretryon", "1")]);
+        assert!(request_edit(&mut bctx, EditMsg::RetriesChanged));
+        assert!(request_edit(&mut bctx, EditMsg::RetryPolicyChanged));
+        assert_eq!(shown(&bctx).retries, MAX_RETRIES);
+        assert_eq!(shown(&bctx).retry_on, RetryPolicy::NetworkAndServerErrors);
+
+        fields(&[("reqretries", "two"), ("reqretryon", "7")]);
+        assert!(request_edit(&mut bctx, EditMsg::RetriesChanged));
+        assert!(request_edit(&mut bctx, EditMsg::RetryPolicyChanged));
+        assert_eq!(shown(&bctx).retries, 0);
+        assert_eq!(shown(&bctx).retry_on, RetryPolicy::NetworkAndServerErrors);
+    }
+
+    #[test]
+    fn transport_and_http_version_come_from_their_dropdowns() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[("reqtransport", "2"), ("reqhttpversion", "1")]);
+        assert!(request_edit(&mut bctx, EditMsg::TransportChanged));
+        assert!(request_edit(&mut bctx, EditMsg::HttpVersionChanged));
+        assert_eq!(shown(&bctx).transport, Transport::BrowserFetch);
+        assert_eq!(shown(&bctx).http_version, Some(HttpVersion::Http2));
+
+        // the Auto entry
+        fields(&[("reqhttpversion", "")]);
+        assert!(request_edit(&mut bctx, EditMsg::HttpVersionChanged));
+        assert_eq!(shown(&bctx).http_version, None);
+    }
+
+    #[test]
+    fn tls_settings_leave_empty_paths_unset() {
+        let mut bctx = context(Page::Home);
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleConditional));
+        assert!(request_edit(&mut bctx, EditMsg::ToggleInsecureTls));
+        assert!(shown(&bctx).conditional);
+        assert!(shown(&bctx).tls.insecure_skip_verify);
+
+        fields(&[("reqcacert", " /etc/ca.pem "), ("reqclientcert", "client.pem"), ("reqclientkey", "")]);
+        assert!(request_edit(&mut bctx, EditMsg::CaCertChanged));
+        assert!(request_edit(&mut bctx, EditMsg::ClientCertChanged));
+        assert_eq!(shown(&bctx).tls.ca_cert_path.as_deref(), Some("/etc/ca.pem"));
+        assert_eq!(shown(&bctx).tls.client_cert, Some(("client.pem".to_string(), String::new())));
+
+        fields(&[("reqcacert", " "), ("reqclientcert", " ")]);
+        assert!(request_edit(&mut bctx, EditMsg::CaCertChanged));
+        assert!(request_edit(&mut bctx, EditMsg::ClientCertChanged));
+        assert_eq!(shown(&bctx).tls.ca_cert_path, None);
+        assert_eq!(shown(&bctx).tls.client_cert, None);
+    }
+
+    #[test]
+    fn a_new_schema_rechecks_the_response_on_screen() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].response.body = "[1, 2]".to_string();
+
+        fields(&[("reqschema", "{\"type\": \"object\"}")]);
+        assert!(request_edit(&mut bctx, EditMsg::SchemaChanged));
+        assert!(shown(&bctx).response_schema.is_some());
+        assert!(shown(&bctx).response.schema_violations.as_ref().is_some_and(|violations| !violations.is_empty()));
+
+        fields(&[("reqschema", "  ")]);
+        assert!(request_edit(&mut bctx, EditMsg::SchemaChanged));
+        assert_eq!(shown(&bctx).response_schema, None);
+        assert!(shown(&bctx).response.schema_violations.is_none());
+    }
+
+    #[test]
+    fn changed_credentials_drop_the_token() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[
+            ("authmode", "1"),
+            ("authtokenurl", " https://auth.test/token "),
+            ("authclientid", "app"),
+            ("authclientsecret", "s3cret"),
+            ("authscopes", "read"),
+        ]);
+
+        assert!(request_edit(&mut bctx, EditMsg::AuthModeChanged));
+        assert!(request_edit(&mut bctx, EditMsg::AuthFieldsChanged));
+        assert_eq!(shown(&bctx).auth.mode, AuthMode::OAuth2ClientCredentials);
+        assert_eq!(shown(&bctx).auth.token_url, "https://auth.test/token");
+
+        bctx.main_col.requests[0].auth.access_token = "t".to_string();
+        bctx.main_col.requests[0].auth.expires_at = Some(1.0);
+
+        // the same credentials keep it
+        assert!(request_edit(&mut bctx, EditMsg::AuthFieldsChanged));
+        assert_eq!(shown(&bctx).auth.access_token, "t");
+
+        fields(&[("authscopes", "read write")]);
+        assert!(request_edit(&mut bctx, EditMsg::AuthFieldsChanged));
+        assert_eq!(shown(&bctx).auth.access_token, "");
+        assert_eq!(shown(&bctx).auth.expires_at, None);
+
+        fields(&[("authusername", " ada "), ("authpassword", " pw ")]);
+        assert!(request_edit(&mut bctx, EditMsg::DigestFieldsChanged));
+        assert_eq!((shown(&bctx).auth.username.as_str(), shown(&bctx).auth.password.as_str()), ("ada", " pw "));
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleRememberSecret));
+        assert!(shown(&bctx).auth.remember_secret);
+    }
+
+    #[test]
+    fn signing_keeps_its_header_when_none_is_typed() {
+        let mut bctx = context(Page::Home);
+
+        fields(&[
+            ("signalgorithm", "1"),
+            ("signcontent", "1"),
+            ("signencoding", "1"),
+            ("signsecret", "key"),
+            ("signheader", " "),
+        ]);
+
+        assert!(request_edit(&mut bctx, EditMsg::SigningAlgorithmChanged));
+        assert!(request_edit(&mut bctx, EditMsg::SigningFieldsChanged));
+
+        let signing = &shown(&bctx).signing;
+        assert_eq!(signing.algorithm, SigningAlgorithm::HmacSha256);
+        assert_eq!((signing.content, signing.encoding), (SignedContent::Body, SignatureEncoding::Base64));
+        assert_eq!((signing.secret.as_str(), signing.header.as_str()), ("key", "X-Signature"));
+
+        fields(&[("signheader", "X-Hub-Signature")]);
+        assert!(request_edit(&mut bctx, EditMsg::SigningFieldsChanged));
+        assert_eq!(shown(&bctx).signing.header, "X-Hub-Signature");
+
+        assert!(request_edit(&mut bctx, EditMsg::ToggleRememberSigningSecret));
+        assert!(shown(&bctx).signing.remember_secret);
+    }
+
+    #[test]
+    fn tags_are_added_once_whatever_their_case() {
+        let mut bctx = context(Page::Home);
+        bctx.main_col.requests[0].tags = vec!["Users".to_string()];
+
+        fields(&[("taginput", "users, v2, ,V2")]);
+        assert!(request_edit(&mut bctx, EditMsg::AddTag));
+        assert_eq!(shown(&bctx).tags, ["Users", "v2"]);
+        assert_eq!(get_input_value("taginput"), "");
+        assert_eq!(bctx.focus.as_deref(), Some("taginput"));
+
+        // a second click on a chip that is already gone
+        assert!(request_edit(&mut bctx, EditMsg::RemoveTag(0)));
+        assert!(request_edit(&mut bctx, EditMsg::RemoveTag(1)));
+        assert_eq!(shown(&bctx).tags, ["v2"]);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::process::tabs::{collection_base_url, collection_variables, request_mut, request_ref};
+use crate::reuse_token;
+use crate::send_request;
+use crate::utils::{now_ms, request_position};
+use crate::BoltContext;
+use crate::CollectionRun;
+use crate::RequestRef;
+use crate::RunResult;
+use crate::RunStep;
+
+// sends every request of the collection, at most `concurrency` at a time. with 1 they go in order,
+// each once the one before it has answered
+pub fn start_run(bctx: &mut BoltContext, col_index: usize, concurrency: usize) {
+    let (collection, steps): (u64, Vec<RunStep>) = match bctx.collections.get(col_index) {
+        Some(col) if !col.requests.is_empty() => (col.id, col.requests.iter().map(|req| RunStep { id: req.id, result: None }).collect()),
+        _ => return,
+    };
+
+    bctx.run = Some(CollectionRun {
+        collection,
+        steps,
+        concurrency: concurrency.max(1),
+        next: 0,
+        stopped: false,
+        started_at: now_ms() as f64,
+        finished_at: None,
+    });
+
+    send_next_steps(bctx);
+}
+
+// nothing more is sent and the requests still in flight no longer count
+pub fn stop_run(bctx: &mut BoltContext) {
+    if let Some(run) = bctx.run.as_mut() {
+        run.stopped = true;
+        run.finished_at.get_or_insert_with(|| now_ms() as f64);
+    }
+}
+
+pub fn is_running(run: &CollectionRun) -> bool {
+    !run.stopped && run.finished_at.is_none()
+}
+
+pub fn in_flight(run: &CollectionRun, index: usize) -> bool {
+    is_running(run) && index < run.next && run.steps[index].result.is_none()
+}
+
+// records the response when the run is waiting for it, then fills the free slots
+pub fn advance_run(bctx: &mut BoltContext, target: &RequestRef, result: RunResult) {
+    let Some(id) = request_ref(bctx, target).map(|req| req.id) else {
+        return;
+    };
+
+    let run = match bctx.run.as_mut() {
+        Some(run) if is_running(run) => run,
+        _ => return,
+    };
+
+    let index = match (0..run.next).find(|index| run.steps[*index].id == id) {
+        Some(index) if run.steps[index].result.is_none() => index,
+        _ => return,
+    };
+
+    run.steps[index].result = Some(result);
+    finish_if_done(run);
+
+    send_next_steps(bctx);
+}
+
+// a deleted request leaves the run, answered or not. its response would never arrive, and
+// an undo brings it back as a request the run didn't start with
+pub fn forget_run_request(bctx: &mut BoltContext, id: u64) {
+    let Some(run) = bctx.run.as_mut() else {
+        return;
+    };
+
+    let Some(index) = run.steps.iter().position(|step| step.id == id) else {
+        return;
+    };
+
+    run.steps.remove(index);
+
+    if index < run.next {
+        run.next -= 1;
+    }
+
+    if is_running(run) {
+        finish_if_done(run);
+    }
+
+    send_next_steps(bctx);
+}
+
+fn finish_if_done(run: &mut CollectionRun) {
+    if run.steps.iter().all(|step| step.result.is_some()) {
+        run.finished_at = Some(now_ms() as f64);
+    }
+}
+
+fn send_next_steps(bctx: &mut BoltContext) {
+    let mut targets = Vec::new();
+
+    if let Some(run) = bctx.run.as_mut().filter(|run| is_running(run)) {
+        let mut waiting = (0..run.next).filter(|index| run.steps[*index].result.is_none()).count();
+
+        // wherever sorting or moving rows left the collection and the request by now
+        let col_index = bctx.collections.iter().position(|col| col.id == run.collection);
+        let requests = col_index.map(|col| bctx.collections[col].requests.as_slice()).unwrap_or_default();
+
+        while waiting < run.concurrency && run.next < run.steps.len() {
+            let id = run.steps[run.next].id;
+
+            match request_position(requests, id) {
+                Some(request) => {
+                    targets.push(RequestRef {
+                        collection: col_index,
+                        request,
+                    });
+                    waiting += 1;
+                }
+                None => run.steps[run.next].result = Some(RunResult::unanswered()),
+            }
+
+            run.next += 1;
+        }
+
+        finish_if_done(run);
+    }
+
+    let settings = bctx.settings.clone();
+
+    for target in targets {
+        reuse_token(bctx, &target);
+
+        let base_url = collection_base_url(bctx, &target);
+        let variables = collection_variables(bctx, &target);
+
+        if let Some(request) = request_mut(bctx, &target) {
+            send_request(request, &settings, base_url.as_deref(), &variables, target);
+        }
+    }
+}
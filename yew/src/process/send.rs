@@ -0,0 +1,685 @@
+use crate::fetch_token;
+use crate::helpers::codegen::to_snippet;
+use crate::helpers::enums::ToastLevel;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::helpers::url::remember_url;
+use crate::process::runner::{is_running, start_run, stop_run};
+use crate::process::tabs::*;
+use crate::process::update::process;
+use crate::reuse_token;
+use crate::send_request;
+use crate::utils::*;
+use crate::{receive_response, receive_send_error, receive_token};
+use crate::BoltContext;
+use crate::SendMsg;
+use crate::NavigationMsg;
+use crate::Msg;
+use crate::Repeat;
+use crate::ResponseType;
+use crate::{MIN_REPEAT_INTERVAL_MS, RECENT_URL_LIMIT};
+
+// the messages about a request going out and what comes back: sends and their answers, token
+// fetches, host and TLS checks, polling, collection runs, and copies of the request or response
+pub fn send(bctx: &mut BoltContext, msg: SendMsg) -> bool {
+    match msg {
+        SendMsg::SendPressed => {
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            reuse_token(bctx, &target);
+
+            // not silently, a header the user wrote is about to be left out
+            let dropped = request_ref(bctx, &target).map(dropped_headers).unwrap_or_default();
+
+            if !dropped.is_empty() {
+                push_toast(bctx, ToastLevel::Info, tr_args("toast.dropped_headers", &[&dropped.join(", ")]));
+            }
+
+            // as typed, {{variables}} and all, that is what gets typed again
+            if let Some(url) = request_ref(bctx, &target).map(|req| req.url.clone()) {
+                remember_url(&mut bctx.recent_urls, &url, RECENT_URL_LIMIT);
+            }
+
+            let settings = bctx.settings.clone();
+            let base_url = collection_base_url(bctx, &target);
+            let variables = collection_variables(bctx, &target);
+
+            let Some(req) = request_mut(bctx, &target) else {
+                return false;
+            };
+
+            send_request(req, &settings, base_url.as_deref(), &variables, target);
+
+            true
+        }
+
+        SendMsg::SendFromSidebar(col_id, req_id) => {
+            if !bctx.settings.double_click_send {
+                return false;
+            }
+
+            let selected = match col_id {
+                Some(col_id) => process(bctx, Msg::Navigation(NavigationMsg::SelectFromCollection(col_id, req_id))),
+                None => process(bctx, Msg::Navigation(NavigationMsg::SelectRequest(req_id))),
+            };
+
+            if !selected {
+                return false;
+            }
+
+            process(bctx, Msg::Send(SendMsg::SendPressed))
+        }
+
+        SendMsg::ReceivedResponse(target, data) => {
+            receive_response(bctx, target, &data);
+
+            true
+        }
+
+        SendMsg::ReceivedToken(target, then_send, data) => {
+            receive_token(bctx, target, then_send, &data);
+
+            true
+        }
+
+        SendMsg::SendFailed(target, err) => {
+            receive_send_error(bctx, target, err);
+
+            true
+        }
+
+        SendMsg::SendBlocked(target, message) => {
+            receive_send_error(bctx, target, message);
+
+            true
+        }
+
+        SendMsg::FetchToken => {
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            let settings = bctx.settings.clone();
+
+            let Some(req) = request_mut(bctx, &target) else {
+                return false;
+            };
+
+            fetch_token(req, &settings, target);
+
+            true
+        }
+
+        SendMsg::CheckHost => {
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            let variables = collection_variables(bctx, &target);
+            let base_url = collection_base_url(bctx, &target);
+
+            // a click that lands after the last request went
+            let Some(req) = request_ref(bctx, &target) else {
+                return false;
+            };
+
+            // the host the request would go to, with the base url and variables applied
+            let url = resolved_request(req, &bctx.settings, base_url.as_deref(), &variables).url;
+
+            invoke_check_host(&url);
+
+            false
+        }
+
+        SendMsg::HostChecked(check) => {
+            match check {
+                Ok(check) if check.reachable => {
+                    push_toast(bctx, ToastLevel::Info, tr_args("toast.host_reachable", &[&check.time.to_string()]));
+                }
+                Ok(check) => {
+                    let reason = check.error_kind.as_deref().and_then(error_hint).unwrap_or(check.error);
+
+                    push_toast(bctx, ToastLevel::Warning, tr_args("toast.host_unreachable", &[&reason]));
+                }
+                Err(err) => push_toast(bctx, ToastLevel::Warning, tr_args("toast.send_failed", &[&err])),
+            }
+
+            true
+        }
+
+        SendMsg::InspectTls => {
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            let variables = collection_variables(bctx, &target);
+            let base_url = collection_base_url(bctx, &target);
+
+            let Some(req) = request_ref(bctx, &target) else {
+                return false;
+            };
+
+            // the same url a send would go to, not the one the last response came from
+            let url = resolved_request(req, &bctx.settings, base_url.as_deref(), &variables).url;
+            invoke_inspect_tls(&url, req.id, target);
+
+            if let Some(req) = request_mut(bctx, &target) {
+                req.inspecting_tls = true;
+            }
+
+            true
+        }
+
+        SendMsg::TlsInspected(target, url, result) => {
+            let Some(req) = request_mut(bctx, &target) else {
+                return false;
+            };
+
+            req.inspecting_tls = false;
+            req.tls_inspection = Some((url, result));
+
+            true
+        }
+
+        SendMsg::StartRepeat(interval_ms) => {
+            stop_repeat(bctx);
+
+            if let Some(request) = current_ref(bctx).and_then(|target| request_ref(bctx, &target)).map(|req| req.id) {
+                let interval_ms = interval_ms.max(MIN_REPEAT_INTERVAL_MS);
+
+                bctx.repeat = Some(Repeat {
+                    request,
+                    interval_ms,
+                    handle: start_repeat_timer(interval_ms),
+                });
+
+                send(bctx, SendMsg::SendPressed);
+            }
+
+            true
+        }
+
+        SendMsg::StopRepeat => {
+            stop_repeat(bctx);
+
+            true
+        }
+
+        SendMsg::RepeatTick => {
+            let target = match bctx.repeat.and_then(|repeat| find_request(bctx, repeat.request)) {
+                Some(target) => target,
+                None => return false,
+            };
+
+            let loading = request_ref(bctx, &target)
+                .map(|request| request.loading)
+                .unwrap_or(true);
+
+            // skip the tick rather than pile up requests behind a slow one
+            if loading {
+                return false;
+            }
+
+            send(bctx, SendMsg::SendPressed)
+        }
+
+        SendMsg::RunCollection(col_index) => {
+            if bctx.run.as_ref().is_some_and(is_running) {
+                return false;
+            }
+
+            start_run(bctx, col_index, 1);
+
+            true
+        }
+
+        SendMsg::RunCollectionParallel(col_index) => {
+            if bctx.run.as_ref().is_some_and(is_running) {
+                return false;
+            }
+
+            start_run(bctx, col_index, bctx.settings.run_concurrency);
+
+            true
+        }
+
+        SendMsg::StopRun => {
+            stop_run(bctx);
+
+            true
+        }
+
+        SendMsg::CloseRun => {
+            stop_run(bctx);
+            bctx.run = None;
+
+            true
+        }
+
+        SendMsg::DownloadBody => {
+            let Some(req) = current_ref(bctx).and_then(|target| request_ref(bctx, &target)) else {
+                return false;
+            };
+
+            let extension = if req.response.response_type == ResponseType::JSON {
+                "json"
+            } else {
+                "txt"
+            };
+
+            download_file(
+                &format!("response.{extension}"),
+                &req.response.body,
+                "text/plain",
+            );
+
+            false
+        }
+
+        SendMsg::CopySnippet(language) => {
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            let mut variables = collection_variables(bctx, &target);
+
+            // secrets stay {{name}} in the copied command unless they are revealed
+            if !bctx.reveal_secrets {
+                let collection = match target.collection {
+                    None => &bctx.main_col,
+                    Some(col) => &bctx.collections[col],
+                };
+
+                variables.retain(|name, _| !collection.secret_variables.contains(name));
+            }
+
+            let base_url = collection_base_url(bctx, &target);
+
+            let Some(req) = request_ref(bctx, &target) else {
+                return false;
+            };
+
+            let mut req = req.clone();
+
+            if !bctx.reveal_secrets {
+                strip_secret_rows(&mut req);
+            }
+
+            // resolved like a send, so the copied command is what would have gone out
+            let resolved = resolved_request(&req, &bctx.settings, base_url.as_deref(), &variables);
+
+            copy_to_clipboard(&to_snippet(&resolved, language));
+            push_toast(bctx, ToastLevel::Info, tr("toast.copied"));
+
+            true
+        }
+
+        SendMsg::ExportHttpFile => {
+            let Some(target) = current_ref(bctx) else {
+                return false;
+            };
+
+            let base_url = collection_base_url(bctx, &target);
+
+            let Some(req) = request_ref(bctx, &target) else {
+                return false;
+            };
+
+            let mut req = with_base_url(req, base_url.as_deref());
+
+            if !bctx.reveal_secrets {
+                strip_secret_rows(&mut req);
+            }
+
+            export_http_file(&req);
+
+            false
+        }
+    }
+}
+
+pub fn stop_repeat(bctx: &mut BoltContext) {
+    if let Some(repeat) = bctx.repeat.take() {
+        stop_repeat_timer(repeat.handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::enums::{AuthMode, Language};
+    use crate::{Collection, CollectionRun, HostCheck, Page, Request, RequestRef, Response};
+
+    fn context() -> BoltContext {
+        let mut bctx = BoltContext::new();
+
+        bctx.main_col.requests = vec![Request::new()];
+        bctx
+    }
+
+    const FIRST: RequestRef = RequestRef {
+        collection: None,
+        request: 0,
+    };
+
+    #[test]
+    fn repeat_ticks_wait_for_the_last_answer() {
+        let mut bctx = context();
+
+        assert!(!send(&mut bctx, SendMsg::RepeatTick));
+
+        bctx.repeat = Some(Repeat {
+            request: bctx.main_col.requests[0].id,
+            interval_ms: MIN_REPEAT_INTERVAL_MS,
+            handle: 0,
+        });
+        bctx.main_col.requests[0].loading = true;
+
+        assert!(!send(&mut bctx, SendMsg::RepeatTick));
+    }
+
+    #[test]
+    fn a_second_run_waits_for_the_first() {
+        let mut bctx = context();
+
+        bctx.run = Some(CollectionRun {
+            collection: 1,
+            steps: Vec::new(),
+            concurrency: 1,
+            next: 0,
+            stopped: false,
+            started_at: 0.0,
+            finished_at: None,
+        });
+
+        assert!(!send(&mut bctx, SendMsg::RunCollection(0)));
+        assert!(!send(&mut bctx, SendMsg::RunCollectionParallel(0)));
+        assert_eq!(bctx.run.as_ref().map(|run| run.started_at), Some(0.0));
+    }
+
+    #[test]
+    fn tls_answers_land_on_their_request() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].inspecting_tls = true;
+
+        let answer = Err("refused".to_string());
+        assert!(send(&mut bctx, SendMsg::TlsInspected(FIRST, "https://a.test/".to_string(), answer)));
+
+        let req = &bctx.main_col.requests[0];
+        assert!(!req.inspecting_tls);
+        assert!(matches!(&req.tls_inspection, Some((url, Err(err))) if url == "https://a.test/" && err == "refused"));
+
+        // the request went away while the handshake ran
+        let gone = RequestRef { collection: Some(0), request: 0 };
+        assert!(!send(&mut bctx, SendMsg::TlsInspected(gone, String::new(), Err(String::new()))));
+    }
+
+    #[test]
+    fn nothing_is_sent_off_the_request_pages() {
+        let mut bctx = context();
+        bctx.page = Page::Settings;
+
+        assert!(!send(&mut bctx, SendMsg::SendPressed));
+        assert!(!send(&mut bctx, SendMsg::FetchToken));
+        assert!(!send(&mut bctx, SendMsg::CheckHost));
+        assert!(bctx.recent_urls.is_empty());
+    }
+
+    // a collection of two on the Collections page, besides the request on Home
+    fn with_collection(bctx: &mut BoltContext) {
+        let mut collection = Collection::new();
+        collection.requests = vec![Request::new(), Request::new()];
+        bctx.collections.push(collection);
+        bctx.col_current = vec![0, 0];
+    }
+
+    fn answer(status: u16, body: &str) -> String {
+        let mut response = Response::new();
+        response.status = status;
+        response.body = body.to_string();
+
+        serde_json::to_string(&response).unwrap()
+    }
+
+    fn toasts(bctx: &BoltContext) -> Vec<(ToastLevel, &str)> {
+        bctx.toasts.iter().map(|toast| (toast.level, toast.text.as_str())).collect()
+    }
+
+    #[test]
+    fn a_send_goes_out_and_remembers_the_url_as_typed() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].url = "https://a.test/users/{{id}}".to_string();
+        bctx.main_col.requests[0].headers = vec![vec!["Content-Length".to_string(), "12".to_string()]];
+
+        assert!(send(&mut bctx, SendMsg::SendPressed));
+
+        let req = &bctx.main_col.requests[0];
+        assert!(req.loading && req.last_sent_at.is_some());
+        assert_eq!(bctx.recent_urls, ["https://a.test/users/{{id}}"]);
+        assert_eq!(toasts(&bctx), [(ToastLevel::Info, "Content-Length removed, the client sets it from the body")]);
+        assert_eq!(take_effects(), ["send"]);
+    }
+
+    #[test]
+    fn tokens_are_fetched_before_a_send_or_on_their_own() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].auth.mode = AuthMode::OAuth2ClientCredentials;
+
+        assert!(send(&mut bctx, SendMsg::SendPressed));
+        assert!(bctx.main_col.requests[0].auth.fetching);
+        assert_eq!(take_effects(), ["token_fetch"]);
+
+        let mut bctx = context();
+        bctx.main_col.requests[0].auth.mode = AuthMode::OAuth2ClientCredentials;
+
+        assert!(send(&mut bctx, SendMsg::FetchToken));
+        assert!(bctx.main_col.requests[0].auth.fetching);
+        assert!(!bctx.main_col.requests[0].loading);
+        assert_eq!(take_effects(), ["token_fetch"]);
+    }
+
+    #[test]
+    fn a_fetched_token_sends_the_request_waiting_for_it() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].auth.mode = AuthMode::OAuth2ClientCredentials;
+        bctx.main_col.requests[0].auth.fetching = true;
+
+        let token = answer(200, "{\"access_token\": \"abc\", \"expires_in\": 60}");
+        assert!(send(&mut bctx, SendMsg::ReceivedToken(FIRST, true, token)));
+
+        let auth = &bctx.main_col.requests[0].auth;
+        assert_eq!(auth.access_token, "abc");
+        assert!(!auth.fetching && auth.expires_at.is_some());
+        assert_eq!(take_effects(), ["send"]);
+
+        // a refused token stops there
+        assert!(send(&mut bctx, SendMsg::ReceivedToken(FIRST, true, answer(401, "denied"))));
+        assert_eq!(bctx.main_col.requests[0].auth.token_error, "denied");
+        assert!(take_effects().is_empty());
+    }
+
+    #[test]
+    fn double_clicks_send_only_when_the_setting_allows() {
+        let mut bctx = context();
+        with_collection(&mut bctx);
+        bctx.page = Page::Home;
+
+        let (col_id, req_id) = (bctx.collections[0].id, bctx.collections[0].requests[1].id);
+
+        bctx.settings.double_click_send = false;
+        assert!(!send(&mut bctx, SendMsg::SendFromSidebar(Some(col_id), req_id)));
+        assert_eq!(bctx.page, Page::Home);
+
+        bctx.settings.double_click_send = true;
+        assert!(send(&mut bctx, SendMsg::SendFromSidebar(Some(col_id), req_id)));
+        assert_eq!((bctx.page, bctx.col_current.clone()), (Page::Collections, vec![0, 1]));
+        assert!(bctx.collections[0].requests[1].loading);
+
+        let home = bctx.main_col.requests[0].id;
+        assert!(send(&mut bctx, SendMsg::SendFromSidebar(None, home)));
+        assert_eq!(bctx.page, Page::Home);
+        assert_eq!(take_effects(), ["send", "send"]);
+
+        // deleted before the second click
+        assert!(!send(&mut bctx, SendMsg::SendFromSidebar(None, 0)));
+    }
+
+    #[test]
+    fn answers_replace_the_response_and_keep_the_last_one() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].loading = true;
+
+        assert!(send(&mut bctx, SendMsg::ReceivedResponse(FIRST, answer(200, "first"))));
+        assert!(send(&mut bctx, SendMsg::ReceivedResponse(FIRST, answer(404, "second"))));
+
+        let req = &bctx.main_col.requests[0];
+        assert!(!req.loading);
+        assert_eq!((req.response.status, req.response.body.as_str()), (404, "second"));
+        assert_eq!(req.last_response.as_ref().map(|last| last.status), Some(200));
+
+        // the backend answered with something else entirely
+        assert!(send(&mut bctx, SendMsg::ReceivedResponse(FIRST, "<html>".to_string())));
+        assert_eq!(bctx.toasts.len(), 1);
+        assert_eq!(bctx.main_col.requests[0].response.status, 404);
+    }
+
+    #[test]
+    fn failed_and_blocked_sends_say_why() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].loading = true;
+
+        assert!(send(&mut bctx, SendMsg::SendFailed(FIRST, "connection reset".to_string())));
+        assert!(!bctx.main_col.requests[0].loading);
+
+        bctx.main_col.requests[0].loading = true;
+
+        assert!(send(&mut bctx, SendMsg::SendBlocked(FIRST, "The URL is empty".to_string())));
+        assert!(!bctx.main_col.requests[0].loading);
+        assert_eq!(
+            toasts(&bctx),
+            [(ToastLevel::Warning, "connection reset"), (ToastLevel::Warning, "The URL is empty")]
+        );
+    }
+
+    #[test]
+    fn host_checks_go_to_the_resolved_url_and_answer_with_a_toast() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].url = "https://a.test/".to_string();
+
+        assert!(!send(&mut bctx, SendMsg::CheckHost));
+        assert_eq!(take_effects(), ["check_host"]);
+
+        let check = |reachable: bool, error_kind: Option<&str>| HostCheck {
+            reachable,
+            time: 12,
+            error_kind: error_kind.map(str::to_string),
+            error: "os error 111".to_string(),
+        };
+
+        assert!(send(&mut bctx, SendMsg::HostChecked(Ok(check(true, None)))));
+        assert!(send(&mut bctx, SendMsg::HostChecked(Ok(check(false, Some("ConnectionRefused"))))));
+        assert!(send(&mut bctx, SendMsg::HostChecked(Ok(check(false, Some("Unknown"))))));
+        assert!(send(&mut bctx, SendMsg::HostChecked(Err("no backend".to_string()))));
+
+        let levels: Vec<ToastLevel> = bctx.toasts.iter().map(|toast| toast.level).collect();
+        assert_eq!(levels, [ToastLevel::Info, ToastLevel::Warning, ToastLevel::Warning, ToastLevel::Warning]);
+        assert_eq!(bctx.toasts[0].text, "The host answered in 12 ms");
+        assert!(bctx.toasts[1].text.contains("refused the connection"));
+        assert_eq!(bctx.toasts[2].text, "The host is not reachable: os error 111");
+        assert_eq!(bctx.toasts[3].text, "Could not reach the backend: no backend");
+    }
+
+    #[test]
+    fn a_tls_inspection_marks_the_request_until_it_answers() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].url = "https://a.test/".to_string();
+
+        assert!(send(&mut bctx, SendMsg::InspectTls));
+        assert!(bctx.main_col.requests[0].inspecting_tls);
+        assert_eq!(take_effects(), ["inspect_tls"]);
+    }
+
+    #[test]
+    fn polling_sends_right_away_and_stops_its_timer() {
+        let mut bctx = context();
+
+        assert!(send(&mut bctx, SendMsg::StartRepeat(10)));
+
+        let repeat = bctx.repeat.unwrap();
+        assert_eq!((repeat.request, repeat.interval_ms), (bctx.main_col.requests[0].id, MIN_REPEAT_INTERVAL_MS));
+        assert_eq!(take_effects(), [format!("repeat every {MIN_REPEAT_INTERVAL_MS} ms"), "send".to_string()]);
+
+        // answered, so the next tick sends again
+        bctx.main_col.requests[0].loading = false;
+        assert!(send(&mut bctx, SendMsg::RepeatTick));
+        assert_eq!(take_effects(), ["send"]);
+
+        assert!(send(&mut bctx, SendMsg::StopRepeat));
+        assert!(bctx.repeat.is_none());
+        assert_eq!(take_effects(), ["stop repeating"]);
+
+        // nothing to poll off the request pages
+        bctx.page = Page::Settings;
+        assert!(send(&mut bctx, SendMsg::StartRepeat(MIN_REPEAT_INTERVAL_MS)));
+        assert!(bctx.repeat.is_none());
+    }
+
+    #[test]
+    fn runs_send_one_or_several_at_a_time() {
+        let mut bctx = context();
+        with_collection(&mut bctx);
+
+        assert!(send(&mut bctx, SendMsg::RunCollection(0)));
+        assert_eq!(bctx.run.as_ref().map(|run| run.concurrency), Some(1));
+        assert_eq!(take_effects(), ["send"]);
+
+        assert!(send(&mut bctx, SendMsg::StopRun));
+        assert!(bctx.run.as_ref().is_some_and(|run| run.stopped && run.finished_at.is_some()));
+
+        bctx.settings.run_concurrency = 4;
+
+        assert!(send(&mut bctx, SendMsg::RunCollectionParallel(0)));
+        assert_eq!(bctx.run.as_ref().map(|run| run.concurrency), Some(4));
+        assert_eq!(take_effects(), ["send", "send"]);
+
+        assert!(send(&mut bctx, SendMsg::CloseRun));
+        assert!(bctx.run.is_none());
+    }
+
+    #[test]
+    fn downloads_are_named_after_the_body_and_the_request() {
+        let mut bctx = context();
+        bctx.main_col.requests[0].name = "list users".to_string();
+        bctx.main_col.requests[0].response.response_type = ResponseType::JSON;
+
+        assert!(!send(&mut bctx, SendMsg::DownloadBody));
+        assert!(!send(&mut bctx, SendMsg::ExportHttpFile));
+        assert_eq!(take_effects(), ["download response.json", "download list_users.http"]);
+
+        bctx.page = Page::Settings;
+        assert!(!send(&mut bctx, SendMsg::DownloadBody));
+        assert!(!send(&mut bctx, SendMsg::ExportHttpFile));
+        assert!(take_effects().is_empty());
+    }
+
+    #[test]
+    fn copied_snippets_keep_secrets_as_placeholders() {
+        let mut bctx = context();
+        bctx.main_col.variables = vec![vec!["token".to_string(), "s3cret".to_string()]];
+        bctx.main_col.secret_variables.insert("token".to_string());
+        bctx.main_col.requests[0].url = "https://a.test/?key={{token}}".to_string();
+
+        assert!(send(&mut bctx, SendMsg::CopySnippet(Language::Curl)));
+
+        let copied = take_effects();
+        assert!(copied[0].starts_with("copy curl") && copied[0].contains("{{token}}"));
+        assert!(!copied[0].contains("s3cret"));
+        assert_eq!(toasts(&bctx), [(ToastLevel::Info, "Copied to the clipboard")]);
+
+        bctx.reveal_secrets = true;
+
+        assert!(send(&mut bctx, SendMsg::CopySnippet(Language::Curl)));
+        assert!(take_effects()[0].contains("key=s3cret"));
+    }
+}
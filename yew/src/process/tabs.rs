@@ -0,0 +1,284 @@
+use crate::BoltContext;
+use crate::Collection;
+use crate::Page;
+use crate::Request;
+use crate::RequestRef;
+use crate::helpers::dynamic::layered_variables;
+use crate::utils::{request_position, secret_rows};
+use std::collections::BTreeMap;
+
+pub fn request_mut<'a>(bctx: &'a mut BoltContext, tab: &RequestRef) -> Option<&'a mut Request> {
+    match tab.collection {
+        None => bctx.main_col.requests.get_mut(tab.request),
+        Some(col) => bctx
+            .collections
+            .get_mut(col)
+            .and_then(|col| col.requests.get_mut(tab.request)),
+    }
+}
+
+pub fn request_ref<'a>(bctx: &'a BoltContext, tab: &RequestRef) -> Option<&'a Request> {
+    match tab.collection {
+        None => bctx.main_col.requests.get(tab.request),
+        Some(col) => bctx.collections.get(col).and_then(|col| col.requests.get(tab.request)),
+    }
+}
+
+// where the request with `id` sits now, None once it is gone
+pub fn find_request(bctx: &BoltContext, id: u64) -> Option<RequestRef> {
+    if let Some(request) = request_position(&bctx.main_col.requests, id) {
+        return Some(RequestRef { collection: None, request });
+    }
+
+    bctx.collections.iter().enumerate().find_map(|(col, collection)| {
+        let request = request_position(&collection.requests, id)?;
+
+        Some(RequestRef { collection: Some(col), request })
+    })
+}
+
+// find_request, checking first whether the request is still at `hint`, where it was when last looked up
+pub fn locate_request(bctx: &BoltContext, id: u64, hint: RequestRef) -> Option<RequestRef> {
+    if request_ref(bctx, &hint).is_some_and(|req| req.id == id) {
+        return Some(hint);
+    }
+
+    find_request(bctx, id)
+}
+
+// col_current by id: the selected collection and, unless it is empty, its selected request
+pub fn collection_selection(bctx: &BoltContext) -> Option<(u64, Option<u64>)> {
+    let collection = bctx.collections.get(bctx.col_current[0])?;
+
+    Some((collection.id, collection.requests.get(bctx.col_current[1]).map(|req| req.id)))
+}
+
+// points col_current back at what collection_selection saw before the collections changed. a
+// request that is gone leaves its collection selected, a collection that is gone the first one
+pub fn restore_collection_selection(bctx: &mut BoltContext, selection: Option<(u64, Option<u64>)>) {
+    let Some((col_index, collection)) = selection
+        .and_then(|(col_id, _)| bctx.collections.iter().enumerate().find(|(_, col)| col.id == col_id))
+    else {
+        bctx.col_current = vec![0, 0];
+        return;
+    };
+
+    let req_index = selection
+        .and_then(|(_, req_id)| request_position(&collection.requests, req_id?))
+        .unwrap_or_else(|| bctx.col_current[1].min(collection.requests.len().saturating_sub(1)));
+
+    bctx.col_current = vec![col_index, req_index];
+}
+
+// false with nothing to edit, e.g. right after the last request was deleted
+pub fn has_current_request(bctx: &BoltContext) -> bool {
+    current_ref(bctx).is_some_and(|current| request_ref(bctx, &current).is_some())
+}
+
+// every request of the collection holding the request, the home page's list for main_col
+pub fn collection_requests_mut<'a>(bctx: &'a mut BoltContext, tab: &RequestRef) -> Option<&'a mut Vec<Request>> {
+    match tab.collection {
+        None => Some(&mut bctx.main_col.requests),
+        Some(col) => bctx.collections.get_mut(col).map(|col| &mut col.requests),
+    }
+}
+
+// the base url of the collection holding the request, the home page uses main_col's
+pub fn collection_base_url(bctx: &BoltContext, tab: &RequestRef) -> Option<String> {
+    let base_url = match tab.collection {
+        None => &bctx.main_col.base_url,
+        Some(col) => &bctx.collections.get(col)?.base_url,
+    };
+
+    base_url.clone()
+}
+
+// the captured values with the collection's variables on top, a send adds the request's own last
+pub fn collection_variables(bctx: &BoltContext, tab: &RequestRef) -> BTreeMap<String, String> {
+    let variables = match tab.collection {
+        None => Some(&bctx.main_col.variables),
+        Some(col) => bctx.collections.get(col).map(|col| &col.variables),
+    };
+
+    layered_variables(&bctx.variables, variables.map_or(&[], |variables| variables.as_slice()), &[])
+}
+
+// everything {{name}} stands for in the request, its own variables included
+pub fn request_variables(bctx: &BoltContext, tab: &RequestRef) -> BTreeMap<String, String> {
+    let request = match tab.collection {
+        None => bctx.main_col.requests.get(tab.request),
+        Some(col) => bctx.collections.get(col).and_then(|col| col.requests.get(tab.request)),
+    };
+
+    let local = request.map_or(&[][..], |request| request.local_variables.as_slice());
+
+    layered_variables(&collection_variables(bctx, tab), &[], local)
+}
+
+// values of the collection's secret variables and of the request's secret headers and params, to be
+// masked wherever they'd show up. empty while the user has them revealed
+pub fn secret_values(bctx: &BoltContext, tab: &RequestRef) -> Vec<String> {
+    if bctx.reveal_secrets {
+        return Vec::new();
+    }
+
+    let collection = match tab.collection {
+        None => Some(&bctx.main_col),
+        Some(col) => bctx.collections.get(col),
+    };
+
+    let Some(collection) = collection else {
+        return Vec::new();
+    };
+
+    let mut values: Vec<String> = collection
+        .variables
+        .iter()
+        .filter(|row| collection.secret_variables.contains(row[0].trim()) && !row[1].is_empty())
+        .map(|row| row[1].clone())
+        .collect();
+
+    if let Some(request) = collection.requests.get(tab.request) {
+        values.extend(secret_rows(request).into_iter().map(|(value, _)| value));
+    }
+
+    values
+}
+
+pub fn current_ref(bctx: &BoltContext) -> Option<RequestRef> {
+    match bctx.page {
+        Page::Home => Some(RequestRef {
+            collection: None,
+            request: bctx.main_current,
+        }),
+        Page::Collections => Some(RequestRef {
+            collection: Some(bctx.col_current[0]),
+            request: bctx.col_current[1],
+        }),
+        _ => None,
+    }
+}
+
+// the request the editor shows, None off the Home and Collections pages
+pub fn current_request_mut(bctx: &mut BoltContext) -> Option<&mut Request> {
+    let target = current_ref(bctx)?;
+
+    request_mut(bctx, &target)
+}
+
+// the collection of the request the editor shows, main_col on the Home page
+pub fn current_collection_mut(bctx: &mut BoltContext) -> Option<&mut Collection> {
+    match current_ref(bctx)?.collection {
+        None => Some(&mut bctx.main_col),
+        Some(col) => bctx.collections.get_mut(col),
+    }
+}
+
+// makes the tab the visible request, switching page if it lives on the other one
+pub fn activate_tab(bctx: &mut BoltContext, index: usize) {
+    let tab = match bctx.open_tabs.get(index).and_then(|id| find_request(bctx, *id)) {
+        Some(tab) => tab,
+        None => return,
+    };
+
+    match tab.collection {
+        None => {
+            bctx.page = Page::Home;
+            bctx.main_current = tab.request;
+            bctx.main_col.requests[tab.request].response.request_index = tab.request;
+        }
+        Some(col) => {
+            bctx.page = Page::Collections;
+            bctx.col_current = vec![col, tab.request];
+            bctx.collections[col].requests[tab.request]
+                .response
+                .request_index = tab.request;
+        }
+    }
+
+    bctx.active_tab = Some(index);
+}
+
+// focuses the tab for a request, opening one if it isn't open yet
+pub fn open_tab(bctx: &mut BoltContext, tab: RequestRef) {
+    let Some(id) = request_ref(bctx, &tab).map(|req| req.id) else {
+        return;
+    };
+
+    let index = match bctx.open_tabs.iter().position(|open| *open == id) {
+        Some(index) => index,
+        None => {
+            bctx.open_tabs.push(id);
+            bctx.open_tabs.len() - 1
+        }
+    };
+
+    activate_tab(bctx, index);
+}
+
+pub fn close_tab(bctx: &mut BoltContext, index: usize) {
+    if index >= bctx.open_tabs.len() {
+        return;
+    }
+
+    bctx.open_tabs.remove(index);
+
+    bctx.active_tab = match bctx.active_tab {
+        Some(active) if active == index => {
+            if bctx.open_tabs.is_empty() {
+                None
+            } else {
+                let next = index.min(bctx.open_tabs.len() - 1);
+                activate_tab(bctx, next);
+
+                Some(next)
+            }
+        }
+        Some(active) if active > index => Some(active - 1),
+        active => active,
+    };
+}
+
+// picks the tab matching the request shown on the current page, if any
+pub fn sync_page_tab(bctx: &mut BoltContext) {
+    let current = current_ref(bctx).and_then(|current| request_ref(bctx, &current)).map(|req| req.id);
+    let collection_page = bctx.page == Page::Collections;
+
+    let index = current.and_then(|current| {
+        bctx.open_tabs.iter().position(|tab| *tab == current).or_else(|| {
+            bctx.open_tabs
+                .iter()
+                .position(|tab| find_request(bctx, *tab).is_some_and(|tab| tab.collection.is_some() == collection_page))
+        })
+    });
+
+    match index {
+        Some(index) => activate_tab(bctx, index),
+        None => bctx.active_tab = None,
+    }
+}
+
+// closes the tabs whose request was deleted, the others keep their place and the active one stays active
+pub fn prune_tabs(bctx: &mut BoltContext) {
+    let active = bctx.active_tab.and_then(|index| bctx.open_tabs.get(index).copied());
+
+    let tabs: Vec<u64> = bctx.open_tabs.iter().copied().filter(|id| find_request(bctx, *id).is_some()).collect();
+    bctx.open_tabs = tabs;
+
+    bctx.active_tab = active.and_then(|active| bctx.open_tabs.iter().position(|tab| *tab == active));
+
+    if active.is_some() && bctx.active_tab.is_none() {
+        sync_page_tab(bctx);
+    }
+}
+
+// state saved before tabs existed opens with the selected request as its only tab
+pub fn ensure_initial_tab(bctx: &mut BoltContext) {
+    if !bctx.open_tabs.is_empty() {
+        return;
+    }
+
+    if let Some(current) = current_ref(bctx) {
+        open_tab(bctx, current);
+    }
+}
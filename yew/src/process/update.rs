@@ -1,354 +1,657 @@
 // use crate::save_state;
-use crate::send_request;
+use crate::helpers::enums::{SaveStatus, ToastLevel};
+use crate::helpers::enums::Lang;
+use crate::helpers::i18n::{set_language, tr, tr_args};
+use crate::helpers::enums::RequestOrder;
+use crate::helpers::import::{detect_format, import_openapi, import_postman, ImportFormat};
+use crate::helpers::enums::ImportMode;
+use crate::helpers::samples::sample_collection;
+use crate::DEFAULT_MAX_RESPONSE_SIZE;
+use crate::{LARGE_BODY_LIMIT, MAX_STATUS_LOG_LENGTH, STATUS_LOG_LENGTH};
+use crate::{DEFAULT_RUN_CONCURRENCY, MAX_RUN_CONCURRENCY};
+use crate::Settings;
+use crate::FindReplace;
 use crate::utils::*;
 use crate::BoltContext;
-use crate::Collection;
+use crate::process::autocomplete::autocomplete;
+use crate::Layout;
+use crate::{REQUEST_RATIO_RANGE, SIDEBAR_RATIO_RANGE};
+use crate::helpers::enums::ResizeHandle;
+use crate::process::tabs::*;
+use crate::process::request_edit::request_edit;
+use crate::process::navigation::navigation;
+use crate::process::send::{send, stop_repeat};
+use crate::process::collections::collections;
+use crate::process::replace::{refresh_matches, replace_selected, undo_replace};
+use crate::RequestRef;
 use crate::Msg;
-use crate::Page;
-use crate::Request;
+use crate::{CollectionsMsg, NavigationMsg, SendMsg};
 
 pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
+    // the editor is hidden without a request, but a late event (a blur, a file read) can still
+    // arrive after the last one was deleted
+    if edits_current_request(&msg) && !has_current_request(bctx) {
+        return false;
+    }
+
+    // checked up front, the match consumes the message
+    let modifies = modifies_current_request(&msg);
+
     let should_render = match msg {
         Msg::Nothing => false,
 
-        Msg::SelectedMethod(meth) => {
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].method = meth;
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]].method = meth;
-            }
+        // the reducers for the request on screen, for the rest of its life and for the collections
+        // around it, see process/
+        Msg::Edit(msg) => request_edit(bctx, msg),
+        Msg::Navigation(msg) => navigation(bctx, msg),
+        Msg::Send(msg) => send(bctx, msg),
+        Msg::Collections(msg) => collections(bctx, msg),
+
+        Msg::HelpPressed => {
+            open_link("https://github.com/hiro-codes/bolt".to_string());
 
             true
         }
 
-        Msg::SendPressed => {
-            if bctx.page == Page::Home {
-                let req = &mut bctx.main_col.requests[bctx.main_current];
-                send_request(req);
-            } else {
-                let current = &bctx.col_current;
-                let req = &mut bctx.collections[current[0]].requests[current[1]];
-                send_request(req);
+        Msg::OpenLink(url) => {
+            open_link(url);
+
+            false
+        }
+
+        Msg::VariableAutocomplete(action) => autocomplete(bctx, action),
+
+        Msg::PickBodyFile => {
+            pick_body_file();
+
+            false
+        }
+
+        Msg::RefreshBodyFileInfo => {
+            let target = match current_ref(bctx) {
+                Some(target) => target,
+                None => return false,
+            };
+
+            if let Some(path) = request_mut(bctx, &target).and_then(|req| req.body_file.clone()) {
+                invoke_body_file_info(&path, target);
             }
 
+            false
+        }
+
+        Msg::BodyFileInfo(target, info) => {
+            match request_mut(bctx, &target) {
+                Some(req) => {
+                    req.body_file_info = Some(info);
+
+                    true
+                }
+                None => false,
+            }
+        }
+
+        Msg::ToggleRevealSecrets => {
+            bctx.reveal_secrets = !bctx.reveal_secrets;
+
             true
         }
 
-        Msg::HelpPressed => {
-            open_link("https://github.com/hiro-codes/bolt".to_string());
+        Msg::ClearVariables => {
+            bctx.variables.clear();
 
             true
         }
 
-        Msg::ReqBodyPressed => {
-            if bctx.page == Page::Home {
-                let req = &mut bctx.main_col.requests[bctx.main_current];
+        Msg::ProxyChanged => {
+            bctx.settings.proxy = get_input_value("proxyinput");
 
-                req.req_tab = 1;
-            } else {
-                let current = &bctx.col_current;
-                let req = &mut bctx.collections[current[0]].requests[current[1]];
-                req.req_tab = 1;
-            }
+            true
+        }
+
+        Msg::NoProxyChanged => {
+            bctx.settings.no_proxy = parse_host_list(&get_input_value("noproxyinput"));
 
             true
         }
 
-        Msg::ReqHeadersPressed => {
-            if bctx.page == Page::Home {
-                let req = &mut bctx.main_col.requests[bctx.main_current];
+        Msg::TogglePersistResponses => {
+            bctx.settings.persist_responses = !bctx.settings.persist_responses;
 
-                req.req_tab = 3;
-            } else {
-                let current = &bctx.col_current;
-                let req = &mut bctx.collections[current[0]].requests[current[1]];
-                req.req_tab = 3;
-            }
+            true
+        }
+
+        Msg::TimeoutChanged => {
+            bctx.settings.timeout_ms = get_input_value("timeoutinput").trim().parse().unwrap_or(0);
 
             true
         }
 
-        Msg::ReqParamsPressed => {
-            if bctx.page == Page::Home {
-                let req = &mut bctx.main_col.requests[bctx.main_current];
+        Msg::ToggleFollowRedirects => {
+            bctx.settings.follow_redirects = !bctx.settings.follow_redirects;
 
-                req.req_tab = 2;
-            } else {
-                let current = &bctx.col_current;
-                let req = &mut bctx.collections[current[0]].requests[current[1]];
-                req.req_tab = 2;
-            }
+            true
+        }
+
+        Msg::ClearRecentUrls => {
+            bctx.recent_urls.clear();
 
             true
         }
 
-        Msg::RespBodyPressed => {
-            if bctx.page == Page::Home {
-                let mut req = &mut bctx.main_col.requests[bctx.main_current];
+        Msg::ToggleDoubleClickSend => {
+            bctx.settings.double_click_send = !bctx.settings.double_click_send;
 
-                req.resp_tab = 1;
-            } else {
-                let current = &bctx.col_current;
-                let req = &mut bctx.collections[current[0]].requests[current[1]];
-                req.resp_tab = 1;
-            }
+            true
+        }
+
+        Msg::UserAgentChanged => {
+            bctx.settings.user_agent = get_input_value("useragentinput").trim().to_string();
 
             true
         }
 
-        Msg::RespHeadersPressed => {
-            if bctx.page == Page::Home {
-                let req = &mut bctx.main_col.requests[bctx.main_current];
+        Msg::StatusLogLengthChanged => {
+            let length = get_input_value("statusloginput")
+                .trim()
+                .parse()
+                .unwrap_or(STATUS_LOG_LENGTH)
+                .clamp(1, MAX_STATUS_LOG_LENGTH);
 
-                req.resp_tab = 2;
-            } else {
-                let current = &bctx.col_current;
-                let req = &mut bctx.collections[current[0]].requests[current[1]];
-                req.resp_tab = 2;
-            }
+            bctx.settings.status_log_length = length;
 
             true
         }
 
-        Msg::ReceivedResponse => true,
+        Msg::TruncateSizeChanged => {
+            let kb: u64 = get_input_value("truncateinput").trim().parse().unwrap_or(LARGE_BODY_LIMIT / 1024);
+
+            bctx.settings.truncate_size = kb.max(1).saturating_mul(1024);
 
-        Msg::AddHeader => {
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current]
-                    .headers
-                    .push(vec!["".to_string(), "".to_string()]);
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]]
-                    .headers
-                    .push(vec!["".to_string(), "".to_string()]);
-            }
             true
         }
 
-        Msg::RemoveHeader(index) => {
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].headers.remove(index);
-            } else {
-                let current = &bctx.col_current;
+        Msg::MaxResponseSizeChanged => {
+            let mb: u64 = get_input_value("maxresponseinput")
+                .trim()
+                .parse()
+                .unwrap_or(DEFAULT_MAX_RESPONSE_SIZE / (1024 * 1024));
 
-                bctx.collections[current[0]].requests[current[1]]
-                    .headers
-                    .remove(index);
-            }
+            // a typed number too big to multiply means as good as no limit
+            bctx.settings.max_response_size = mb.saturating_mul(1024 * 1024);
 
             true
         }
 
-        Msg::AddParam => {
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current]
-                    .params
-                    .push(vec!["".to_string(), "".to_string()]);
+        // only the request defaults, the proxy and storage choices stay
+        Msg::RestoreDefaultSettings => {
+            let current = std::mem::take(&mut bctx.settings);
+
+            bctx.settings = Settings {
+                proxy: current.proxy,
+                no_proxy: current.no_proxy,
+                persist_responses: current.persist_responses,
+                language: current.language,
+                collection_order: current.collection_order,
+                default_headers: current.default_headers,
+                ..Settings::default()
+            };
+
+            true
+        }
+
+        Msg::CollectionOrderChanged => {
+            let index: usize = get_select_value("collectionorderselect").parse().unwrap_or(0);
+
+            bctx.settings.collection_order = if index < RequestOrder::count() {
+                RequestOrder::from(index)
             } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]]
-                    .params
-                    .push(vec!["".to_string(), "".to_string()]);
-            }
+                RequestOrder::default()
+            };
+
+            true
+        }
+
+        Msg::RunConcurrencyChanged => {
+            let concurrency = get_input_value("runconcurrencyinput")
+                .trim()
+                .parse()
+                .unwrap_or(DEFAULT_RUN_CONCURRENCY)
+                .clamp(1, MAX_RUN_CONCURRENCY);
+
+            bctx.settings.run_concurrency = concurrency;
+
             true
         }
 
-        Msg::AddCollection => {
-            let mut new_collection = Collection::new();
+        Msg::AddDefaultHeader => {
+            bctx.settings.default_headers.push(vec![String::new(), String::new()]);
+            bctx.focus = Some(format!("defaultheaderkey{}", bctx.settings.default_headers.len() - 1));
+
+            true
+        }
 
-            new_collection.name = new_collection.name + &(bctx.collections.len() + 1).to_string();
-            bctx.collections.push(new_collection);
+        Msg::RemoveDefaultHeader(index) => {
+            bctx.settings.default_headers.remove(index);
 
             true
         }
 
-        Msg::RemoveCollection(index) => {
-            bctx.collections.remove(index);
+        Msg::DefaultHeaderChanged(index) => {
+            bctx.settings.default_headers[index] = get_default_header(index);
 
-            bctx.col_current = vec![0, 0];
+            true
+        }
+
+        Msg::LanguageChanged => {
+            let index: usize = get_select_value("langselect").parse().unwrap_or(0);
+            let lang = if index < Lang::count() { Lang::from(index) } else { Lang::English };
+
+            bctx.settings.language = lang;
+            set_language(lang);
 
             true
         }
 
-        Msg::RemoveParam(index) => {
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].params.remove(index);
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]]
-                    .params
-                    .remove(index);
-            }
+        Msg::SaveState => {
+            save_state(bctx);
+
+            // only the save button clears the marks, the autosave after every edit would right away
+            bctx.main_col
+                .requests
+                .iter_mut()
+                .chain(bctx.collections.iter_mut().flat_map(|col| col.requests.iter_mut()))
+                .for_each(|request| request.modified = false);
 
             true
         }
 
-        Msg::MethodChanged => {
-            let method = get_method();
+        Msg::SaveFinished(generation, result) => {
+            // a newer save is still on its way
+            if generation != bctx.save_generation {
+                return false;
+            }
 
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].method = method;
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]].method = method;
+            // push_toast skips it while the same failure is still showing
+            if let Err(err) = &result {
+                push_toast(bctx, ToastLevel::Error, tr_args("toast.save_failed", &[err]));
+            }
+
+            bctx.save_status = match result {
+                Ok(()) => SaveStatus::Saved,
+                Err(err) => SaveStatus::Failed(err),
+            };
+
+            if bctx.close_after_save {
+                bctx.close_after_save = false;
+
+                if bctx.save_status == SaveStatus::Saved || ask_confirm(&tr("save.close_unsaved")) {
+                    close_window();
+                }
             }
 
             true
         }
 
-        Msg::UrlChanged => {
-            let url = get_url();
+        Msg::ShowToast(level, text) => {
+            push_toast(bctx, level, text);
 
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].url = url.clone();
-                bctx.main_col.requests[current].name = url;
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]].url = url.clone();
-                bctx.collections[current[0]].requests[current[1]].name = url;
+            true
+        }
+
+        Msg::DismissToast(id) => {
+            let count = bctx.toasts.len();
+            bctx.toasts.retain(|toast| toast.id != id);
+
+            bctx.toasts.len() != count
+        }
+
+        Msg::CloseRequested => {
+            if bctx.save_status == SaveStatus::Saved {
+                close_window();
+                return false;
             }
 
+            bctx.close_after_save = true;
+            save_state(bctx);
+
             true
         }
 
-        Msg::BodyChanged => {
-            let body = get_body();
+        Msg::TagFilterChanged => {
+            let tag = get_select_value("tagfilter");
 
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].body = body;
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]].body = body;
+            bctx.tag_filter = if tag.is_empty() { None } else { Some(tag) };
+
+            true
+        }
+
+        Msg::OpenFindReplace => {
+            if bctx.find_replace.is_none() {
+                bctx.find_replace = Some(FindReplace::default());
             }
 
+            bctx.focus = Some("findinput".to_string());
+
             true
         }
 
-        Msg::HeaderChanged(index) => {
-            let header = get_header(index);
+        Msg::CloseFindReplace => bctx.find_replace.take().is_some(),
 
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].headers[index] = header;
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]].headers[index] = header;
+        Msg::FindReplaceChanged => {
+            let dialog = match bctx.find_replace.as_mut() {
+                Some(dialog) => dialog,
+                None => return false,
+            };
+
+            let query = get_input_value("findinput");
+            dialog.replacement = get_input_value("replaceinput");
+
+            // a new replacement keeps the ticks, the matches are still the same
+            if dialog.query == query {
+                return false;
             }
 
+            dialog.query = query;
+            refresh_matches(bctx);
+
             true
         }
 
-        Msg::ParamChanged(index) => {
-            let param = get_param(index);
+        Msg::ToggleFindCase => {
+            if let Some(dialog) = bctx.find_replace.as_mut() {
+                dialog.options.case_sensitive = !dialog.options.case_sensitive;
+            }
 
-            if bctx.page == Page::Home {
-                let current = bctx.main_current;
-                bctx.main_col.requests[current].params[index] = param;
-            } else {
-                let current = &bctx.col_current;
-                bctx.collections[current[0]].requests[current[1]].params[index] = param;
+            refresh_matches(bctx);
+
+            true
+        }
+
+        Msg::ToggleFindWholeWord => {
+            if let Some(dialog) = bctx.find_replace.as_mut() {
+                dialog.options.whole_word = !dialog.options.whole_word;
             }
 
+            refresh_matches(bctx);
+
             true
         }
 
-        Msg::AddRequest => {
-            let mut new_request = Request::new();
-            new_request.name = new_request.name + &(bctx.main_col.requests.len() + 1).to_string();
+        Msg::ToggleReplaceMatch(index) => {
+            let dialog = match bctx.find_replace.as_mut() {
+                Some(dialog) => dialog,
+                None => return false,
+            };
 
-            bctx.main_col.requests.push(new_request);
+            if !dialog.skipped.remove(&index) {
+                dialog.skipped.insert(index);
+            }
 
             true
         }
 
-        Msg::AddToCollection(index) => {
-            let collection = &mut bctx.collections[index];
+        Msg::ApplyReplace => replace_selected(bctx),
 
-            let mut new_request = Request::new();
-            new_request.name = new_request.name + &(collection.requests.len() + 1).to_string();
+        Msg::UndoReplace => undo_replace(bctx),
 
-            collection.requests.push(new_request);
+        Msg::SetTheme(theme) => {
+            bctx.theme = theme;
+            rehighlight_responses(bctx);
 
             true
         }
 
-        Msg::ToggleCollapsed(index) => {
-            let collection = &mut bctx.collections[index];
+        Msg::PickHttpFile => {
+            import_http_file();
+
+            false
+        }
+
+        Msg::ImportHttpFile(text) => {
+            let requests = parse_http_file(&text);
+
+            if requests.is_empty() {
+                return false;
+            }
+
+            // into the collection on screen, the main list everywhere else
+            let collection = current_ref(bctx)
+                .and_then(|target| target.collection)
+                .filter(|col| *col < bctx.collections.len());
+            let target = RequestRef { collection, request: 0 };
+
+            let Some(list) = collection_requests_mut(bctx, &target) else {
+                return false;
+            };
+
+            let first = list.len();
+            list.extend(requests);
 
-            collection.collapsed = !collection.collapsed;
+            open_tab(bctx, RequestRef { collection, request: first });
 
             true
         }
 
-        Msg::RemoveRequest(index) => {
-            bctx.main_col.requests.remove(index);
-            if !bctx.main_col.requests.is_empty()
-                && bctx.main_current > bctx.main_col.requests.len() - 1
-            {
-                bctx.main_current = bctx.main_col.requests.len() - 1;
+        Msg::PickOpenApiSpec => {
+            pick_openapi_spec(&bctx.settings);
+
+            false
+        }
+
+        Msg::ImportOpenApi(spec) => match import_openapi(&spec, None) {
+            Ok(collection) => {
+                bctx.collections.push(collection);
+
+                true
+            }
+            Err(err) => {
+                show_alert(&tr_args("import.openapi_failed", &[&err.to_string()]));
+
+                false
+            }
+        },
+
+        Msg::ImportFromUrl(url) => {
+            if url.trim().is_empty() {
+                return false;
+            }
+
+            fetch_import(&url, &bctx.settings);
+
+            false
+        }
+
+        Msg::ImportDocument(text) => {
+            let imported = match detect_format(&text) {
+                Ok(ImportFormat::Workspace) => parse_workspace(&text).map(|state| {
+                    let collections = bctx.collections.len();
+                    let requests = bctx.main_col.requests.len();
+
+                    // merged, pulling a shared file shouldn't wipe what is already here
+                    import_save_state(bctx, state, ImportMode::Merge);
+
+                    tr_args("toast.imported_workspace", &[
+                        &(bctx.collections.len() - collections).to_string(),
+                        &(bctx.main_col.requests.len() - requests).to_string(),
+                    ])
+                }),
+                Ok(format) => {
+                    let collection = if format == ImportFormat::Postman {
+                        import_postman(&text)
+                    } else {
+                        import_openapi(&text, None)
+                    };
+
+                    collection.map_err(|err| err.to_string()).map(|collection| {
+                        let message = tr_args("toast.imported_collection", &[&collection.name, &collection.requests.len().to_string()]);
+                        bctx.collections.push(collection);
+
+                        message
+                    })
+                }
+                Err(err) => Err(err.to_string()),
+            };
+
+            match imported {
+                Ok(message) => push_toast(bctx, ToastLevel::Info, message),
+                Err(err) => push_toast(bctx, ToastLevel::Error, tr_args("toast.import_failed", &[&err])),
             }
 
             true
         }
 
-        Msg::RemoveFromCollection(col_index, req_index) => {
-            bctx.collections[col_index].requests.remove(req_index);
-            bctx.col_current = vec![0, 0];
+        Msg::CopyJsonPath(path) => {
+            copy_to_clipboard(&path);
+            push_toast(bctx, ToastLevel::Info, tr("toast.copied"));
 
             true
         }
 
-        Msg::SelectRequest(index) => {
-            let mut new_index = index;
+        Msg::ResizeStart(handle) => {
+            bctx.resizing = Some(handle);
 
-            if bctx.main_col.requests.len() == 0 {
-                bctx.main_current = new_index;
+            false
+        }
 
-                // bctx.main_col.requests[new_index].response.request_index = new_index;
-            } else {
-                if index >= bctx.main_col.requests.len() {
-                    new_index = bctx.main_col.requests.len() - 1;
-                    bctx.main_current = new_index;
+        Msg::ResizeDrag(x, y) => {
+            let (width, height) = window_size();
+
+            // the navbar takes the top 5vh, the panels share the rest
+            let navbar = height * 0.05;
 
-                    bctx.main_col.requests[new_index].response.request_index = new_index;
-                } else {
-                    bctx.main_current = new_index;
+            match bctx.resizing {
+                Some(ResizeHandle::Sidebar) => {
+                    let (min, max) = SIDEBAR_RATIO_RANGE;
+                    bctx.layout.sidebar_ratio = (x as f64 / width).clamp(min, max);
 
-                    bctx.main_col.requests[new_index].response.request_index = new_index;
+                    true
                 }
+                Some(ResizeHandle::Request) => {
+                    let (min, max) = REQUEST_RATIO_RANGE;
+                    bctx.layout.request_ratio = ((y as f64 - navbar) / (height - navbar)).clamp(min, max);
+
+                    true
+                }
+                None => false,
             }
+        }
+
+        Msg::ResizeStop => {
+            let was_resizing = bctx.resizing.is_some();
+            bctx.resizing = None;
+
+            was_resizing
+        }
+
+        Msg::ResetLayout(handle) => {
+            let default = Layout::default();
+
+            match handle {
+                ResizeHandle::Sidebar => bctx.layout.sidebar_ratio = default.sidebar_ratio,
+                ResizeHandle::Request => bctx.layout.request_ratio = default.request_ratio,
+            }
+
+            true
+        }
+
+        Msg::ExportWorkspace => {
+            export_workspace(bctx);
+
+            false
+        }
+
+        Msg::ImportWorkspace(mode) => {
+            import_workspace(mode);
+
+            false
+        }
+
+        Msg::WorkspaceImported(incoming, mode) => {
+            import_save_state(bctx, incoming, mode);
 
             true
         }
 
-        Msg::SelectFromCollection(col_index, req_index) => {
-            bctx.col_current = vec![col_index, req_index];
+        Msg::StateRestored(Some(state)) => {
+            apply_save_state(bctx, state);
 
-            bctx.collections[col_index].requests[req_index]
-                .response
-                .request_index = req_index;
+            true
+        }
+
+        // a first run starts with the sample collection
+        Msg::StateRestored(None) => {
+            bctx.collections.push(sample_collection());
 
             true
         }
 
         Msg::Update => true,
 
-        Msg::SwitchPage(page) => {
-            bctx.page = page;
+        Msg::SetSidebarSort(sort) => {
+            bctx.sidebar_sort = sort;
 
             true
         }
     };
 
+    if modifies && should_render {
+        if let Some(request) = current_ref(bctx).and_then(|target| request_mut(bctx, &target)) {
+            request.modified_at = Some(now_ms());
+            request.modified = true;
+        }
+    }
+
+    // polling stops as soon as its request is no longer the one on screen
+    if let Some(repeat) = bctx.repeat {
+        let shown = current_ref(bctx).and_then(|target| request_ref(bctx, &target)).map(|req| req.id);
+
+        if shown != Some(repeat.request) || bctx.active_tab.is_none() {
+            stop_repeat(bctx);
+        }
+    }
+
     should_render
 }
+
+// messages that read or change the request shown in the editor
+fn edits_current_request(msg: &Msg) -> bool {
+    match msg {
+        Msg::Edit(_) => true,
+        Msg::Navigation(msg) => matches!(
+            msg,
+            NavigationMsg::ReqBodyPressed | NavigationMsg::ReqParamsPressed |
+            NavigationMsg::ReqHeadersPressed | NavigationMsg::ReqDocsPressed |
+            NavigationMsg::ReqSettingsPressed | NavigationMsg::ReqAuthPressed |
+            NavigationMsg::ReqTestsPressed | NavigationMsg::ReqPreviewPressed |
+            NavigationMsg::ToggleDocsPreview | NavigationMsg::RespBodyPressed |
+            NavigationMsg::RespHeadersPressed | NavigationMsg::RespSentPressed |
+            NavigationMsg::RespRawPressed | NavigationMsg::RespTlsPressed |
+            NavigationMsg::DismissContentType | NavigationMsg::DismissBodyNote |
+            NavigationMsg::BodyFormatChanged | NavigationMsg::ToggleJsonTree |
+            NavigationMsg::FilterRespHeaders(..) | NavigationMsg::ToggleSortRespHeaders |
+            NavigationMsg::ToggleJsonNode(..) | NavigationMsg::ToggleDiffView |
+            NavigationMsg::ToggleBase64Decode | NavigationMsg::ShowFullBody
+        ),
+        Msg::Send(msg) => matches!(
+            msg,
+            SendMsg::SendPressed | SendMsg::FetchToken | SendMsg::CheckHost | SendMsg::InspectTls |
+            SendMsg::DownloadBody | SendMsg::CopySnippet(..) | SendMsg::ExportHttpFile
+        ),
+        Msg::Collections(msg) => matches!(
+            msg,
+            CollectionsMsg::AddCollectionVariable | CollectionsMsg::RemoveCollectionVariable(..) |
+            CollectionsMsg::CollectionVariableChanged(..) | CollectionsMsg::ToggleSecretVariable(..) |
+            CollectionsMsg::BaseUrlChanged
+        ),
+        _ => false,
+    }
+}
+
+// the edits that count as changing the request, switching tabs or views doesn't
+fn modifies_current_request(msg: &Msg) -> bool {
+    matches!(msg, Msg::Edit(_))
+}
+
+
+
@@ -1 +1,2 @@
-pub mod style;
\ No newline at end of file
+pub mod style;
+pub mod palette;
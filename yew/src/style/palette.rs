@@ -0,0 +1,113 @@
+use crate::helpers::enums::Theme;
+use crate::utils::prefers_dark_scheme;
+
+// every color used by the stylesheets, exposed to them as css variables
+pub struct Palette {
+    background: &'static str,
+    panel: &'static str,
+    item: &'static str,
+    item_hover: &'static str,
+    select_arrow: &'static str,
+    text: &'static str,
+    border: &'static str,
+    accent: &'static str,
+    button: &'static str,
+    button_hover: &'static str,
+    error: &'static str,
+    resizer: &'static str,
+    diff_added: &'static str,
+    diff_removed: &'static str,
+}
+
+impl Palette {
+    pub fn dark() -> Self {
+        Palette {
+            background: "rgb(3, 7, 13)",
+            panel: "rgb(4, 12, 23)",
+            item: "rgb(7, 31, 56)",
+            item_hover: "rgb(23, 59, 97)",
+            select_arrow: "rgb(24, 97, 153)",
+            text: "white",
+            border: "gray",
+            accent: "orange",
+            button: "rgb(171, 113, 7)",
+            button_hover: "rgb(186, 123, 6)",
+            error: "rgb(247, 81, 62)",
+            resizer: "rgb(27, 28, 28)",
+            diff_added: "rgba(46, 160, 67, 0.25)",
+            diff_removed: "rgba(248, 81, 73, 0.25)",
+        }
+    }
+
+    pub fn light() -> Self {
+        Palette {
+            background: "rgb(255, 255, 255)",
+            panel: "rgb(241, 243, 246)",
+            item: "rgb(226, 232, 240)",
+            item_hover: "rgb(203, 213, 225)",
+            select_arrow: "rgb(120, 155, 200)",
+            text: "rgb(20, 24, 31)",
+            border: "rgb(170, 170, 170)",
+            accent: "rgb(222, 120, 0)",
+            button: "rgb(171, 113, 7)",
+            button_hover: "rgb(186, 123, 6)",
+            error: "rgb(200, 40, 30)",
+            resizer: "rgb(215, 215, 215)",
+            diff_added: "rgba(46, 160, 67, 0.18)",
+            diff_removed: "rgba(207, 34, 46, 0.15)",
+        }
+    }
+
+    pub fn for_theme(theme: Theme) -> Self {
+        if is_dark(theme) {
+            Palette::dark()
+        } else {
+            Palette::light()
+        }
+    }
+
+    pub fn css_variables(&self) -> String {
+        [
+            ("background", self.background),
+            ("panel", self.panel),
+            ("item", self.item),
+            ("item-hover", self.item_hover),
+            ("select-arrow", self.select_arrow),
+            ("text", self.text),
+            ("border", self.border),
+            ("accent", self.accent),
+            ("button", self.button),
+            ("button-hover", self.button_hover),
+            ("error", self.error),
+            ("resizer", self.resizer),
+            ("diff-added", self.diff_added),
+            ("diff-removed", self.diff_removed),
+        ]
+        .iter()
+        .map(|(name, value)| format!("--{name}: {value};"))
+        .collect::<Vec<String>>()
+        .join(" ")
+    }
+}
+
+pub fn is_dark(theme: Theme) -> bool {
+    match theme {
+        Theme::Dark => true,
+        Theme::Light => false,
+        Theme::System => prefers_dark_scheme(),
+    }
+}
+
+// inline style for the view root so the palette cascades to every panel
+pub fn theme_style(theme: Theme) -> String {
+    Palette::for_theme(theme).css_variables()
+}
+
+// class on the view root, lets the stylesheets match native controls to the theme
+pub fn theme_class(theme: Theme) -> &'static str {
+    if is_dark(theme) {
+        "theme-dark"
+    } else {
+        "theme-light"
+    }
+}
@@ -10,7 +10,7 @@ pub fn _get_styles() -> stylist::StyleSource {
 body {
 	cursor: default;
 	user-select: none;
-	color: white;
+	color: var(--text);
 	font-family: Arial, Helvetica, sans-serif;
 }
 
@@ -32,21 +32,21 @@ body {
 	display: flex;
 	justify-content: space-between;
 	align-items: center;
-	background-color: rgb(4, 12, 23);
+	background-color: var(--panel);
 	padding: 0 20px;
-	border-bottom: 0.5px solid gray;
+	border-bottom: 0.5px solid var(--border);
 }
 
 .sidebar1 {
 	width: 8vw;
-	background-color: rgb(4, 12, 23);
+	background-color: var(--panel);
 	height: 95vh;
-	border-right: 0.5px solid gray;
+	border-right: 0.5px solid var(--border);
 }
 
 .sidebar2 {
 	width: 100%;
-	background-color: rgb(4, 12, 23);
+	background-color: var(--panel);
 	height: 95vh;
 
 	overflow: scroll;
@@ -57,7 +57,7 @@ body {
 	flex-direction: column;
 	align-items: center;
 	justify-content: center;
-	background: rgb(7, 31, 56);
+	background: var(--item);
 	height: 50px;
 	font-size: 10px;
 	margin-bottom: 5px;
@@ -65,12 +65,12 @@ body {
 }
 
 .sidebaritem:hover {
-	 background: rgb(23, 59, 97);
+	 background: var(--item-hover);
 }
 
 .sidebaritem-selected {
-	border-left: 2px solid orange;
-	background: rgb(23, 59, 97);
+	border-left: 2px solid var(--accent);
+	background: var(--item-hover);
 }
 
 
@@ -79,16 +79,16 @@ body {
 	flex-direction: row;
 	align-items: center;
 	justify-content: space-between;
-	background: rgb(7, 31, 56);
+	background: var(--item);
 	height: 25px;
 	font-size: 12px;
 	padding: 5px;
 
-	border-bottom: 0.5px solid white;
+	border-bottom: 0.5px solid var(--text);
 }
 
 .sidebar2item:hover {
-	 background: rgb(23, 59, 97);
+	 background: var(--item-hover);
 }
 
 .sidebar2item-child {
@@ -96,30 +96,30 @@ body {
 	flex-direction: row;
 	align-items: center;
 	justify-content: space-between;
-	background: rgb(7, 31, 56);
+	background: var(--item);
 	height: 25px;
 	font-size: 12px;
 	padding: 5px;
 
-	border-bottom: 0.5px solid white;
-	border-left: 0.5px solid white;
+	border-bottom: 0.5px solid var(--text);
+	border-left: 0.5px solid var(--text);
 
 	margin-left: 15px;		
 }
 
 .sidebar2item:hover {
-	 background: rgb(23, 59, 97);
+	 background: var(--item-hover);
 }
 		
 .sidebar2item-selected {
-	background: rgb(23, 59, 97);
-	border-left: 2px solid orange;
+	background: var(--item-hover);
+	border-left: 2px solid var(--accent);
 }
 
 .content {
 	height: 95vh;
 	width: 80vw;
-	background-color: rgb(3, 7, 13);
+	background-color: var(--background);
 	display: flex;
 	flex-direction: column;
 	align-items: left;
@@ -138,8 +138,8 @@ body {
 	height: 5vh;
 	display: flex;
 	flex-direction: column;
-	background-color: rgb(4, 12, 23);
-	border-top: 0.5px solid gray;
+	background-color: var(--panel);
+	border-top: 0.5px solid var(--border);
 }
 
 .logo {
@@ -170,14 +170,14 @@ body {
 	width: 120px;
 	height: 40px;
   
-  border: 0.5px solid gray;
-	color: white;
+  border: 0.5px solid var(--border);
+	color: var(--text);
 	font-size: 15px;
  
   -webkit-appearance: none;
   -moz-appearance: none;
 
-	background: rgb(23, 59, 97);
+	background: var(--item-hover);
 	border-radius: 0px;
 
 	display: flex;
@@ -189,10 +189,10 @@ body {
 .urlinput {
 	height: 40px;
 	width: 60vw;
-	background: rgb(23, 59, 97);
-	color: white;
+	background: var(--item-hover);
+	color: var(--text);
 	font-size: 15px;
-	border: 0.5px solid gray;
+	border: 0.5px solid var(--border);
 }
 
 .urlinput:focus {
@@ -202,15 +202,15 @@ body {
 .sendbtn {
 	height: 40px;
 	width: 70px;
-	background: rgb(171, 113, 7);
+	background: var(--button);
 	border: 0px;
-	color: white;
+	color: var(--text);
 	margin-left: 5px;
 	font-size: 15px;
 }
 
 .sendbtn:hover {
-	 background: rgb(186, 123, 6);
+	 background: var(--button-hover);
 }
 
 
@@ -233,9 +233,9 @@ body {
 	
 	height: 200px;
 	
-	background-color: rgb(3, 7, 13);
-	border: 0.5px solid gray;
-	color: white;
+	background-color: var(--background);
+	border: 0.5px solid var(--border);
+	color: var(--text);
 	font-size: 15px;
 
 	overflow: scroll;
@@ -249,9 +249,9 @@ body {
 .reqbody {
 	width: 100%;
 	height: 70%;
-	background-color: rgb(3, 7, 13);
-	border: 0.5px solid gray;
-	color: white;
+	background-color: var(--background);
+	border: 0.5px solid var(--border);
+	color: var(--text);
 	font-size: 15px;
 }
 
@@ -284,11 +284,11 @@ body {
 }
 
 .tab:hover {
-	 background: rgb(23, 59, 97);
+	 background: var(--item-hover);
 }
 
 .tabSelected {
-  background: rgb(23, 59, 97);
+  background: var(--item-hover);
 }
 
 
@@ -340,9 +340,9 @@ td {
 .tableinput {
 	width: 100%;
 	background: none;
-	color: white;
+	color: var(--text);
 	font-size: 13px;
-	border: 0.5px solid gray;			
+	border: 0.5px solid var(--border);			
 }
 
 .tableinput:focus {
@@ -366,7 +366,7 @@ td {
   width: 5px;
   z-index: 2;
   cursor: col-resize;
-  background: rgb(27, 28, 28);
+  background: var(--resizer);
 }
 
 
@@ -382,7 +382,7 @@ td {
   height: 5px;
   z-index: 2;
   cursor: col-resize;
-  background: rgb(27, 28, 28);
+  background: var(--resizer);
 }
 
 .content {
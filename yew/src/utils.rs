@@ -1,16 +1,57 @@
 use crate::BoltContext;
 use crate::Method;
+use crate::{EditMsg, NavigationMsg, SendMsg};
 use crate::Msg;
+use crate::Page;
 use crate::Request;
+use crate::Response;
+use crate::HostCheck;
+use crate::TlsInspection;
+use crate::AuthConfig;
+use crate::SigningConfig;
+use crate::helpers::schema::{validate, Violation};
+use crate::helpers::dynamic::{layered_variables, resolve_dynamic, resolve_variables, unresolved_tokens};
+use crate::helpers::extract::{extract_all, header_value};
+use crate::helpers::url::{normalize_url, parse_query, query_string, validate_method, validate_url};
+use crate::helpers::i18n::{set_language, tr, tr_args};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use crate::helpers::signing::sign_request;
+use crate::RequestRef;
+use crate::RequestPreview;
 use crate::SaveState;
-use crate::GLOBAL_STATE;
+use crate::Settings;
+use crate::TlsConfig;
+use crate::WorkspaceFile;
+use crate::WORKSPACE_VERSION;
+use crate::helpers::enums::AuthMode;
+use crate::helpers::enums::ImportMode;
+use crate::helpers::enums::RetryPolicy;
+use crate::helpers::enums::SidebarSort;
+use crate::helpers::enums::RequestOrder;
+use crate::helpers::enums::Transport;
+use crate::helpers::enums::HttpVersion;
+use crate::helpers::enums::BodyFormat;
+use crate::process::tabs::{ensure_initial_tab, locate_request, prune_tabs};
+use crate::helpers::enums::Theme;
+use crate::style::palette::is_dark;
+use crate::ResponseType;
+use crate::LARGE_BODY_LIMIT;
+use crate::BODY_FILE_WARN_SIZE;
+use crate::RECENT_URL_LIMIT;
+use crate::{TrashedContent, TrashedItem, TRASH_LIMIT, TRASH_MAX_AGE_MS};
+use crate::Toast;
+use crate::helpers::enums::ToastLevel;
+use crate::SCROLL_SAVE_DELAY_MS;
+use crate::helpers::enums::{SaveStatus, ScrollPanel};
+use crate::global_state;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::closure::Closure;
-use wasm_bindgen::JsCast;
-use web_sys::{EventTarget, MouseEvent};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{EventTarget, KeyboardEvent, MouseEvent};
 
 use syntect::highlighting::ThemeSet;
-use syntect::highlighting::{Color, Theme};
+use syntect::highlighting::{Color, Theme as SyntectTheme};
 use syntect::html::highlighted_html_for_string;
 use syntect::parsing::SyntaxSet;
 
@@ -21,297 +62,3185 @@ pub fn _bolt_log(_log: &str) {
     web_sys::console::log_1(&wasm_bindgen::JsValue::from_str(_log));
 }
 
-pub fn invoke_send(request: &mut Request) {
-    #[derive(Debug, Serialize, Clone, Deserialize)]
-    pub struct SendPayload {
-        url: String,
-        method: Method,
-        body: String,
-        headers: Vec<Vec<String>>,
-        index: usize,
+#[derive(Debug, Serialize, Clone, Deserialize)]
+struct SendPayload {
+    url: String,
+    method: Method,
+    body: String,
+    headers: Vec<Vec<String>>,
+    index: usize,
+    proxy: String,
+    no_proxy: Vec<String>,
+    tls: TlsConfig,
+    retries: u8,
+    retry_on: RetryPolicy,
+    body_file: Option<String>,
+    timeout_ms: u64,
+    follow_redirects: bool,
+    user_agent: String,
+    digest_auth: Option<DigestCredentials>,
+    max_response_size: u64,
+    http_version: Option<HttpVersion>,
+    // decided here, the backend only ever sees the requests sent through it
+    #[serde(skip)]
+    transport: Transport,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+struct DigestCredentials {
+    username: String,
+    password: String,
+}
+
+fn send_payload(request: &Request, settings: &Settings, base_url: Option<&str>) -> SendPayload {
+    let proxy = if request.proxy.trim().is_empty() {
+        settings.proxy.clone()
+    } else {
+        request.proxy.clone()
+    };
+
+    SendPayload {
+        url: parse_url(join_base_url(base_url, &request.url), &request.params, request.raw_query),
+        method: request.method.clone(),
+        // the inline body stays around for when the file is cleared, but isn't sent
+        body: if request.body_file.is_some() { String::new() } else { request.body.clone() },
+        headers: outgoing_headers(request),
+        index: request.response.request_index,
+        proxy,
+        no_proxy: settings.no_proxy.clone(),
+        tls: request.tls.clone(),
+        retries: request.retries,
+        retry_on: request.retry_on,
+        body_file: request.body_file.clone(),
+        timeout_ms: settings.timeout_ms,
+        follow_redirects: settings.follow_redirects,
+        user_agent: effective_user_agent(request, settings),
+        max_response_size: settings.max_response_size,
+        http_version: request.http_version,
+        transport: request.transport,
+        digest_auth: match request.auth.mode {
+            AuthMode::Digest => Some(DigestCredentials {
+                username: request.auth.username.clone(),
+                password: request.auth.password.clone(),
+            }),
+            _ => None,
+        },
+    }
+}
+
+// empty sends none, the backend leaves the client without a default
+fn effective_user_agent(request: &Request, settings: &Settings) -> String {
+    if request.omit_user_agent {
+        String::new()
+    } else if request.user_agent.trim().is_empty() {
+        settings.user_agent.clone()
+    } else {
+        request.user_agent.clone()
+    }
+}
+
+// Err when the backend itself couldn't be reached, a failed request still comes back as Ok
+async fn post_payload(payload: &SendPayload) -> Result<String, String> {
+    let payload = serde_json::to_string(payload).map_err(|err| err.to_string())?;
+
+    let client = reqwest::Client::new();
+
+    let res = client
+        .post(BACKEND.to_string() + "send_request")
+        .body(payload)
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+
+    res.text().await.map_err(|err| err.to_string())
+}
+
+// the request straight from the webview, CORS and all. the browser decides which headers it may
+// set and which response headers the page gets to see, and bodies arrive already decoded. the
+// answer is shaped like the backend's so the rest of the ui can't tell the difference
+async fn fetch_in_browser(payload: &SendPayload) -> Response {
+    let mut response = Response::new();
+
+    response.request_index = payload.index;
+    response.via_browser = true;
+    response.sent_method = payload.method.to_string();
+    response.sent_url = payload.url.clone();
+    response.sent_headers = payload.headers.clone();
+    response.sent_body = payload.body.clone();
+
+    if payload.body_file.is_some() {
+        response.failed = true;
+        response.body = tr("transport.no_body_file");
+
+        return response;
+    }
+
+    let method = match reqwest::Method::from_bytes(payload.method.to_string().as_bytes()) {
+        Ok(method) => method,
+        Err(err) => {
+            response.failed = true;
+            response.body = err.to_string();
+
+            return response;
+        }
+    };
+
+    let mut request = reqwest::Client::new().request(method, &payload.url);
+
+    for header in payload.headers.iter() {
+        request = request.header(header[0].trim(), header[1].as_str());
+    }
+
+    if !payload.body.is_empty() {
+        request = request.body(payload.body.clone());
+    }
+
+    let start = now_ms() as f64;
+
+    let resp = match request.send().await {
+        Ok(resp) => resp,
+        Err(err) => {
+            response.failed = true;
+            response.body = tr_args("transport.fetch_failed", &[&err.to_string()]);
+
+            return response;
+        }
+    };
+
+    response.time_to_first_byte = (now_ms() as f64 - start) as u32;
+    response.status = resp.status().as_u16();
+    response.headers = resp
+        .headers()
+        .iter()
+        .map(|(name, value)| vec![name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()])
+        .collect();
+
+    let is_json = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().starts_with("application/json"));
+
+    let mut body = match resp.bytes().await {
+        Ok(body) => body.to_vec(),
+        Err(err) => {
+            response.failed = true;
+            response.body = format!("could not read the response body: {}", err);
+
+            return response;
+        }
+    };
+
+    response.time_total = (now_ms() as f64 - start) as u32;
+    response.time = response.time_total;
+
+    // fetch has no way to stop reading early, but the limit still keeps the ui responsive
+    if payload.max_response_size != 0 && body.len() as u64 > payload.max_response_size {
+        body.truncate(payload.max_response_size as usize);
+        response.truncated_at = Some(payload.max_response_size);
+    }
+
+    response.size = body.len() as u64;
+    response.encoded_size = response.size;
+    response.body = String::from_utf8_lossy(&body).into_owned();
+
+    if is_json {
+        response.response_type = ResponseType::JSON;
+    }
+
+    response
+}
+
+// the backend for Auto and Backend, the browser for BrowserFetch or when Auto can't reach the
+// backend. a forced Backend reports the unreachable backend instead
+async fn send_with_transport(payload: &SendPayload) -> Result<String, String> {
+    if payload.transport != Transport::BrowserFetch {
+        match post_payload(payload).await {
+            Ok(resp) => return Ok(resp),
+            Err(err) if payload.transport == Transport::Backend => return Err(err),
+            Err(err) => toast_later(ToastLevel::Info, tr_args("toast.transport_fallback", &[&err])),
+        }
+    }
+
+    let response = fetch_in_browser(payload).await;
+
+    serde_json::to_string(&response).map_err(|err| err.to_string())
+}
+
+// the headers a send actually carries: filled in rows plus the auth tab's token
+// headers the client works out from the body, the backend drops hand written ones as well
+const CLIENT_MANAGED_HEADERS: [&str; 2] = ["content-length", "transfer-encoding"];
+
+fn is_client_managed_header(name: &str) -> bool {
+    CLIENT_MANAGED_HEADERS.iter().any(|managed| name.trim().eq_ignore_ascii_case(managed))
+}
+
+// the request's headers the client sets itself, as the user named them
+pub fn dropped_headers(request: &Request) -> Vec<String> {
+    request
+        .headers
+        .iter()
+        .filter(|header| !header[0].is_empty() && !header[1].is_empty() && is_client_managed_header(&header[0]))
+        .map(|header| header[0].trim().to_string())
+        .collect()
+}
+
+pub fn outgoing_headers(request: &Request) -> Vec<Vec<String>> {
+    let mut headers: Vec<Vec<String>> = request
+        .headers
+        .iter()
+        .filter(|header| !header[0].is_empty() && !header[1].is_empty() && !is_client_managed_header(&header[0]))
+        .cloned()
+        .collect();
+
+    if request.auth.mode == AuthMode::OAuth2ClientCredentials && !request.auth.access_token.is_empty() {
+        // the auth tab wins over a hand written Authorization header
+        headers.retain(|header| !header[0].trim().eq_ignore_ascii_case("authorization"));
+
+        headers.push(vec![
+            "Authorization".to_string(),
+            format!("Bearer {}", request.auth.access_token),
+        ]);
+    }
+
+    if request.omit_user_agent {
+        headers.retain(|header| !header[0].trim().eq_ignore_ascii_case("user-agent"));
+    }
+
+    if request.conditional {
+        add_conditional_headers(&mut headers, request.etag.as_deref(), request.last_modified.as_deref());
+    }
+
+    headers
+}
+
+// If-None-Match and If-Modified-Since from the stored validators, a header of the same name the
+// user wrote stays the only one
+pub fn add_conditional_headers(headers: &mut Vec<Vec<String>>, etag: Option<&str>, last_modified: Option<&str>) {
+    for (name, value) in [("If-None-Match", etag), ("If-Modified-Since", last_modified)] {
+        let value = match value {
+            Some(value) if !value.trim().is_empty() => value,
+            _ => continue,
+        };
+
+        if headers.iter().any(|header| header[0].trim().eq_ignore_ascii_case(name)) {
+            continue;
+        }
+
+        headers.push(vec![name.to_string(), value.to_string()]);
+    }
+}
+
+// a full response replaces both validators, a 304 only refreshes the ones it came with
+pub fn store_validators(request: &mut Request, response: &Response) {
+    if response.failed {
+        return;
+    }
+
+    let etag = header_value(&response.headers, "etag");
+    let last_modified = header_value(&response.headers, "last-modified");
+
+    if response.status == 304 {
+        request.etag = etag.or(request.etag.take());
+        request.last_modified = last_modified.or(request.last_modified.take());
+    } else if (200..300).contains(&response.status) {
+        request.etag = etag;
+        request.last_modified = last_modified;
+    }
+}
+
+// gives a 304 the body of the response it confirmed, which may itself be a 304 showing one.
+// false when there is no such body and the 304 is shown as it came
+pub fn not_modified_fallback(response: &mut Response, previous: &Response) -> bool {
+    let has_body = previous.cached_body || (200..300).contains(&previous.status);
+
+    if response.status != 304 || previous.failed || !has_body {
+        return false;
+    }
+
+    response.body = previous.body.clone();
+    response.highlighted = previous.highlighted.clone();
+    response.response_type = previous.response_type;
+    response.size = previous.size;
+    response.truncated_at = previous.truncated_at;
+    response.cached_body = true;
+
+    true
+}
+
+// urls starting with "/" are relative to the collection's base url, anything else is used as is
+pub fn join_base_url(base_url: Option<&str>, url: &str) -> String {
+    let base_url = base_url.map(str::trim).unwrap_or_default();
+
+    // "//host/path" is protocol relative, not a path
+    if base_url.is_empty() || !url.starts_with('/') || url.starts_with("//") {
+        return url.to_string();
+    }
+
+    format!("{}{}", base_url.trim_end_matches('/'), url)
+}
+
+// a copy of the request carrying the url it is actually sent to, for exports
+pub fn with_base_url(request: &Request, base_url: Option<&str>) -> Request {
+    let mut request = request.clone();
+    request.url = join_base_url(base_url, &request.url);
+
+    request
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn copy_to_clipboard(text: &str) {
+    let window = web_sys::window().unwrap();
+
+    // navigator.clipboard is behind web-sys' unstable apis, so call it dynamically
+    let write_text = js_sys::Reflect::get(&window, &"navigator".into())
+        .and_then(|navigator| js_sys::Reflect::get(&navigator, &"clipboard".into()))
+        .and_then(|clipboard| {
+            js_sys::Reflect::get(&clipboard, &"writeText".into())
+                .map(|write_text| (clipboard, write_text))
+        });
+
+    match write_text {
+        Ok((clipboard, write_text)) if write_text.is_function() => {
+            let write_text: js_sys::Function = write_text.unchecked_into();
+            let _ = write_text.call1(&clipboard, &text.into());
+        }
+        _ => _bolt_log("clipboard is not available"),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn copy_to_clipboard(text: &str) {
+    record_effect(format!("copy {text}"));
+}
+
+pub fn invoke_send(
+    request: &mut Request,
+    settings: &Settings,
+    base_url: Option<&str>,
+    variables: &BTreeMap<String, String>,
+    target: RequestRef,
+) {
+    let payload = final_payload(request, settings, base_url, variables);
+    let invalid = validate_method(&payload.method)
+        .err()
+        .map(|err| tr_args("toast.invalid_method", &[&err.to_string()]))
+        .or_else(|| validate_url(&payload.url).err().map(|err| tr_args("toast.invalid_url", &[&err.to_string()])));
+    let id = request.id;
+
+    spawn("send", async move {
+        // still answered as a message, update holds the state while this runs
+        if let Some(err) = invalid {
+            dispatch_to_request(id, target, |target| Msg::Send(SendMsg::SendBlocked(target, err)));
+            return;
+        }
+
+        match send_with_transport(&payload).await {
+            Ok(resp) => dispatch_to_request(id, target, |target| Msg::Send(SendMsg::ReceivedResponse(target, resp))),
+            Err(err) => dispatch_to_request(id, target, |target| Msg::Send(SendMsg::SendFailed(target, err))),
+        }
+    });
+}
+
+// rows above the request may have been removed while it was in flight, so the answer goes to where
+// the request is now. a request that is gone isn't answered at all
+fn dispatch_to_request(id: u64, target: RequestRef, msg: impl FnOnce(RequestRef) -> Msg) {
+    let target = locate_request(&global_state().bctx, id, target);
+
+    if let Some(target) = target {
+        dispatch(msg(target));
+    }
+}
+
+// built the same way a send builds it, so the preview can't drift from what goes out
+pub fn preview_request(
+    request: &Request,
+    settings: &Settings,
+    base_url: Option<&str>,
+    variables: &BTreeMap<String, String>,
+) -> RequestPreview {
+    let payload = final_payload(request, settings, base_url, variables);
+
+    let mut unresolved = unresolved_tokens(&payload.url);
+    unresolved.extend(unresolved_tokens(&payload.body));
+    unresolved.extend(payload.headers.iter().flat_map(|header| unresolved_tokens(&header[1])));
+    unresolved.sort();
+    unresolved.dedup();
+
+    RequestPreview {
+        method: payload.method.to_string(),
+        url: payload.url,
+        headers: payload.headers,
+        body: match payload.body_file {
+            Some(path) => format!("< {path}"),
+            None => payload.body,
+        },
+        unresolved,
+    }
+}
+
+pub static SECRET_MASK: &str = "••••••";
+
+// hides every secret value in a text meant for the screen
+pub fn mask_secrets(text: &str, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |text, secret| text.replace(secret.as_str(), SECRET_MASK))
+}
+
+// the same for everything the preview shows
+pub fn mask_preview(preview: &mut RequestPreview, secrets: &[String]) {
+    preview.url = mask_secrets(&preview.url, secrets);
+    preview.body = mask_secrets(&preview.body, secrets);
+
+    for header in preview.headers.iter_mut() {
+        header[1] = mask_secrets(&header[1], secrets);
+    }
+}
+
+// the request the way a send would post it, for the code snippets: base url, variables and
+// {{$...}} tokens filled in, auth headers added and the params already in the url
+pub fn resolved_request(
+    request: &Request,
+    settings: &Settings,
+    base_url: Option<&str>,
+    variables: &BTreeMap<String, String>,
+) -> Request {
+    let payload = final_payload(request, settings, base_url, variables);
+
+    let mut resolved = request.clone();
+
+    resolved.method = payload.method;
+    resolved.url = payload.url;
+    resolved.params = crate::empty_row();
+    resolved.headers = payload.headers;
+    resolved.auth = AuthConfig::default();
+    resolved.signing = SigningConfig::default();
+
+    if resolved.body_file.is_none() {
+        resolved.body = payload.body;
+    }
+
+    resolved
+}
+
+// everything a send does to the request before posting it to the backend
+fn final_payload(
+    request: &Request,
+    settings: &Settings,
+    base_url: Option<&str>,
+    variables: &BTreeMap<String, String>,
+) -> SendPayload {
+    let mut payload = send_payload(request, settings, base_url);
+    // `variables` already carries the collection's, see collection_variables
+    resolve_payload_tokens(&mut payload, &layered_variables(variables, &[], &request.local_variables));
+    payload.url = normalize_url(&payload.url);
+
+    // signed last, so the signature covers the values that actually go out
+    let signature = sign_request(&request.signing, &payload.method.to_string(), &payload.url, &payload.body);
+
+    if let Some(signature) = signature {
+        let name = request.signing.header.trim();
+
+        payload.headers.retain(|header| !header[0].trim().eq_ignore_ascii_case(name));
+        payload.headers.push(vec![name.to_string(), signature]);
+    }
+
+    payload
+}
+
+// fills in captured {{variables}}, then {{$uuid}} and friends with one clock reading per send
+fn resolve_payload_tokens(payload: &mut SendPayload, variables: &BTreeMap<String, String>) {
+    let now = now_ms() as f64;
+    let resolve = |text: &str| resolve_dynamic(&resolve_variables(text, variables), now);
+
+    payload.url = resolve(&payload.url);
+    payload.body = resolve(&payload.body);
+
+    for header in payload.headers.iter_mut() {
+        header[1] = resolve(&header[1]);
+    }
+}
+
+// [variable, value] for each capture rule that matched the response
+pub fn capture_values(captures: &[Vec<String>], response: &Response) -> Vec<(String, String)> {
+    if response.failed {
+        return Vec::new();
+    }
+
+    // headers can still be captured from a body too large to parse
+    let body = if response.size > LARGE_BODY_LIMIT { "" } else { &response.body };
+
+    extract_all(captures, body, &response.headers)
+}
+
+pub fn get_collection_variable(index: usize) -> Vec<String> {
+    vec![
+        get_input_value(&format!("colvarname{index}")),
+        get_input_value(&format!("colvarvalue{index}")),
+    ]
+}
+
+pub fn get_local_variable(index: usize) -> Vec<String> {
+    vec![
+        get_input_value(&format!("localvarname{index}")),
+        get_input_value(&format!("localvarvalue{index}")),
+    ]
+}
+
+pub fn get_default_header(index: usize) -> Vec<String> {
+    vec![
+        get_input_value(&format!("defaultheaderkey{index}")),
+        get_input_value(&format!("defaultheadervalue{index}")),
+    ]
+}
+
+// the filled rows of the template plus the blank row new requests start with
+pub fn template_headers(settings: &Settings) -> Vec<Vec<String>> {
+    let mut headers: Vec<Vec<String>> = settings
+        .default_headers
+        .iter()
+        .filter(|header| !header[0].trim().is_empty())
+        .cloned()
+        .collect();
+
+    headers.push(vec![String::new(), String::new()]);
+
+    headers
+}
+
+// a comma separated list, as typed in the tag input or written in a .http file
+pub fn parse_tags(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| tag.to_string())
+        .collect()
+}
+
+// every tag used by the requests, sorted and without repeats
+pub fn all_tags<'a>(requests: impl Iterator<Item = &'a Request>) -> Vec<String> {
+    let mut tags: Vec<String> = requests.flat_map(|req| req.tags.iter().cloned()).collect();
+
+    tags.sort_by_key(|tag| tag.to_lowercase());
+    tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+    tags
+}
+
+pub fn matches_tag_filter(filter: &Option<String>, req: &Request) -> bool {
+    match filter {
+        Some(filter) => req.tags.iter().any(|tag| tag.eq_ignore_ascii_case(filter)),
+        None => true,
+    }
+}
+
+// (storage index, request) pairs in the order the sidebar shows them. the index travels along so
+// selecting and deleting still hit the right request, and the stored order is never touched so
+// Manual gets it back. requests missing the key (no timestamp, no host) go last, ties keep their
+// manual order
+pub fn sort_requests(requests: &mut [(usize, &Request)], sort: SidebarSort, base_url: Option<&str>) {
+    match sort {
+        SidebarSort::Manual => {}
+        SidebarSort::Alphabetical => requests.sort_by_cached_key(|(_, req)| req.name.to_lowercase()),
+        SidebarSort::ByMethod => requests.sort_by_key(|(_, req)| req.method.rank()),
+        SidebarSort::ByHost => requests.sort_by_cached_key(|(_, req)| {
+            let host = request_host(req, base_url);
+            (host.is_none(), host)
+        }),
+        SidebarSort::RecentlySent => requests.sort_by_key(|(_, req)| std::cmp::Reverse(req.last_sent_at)),
+        SidebarSort::RecentlyModified => requests.sort_by_key(|(_, req)| std::cmp::Reverse(req.modified_at)),
+    }
+}
+
+// the storage indices of the requests in their sorted order, ties keep the order they had.
+// method and url sorts fall back to the name so equal ones still come out tidy
+pub fn sorted_request_order(requests: &[Request], order: RequestOrder) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..requests.len()).collect();
+
+    indices.sort_by_cached_key(|index| {
+        let req = &requests[*index];
+        let name = req.name.to_lowercase();
+
+        match order {
+            RequestOrder::Name => (0, String::new(), name),
+            RequestOrder::Method => (req.method.rank(), String::new(), name),
+            RequestOrder::Url => (0, req.url.trim().to_lowercase(), name),
+        }
+    });
+
+    indices
+}
+
+// a sidebar heading and the requests under it, with their positions in the collection
+pub type RequestGroup<'a> = (Option<String>, Vec<(usize, &'a Request)>);
+
+// sorted requests split under a heading each for the grouping sorts, one group without a heading
+// for the others. None heads the requests without a host
+pub fn group_requests<'a>(
+    mut requests: Vec<(usize, &'a Request)>,
+    sort: SidebarSort,
+    base_url: Option<&str>,
+) -> Vec<RequestGroup<'a>> {
+    sort_requests(&mut requests, sort, base_url);
+
+    if !sort.groups() {
+        return vec![(None, requests)];
+    }
+
+    let heading = |req: &Request| match sort {
+        SidebarSort::ByMethod => Some(req.method.to_string()),
+        _ => request_host(req, base_url),
+    };
+
+    let mut groups: Vec<RequestGroup> = Vec::new();
+
+    for (index, req) in requests {
+        let heading = heading(req);
+
+        match groups.last_mut() {
+            Some((last, members)) if *last == heading => members.push((index, req)),
+            _ => groups.push((heading, vec![(index, req)])),
+        }
+    }
+
+    groups
+}
+
+// host[:port] the request goes to, lowercased. {{variables}} are kept as written, they only
+// have a value at send time
+pub fn request_host(req: &Request, base_url: Option<&str>) -> Option<String> {
+    let url = join_base_url(base_url, req.url.trim());
+    let rest = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_lowercase())
+    }
+}
+
+// epoch millis from the browser clock, or the system one outside of wasm
+pub fn now_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now() as u64
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64)
+    }
+}
+
+// date and time in the user's locale
+pub fn format_timestamp(ms: u64) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(ms as f64));
+
+    String::from(date.to_locale_string("default", &JsValue::UNDEFINED))
+}
+
+pub fn get_capture(index: usize) -> Vec<String> {
+    vec![
+        get_input_value(&format!("capturename{index}")),
+        get_input_value(&format!("capturepath{index}")),
+    ]
+}
+
+// requests a client credentials token through the backend, so the request's proxy and tls apply
+pub fn invoke_token_fetch(request: &Request, settings: &Settings, target: RequestRef, then_send: bool) {
+    let auth = &request.auth;
+
+    let mut form = vec![
+        ("grant_type", "client_credentials"),
+        ("client_id", auth.client_id.as_str()),
+        ("client_secret", auth.client_secret.as_str()),
+    ];
+
+    if !auth.scopes.trim().is_empty() {
+        form.push(("scope", auth.scopes.trim()));
+    }
+
+    let mut payload = send_payload(request, settings, None);
+
+    payload.url = auth.token_url.trim().to_string();
+    payload.method = Method::POST;
+    payload.body = form
+        .iter()
+        .map(|(key, value)| format!("{}={}", form_encode(key), form_encode(value)))
+        .collect::<Vec<String>>()
+        .join("&");
+    payload.headers = vec![
+        vec![
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        ],
+        vec!["Accept".to_string(), "application/json".to_string()],
+    ];
+
+    let id = request.id;
+
+    spawn("token_fetch", async move {
+        match post_payload(&payload).await {
+            Ok(resp) => dispatch_to_request(id, target, |target| Msg::Send(SendMsg::ReceivedToken(target, then_send, resp))),
+            Err(err) => dispatch_to_request(id, target, |target| Msg::Send(SendMsg::SendFailed(target, err))),
+        }
+    });
+}
+
+// percent encodes a value for an application/x-www-form-urlencoded body
+pub fn form_encode(value: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+// pulls the access token and its lifetime in seconds out of a token endpoint response
+pub fn parse_token_response(status: u16, failed: bool, body: &str) -> Result<(String, Option<f64>), String> {
+    let error = || {
+        if body.trim().is_empty() {
+            format!("token request failed with status {status}")
+        } else {
+            body.to_string()
+        }
+    };
+
+    if failed || !(200..300).contains(&status) {
+        return Err(error());
+    }
+
+    let json: serde_json::Value = serde_json::from_str(body).map_err(|_| error())?;
+
+    let token = match json.get("access_token").and_then(|token| token.as_str()) {
+        Some(token) if !token.is_empty() => token.to_string(),
+        _ => return Err(error()),
+    };
+
+    // some servers send expires_in as a string
+    let expires_in = json.get("expires_in").and_then(|value| {
+        value
+            .as_f64()
+            .or_else(|| value.as_str().and_then(|value| value.trim().parse().ok()))
+    });
+
+    Ok((token, expires_in))
+}
+
+pub fn build_save_state(bctx: &BoltContext) -> SaveState {
+    let mut state = SaveState {
+        page: bctx.page,
+        main_current: bctx.main_current,
+        col_current: bctx.col_current.clone(),
+
+        main_col: bctx.main_col.clone(),
+        collections: bctx.collections.clone(),
+
+        tabs: bctx.open_tabs.clone(),
+        active_tab: bctx.active_tab,
+
+        settings: bctx.settings.clone(),
+
+        theme: bctx.theme,
+
+        variables: bctx.variables.clone(),
+
+        layout: bctx.layout.clone(),
+
+        sidebar_sort: bctx.sidebar_sort,
+
+        trash: bctx.trash.clone(),
+
+        recent_urls: bctx.recent_urls.clone(),
+    };
+
+    strip_auth_secrets(&mut state);
+
+    state
+}
+
+// drops oauth, digest and signing secrets unless the user asked for them to be remembered
+fn strip_auth_secrets(state: &mut SaveState) {
+    let requests = state
+        .main_col
+        .requests
+        .iter_mut()
+        .chain(state.collections.iter_mut().flat_map(|col| col.requests.iter_mut()))
+        .chain(state.trash.iter_mut().flat_map(|item| item.requests_mut().iter_mut()));
+
+    for request in requests {
+        if !request.auth.remember_secret {
+            request.auth.client_secret = String::new();
+            request.auth.access_token = String::new();
+            request.auth.expires_at = None;
+            request.auth.password = String::new();
+
+            // the token also went out in the sent request
+            let responses = std::iter::once(&mut request.response).chain(request.last_response.as_mut());
+
+            for response in responses {
+                response.sent_headers.retain(|header| !header[0].eq_ignore_ascii_case("authorization"));
+                response.raw_request = without_raw_header(&response.raw_request, "authorization");
+            }
+        }
+
+        if !request.signing.remember_secret {
+            request.signing.secret = String::new();
+        }
+    }
+}
+
+// drops a header's lines from the head of a raw request, the body after the blank line is kept
+// as is even when a line of it looks like that header
+fn without_raw_header(raw: &str, name: &str) -> String {
+    let (head, body) = match raw.find("\r\n\r\n") {
+        Some(end) => raw.split_at(end + 2),
+        None => (raw, ""),
+    };
+
+    let head: String = head
+        .split_inclusive("\r\n")
+        .filter(|line| {
+            let header = line.split(':').next().unwrap_or_default();
+            !(line.contains(':') && header.trim().eq_ignore_ascii_case(name))
+        })
+        .collect();
+
+    head + body
+}
+
+// exports keep the names of secret variables but not their values, secret headers and params
+// are written as placeholders. what the responses recorded as sent had the values filled in, so
+// they get the placeholders there too
+fn strip_secret_variables(state: &mut SaveState) {
+    for collection in std::iter::once(&mut state.main_col).chain(state.collections.iter_mut()) {
+        let replacements: Vec<(String, String)> = collection
+            .variables
+            .iter()
+            .filter(|row| collection.secret_variables.contains(row[0].trim()) && !row[1].is_empty())
+            .map(|row| (row[1].clone(), format!("{{{{{}}}}}", row[0].trim())))
+            .collect();
+
+        for row in collection.variables.iter_mut() {
+            if collection.secret_variables.contains(row[0].trim()) {
+                row[1] = String::new();
+            }
+        }
+
+        for request in collection.requests.iter_mut() {
+            strip_secret_rows(request);
+            strip_sent_values(request, &replacements);
+        }
+    }
+}
+
+pub fn is_secret_header(request: &Request, name: &str) -> bool {
+    request.secret_headers.contains(&name.trim().to_lowercase())
+}
+
+pub fn is_secret_param(request: &Request, name: &str) -> bool {
+    request.secret_params.contains(name.trim())
+}
+
+// the values of the request's secret headers and params, each with the {{name}} placeholder
+// exports write in its place
+pub fn secret_rows(request: &Request) -> Vec<(String, String)> {
+    let headers = request.headers.iter().filter(|row| is_secret_header(request, &row[0]));
+    let params = request.params.iter().filter(|row| is_secret_param(request, &row[0]));
+
+    headers
+        .chain(params)
+        .filter(|row| !row[1].is_empty())
+        .map(|row| (row[1].clone(), format!("{{{{{}}}}}", row[0].trim())))
+        .collect()
+}
+
+// swaps every secret header and param value for its placeholder, in the rows and in what the
+// responses recorded as sent. importing the result back gives the placeholders, not the values
+pub fn strip_secret_rows(request: &mut Request) {
+    let replacements = secret_rows(request);
+
+    if replacements.is_empty() {
+        return;
+    }
+
+    let secret_headers = &request.secret_headers;
+    let secret_params = &request.secret_params;
+
+    let headers = request.headers.iter_mut().filter(|row| secret_headers.contains(&row[0].trim().to_lowercase()));
+    let params = request.params.iter_mut().filter(|row| secret_params.contains(row[0].trim()));
+
+    for row in headers.chain(params).filter(|row| !row[1].is_empty()) {
+        row[1] = format!("{{{{{}}}}}", row[0].trim());
+    }
+
+    strip_sent_values(request, &replacements);
+}
+
+// swaps each (value, placeholder) in what the request's responses recorded as sent
+fn strip_sent_values(request: &mut Request, replacements: &[(String, String)]) {
+    if replacements.is_empty() {
+        return;
+    }
+
+    let strip = |text: &str| {
+        replacements
+            .iter()
+            .fold(text.to_string(), |text, (value, placeholder)| text.replace(value.as_str(), placeholder))
+    };
+
+    let responses = std::iter::once(&mut request.response).chain(request.last_response.as_mut());
+
+    for response in responses {
+        response.sent_url = strip(&response.sent_url);
+        response.sent_body = strip(&response.sent_body);
+        response.raw_request = strip(&response.raw_request);
+
+        for header in response.sent_headers.iter_mut() {
+            header[1] = strip(&header[1]);
+        }
+    }
+}
+
+// a request as it was before it was ever sent
+pub fn clear_responses(request: &mut Request) {
+    request.response = Response::new();
+    request.last_response = None;
+    request.status_log.clear();
+    request.time_history.clear();
+    request.resp_scroll = 0;
+    request.loading = false;
+    request.auth.fetching = false;
+}
+
+// responses are dropped on the way in, they are what makes a trashed request heavy to keep
+pub fn move_to_trash(bctx: &mut BoltContext, mut content: TrashedContent) {
+    match &mut content {
+        TrashedContent::Request(_, request) => clear_responses(request),
+        TrashedContent::Collection(collection) => collection.requests.iter_mut().for_each(clear_responses),
+    }
+
+    bctx.trash.push(TrashedItem {
+        deleted_at: now_ms() as f64,
+        content,
+    });
+
+    if bctx.trash.len() > TRASH_LIMIT {
+        bctx.trash.remove(0);
+    }
+}
+
+// drops what is older than TRASH_MAX_AGE_MS, then the oldest items past TRASH_LIMIT
+pub fn purge_trash(trash: &mut Vec<TrashedItem>, now: f64) {
+    trash.retain(|item| now - item.deleted_at <= TRASH_MAX_AGE_MS);
+
+    if trash.len() > TRASH_LIMIT {
+        trash.drain(..trash.len() - TRASH_LIMIT);
+    }
+}
+
+// random rather than counted, ids from a saved state or another workspace must not meet a fresh one
+#[cfg(target_arch = "wasm32")]
+pub fn new_id() -> u64 {
+    // 53 bits, what an f64 holds exactly
+    let id = (js_sys::Math::random() * 9007199254740992.0) as u64;
+
+    id.max(1)
+}
+
+// the tests run natively, where js_sys can't be called. nothing there loads a saved state
+#[cfg(not(target_arch = "wasm32"))]
+pub fn new_id() -> u64 {
+    static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+    NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+// the same goes for the window and the backend. natively the fields read from the page come
+// from FIELDS, prompts are answered from ANSWERS and whatever would have gone out to the
+// browser or the backend is written to EFFECTS, so the reducers can be tested message by message
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    pub static FIELDS: RefCell<std::collections::HashMap<String, String>> = RefCell::new(std::collections::HashMap::new());
+    pub static ANSWERS: RefCell<std::collections::VecDeque<Option<String>>> = const { RefCell::new(std::collections::VecDeque::new()) };
+    pub static EFFECTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn record_effect(effect: String) {
+    EFFECTS.with(|effects| effects.borrow_mut().push(effect));
+}
+
+// what went out since the last call
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub fn take_effects() -> Vec<String> {
+    EFFECTS.with(|effects| std::mem::take(&mut *effects.borrow_mut()))
+}
+
+// runs a request to the backend or the browser in the background, `label` is what the tests see
+#[cfg(target_arch = "wasm32")]
+pub fn spawn(_label: &str, future: impl std::future::Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn(label: &str, _future: impl std::future::Future<Output = ()> + 'static) {
+    record_effect(label.to_string());
+}
+
+// states saved before ids existed load with 0 everywhere, copies and merges can bring duplicates
+pub fn ensure_ids(bctx: &mut BoltContext) {
+    let mut seen = HashSet::new();
+
+    for collection in std::iter::once(&mut bctx.main_col).chain(bctx.collections.iter_mut()) {
+        if collection.id == 0 || !seen.insert(collection.id) {
+            collection.id = new_id();
+            seen.insert(collection.id);
+        }
+
+        for request in collection.requests.iter_mut() {
+            if request.id == 0 || !seen.insert(request.id) {
+                request.id = new_id();
+                seen.insert(request.id);
+            }
+        }
+    }
+}
+
+// the backend echoes request_index back, it has to follow the request when rows move
+pub fn sync_request_indices(requests: &mut [Request]) {
+    for (index, request) in requests.iter_mut().enumerate() {
+        request.response.request_index = index;
+    }
+}
+
+pub fn request_position(requests: &[Request], id: u64) -> Option<usize> {
+    requests.iter().position(|req| req.id == id)
+}
+
+pub fn collection_position(bctx: &BoltContext, id: u64) -> Option<usize> {
+    bctx.collections.iter().position(|col| col.id == id)
+}
+
+// where a collection request sits right now, None once either of them is gone
+pub fn collection_request_position(bctx: &BoltContext, col_id: u64, req_id: u64) -> Option<(usize, usize)> {
+    let col_index = collection_position(bctx, col_id)?;
+    let req_index = request_position(&bctx.collections[col_index].requests, req_id)?;
+
+    Some((col_index, req_index))
+}
+
+pub fn apply_save_state(bctx: &mut BoltContext, new_state: SaveState) {
+    bctx.main_col = new_state.main_col;
+    bctx.collections = new_state.collections;
+    ensure_ids(bctx);
+
+    // a hand edited file could have anything here, and col_current is indexed straight away
+    bctx.col_current = if new_state.col_current.len() == 2 { new_state.col_current } else { vec![0, 0] };
+    bctx.main_current = new_state.main_current;
+
+    bctx.page = new_state.page;
+
+    bctx.settings = new_state.settings;
+    set_language(bctx.settings.language);
+
+    bctx.theme = new_state.theme;
+
+    bctx.variables = new_state.variables;
+
+    bctx.layout = new_state.layout.clamped();
+    bctx.sidebar_sort = new_state.sidebar_sort;
+
+    bctx.trash = new_state.trash;
+    purge_trash(&mut bctx.trash, now_ms() as f64);
+
+    bctx.recent_urls = new_state.recent_urls;
+    bctx.recent_urls.truncate(RECENT_URL_LIMIT);
+
+    // ensure_ids may have renumbered a request an old tab pointed at
+    bctx.open_tabs = new_state.tabs;
+    bctx.active_tab = new_state.active_tab.filter(|index| *index < bctx.open_tabs.len());
+    prune_tabs(bctx);
+    ensure_initial_tab(bctx);
+
+    bctx.restore_scroll = true;
+}
+
+// drops response bodies but keeps status, time, size and headers
+fn strip_response_bodies(state: &mut SaveState) {
+    let requests = state
+        .main_col
+        .requests
+        .iter_mut()
+        .chain(state.collections.iter_mut().flat_map(|col| col.requests.iter_mut()));
+
+    for request in requests {
+        request.response.body = String::new();
+        request.response.highlighted = String::new();
+        request.response.sent_body = String::new();
+        request.response.raw_request = String::new();
+        request.response.raw_response = String::new();
+        request.last_response = None;
+    }
+}
+
+fn serialize_state(bctx: &BoltContext) -> String {
+    let mut save_state = build_save_state(bctx);
+
+    if !bctx.settings.persist_responses {
+        strip_response_bodies(&mut save_state);
+    }
+
+    serde_json::to_string(&save_state).unwrap()
+}
+
+// answers with Msg::SaveFinished once the backend has written the file
+pub fn save_state(bctx: &mut BoltContext) {
+    let save = serialize_state(bctx);
+
+    bctx.save_generation = bctx.save_generation.wrapping_add(1);
+    bctx.save_status = SaveStatus::Saving;
+
+    let generation = bctx.save_generation;
+
+    spawn("save_state", async move {
+        let client = reqwest::Client::new();
+
+        let result = match client.post(BACKEND.to_string() + "save_state").body(save).send().await {
+            Ok(res) if res.status().is_success() => Ok(()),
+            Ok(res) => Err(res.text().await.unwrap_or_default()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        let state = global_state();
+        let link = state.bctx.link.as_ref().unwrap();
+
+        link.send_message(Msg::SaveFinished(generation, result));
+    });
+}
+
+// the page can go away before a pending save answers: leaving hands the latest state to the
+// browser to deliver, and asks first when the last save failed. the desktop window sends
+// bolt://close-requested instead and waits for close_window
+pub fn guard_unsaved_changes() {
+    let window = web_sys::window().unwrap();
+
+    let before_unload = Closure::wrap(Box::new(move |event: web_sys::BeforeUnloadEvent| {
+        let state = global_state();
+
+        if state.bctx.save_status == SaveStatus::Saved {
+            return;
+        }
+
+        if let Some(window) = web_sys::window() {
+            let _ = window
+                .navigator()
+                .send_beacon_with_opt_str(&(BACKEND.to_string() + "save_state"), Some(&serialize_state(&state.bctx)));
+        }
+
+        if matches!(state.bctx.save_status, SaveStatus::Failed(_)) {
+            event.prevent_default();
+            event.set_return_value(&tr("save.leave_unsaved"));
+        }
+    }) as Box<dyn FnMut(_)>);
+
+    let _ = EventTarget::from(window.clone())
+        .add_event_listener_with_callback("beforeunload", before_unload.as_ref().unchecked_ref());
+    before_unload.forget();
+
+    let Some(event_api) = tauri_api(&["event", "listen"]) else {
+        return;
+    };
+
+    let close_requested = Closure::wrap(Box::new(move |_: JsValue| {
+        let state = global_state();
+        let link = state.bctx.link.as_ref().unwrap();
+
+        link.send_message(Msg::CloseRequested);
+    }) as Box<dyn FnMut(JsValue)>);
+
+    let _ = event_api.1.call2(
+        &event_api.0,
+        &JsValue::from_str("bolt://close-requested"),
+        close_requested.as_ref().unchecked_ref(),
+    );
+    close_requested.forget();
+}
+
+// closes the desktop window, see guard_unsaved_changes
+pub fn close_window() {
+    if let Some((tauri, invoke)) = tauri_api(&["invoke"]) {
+        let _ = invoke.call1(&tauri, &JsValue::from_str("close_window"));
+    }
+}
+
+// a function of window.__TAURI__ with the object it belongs to, None outside the desktop app
+fn tauri_api(path: &[&str]) -> Option<(JsValue, js_sys::Function)> {
+    let mut parent = JsValue::from(web_sys::window()?);
+    let mut value = js_sys::Reflect::get(&parent, &JsValue::from_str("__TAURI__")).ok()?;
+
+    for key in path {
+        if value.is_undefined() {
+            return None;
+        }
+
+        parent = value;
+        value = js_sys::Reflect::get(&parent, &JsValue::from_str(key)).ok()?;
+    }
+
+    value.dyn_into::<js_sys::Function>().ok().map(|function| (parent, function))
+}
+
+fn set_save_state(state: String) {
+    // nothing saved yet, a first run starts with the sample collection
+    let restored = if state.trim().is_empty() {
+        None
+    } else {
+        match serde_json::from_str(&state) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                toast_later(ToastLevel::Error, tr_args("toast.restore_failed", &[&err.to_string()]));
+                return;
+            }
+        }
+    };
+
+    dispatch(Msg::StateRestored(restored));
+}
+
+pub fn restore_state() {
+    spawn("restore_state", async move {
+        let client = reqwest::Client::new();
+
+        let resp = match client.post(BACKEND.to_string() + "restore_state").send().await {
+            Ok(res) => res.text().await.map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        match resp {
+            Ok(resp) => set_save_state(resp),
+            Err(err) => toast_later(ToastLevel::Error, tr_args("toast.restore_failed", &[&err])),
+        }
+    });
+}
+
+pub fn open_link(link: String) {
+    let _link = link.clone();
+
+    spawn("open_link", async move {
+        let client = reqwest::Client::new();
+
+        let res = client
+            .post(BACKEND.to_string() + "open_link")
+            .body(_link)
+            .send()
+            .await
+            .expect("open_link failed");
+
+        let _resp = res.text().await.unwrap();
+    });
+}
+
+pub fn bolt_panic(log: &str) {
+    #[derive(Serialize, Deserialize)]
+    struct Payload<'a> {
+        log: &'a str,
+    }
+
+    let log = log.to_string();
+
+    panic!("{}", log);
+}
+
+pub fn _set_html(id: &str, content: String) {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+
+    div.set_inner_html(&content);
+}
+
+pub fn _set_focus(id: &str) {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+
+    let div = div.dyn_into::<web_sys::HtmlElement>().unwrap();
+
+    div.focus().unwrap();
+}
+
+pub fn get_method() -> Method {
+    match get_select_value("methodselect").as_str() {
+        "get" => Method::GET,
+        "post" => Method::POST,
+        "put" => Method::PUT,
+        "delete" => Method::DELETE,
+        "head" => Method::HEAD,
+        "patch" => Method::PATCH,
+        "options" => Method::OPTIONS,
+        "connect" => Method::CONNECT,
+        "copy" => Method::COPY,
+        // the name field only exists once "Other…" was picked, it starts out empty
+        "other" => Method::Custom(find_input_value("methodinput").unwrap_or_default().trim().to_string()),
+
+        _ => {
+            bolt_panic("invalid method");
+
+            Method::GET
+        }
+    }
+}
+
+pub fn get_url() -> String {
+    get_input_value("urlinput")
+}
+
+// the dom counts caret positions in utf-16 units, rust strings in bytes
+pub fn utf16_to_byte(text: &str, offset: u32) -> usize {
+    let mut units = 0;
+
+    for (index, c) in text.char_indices() {
+        if units >= offset as usize {
+            return index;
+        }
+
+        units += c.len_utf16();
+    }
+
+    text.len()
+}
+
+pub fn byte_to_utf16(text: &str, index: usize) -> u32 {
+    text[..index].encode_utf16().count() as u32
+}
+
+pub fn get_body() -> String {
+    get_textarea_value("reqbody")
+}
+
+// a guess at the body's media type, only for the formats that are easy to tell apart
+pub fn detect_content_type(body: &str) -> Option<&'static str> {
+    let body = body.trim();
+
+    if body.is_empty() {
+        return None;
+    }
+
+    if (body.starts_with('{') || body.starts_with('[')) && serde_json::from_str::<serde_json::Value>(body).is_ok() {
+        return Some("application/json");
+    }
+
+    if body.starts_with('<') && body.ends_with('>') {
+        return Some("application/xml");
+    }
+
+    let is_form = !body.contains(char::is_whitespace)
+        && body.split('&').all(|pair| matches!(pair.split_once('='), Some((key, _)) if !key.is_empty()));
+
+    if is_form {
+        return Some("application/x-www-form-urlencoded");
+    }
+
+    None
+}
+
+pub fn has_header(headers: &[Vec<String>], name: &str) -> bool {
+    headers
+        .iter()
+        .any(|header| header[0].trim().eq_ignore_ascii_case(name) && !header[1].trim().is_empty())
+}
+
+pub fn get_description() -> String {
+    get_textarea_value("reqdescription")
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn get_textarea_value(id: &str) -> String {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+
+    div.dyn_into::<web_sys::HtmlTextAreaElement>()
+        .unwrap()
+        .value()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_textarea_value(id: &str) -> String {
+    get_input_value(id)
+}
+
+// runs the request's schema over a response, problems with the schema itself come back as violations
+pub fn check_schema(schema: &Option<String>, response: &Response) -> Option<Vec<Violation>> {
+    let schema = schema.as_ref().filter(|schema| !schema.trim().is_empty())?;
+
+    if response.failed || response.size > LARGE_BODY_LIMIT {
+        return None;
+    }
+
+    let invalid = |message: String| {
+        Some(vec![Violation {
+            pointer: String::new(),
+            message,
+        }])
+    };
+
+    let schema: serde_json::Value = match serde_json::from_str(schema) {
+        Ok(schema) => schema,
+        Err(err) => return invalid(format!("the schema is not valid JSON: {err}")),
+    };
+
+    let body: serde_json::Value = match serde_json::from_str(&response.body) {
+        Ok(body) => body,
+        Err(_) => return invalid("the response body is not JSON".to_string()),
+    };
+
+    Some(validate(&schema, &body))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn get_input_value(id: &str) -> String {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+
+    div.dyn_into::<web_sys::HtmlInputElement>().unwrap().value()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_input_value(id: &str) -> String {
+    find_input_value(id).unwrap_or_default()
+}
+
+// None when the input isn't on the page
+#[cfg(target_arch = "wasm32")]
+pub fn find_input_value(id: &str) -> Option<String> {
+    let input = web_sys::window()?
+        .document()?
+        .get_element_by_id(id)?
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .ok()?;
+
+    Some(input.value())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn find_input_value(id: &str) -> Option<String> {
+    FIELDS.with(|fields| fields.borrow().get(id).cloned())
+}
+
+// for inputs the view leaves uncontrolled, like the one tags are typed into
+#[cfg(target_arch = "wasm32")]
+pub fn set_input_value(id: &str, value: &str) {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    if let Some(input) = doc
+        .get_element_by_id(id)
+        .and_then(|element| element.dyn_into::<web_sys::HtmlInputElement>().ok())
+    {
+        input.set_value(value);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_input_value(id: &str, value: &str) {
+    FIELDS.with(|fields| fields.borrow_mut().insert(id.to_string(), value.to_string()));
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn get_select_value(id: &str) -> String {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+
+    div.dyn_into::<web_sys::HtmlSelectElement>().unwrap().value()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_select_value(id: &str) -> String {
+    get_input_value(id)
+}
+
+// None for an empty input so unset paths aren't sent as ""
+pub fn get_optional_input(id: &str) -> Option<String> {
+    let value = get_input_value(id).trim().to_string();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+// splits a comma or whitespace separated host list
+pub fn parse_host_list(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|host| !host.is_empty())
+        .map(|host| host.to_string())
+        .collect()
+}
+
+// adds a toast, one that is already showing is not repeated
+pub fn push_toast(bctx: &mut BoltContext, level: ToastLevel, text: String) {
+    if bctx.toasts.iter().any(|toast| toast.level == level && toast.text == text) {
+        return;
+    }
+
+    bctx.toast_counter = bctx.toast_counter.wrapping_add(1);
+
+    let id = bctx.toast_counter;
+
+    bctx.toasts.push(Toast {
+        id,
+        level,
+        text,
+        created: now_ms() as f64,
+    });
+
+    if level != ToastLevel::Error {
+        schedule_toast_dismiss(id);
+    }
+}
+
+// for code that doesn't hold the state, like a future that just finished
+pub fn toast_later(level: ToastLevel, text: String) {
+    let link = global_state().bctx.link.clone();
+
+    match link {
+        Some(link) => link.send_message(Msg::ShowToast(level, text)),
+        None => _bolt_log(&text),
+    }
+}
+
+// hands a message to the app from outside update. the state is only locked long enough to
+// copy the link, the message itself is handled when update gets to it
+pub fn dispatch(msg: Msg) {
+    let link = global_state().bctx.link.clone();
+
+    if let Some(link) = link {
+        link.send_message(msg);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn schedule_toast_dismiss(id: u32) {
+    let window = web_sys::window().unwrap();
+
+    let dismiss = Closure::once(move || {
+        let state = global_state();
+        let link = state.bctx.link.as_ref().unwrap();
+
+        link.send_message(Msg::DismissToast(id));
+    });
+
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            dismiss.as_ref().unchecked_ref(),
+            crate::TOAST_TIMEOUT_MS,
+        )
+        .unwrap();
+
+    dismiss.forget();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn schedule_toast_dismiss(_id: u32) {}
+
+#[cfg(target_arch = "wasm32")]
+pub fn schedule_undo_expiry(generation: u32) {
+    let window = web_sys::window().unwrap();
+
+    let expire = Closure::once(move || {
+        let state = global_state();
+        let link = state.bctx.link.as_ref().unwrap();
+
+        link.send_message(Msg::Collections(crate::CollectionsMsg::ExpireUndo(generation)));
+    });
+
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            expire.as_ref().unchecked_ref(),
+            crate::UNDO_TIMEOUT_MS,
+        )
+        .unwrap();
+
+    expire.forget();
+}
+
+// no timers natively, the tests expire the undo themselves
+#[cfg(not(target_arch = "wasm32"))]
+pub fn schedule_undo_expiry(_generation: u32) {}
+
+// fires Msg::Send(SendMsg::RepeatTick) every `interval_ms`, returns the id for clear_repeat
+#[cfg(target_arch = "wasm32")]
+pub fn start_repeat_timer(interval_ms: u32) -> i32 {
+    let window = web_sys::window().unwrap();
+
+    let tick = Closure::wrap(Box::new(move || {
+        let state = global_state();
+        let link = state.bctx.link.as_ref().unwrap();
+
+        link.send_message(Msg::Send(SendMsg::RepeatTick));
+    }) as Box<dyn FnMut()>);
+
+    let handle = window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            tick.as_ref().unchecked_ref(),
+            interval_ms as i32,
+        )
+        .unwrap();
+
+    tick.forget();
+
+    handle
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn start_repeat_timer(interval_ms: u32) -> i32 {
+    record_effect(format!("repeat every {interval_ms} ms"));
+
+    0
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn stop_repeat_timer(handle: i32) {
+    let window = web_sys::window().unwrap();
+
+    window.clear_interval_with_handle(handle);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn stop_repeat_timer(_handle: i32) {
+    record_effect("stop repeating".to_string());
+}
+
+pub fn get_scroll_top(id: &str) -> i32 {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    match web_sys::Document::get_element_by_id(&doc, id) {
+        Some(element) => element.scroll_top(),
+        None => 0,
+    }
+}
+
+// puts the shown request's panels back where they were left
+pub fn restore_panels(bctx: &BoltContext) {
+    let request = if bctx.page == Page::Home {
+        bctx.main_col.requests.get(bctx.main_current)
+    } else {
+        bctx.collections
+            .get(bctx.col_current[0])
+            .and_then(|col| col.requests.get(bctx.col_current[1]))
+    };
+
+    let Some(request) = request else {
+        return;
+    };
+
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    if let Some(element) = doc.get_element_by_id(ScrollPanel::ResponseBody.element_id()) {
+        element.set_scroll_top(request.resp_scroll);
+    }
+
+    let body = doc
+        .get_element_by_id(ScrollPanel::RequestBody.element_id())
+        .and_then(|element| element.dyn_into::<web_sys::HtmlTextAreaElement>().ok());
+
+    if let Some(body) = body {
+        // moving the caret scrolls to it, so the offset goes last
+        if let Some((start, end)) = request.body_selection {
+            let _ = body.set_selection_range(start, end);
+        }
+
+        body.set_scroll_top(request.body_scroll);
+    }
+}
+
+thread_local! {
+    // pending save per ScrollPanel, restarted by every scroll event
+    static SCROLL_TIMERS: RefCell<[Option<i32>; 2]> = const { RefCell::new([None, None]) };
+}
+
+// sends Msg::Navigation(NavigationMsg::PanelScrolled) once the panel has been still for SCROLL_SAVE_DELAY_MS
+pub fn save_scroll_later(target: RequestRef, panel: ScrollPanel) {
+    let window = web_sys::window().unwrap();
+
+    // read now, by the time the timer fires the panel may show another request
+    let offset = get_scroll_top(panel.element_id());
+
+    let save = Closure::once(move || {
+        SCROLL_TIMERS.with(|timers| timers.borrow_mut()[panel as usize] = None);
+
+        let state = global_state();
+        let link = state.bctx.link.as_ref().unwrap();
+
+        link.send_message(Msg::Navigation(NavigationMsg::PanelScrolled(target, panel, offset)));
+    });
+
+    let handle = window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            save.as_ref().unchecked_ref(),
+            SCROLL_SAVE_DELAY_MS,
+        )
+        .unwrap();
+
+    save.forget();
+
+    let previous = SCROLL_TIMERS.with(|timers| timers.borrow_mut()[panel as usize].replace(handle));
+
+    if let Some(previous) = previous {
+        window.clear_timeout_with_handle(previous);
+    }
+}
+
+// None when the body editor isn't shown
+pub fn get_body_selection() -> Option<(u32, u32)> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    let body = doc
+        .get_element_by_id(ScrollPanel::RequestBody.element_id())?
+        .dyn_into::<web_sys::HtmlTextAreaElement>()
+        .ok()?;
+
+    Some((body.selection_start().ok()??, body.selection_end().ok()??))
+}
+
+pub fn get_header(index: usize) -> Vec<String> {
+    vec![
+        get_input_value(&format!("headerkey{index}")),
+        get_input_value(&format!("headervalue{index}")),
+    ]
+}
+
+pub fn get_param(index: usize) -> Vec<String> {
+    vec![
+        get_input_value(&format!("paramkey{index}")),
+        get_input_value(&format!("paramvalue{index}")),
+    ]
+}
+
+// used after a render, when the element the focus belongs on exists
+pub fn focus_element(id: &str) {
+    let element = web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|doc| doc.get_element_by_id(id))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+
+    if let Some(element) = element {
+        let _ = element.focus();
+    }
+}
+
+// Enter and Space press the icon buttons, which are divs with role="button"
+pub fn enable_button_keys() {
+    if let Some(document) = web_sys::window().and_then(|win| win.document()) {
+        let listener = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+            if event.key() != "Enter" && event.key() != " " {
+                return;
+            }
+
+            let target = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok());
+
+            if let Some(target) = target {
+                if target.get_attribute("role").as_deref() == Some("button") {
+                    event.prevent_default();
+                    target.click();
+                }
+            }
+        }) as Box<dyn FnMut(_)>);
+
+        let _ = EventTarget::from(document)
+            .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+        listener.forget();
+    }
+}
+
+// where the arrow keys move in a strip of count tabs, None for other keys. right to left
+// layouts show the strip mirrored, so the arrows swap
+pub fn tab_key_target(key: &str, position: usize, count: usize, rtl: bool) -> Option<usize> {
+    let (next, previous) = if rtl { ("ArrowLeft", "ArrowRight") } else { ("ArrowRight", "ArrowLeft") };
+
+    match key {
+        _ if key == next => Some((position + 1) % count),
+        _ if key == previous => Some((position + count - 1) % count),
+        "Home" => Some(0),
+        "End" => Some(count - 1),
+        _ => None,
+    }
+}
+
+// where the arrow keys move in a list of count rows, None for other keys. the ends don't wrap,
+// and with nothing selected yet the first key lands on the first or last row
+pub fn list_key_target(key: &str, position: Option<usize>, count: usize) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+
+    match key {
+        "ArrowDown" => Some(position.map_or(0, |position| (position + 1).min(count - 1))),
+        "ArrowUp" => Some(position.map_or(count - 1, |position| position.saturating_sub(1))),
+        "Home" => Some(0),
+        "End" => Some(count - 1),
+        _ => None,
+    }
+}
+
+// scrolls the element just enough to be fully visible, e.g. a row the keyboard moved to
+pub fn reveal_element(id: &str) {
+    let element = web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|doc| doc.get_element_by_id(id));
+
+    if let Some(element) = element {
+        let mut options = web_sys::ScrollIntoViewOptions::new();
+        options.block(web_sys::ScrollLogicalPosition::Nearest);
+
+        element.scroll_into_view_with_scroll_into_view_options(&options);
+    }
+}
+
+// HACK: disables selecting text
+pub fn disable_text_selection() {
+    if let Some(document) = web_sys::window().and_then(|win| win.document()) {
+        if let Some(body) = document.body() {
+            let listener = Closure::wrap(Box::new(move |event: MouseEvent| {
+                event.prevent_default();
+            }) as Box<dyn FnMut(_)>);
+            let _ = EventTarget::from(body)
+                .add_event_listener_with_callback("selectstart", listener.as_ref().unchecked_ref());
+            listener.forget();
+        }
+    }
+}
+
+// the response event from the backend, with json bodies pretty printed
+pub fn parse_response(data: &str) -> Result<Response, serde_json::Error> {
+    let mut response: Response = serde_json::from_str(data)?;
+
+    if response.response_type == ResponseType::JSON && response.size <= LARGE_BODY_LIMIT {
+        response.body = format_json(&response.body);
+    }
+
+    Ok(response)
+}
+
+// bodies that only claim to be json are shown as they came
+pub fn format_json(data: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(data) {
+        Ok(value) => serde_json::to_string_pretty(&value).unwrap_or_else(|_| data.to_string()),
+        Err(_) => data.to_string(),
+    }
+}
+
+fn create_custom_theme(dark: bool) -> SyntectTheme {
+    if !dark {
+        let mut theme = ThemeSet::load_defaults().themes["InspiredGitHub"].clone();
+
+        theme.settings.background = Some(Color {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 1,
+        });
+
+        return theme;
+    }
+
+    let mut theme = ThemeSet::load_defaults().themes["Solarized (dark)"].clone();
+
+    // Change the background color
+    theme.settings.background = Some(Color {
+        r: 3,
+        g: 7,
+        b: 13,
+        a: 1,
+    });
+
+    theme
+}
+
+pub fn highlight_body(body: &str, theme: Theme) -> String {
+    highlight_as(body, "json", theme)
+}
+
+// `extension` picks the syntax, e.g. "json" or "xml"
+pub fn highlight_as(body: &str, extension: &str, theme: Theme) -> String {
+    // Add syntax highlighting
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme = create_custom_theme(is_dark(theme));
+    let syntax = syntax_set
+        .find_syntax_by_extension(extension)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+
+    highlighted_html_for_string(body, &syntax_set, syntax, &theme).unwrap()
+}
+
+// what the body is shown as, Auto resolved from the Content-Type the response came with
+pub fn effective_format(response: &Response, chosen: BodyFormat) -> BodyFormat {
+    if chosen != BodyFormat::Auto {
+        return chosen;
+    }
+
+    let is_xml = response.headers.iter().any(|header| {
+        header[0].eq_ignore_ascii_case("content-type") && header[1].to_lowercase().contains("xml")
+    });
+
+    if response.response_type == ResponseType::JSON {
+        BodyFormat::Json
+    } else if is_xml {
+        BodyFormat::Xml
+    } else {
+        BodyFormat::Text
+    }
+}
+
+// pretty prints and highlights the body the way it is to be shown. large bodies stay as they
+// came, highlighting them would stall the ui
+pub fn apply_body_format(response: &mut Response, chosen: BodyFormat, theme: Theme) {
+    response.highlighted = String::new();
+
+    if response.body.is_empty() || response.size > LARGE_BODY_LIMIT {
+        return;
+    }
+
+    match effective_format(response, chosen) {
+        BodyFormat::Json => {
+            response.body = format_json(&response.body);
+            response.highlighted = highlight_body(&response.body, theme);
+        }
+        BodyFormat::Xml => {
+            response.body = format_xml(&response.body);
+            response.highlighted = highlight_as(&response.body, "xml", theme);
+        }
+        BodyFormat::Auto | BodyFormat::Text => {}
     }
+}
+
+// one element per line, indented by depth. an element holding nothing but text stays on one
+// line, and a body that isn't markup comes back as it was
+pub fn format_xml(data: &str) -> String {
+    let trimmed = data.trim();
+
+    if !trimmed.starts_with('<') {
+        return data.to_string();
+    }
+
+    // tags and the text between them, in order
+    let mut tokens: Vec<&str> = Vec::new();
+    let mut rest = trimmed;
+
+    while !rest.is_empty() {
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|end| end + 3)
+        } else if rest.starts_with("<![CDATA[") {
+            rest.find("]]>").map(|end| end + 3)
+        } else if rest.starts_with('<') {
+            rest.find('>').map(|end| end + 1)
+        } else {
+            Some(rest.find('<').unwrap_or(rest.len()))
+        };
+
+        // an unclosed tag, not worth guessing at
+        let Some(end) = end else {
+            return data.to_string();
+        };
+
+        let token = rest[..end].trim();
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+
+        rest = &rest[end..];
+    }
+
+    let is_open = |token: &str| {
+        token.starts_with('<') && !token.starts_with("</") && !token.starts_with("<?") && !token.starts_with("<!") && !token.ends_with("/>")
+    };
+
+    let mut lines = Vec::new();
+    let mut depth: usize = 0;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = tokens[index];
+
+        if token.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            lines.push(format!("{}{}", "  ".repeat(depth), token));
+        } else if is_open(token)
+            && tokens.get(index + 2).is_some_and(|close| close.starts_with("</"))
+            && tokens.get(index + 1).is_some_and(|text| !text.starts_with('<'))
+        {
+            lines.push(format!("{}{}{}{}", "  ".repeat(depth), token, tokens[index + 1], tokens[index + 2]));
+            index += 2;
+        } else {
+            lines.push(format!("{}{}", "  ".repeat(depth), token));
+
+            if is_open(token) {
+                depth += 1;
+            }
+        }
+
+        index += 1;
+    }
+
+    lines.join("\n")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                html.push_str(&format!("<code>{}</code>", &rest[1..1 + end]));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+
+        if rest.starts_with("**") {
+            if let Some(end) = rest[2..].find("**") {
+                html.push_str(&format!("<strong>{}</strong>", render_inline(&rest[2..2 + end])));
+                rest = &rest[end + 4..];
+                continue;
+            }
+        }
+
+        if c == '[' {
+            if let Some(close) = rest.find("](") {
+                if let Some(end) = rest[close + 2..].find(')') {
+                    let label = &rest[1..close];
+                    let url = &rest[close + 2..close + 2 + end];
+
+                    html.push_str(&format!(
+                        "<a class=\"doclink pointer\" data-href=\"{}\">{}</a>",
+                        url,
+                        render_inline(label)
+                    ));
+                    rest = &rest[close + 3 + end..];
+                    continue;
+                }
+            }
+        }
+
+        html.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    html
+}
+
+// renders the small markdown subset used in request docs:
+// headings, bold, code spans, links and paragraphs
+pub fn render_markdown(text: &str) -> String {
+    let text = escape_html(text);
+    let mut html = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+
+    fn flush(html: &mut String, paragraph: &mut Vec<String>) {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>", paragraph.join("<br/>")));
+            paragraph.clear();
+        }
+    }
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        let heading = ["### ", "## ", "# "]
+            .iter()
+            .find(|prefix| trimmed.starts_with(*prefix));
+
+        if let Some(prefix) = heading {
+            flush(&mut html, &mut paragraph);
+
+            let level = prefix.len() - 1;
+            html.push_str(&format!(
+                "<h{level}>{}</h{level}>",
+                render_inline(&trimmed[prefix.len()..])
+            ));
+        } else if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(render_inline(trimmed));
+        }
+    }
+
+    flush(&mut html, &mut paragraph);
+
+    html
+}
+
+// returns the target of a rendered docs link if the click landed on one
+pub fn get_doc_link(event: &web_sys::MouseEvent) -> Option<String> {
+    let target = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+    let anchor = target.closest("a[data-href]").ok()??;
+
+    anchor.get_attribute("data-href")
+}
+
+pub fn window_size() -> (f64, f64) {
+    let window = web_sys::window().unwrap();
+
+    let width = window.inner_width().unwrap().as_f64().unwrap_or(1000.0);
+    let height = window.inner_height().unwrap().as_f64().unwrap_or(600.0);
+
+    (width, height)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn prefers_dark_scheme() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media("(prefers-color-scheme: dark)").ok())
+        .flatten()
+        .map(|query| query.matches())
+        .unwrap_or(true)
+}
+
+// dark, like a browser that can't say
+#[cfg(not(target_arch = "wasm32"))]
+pub fn prefers_dark_scheme() -> bool {
+    true
+}
+
+// re-renders highlighted bodies so they stay readable after a theme switch
+pub fn rehighlight_responses(bctx: &mut BoltContext) {
+    let theme = bctx.theme;
+
+    let requests = bctx
+        .main_col
+        .requests
+        .iter_mut()
+        .chain(bctx.collections.iter_mut().flat_map(|col| col.requests.iter_mut()));
+
+    for request in requests {
+        apply_body_format(&mut request.response, request.body_format, theme);
+    }
+}
+
+pub fn parse_url(url: String, params: &[Vec<String>], raw: bool) -> String {
+    let query = query_string(params, raw);
+
+    if query.is_empty() {
+        return url;
+    }
+
+    let separator = if url.contains('?') { '&' } else { '?' };
+
+    format!("{url}{separator}{query}")
+}
+
+// renders the request in the VS Code REST Client .http format, noting the last response status
+pub fn build_http_file(request: &Request) -> String {
+    let mut file = format!("### {}\n", request.name.trim());
+
+    if !request.tags.is_empty() {
+        file.push_str(&format!("# @tags {}\n", request.tags.join(", ")));
+    }
+
+    if request.response.status != 0 {
+        file.push_str(&format!(
+            "# Response: {} ({} ms, {} B)\n",
+            request.response.status, request.response.time, request.response.size
+        ));
+    }
+
+    file.push_str(&format!(
+        "{} {} {}\n",
+        request.method,
+        parse_url(request.url.clone(), &request.params, request.raw_query),
+        request.http_version.unwrap_or(HttpVersion::Http1)
+    ));
+
+    for header in &request.headers {
+        if header[0].trim().is_empty() {
+            continue;
+        }
+
+        file.push_str(&format!("{}: {}\n", header[0].trim(), header[1]));
+    }
+
+    if let Some(path) = &request.body_file {
+        file.push_str(&format!("\n< {path}\n"));
+    } else if !request.body.is_empty() {
+        file.push('\n');
+        file.push_str(&request.body);
+        file.push('\n');
+    }
+
+    file
+}
+
+// parses a VS Code REST Client .http file, one request per ### separated block
+pub fn parse_http_file(text: &str) -> Vec<Request> {
+    let mut blocks: Vec<(String, Vec<&str>)> = vec![(String::new(), Vec::new())];
+
+    for line in text.lines() {
+        if let Some(name) = line.trim_start().strip_prefix("###") {
+            blocks.push((name.trim().to_string(), Vec::new()));
+        } else {
+            blocks.last_mut().unwrap().1.push(line);
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter_map(|(name, lines)| parse_http_block(name, &lines))
+        .collect()
+}
+
+fn parse_http_block(mut name: String, lines: &[&str]) -> Option<Request> {
+    let mut lines = lines.iter().map(|line| line.trim_end());
+
+    let mut tags = Vec::new();
+
+    // skip blank lines and comments up to the request line, picking up `# @name` and `# @tags`
+    let request_line = loop {
+        let line = lines.next()?.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#').or_else(|| line.strip_prefix("//")) {
+            let comment = comment.trim();
+
+            if let Some(tag) = comment.strip_prefix("@name") {
+                name = tag.trim().to_string();
+            } else if let Some(list) = comment.strip_prefix("@tags") {
+                tags = parse_tags(list);
+            }
+
+            continue;
+        }
+
+        break line;
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let first = parts.next()?;
+
+    let method = (0..Method::count())
+        .map(Method::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(first));
+
+    let url = match method {
+        Some(_) => parts.next()?.to_string(),
+        None => first.to_string(),
+    };
+
+    let mut request = Request::new();
+    request.method = method.unwrap_or(Method::GET);
+    request.tags = tags;
+
+    match url.split_once('?') {
+        Some((base, query)) => {
+            request.url = base.to_string();
+            request.params = parse_query(query);
+        }
+        None => request.url = url,
+    }
+
+    let mut headers = Vec::new();
+
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push(vec![key.trim().to_string(), value.trim().to_string()]);
+        }
+    }
+
+    headers.push(vec![String::new(), String::new()]);
+    request.headers = headers;
+
+    let body: Vec<&str> = lines.collect();
+    let body = body.join("\n").trim_end().to_string();
+
+    // `< path` sends a file instead of an inline body
+    match body.trim_start().strip_prefix("< ") {
+        Some(path) if !path.contains('\n') => request.body_file = Some(path.trim().to_string()),
+        _ => request.body = body,
+    }
+
+    request.name = if name.is_empty() {
+        format!("{} {}", request.method, request.url)
+    } else {
+        name
+    };
+
+    Some(request)
+}
+
+pub fn export_http_file(request: &Request) {
+    let name: String = request
+        .name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+
+    let name = if name.is_empty() { "request".to_string() } else { name };
+
+    download_file(&format!("{name}.http"), &build_http_file(request), "text/plain");
+}
+
+pub fn serialize_workspace(bctx: &BoltContext, timestamp: u64) -> String {
+    let mut state = build_save_state(bctx);
+
+    // the trash and the recent urls stay with this install
+    state.trash.clear();
+    state.recent_urls.clear();
+
+    if !bctx.reveal_secrets {
+        strip_secret_variables(&mut state);
+    }
+
+    let workspace = WorkspaceFile {
+        version: WORKSPACE_VERSION,
+        timestamp,
+
+        state,
+    };
+
+    serde_json::to_string_pretty(&workspace).unwrap()
+}
 
-    let payload = SendPayload {
-        url: parse_url(request.url.clone(), request.params.clone()),
-        method: request.method,
-        body: request.body.clone(),
-        headers: request.headers.clone(),
-        index: request.response.request_index,
+pub fn parse_workspace(data: &str) -> Result<SaveState, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(data).map_err(|_| tr("import.not_workspace"))?;
+
+    let version = match value.get("version").and_then(|v| v.as_u64()) {
+        Some(version) => version,
+        // a bare state.json copied from another machine
+        None if value.get("main_col").is_some() => {
+            return serde_json::from_value(value).map_err(|err| tr_args("import.invalid_state", &[&err.to_string()]));
+        }
+        None => return Err(tr("import.no_version")),
     };
 
-    let _payload = payload.clone();
+    if version != WORKSPACE_VERSION as u64 {
+        return Err(tr_args(
+            "import.unsupported_version",
+            &[&version.to_string(), &WORKSPACE_VERSION.to_string()],
+        ));
+    }
 
-    wasm_bindgen_futures::spawn_local(async move {
-        let payload = serde_json::to_string(&_payload).unwrap();
+    let workspace: WorkspaceFile =
+        serde_json::from_value(value).map_err(|err| tr_args("import.invalid_workspace", &[&err.to_string()]))?;
 
-        let client = reqwest::Client::new();
+    Ok(workspace.state)
+}
 
-        let res = client
-            .post(BACKEND.to_string() + "send_request")
-            .body(payload)
-            .send()
-            .await
-            .expect("send request failed");
+pub fn merge_save_state(bctx: &mut BoltContext, incoming: SaveState) {
+    bctx.main_col.requests.extend(incoming.main_col.requests);
 
-        let resp = res.text().await.unwrap();
-        crate::receive_response(&resp);
-    });
+    for col in incoming.collections {
+        if bctx.collections.iter().any(|existing| existing.name == col.name) {
+            continue;
+        }
+
+        bctx.collections.push(col);
+    }
+
+    // a workspace exported from this one carries the same ids
+    ensure_ids(bctx);
 }
 
-pub fn save_state(bctx: &mut BoltContext) {
-    let save_state = SaveState {
-        page: bctx.page.clone(),
-        main_current: bctx.main_current.clone(),
-        col_current: bctx.col_current.clone(),
+pub fn import_save_state(bctx: &mut BoltContext, incoming: SaveState, mode: ImportMode) {
+    match mode {
+        ImportMode::Replace => {
+            // the collections it was running are gone
+            bctx.run = None;
+            apply_save_state(bctx, incoming);
+        }
+        ImportMode::Merge => merge_save_state(bctx, incoming),
+    }
 
-        main_col: bctx.main_col.clone(),
-        collections: bctx.collections.clone(),
-    };
+    if bctx.main_current >= bctx.main_col.requests.len() {
+        bctx.main_current = 0;
+    }
 
-    let _save = serde_json::to_string(&save_state).unwrap();
-    let _save2 = _save.clone();
+    let col_valid = bctx
+        .collections
+        .get(bctx.col_current[0])
+        .map(|col| bctx.col_current[1] < col.requests.len())
+        .unwrap_or(false);
 
-    wasm_bindgen_futures::spawn_local(async move {
-        let client = reqwest::Client::new();
+    if !col_valid {
+        bctx.col_current = vec![0, 0];
+    }
+}
 
-        let res = client
-            .post(BACKEND.to_string() + "save_state")
-            .body(_save2)
-            .send()
-            .await
-            .expect("save state failed");
+#[cfg(target_arch = "wasm32")]
+pub fn download_file(name: &str, data: &str, mime: &str) {
+    let parts = js_sys::Array::new();
+    parts.push(&wasm_bindgen::JsValue::from_str(data));
 
-        let _resp = res.text().await.unwrap();
-    });
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime);
+
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options).unwrap();
+    let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
+
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    let anchor = doc
+        .create_element("a")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .unwrap();
+
+    anchor.set_href(&url);
+    anchor.set_download(name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).unwrap();
 }
 
-fn set_save_state(state: String) {
-    let new_state: SaveState = serde_json::from_str(&state).unwrap();
+#[cfg(not(target_arch = "wasm32"))]
+pub fn download_file(name: &str, _data: &str, _mime: &str) {
+    record_effect(format!("download {name}"));
+}
 
-    let mut global_state = GLOBAL_STATE.lock().unwrap();
+pub fn export_workspace(bctx: &BoltContext) {
+    let timestamp = now_ms();
+    let data = serialize_workspace(bctx, timestamp);
 
-    global_state.bctx.main_col = new_state.main_col;
-    global_state.bctx.collections = new_state.collections;
+    download_file(
+        &format!("bolt-workspace-{timestamp}.json"),
+        &data,
+        "application/json",
+    );
+}
 
-    global_state.bctx.col_current = new_state.col_current;
-    global_state.bctx.main_current = new_state.main_current;
+// cuts a body down to at most `limit` bytes without splitting a character
+pub fn truncate_body(body: &str, limit: usize) -> &str {
+    if body.len() <= limit {
+        return body;
+    }
 
-    global_state.bctx.page = new_state.page;
+    let mut end = limit;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
 
-    let link = global_state.bctx.link.as_ref().unwrap();
-    link.send_message(Msg::Update);
+    &body[..end]
 }
 
-pub fn restore_state() {
-    wasm_bindgen_futures::spawn_local(async move {
-        let client = reqwest::Client::new();
+// None when both questions are cancelled, the import doesn't go ahead then
+pub fn ask_import_mode() -> Option<ImportMode> {
+    if ask_confirm(&tr("import.replace_prompt")) {
+        Some(ImportMode::Replace)
+    } else if ask_confirm(&tr("import.merge_prompt")) {
+        Some(ImportMode::Merge)
+    } else {
+        None
+    }
+}
 
-        let res = client
-            .post(BACKEND.to_string() + "restore_state")
-            .send()
-            .await
-            .expect("restore state failed");
+pub fn import_workspace(mode: ImportMode) {
+    pick_text_file(".json,application/json", move |text| {
+        let incoming = match parse_workspace(&text) {
+            Ok(state) => state,
+            Err(err) => {
+                show_alert(&tr_args("import.workspace_failed", &[&err]));
 
-        let resp = res.text().await.unwrap();
-        // _bolt_log(&text);
+                return;
+            }
+        };
 
-        set_save_state(resp);
+        dispatch(Msg::WorkspaceImported(incoming, mode));
     });
 }
 
-pub fn open_link(link: String) {
-    let _link = link.clone();
+pub fn import_http_file() {
+    pick_text_file(".http,.rest", |text| {
+        let global_state = global_state();
 
-    wasm_bindgen_futures::spawn_local(async move {
-        let client = reqwest::Client::new();
+        let link = global_state.bctx.link.as_ref().unwrap();
+        link.send_message(Msg::ImportHttpFile(text));
+    });
+}
 
-        let res = client
-            .post(BACKEND.to_string() + "open_link")
-            .body(_link)
-            .send()
-            .await
-            .expect("open_link failed");
+// loads a file into the body editor, asking first when it is big enough to slow the editor down
+pub fn pick_body_file() {
+    pick_text_file("", |text| {
+        if text.len() > BODY_FILE_WARN_SIZE && !ask_confirm(&tr_args("body.large_file", &[&format_size(text.len() as u64)])) {
+            return;
+        }
 
-        let _resp = res.text().await.unwrap();
+        let global_state = global_state();
+
+        let link = global_state.bctx.link.as_ref().unwrap();
+        link.send_message(Msg::Edit(EditMsg::BodyLoaded(text)));
     });
 }
 
-pub fn bolt_panic(log: &str) {
-    #[derive(Serialize, Deserialize)]
-    struct Payload<'a> {
-        log: &'a str,
-    }
+// asks the backend for the size of a body file, the answer arrives as Msg::BodyFileInfo
+pub fn invoke_body_file_info(path: &str, target: RequestRef) {
+    let path = path.to_string();
 
-    let log = log.to_string();
+    spawn("file_info", async move {
+        #[derive(Deserialize)]
+        struct FileInfo {
+            size: Option<u64>,
+            error: String,
+        }
 
-    panic!("{}", log);
+        let client = reqwest::Client::new();
+
+        let info = match client.post(BACKEND.to_string() + "file_info").body(path).send().await {
+            Ok(res) => res.json::<FileInfo>().await.map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        let info = match info {
+            Ok(FileInfo { size: Some(size), .. }) => Ok(size),
+            Ok(FileInfo { error, .. }) => Err(error),
+            Err(err) => Err(err),
+        };
+
+        let global_state = global_state();
+
+        let link = global_state.bctx.link.as_ref().unwrap();
+        link.send_message(Msg::BodyFileInfo(target, info));
+    });
 }
 
-pub fn _set_html(id: &str, content: String) {
-    let window = web_sys::window().unwrap();
-    let doc = web_sys::Window::document(&window).unwrap();
-    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+// what to check for a failure the backend classified, None when there is nothing better to say
+// than the error itself
+pub fn error_hint(kind: &str) -> Option<String> {
+    let key = match kind {
+        "DnsResolution" => "error.dns",
+        "ConnectionRefused" => "error.refused",
+        "ConnectionTimeout" => "error.timeout",
+        "TlsHandshake" => "error.tls",
+        "ProtocolError" => "error.protocol",
+        "Cancelled" => "error.cancelled",
+        _ => return None,
+    };
 
-    div.set_inner_html(&content);
+    Some(tr(key))
 }
 
-pub fn _set_focus(id: &str) {
-    let window = web_sys::window().unwrap();
-    let doc = web_sys::Window::document(&window).unwrap();
-    let div = web_sys::Document::get_element_by_id(&doc, id).unwrap();
+// asks the backend whether the url's host takes connections, the answer arrives as Msg::Send(SendMsg::HostChecked)
+pub fn invoke_check_host(url: &str) {
+    let url = url.to_string();
 
-    let div = div.dyn_into::<web_sys::HtmlElement>().unwrap();
+    spawn("check_host", async move {
+        let client = reqwest::Client::new();
 
-    div.focus().unwrap();
+        let check = match client.post(BACKEND.to_string() + "check_host").body(url).send().await {
+            Ok(res) => res.json::<HostCheck>().await.map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        dispatch(Msg::Send(SendMsg::HostChecked(check)));
+    });
 }
 
-pub fn get_method() -> Method {
-    let window = web_sys::window().unwrap();
-    let doc = web_sys::Window::document(&window).unwrap();
-    let div = web_sys::Document::get_element_by_id(&doc, "methodselect").unwrap();
+// has the backend open its own TLS connection to the url's host, the answer arrives as Msg::Send(SendMsg::TlsInspected)
+pub fn invoke_inspect_tls(url: &str, id: u64, target: RequestRef) {
+    let url = url.to_string();
 
-    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+    spawn("inspect_tls", async move {
+        let client = reqwest::Client::new();
 
-    let value = select.value();
+        let inspection = match client.post(BACKEND.to_string() + "inspect_tls").body(url.clone()).send().await {
+            Ok(res) => res.json::<TlsInspection>().await.map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
 
-    match value.as_str() {
-        "get" => Method::GET,
-        "post" => Method::POST,
-        "put" => Method::PUT,
-        "delete" => Method::DELETE,
-        "head" => Method::HEAD,
-        "patch" => Method::PATCH,
-        "options" => Method::OPTIONS,
-        "connect" => Method::CONNECT,
+        let result = match inspection {
+            Ok(TlsInspection { details: Some(details), .. }) => Ok(details),
+            Ok(TlsInspection { error, .. }) => Err(error),
+            Err(err) => Err(err),
+        };
 
-        _ => {
-            bolt_panic("invalid method");
+        dispatch_to_request(id, target, |target| Msg::Send(SendMsg::TlsInspected(target, url, result)));
+    });
+}
 
-            Method::GET
-        }
+// "840 ms" under a second, "2.4 s" from there on
+pub fn format_duration(ms: u32) -> String {
+    if ms < 1000 {
+        format!("{ms} ms")
+    } else {
+        format!("{:.1} s", ms as f64 / 1000.0)
     }
 }
 
-pub fn get_url() -> String {
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        bytes if bytes >= 1024 * 1024 => format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)),
+        bytes if bytes >= 1024 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        bytes => format!("{bytes} B"),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn ask_confirm(message: &str) -> bool {
     let window = web_sys::window().unwrap();
-    let doc = web_sys::Window::document(&window).unwrap();
-    let div = web_sys::Document::get_element_by_id(&doc, "urlinput").unwrap();
 
-    div.dyn_into::<web_sys::HtmlInputElement>().unwrap().value()
+    window.confirm_with_message(message).unwrap_or(false)
 }
 
-pub fn get_body() -> String {
+#[cfg(not(target_arch = "wasm32"))]
+pub fn ask_confirm(_message: &str) -> bool {
+    ANSWERS.with(|answers| answers.borrow_mut().pop_front()).flatten().is_some()
+}
+
+// None when the prompt is cancelled
+#[cfg(target_arch = "wasm32")]
+pub fn ask_text(message: &str, default: &str) -> Option<String> {
     let window = web_sys::window().unwrap();
-    let doc = web_sys::Window::document(&window).unwrap();
-    let div = web_sys::Document::get_element_by_id(&doc, "reqbody").unwrap();
 
-    div.dyn_into::<web_sys::HtmlTextAreaElement>()
-        .unwrap()
-        .value()
+    window
+        .prompt_with_message_and_default(message, default)
+        .ok()
+        .flatten()
 }
 
-pub fn get_header(index: usize) -> Vec<String> {
+// an answer that was never queued is a cancel
+#[cfg(not(target_arch = "wasm32"))]
+pub fn ask_text(_message: &str, _default: &str) -> Option<String> {
+    ANSWERS.with(|answers| answers.borrow_mut().pop_front()).flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn show_alert(message: &str) {
     let window = web_sys::window().unwrap();
-    let doc = web_sys::Window::document(&window).unwrap();
 
-    let key =
-        web_sys::Document::get_element_by_id(&doc, &("headerkey".to_string() + &index.to_string()))
-            .unwrap();
-    let value = web_sys::Document::get_element_by_id(
-        &doc,
-        &("headervalue".to_string() + &index.to_string()),
-    )
-    .unwrap();
+    window.alert_with_message(message).unwrap();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn show_alert(message: &str) {
+    record_effect(format!("alert {message}"));
+}
+
+// asks for a spec URL, falling back to a file picker when none is given
+pub fn pick_openapi_spec(settings: &Settings) {
+    let url = ask_text(&tr("import.openapi_prompt"), "").unwrap_or_default();
+
+    let on_text = |text: String| {
+        let global_state = global_state();
+
+        let link = global_state.bctx.link.as_ref().unwrap();
+        link.send_message(Msg::ImportOpenApi(text));
+    };
+
+    if url.trim().is_empty() {
+        pick_text_file(".json,application/json", on_text);
+        return;
+    }
 
-    let key = key.dyn_into::<web_sys::HtmlInputElement>().unwrap();
-    let value = value.dyn_into::<web_sys::HtmlInputElement>().unwrap();
+    // fetched through the backend like any other request, so proxies and cors don't get in the way
+    let mut request = Request::new();
+    request.url = url.trim().to_string();
+
+    let payload = send_payload(&request, settings, None);
+
+    spawn("fetch_spec", async move {
+        let resp = match post_payload(&payload).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                toast_later(ToastLevel::Warning, tr_args("toast.send_failed", &[&err]));
+                return;
+            }
+        };
+
+        #[derive(Deserialize)]
+        struct SpecResponse {
+            status: u16,
+            body: String,
+            failed: bool,
+        }
 
-    vec![key.value(), value.value()]
+        match serde_json::from_str::<SpecResponse>(&resp) {
+            Ok(spec) if !spec.failed && (200..300).contains(&spec.status) => on_text(spec.body),
+            Ok(spec) => {
+                show_alert(&tr_args("import.spec_fetch_failed", &[&spec.status.to_string(), &spec.body]));
+            }
+            Err(_) => _bolt_log("unexpected response while fetching a spec"),
+        }
+    });
 }
 
-pub fn get_param(index: usize) -> Vec<String> {
+// fetches a workspace, Postman collection or OpenAPI spec the way a request to the url would go
+// out, then hands the body over to be told apart and imported
+pub fn fetch_import(url: &str, settings: &Settings) {
+    let mut request = Request::new();
+    request.url = url.trim().to_string();
+
+    let mut payload = send_payload(&request, settings, None);
+    payload.url = normalize_url(&payload.url);
+
+    spawn("fetch_import", async move {
+        let resp = match send_with_transport(&payload).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                toast_later(ToastLevel::Error, tr_args("toast.send_failed", &[&err]));
+                return;
+            }
+        };
+
+        match parse_response(&resp) {
+            Ok(resp) if !resp.failed && (200..300).contains(&resp.status) => dispatch(Msg::ImportDocument(resp.body)),
+            Ok(resp) => {
+                let status = if resp.failed { tr("import.no_status") } else { resp.status.to_string() };
+                toast_later(ToastLevel::Error, tr_args("toast.import_fetch_failed", &[&status, &resp.body]));
+            }
+            Err(err) => toast_later(ToastLevel::Error, tr_args("toast.bad_response", &[&err.to_string()])),
+        }
+    });
+}
+
+// opens a file picker and hands the chosen file's text to `on_text`
+fn pick_text_file<F>(accept: &str, on_text: F)
+where
+    F: Fn(String) + 'static,
+{
     let window = web_sys::window().unwrap();
     let doc = web_sys::Window::document(&window).unwrap();
 
-    let key =
-        web_sys::Document::get_element_by_id(&doc, &("paramkey".to_string() + &index.to_string()))
-            .unwrap();
-    let value = web_sys::Document::get_element_by_id(
-        &doc,
-        &("paramvalue".to_string() + &index.to_string()),
-    )
-    .unwrap();
+    let input = doc
+        .create_element("input")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .unwrap();
+
+    input.set_type("file");
+    input.set_accept(accept);
+
+    let _input = input.clone();
+    let on_text = std::rc::Rc::new(on_text);
+
+    let listener = Closure::wrap(Box::new(move || {
+        let file = match _input.files().and_then(|files| files.get(0)) {
+            Some(file) => file,
+            None => return,
+        };
+
+        let on_text = on_text.clone();
 
-    let key = key.dyn_into::<web_sys::HtmlInputElement>().unwrap();
-    let value = value.dyn_into::<web_sys::HtmlInputElement>().unwrap();
+        spawn("read_file", async move {
+            let text = wasm_bindgen_futures::JsFuture::from(file.text()).await.unwrap();
+            let text = text.as_string().unwrap_or_default();
 
-    vec![key.value(), value.value()]
+            on_text(text);
+        });
+    }) as Box<dyn FnMut()>);
+
+    input.set_onchange(Some(listener.as_ref().unchecked_ref()));
+    listener.forget();
+
+    input.click();
 }
 
-// HACK: disables selecting text
-pub fn disable_text_selection() {
-    if let Some(document) = web_sys::window().and_then(|win| win.document()) {
-        if let Some(body) = document.body() {
-            let listener = Closure::wrap(Box::new(move |event: MouseEvent| {
-                event.prevent_default();
-            }) as Box<dyn FnMut(_)>);
-            let _ = EventTarget::from(body)
-                .add_event_listener_with_callback("selectstart", listener.as_ref().unchecked_ref());
-            listener.forget();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collection;
+
+    #[test]
+    fn relative_urls_join_the_base_url() {
+        assert_eq!(join_base_url(Some("https://a.test"), "/users"), "https://a.test/users");
+        assert_eq!(join_base_url(Some("https://a.test/"), "/users"), "https://a.test/users");
+        assert_eq!(join_base_url(Some("https://a.test//"), "/users?page=2"), "https://a.test/users?page=2");
+        assert_eq!(join_base_url(Some("  https://a.test  "), "/"), "https://a.test/");
+    }
+
+    #[test]
+    fn base_urls_keep_their_path() {
+        assert_eq!(join_base_url(Some("https://a.test/api/v1"), "/users"), "https://a.test/api/v1/users");
+        assert_eq!(join_base_url(Some("https://a.test/api/v1/"), "/users/"), "https://a.test/api/v1/users/");
+        assert_eq!(join_base_url(Some("{{host}}/v2"), "/me"), "{{host}}/v2/me");
+    }
+
+    #[test]
+    fn other_urls_are_used_as_is() {
+        // absolute, protocol relative, a bare host or a variable
+        for url in ["https://b.test/users", "//b.test/users", "b.test/users", "{{host}}/users", ""] {
+            assert_eq!(join_base_url(Some("https://a.test"), url), url);
         }
+
+        // no base url, or a blank one
+        assert_eq!(join_base_url(None, "/users"), "/users");
+        assert_eq!(join_base_url(Some("   "), "/users"), "/users");
     }
-}
 
-pub fn format_json(data: &str) -> String {
-    let value: serde_json::Value = serde_json::from_str(data).unwrap();
+    // a response event as the backend sends it, with the body and type given
+    fn event(body: &str, response_type: &str, size: u64) -> String {
+        serde_json::json!({
+            "status": 200,
+            "body": body,
+            "headers": [],
+            "time": 1,
+            "size": size,
+            "response_type": response_type,
+            "request_index": 0,
+            "failed": false,
+        })
+        .to_string()
+    }
 
-    serde_json::to_string_pretty(&value).unwrap()
-}
+    #[test]
+    fn garbage_events_are_errors_not_panics() {
+        for data in ["", "null", "not json", "{\"status\": 200", "{\"status\": \"ok\"}", "[1, 2]", "\u{fffd}\u{fffd}"] {
+            assert!(parse_response(data).is_err(), "{data:?} parsed");
+        }
 
-fn create_custom_theme() -> Theme {
-    let mut theme = ThemeSet::load_defaults().themes["Solarized (dark)"].clone();
+        // cut off halfway through
+        let whole = event("{}", "JSON", 2);
+        assert!(parse_response(&whole[..whole.len() / 2]).is_err());
+    }
 
-    // Change the background color
-    theme.settings.background = Some(Color {
-        r: 3,
-        g: 7,
-        b: 13,
-        a: 1,
-    });
+    #[test]
+    fn json_bodies_are_pretty_printed() {
+        let response = parse_response(&event("{\"a\":[1,2]}", "JSON", 11)).unwrap();
 
-    theme
-}
+        assert_eq!(response.body, "{\n  \"a\": [\n    1,\n    2\n  ]\n}");
+    }
 
-pub fn highlight_body(body: &str) -> String {
-    // Add syntax highlighting
-    let syntax_set = SyntaxSet::load_defaults_newlines();
-    let theme = create_custom_theme();
-    let syntax = syntax_set.find_syntax_by_extension("json").unwrap();
+    #[test]
+    fn bodies_that_only_claim_to_be_json_are_kept() {
+        for body in ["{\"a\": 1", "<html>error</html>", "", "caf\u{fffd} \u{fffd}"] {
+            let response = parse_response(&event(body, "JSON", body.len() as u64)).unwrap();
 
-    highlighted_html_for_string(body, &syntax_set, syntax, &theme).unwrap()
-}
+            assert_eq!(response.body, body);
+        }
+
+        // text and large bodies are left as they came
+        assert_eq!(parse_response(&event("{\"a\":1}", "TEXT", 7)).unwrap().body, "{\"a\":1}");
+        assert_eq!(
+            parse_response(&event("{\"a\":1}", "JSON", LARGE_BODY_LIMIT + 1)).unwrap().body,
+            "{\"a\":1}"
+        );
+    }
+
+    #[test]
+    fn format_json_falls_back_to_the_original() {
+        assert_eq!(format_json("[1]"), "[\n  1\n]");
+        assert_eq!(format_json("  \"text\"  "), "\"text\"");
+        assert_eq!(format_json("{oops}"), "{oops}");
+        assert_eq!(format_json(""), "");
+    }
 
-pub fn parse_url(url: String, params: Vec<Vec<String>>) -> String {
-    let mut new_url = url;
+    #[test]
+    fn exports_carry_the_joined_url() {
+        let mut request = Request::new();
+        request.url = "/users".to_string();
 
-    if !params.is_empty() && !params[0][0].is_empty() {
-        new_url.push('?');
+        assert_eq!(with_base_url(&request, Some("https://a.test/v1/")).url, "https://a.test/v1/users");
+        assert_eq!(request.url, "/users");
     }
 
-    for (i, param) in params.iter().enumerate() {
-        if param[0].is_empty() || param[1].is_empty() {
-            continue;
+    fn workspace_with(names: &[&str]) -> BoltContext {
+        let mut bctx = BoltContext::new();
+
+        for name in names {
+            let mut collection = Collection::new();
+            collection.name = name.to_string();
+
+            let mut request = Request::new();
+            request.url = format!("https://a.test/{name}");
+            collection.requests.push(request);
+
+            bctx.collections.push(collection);
+        }
+
+        bctx
+    }
+
+    fn names(bctx: &BoltContext) -> Vec<&str> {
+        bctx.collections.iter().map(|col| col.name.as_str()).collect()
+    }
+
+    #[test]
+    fn workspaces_round_trip() {
+        let bctx = workspace_with(&["Users", "Orders"]);
+
+        let state = parse_workspace(&serialize_workspace(&bctx, 7)).unwrap();
+
+        assert_eq!(state.collections.len(), 2);
+        assert_eq!(state.collections[0].name, "Users");
+        assert_eq!(state.collections[1].requests[0].url, "https://a.test/Orders");
+        assert_eq!(state.collections[1].requests[0].id, bctx.collections[1].requests[0].id);
+    }
+
+    #[test]
+    fn other_versions_are_refused() {
+        let bctx = workspace_with(&["Users"]);
+        let exported = serialize_workspace(&bctx, 7).replacen("\"version\": 1", "\"version\": 2", 1);
+
+        assert_eq!(
+            parse_workspace(&exported).err().unwrap(),
+            "workspace version 2 is not supported (expected 1)"
+        );
+        assert_eq!(parse_workspace("{\"state\": {}}").err().unwrap(), "workspace file has no version");
+        assert_eq!(parse_workspace("not json").err().unwrap(), "not a valid Bolt workspace file");
+    }
+
+    #[test]
+    fn bare_state_files_are_workspaces_too() {
+        let bctx = workspace_with(&["Users"]);
+        let bare = serde_json::to_string(&build_save_state(&bctx)).unwrap();
+
+        let state = parse_workspace(&bare).unwrap();
+
+        assert_eq!(state.collections[0].name, "Users");
+        assert_eq!(state.collections[0].requests[0].url, "https://a.test/Users");
+    }
+
+    #[test]
+    fn merging_skips_collections_that_are_already_there() {
+        let mut bctx = workspace_with(&["Users"]);
+        let incoming = parse_workspace(&serialize_workspace(&workspace_with(&["Users", "Orders"]), 7)).unwrap();
+        let kept = bctx.collections[0].requests[0].id;
+
+        merge_save_state(&mut bctx, incoming);
+
+        assert_eq!(names(&bctx), ["Users", "Orders"]);
+        assert_eq!(bctx.collections[0].requests.len(), 1);
+        assert_eq!(bctx.collections[0].requests[0].id, kept);
+    }
+
+    #[test]
+    fn merging_a_copy_gives_the_copies_new_ids() {
+        let mut bctx = workspace_with(&["Users"]);
+        bctx.main_col.requests.push(Request::new());
+
+        let original = bctx.main_col.requests[0].id;
+        let incoming = parse_workspace(&serialize_workspace(&bctx, 7)).unwrap();
+
+        merge_save_state(&mut bctx, incoming);
+
+        let requests = &bctx.main_col.requests;
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].id, original);
+        assert_ne!(requests[1].id, original);
+        assert_ne!(requests[1].id, 0);
+    }
+
+    #[test]
+    fn cancelling_both_questions_cancels_the_import() {
+        ANSWERS.with(|answers| answers.borrow_mut().push_back(Some(String::new())));
+        assert_eq!(ask_import_mode(), Some(ImportMode::Replace));
+
+        ANSWERS.with(|answers| answers.borrow_mut().extend([None, Some(String::new())]));
+        assert_eq!(ask_import_mode(), Some(ImportMode::Merge));
+
+        assert_eq!(ask_import_mode(), None);
+    }
+
+    fn request_with_secrets() -> Request {
+        let mut request = Request::new();
+        request.headers = vec![
+            vec!["Authorization".to_string(), "Bearer t0ken".to_string()],
+            vec!["Accept".to_string(), "*/*".to_string()],
+        ];
+        request.params = vec![
+            vec!["key".to_string(), "k3y".to_string()],
+            vec!["page".to_string(), "2".to_string()],
+            vec!["empty".to_string(), String::new()],
+        ];
+        request.secret_headers.insert("authorization".to_string());
+        request.secret_params.extend(["key".to_string(), "empty".to_string()]);
+
+        request.response.sent_url = "https://a.test/?key=k3y&page=2".to_string();
+        request.response.sent_headers = vec![vec!["authorization".to_string(), "Bearer t0ken".to_string()]];
+        request.response.raw_request = "GET /?key=k3y&page=2 HTTP/1.1\r\nauthorization: Bearer t0ken\r\n\r\n".to_string();
+        request.last_response = Some(request.response.clone());
+
+        request
+    }
+
+    #[test]
+    fn secret_rows_pair_each_value_with_its_placeholder() {
+        let request = request_with_secrets();
+
+        assert_eq!(
+            secret_rows(&request),
+            [
+                ("Bearer t0ken".to_string(), "{{Authorization}}".to_string()),
+                ("k3y".to_string(), "{{key}}".to_string()),
+            ]
+        );
+        assert!(secret_rows(&Request::new()).is_empty());
+    }
+
+    #[test]
+    fn stripped_rows_keep_only_placeholders() {
+        let mut request = request_with_secrets();
+
+        strip_secret_rows(&mut request);
+
+        assert_eq!(request.headers[0][1], "{{Authorization}}");
+        assert_eq!(request.headers[1][1], "*/*");
+        assert_eq!(request.params[0][1], "{{key}}");
+        assert_eq!(request.params[1][1], "2");
+        assert_eq!(request.params[2][1], "");
+
+        for response in [&request.response, request.last_response.as_ref().unwrap()] {
+            assert_eq!(response.sent_url, "https://a.test/?key={{key}}&page=2");
+            assert_eq!(response.sent_headers[0][1], "{{Authorization}}");
+            assert_eq!(response.raw_request, "GET /?key={{key}}&page=2 HTTP/1.1\r\nauthorization: {{Authorization}}\r\n\r\n");
         }
+    }
+
+    #[test]
+    fn exports_keep_secret_rows_only_when_revealed() {
+        let mut bctx = BoltContext::new();
+        bctx.main_col.requests = vec![request_with_secrets()];
+        // the token is kept so the raw request still has it for the rows to mask
+        bctx.main_col.requests[0].auth.remember_secret = true;
+
+        let masked = serialize_workspace(&bctx, 7);
+        assert!(!masked.contains("t0ken") && !masked.contains("k3y"));
+
+        bctx.reveal_secrets = true;
+
+        let revealed = serialize_workspace(&bctx, 7);
+        assert!(revealed.contains("Bearer t0ken") && revealed.contains("k3y"));
+    }
+
+    #[test]
+    fn masked_exports_import_as_placeholders() {
+        let mut bctx = BoltContext::new();
+        bctx.main_col.requests = vec![request_with_secrets()];
+
+        let incoming = parse_workspace(&serialize_workspace(&bctx, 7)).unwrap();
+        let mut imported = BoltContext::new();
+
+        import_save_state(&mut imported, incoming, ImportMode::Replace);
+
+        let request = &imported.main_col.requests[0];
+        assert_eq!(request.headers[0][1], "{{Authorization}}");
+        assert_eq!(request.params[0][1], "{{key}}");
+        assert!(is_secret_header(request, "Authorization"));
+        assert!(is_secret_param(request, "key"));
+        assert!(!request.response.raw_request.contains("t0ken"));
+    }
+
+    #[test]
+    fn sending_leaves_a_large_previous_response_where_it_is() {
+        let mut request = Request::new();
+        request.url = "https://a.test/users".to_string();
+        request.body = "{}".to_string();
+        request.response.status = 200;
+        request.response.body = "x".repeat(10 * 1024 * 1024);
+        request.last_response = Some(request.response.clone());
+
+        let settings = Settings::default();
+        let variables = BTreeMap::new();
 
-        new_url.push_str(&param[0]);
-        new_url.push('=');
-        new_url.push_str(&param[1]);
+        // only request fields go into what is posted, none of the response
+        let payload = final_payload(&request, &settings, None, &variables);
+        assert!(serde_json::to_string(&payload).unwrap().len() < 4096);
 
-        if i != params.len() - 1 {
-            new_url.push('&');
+        let body = request.response.body.as_ptr();
+        let target = RequestRef { collection: None, request: 0 };
+
+        invoke_send(&mut request, &settings, None, &variables, target);
+
+        assert_eq!(take_effects(), ["send"]);
+        assert_eq!(request.response.body.as_ptr(), body);
+        assert_eq!(request.response.body.len(), 10 * 1024 * 1024);
+    }
+
+    #[test]
+    fn forgotten_tokens_leave_the_raw_request() {
+        let mut bctx = BoltContext::new();
+
+        let mut request = Request::new();
+        request.auth.access_token = "t0ken".to_string();
+        request.response.sent_headers = vec![
+            vec!["authorization".to_string(), "Bearer t0ken".to_string()],
+            vec!["accept".to_string(), "*/*".to_string()],
+        ];
+        request.response.raw_request =
+            "GET / HTTP/1.1\r\nauthorization: Bearer t0ken\r\naccept: */*\r\n\r\nauthorization: kept".to_string();
+        request.last_response = Some(request.response.clone());
+        bctx.main_col.requests = vec![request];
+
+        let state = build_save_state(&bctx);
+        let request = &state.main_col.requests[0];
+
+        for response in [&request.response, request.last_response.as_ref().unwrap()] {
+            assert_eq!(response.sent_headers, vec![vec!["accept".to_string(), "*/*".to_string()]]);
+            assert_eq!(response.raw_request, "GET / HTTP/1.1\r\naccept: */*\r\n\r\nauthorization: kept");
         }
+
+        bctx.main_col.requests[0].auth.remember_secret = true;
+
+        let state = build_save_state(&bctx);
+        assert!(state.main_col.requests[0].response.raw_request.contains("Bearer t0ken"));
     }
 
-    // bolt_log(&format!("url is: {new_url}"));
-    new_url
+    #[test]
+    fn exports_mask_secret_variables_in_what_was_sent() {
+        let mut bctx = BoltContext::new();
+        bctx.main_col.variables = vec![vec!["token".to_string(), "s3cret".to_string()]];
+        bctx.main_col.secret_variables.insert("token".to_string());
+
+        let mut request = Request::new();
+        request.response.sent_url = "https://a.test/?key=s3cret".to_string();
+        request.response.sent_headers = vec![vec!["x-token".to_string(), "s3cret".to_string()]];
+        request.response.sent_body = "{\"token\": \"s3cret\"}".to_string();
+        request.response.raw_request = "GET /?key=s3cret HTTP/1.1".to_string();
+        request.last_response = Some(request.response.clone());
+        bctx.main_col.requests = vec![request];
+
+        let exported = serialize_workspace(&bctx, 0);
+        assert!(!exported.contains("s3cret"));
+
+        let state = parse_workspace(&exported).unwrap();
+        let request = &state.main_col.requests[0];
+
+        for response in [&request.response, request.last_response.as_ref().unwrap()] {
+            assert_eq!(response.sent_url, "https://a.test/?key={{token}}");
+            assert_eq!(response.sent_headers[0][1], "{{token}}");
+            assert_eq!(response.sent_body, "{\"token\": \"{{token}}\"}");
+            assert_eq!(response.raw_request, "GET /?key={{token}} HTTP/1.1");
+        }
+    }
 }
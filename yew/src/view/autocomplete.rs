@@ -0,0 +1,51 @@
+use crate::helpers::enums::{AutocompleteAction, AutocompleteField};
+use crate::BoltContext;
+use crate::Msg;
+use yew::{html, Html, KeyboardEvent, MouseEvent};
+
+pub fn is_open(bctx: &BoltContext, field: AutocompleteField) -> bool {
+    bctx.autocomplete.as_ref().is_some_and(|popup| popup.field == field)
+}
+
+// the keys the suggestions take over while they are open, None leaves the key to the input
+pub fn autocomplete_key(e: &KeyboardEvent, open: bool) -> Option<Msg> {
+    if !open {
+        return None;
+    }
+
+    let action = match e.key().as_str() {
+        "ArrowDown" => AutocompleteAction::Next,
+        "ArrowUp" => AutocompleteAction::Previous,
+        "Enter" | "Tab" => AutocompleteAction::Accept(None),
+        "Escape" => AutocompleteAction::Dismiss,
+        _ => return None,
+    };
+
+    e.prevent_default();
+
+    Some(Msg::VariableAutocomplete(action))
+}
+
+// fixed under the input it completes, drawn over whatever page is open
+pub fn autocomplete_popup(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let popup = match &bctx.autocomplete {
+        Some(popup) => popup,
+        None => return html! {},
+    };
+
+    html! {
+        <div class="autocomplete" role="listbox" style={format!("left: {}px; top: {}px; min-width: {}px;", popup.left, popup.top, if popup.whole_value { popup.width } else { popup.width.min(240.0) })}>
+            { for popup.matches.iter().enumerate().map(|(index, name)| html! {
+                // mousedown, a click would blur the input first and close the list
+                <div role="option" aria-selected={(index == popup.selected).to_string()} class={if index == popup.selected { "autocompleteitem autocompleteitem-selected pointer" } else { "autocompleteitem pointer" }} onmousedown={link.callback(move |e: MouseEvent| {
+                    e.prevent_default();
+                    Msg::VariableAutocomplete(AutocompleteAction::Accept(Some(index)))
+                })}>
+                    {if popup.whole_value { name.clone() } else { format!("{{{{{name}}}}}") }}
+                </div>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,100 @@
+use crate::BoltApp;
+use crate::Msg;
+use crate::Request;
+use yew::html::Scope;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::utils::format_duration;
+use yew::{html, Html};
+
+// shared by the status strip and the sidebar badges
+pub fn status_class(status: u16, failed: bool) -> &'static str {
+    if failed || status >= 500 {
+        "statuserror"
+    } else if status >= 400 {
+        "statuswarn"
+    } else {
+        "statusok"
+    }
+}
+
+// one entry of a tab strip. only the selected tab sits in the tab order, the arrow keys
+// on the strip reach the others
+pub fn tab(link: &Scope<BoltApp>, id: &'static str, selected: bool, label: String, title: Option<String>, msg: Msg) -> Html {
+    html! {
+        <div {id} class={if selected {"tab pointer tabSelected"} else {"tab pointer"}} role="tab" aria-selected={selected.to_string()} tabindex={if selected {"0"} else {"-1"}} {title} onclick={link.callback(move |_| msg.clone())}>{label}</div>
+    }
+}
+
+// the same tag always gets the same color
+pub fn tag_chip(tag: &str) -> Html {
+    let hue = tag
+        .to_lowercase()
+        .bytes()
+        .fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(u32::from(byte)))
+        % 360;
+
+    html! {
+        <span class="tagchip" style={format!("--tag-hue: {hue};")}>{tag.to_string()}</span>
+    }
+}
+
+// method chip, name and last status of a request, as listed in the sidebar
+pub fn request_row(req: &Request) -> Html {
+    let method = req.method.to_string();
+    let response = &req.response;
+
+    html! {
+        <>
+            <span class={format!("methodchip method-{}", method.to_lowercase())}>{method}</span>
+            <span class="reqname" title={req.url.clone()}>{req.name.clone()}</span>
+            if req.modified {
+                <span class="modifieddot" title={tr("sidebar.modified")}>{"●"}</span>
+            }
+            { for req.tags.iter().map(|tag| tag_chip(tag)) }
+
+            if response.failed {
+                <span class={format!("statusbadge {}", status_class(response.status, true))} title={tr("sidebar.send_failed")}>{"ERR"}</span>
+            } else if response.status != 0 {
+                <span class={format!("statusbadge {}", status_class(response.status, false))} title={format_duration(response.time)}>{response.status}</span>
+            }
+        </>
+    }
+}
+
+// response times as a line, scaled between the fastest and the slowest
+pub fn sparkline(times: &[u32]) -> Html {
+    const WIDTH: f64 = 80.0;
+    const HEIGHT: f64 = 16.0;
+
+    if times.len() < 2 {
+        return html! {};
+    }
+
+    let min = *times.iter().min().unwrap();
+    let max = *times.iter().max().unwrap();
+    let average = times.iter().map(|time| u64::from(*time)).sum::<u64>() / times.len() as u64;
+
+    let range = f64::from((max - min).max(1));
+    let step = WIDTH / (times.len() - 1) as f64;
+
+    let points: Vec<String> = times
+        .iter()
+        .enumerate()
+        .map(|(index, time)| {
+            let y = HEIGHT - 1.0 - f64::from(time - min) / range * (HEIGHT - 2.0);
+            format!("{:.1},{:.1}", index as f64 * step, y)
+        })
+        .collect();
+
+    let title = tr_args(
+        "resp.sparkline",
+        &[&times.len().to_string(), &min.to_string(), &average.to_string(), &max.to_string()],
+    );
+
+    html! {
+        <svg class="sparkline" viewBox={format!("0 0 {WIDTH} {HEIGHT}")} width={WIDTH.to_string()} height={HEIGHT.to_string()}>
+            <title>{title}</title>
+            <polyline points={points.join(" ")} />
+        </svg>
+    }
+}
@@ -1,6 +1,10 @@
 use crate::BoltContext;
+use crate::EditMsg;
 use crate::Msg;
-use yew::{html, Html};
+use crate::helpers::i18n::tr;
+use crate::helpers::enums::{AutocompleteAction, AutocompleteField};
+use crate::view::autocomplete::{autocomplete_key, is_open};
+use yew::{html, Html, KeyboardEvent};
 
 pub fn render_header(key: &String, value: &String) -> Html {
     html! {
@@ -12,24 +16,58 @@ pub fn render_header(key: &String, value: &String) -> Html {
 }
 
 pub fn render_reqheader(
-    bctx: &mut BoltContext,
+    bctx: &BoltContext,
     index: usize,
     length: usize,
     key: &String,
     value: &String,
+    secret: bool,
 ) -> Html {
     let link = bctx.link.as_ref().unwrap();
+    let field = AutocompleteField::HeaderValue(index);
+    let suggesting = is_open(bctx, field);
+
+    let masked = secret && !bctx.reveal_secrets;
+
     html! {
         <tr>
-            <td><input id={"headerkey".to_string() + &index.to_string()} type="text" class="tableinput" value={key.to_string()} onchange={link.callback(move |_| Msg::HeaderChanged(index))}/></td>
+            <td><input id={"headerkey".to_string() + &index.to_string()} type="text" class="tableinput" value={key.to_string()} onchange={link.callback(move |_| Msg::Edit(EditMsg::HeaderChanged(index)))}/></td>
             <td class="tableline">
-                <input id={"headervalue".to_string() + &index.to_string()} type="text" class="tableinput" value={value.to_string()} onchange={link.callback(move |_| Msg::HeaderChanged(index))}/>
+                <input id={"headervalue".to_string() + &index.to_string()} type={if masked { "password" } else { "text" }} class="tableinput" value={value.to_string()} onchange={link.callback(move |_| Msg::Edit(EditMsg::HeaderChanged(index)))}
+                    oninput={link.callback(move |_| Msg::VariableAutocomplete(AutocompleteAction::Typed(field)))}
+                    onkeydown={link.callback(move |e: KeyboardEvent| autocomplete_key(&e, suggesting).unwrap_or(Msg::Nothing))}
+                    onblur={link.callback(|_| Msg::VariableAutocomplete(AutocompleteAction::Dismiss))}/>
+                <div class={if secret { "pointer secretflag active" } else { "pointer secretflag" }} role="button" tabindex="0" title={if secret { tr("secret.plain_header") } else { tr("secret.mark") }} onclick={link.callback(move |_| Msg::Edit(EditMsg::ToggleSecretHeader(index)))}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="1em" width="1em"><path d="M12 1a5 5 0 00-5 5v4H6a2 2 0 00-2 2v9a2 2 0 002 2h12a2 2 0 002-2v-9a2 2 0 00-2-2h-1V6a5 5 0 00-5-5zm-3 9V6a3 3 0 116 0v4H9z" /></svg>
+                </div>
                 if index == length - 1 {
-                    <div class="pointer" onclick={link.callback(|_| Msg::AddHeader)}>
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.add_header")} onclick={link.callback(|_| Msg::Edit(EditMsg::AddHeader))}>
                         <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
                     </div>
                 }else {
-                    <div class="pointer" onclick={link.callback(move |_| Msg::RemoveHeader(index))}>
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.remove_header")} onclick={link.callback(move |_| Msg::Edit(EditMsg::RemoveHeader(index)))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                    </div>
+                }
+            </td>
+        </tr>
+    }
+}
+
+// a row of the header template new requests start with, edited in the settings
+pub fn render_default_header(bctx: &BoltContext, index: usize, length: usize, key: &str, value: &str) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+    html! {
+        <tr>
+            <td><input id={format!("defaultheaderkey{index}")} type="text" class="tableinput" value={key.to_string()} placeholder="Accept" onchange={link.callback(move |_| Msg::DefaultHeaderChanged(index))}/></td>
+            <td class="tableline">
+                <input id={format!("defaultheadervalue{index}")} type="text" class="tableinput" value={value.to_string()} placeholder="application/json" onchange={link.callback(move |_| Msg::DefaultHeaderChanged(index))}/>
+                if index == length - 1 {
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.add_header")} onclick={link.callback(|_| Msg::AddDefaultHeader)}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                    </div>
+                } else {
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.remove_header")} onclick={link.callback(move |_| Msg::RemoveDefaultHeader(index))}>
                         <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
                     </div>
                 }
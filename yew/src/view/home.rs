@@ -1,24 +1,32 @@
 use crate::BoltContext;
 use crate::view;
-use yew::{html, Html};
+use crate::style::palette::{theme_class, theme_style};
+use crate::helpers::enums::ResizeHandle;
+use crate::Msg;
+use crate::helpers::i18n::text_direction;
+use yew::{html, Html, MouseEvent};
 
 pub fn home_view(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.clone().unwrap();
+
     html! {
-       <body>
+       <body class={theme_class(bctx.theme)} style={theme_style(bctx.theme)} dir={text_direction()} onmousemove={link.callback(|e: MouseEvent| Msg::ResizeDrag(e.client_x(), e.client_y()))} onmouseup={link.callback(|_| Msg::ResizeStop)}>
             {view::navbar::get_navbar(bctx)}
 
             <div class="main">
-                <div class="sidebars">
+                <div class="sidebars" style={format!("width: {}vw;", bctx.layout.sidebar_ratio * 100.0)}>
                     {view::sidebar1::sidebar(bctx, 0)}
                     {view::sidebar2::sidebar_requests(bctx)}
                 </div>
                 
-                <div class="resizer"></div>
+                <div class="resizer" onmousedown={link.callback(|_| Msg::ResizeStart(ResizeHandle::Sidebar))} ondblclick={link.callback(|_| Msg::ResetLayout(ResizeHandle::Sidebar))}></div>
         
                 <div class="content">
+                    {view::tabs::tab_strip(bctx)}
+
                     {view::request::request(bctx)}
 
-                    <div class="resizer2"></div>
+                    <div class="resizer2" onmousedown={link.callback(|_| Msg::ResizeStart(ResizeHandle::Request))} ondblclick={link.callback(|_| Msg::ResetLayout(ResizeHandle::Request))}></div>
         
                     {view::response::response(bctx)}
                 </div>
@@ -0,0 +1,140 @@
+use crate::helpers::i18n::tr_args;
+use crate::NavigationMsg;
+use crate::Msg;
+use serde_json::Value;
+use std::collections::HashSet;
+use yew::{html, html::Scope, Html, MouseEvent};
+
+pub fn json_tree(link: &Scope<crate::BoltApp>, body: &str, collapsed: &HashSet<String>) -> Html {
+    match serde_json::from_str::<Value>(body) {
+        Ok(value) => html! {
+            <div class="jsontree">
+                {render_node(link, None, &value, String::new(), String::new(), collapsed)}
+            </div>
+        },
+        Err(_) => html! {
+            <div>{body.to_string()}</div>
+        },
+    }
+}
+
+// `pointer` is the node's json pointer, it names the node when folding it.
+// `path` is the same location written the way accessors read, like data.items[0].id
+fn render_node(
+    link: &Scope<crate::BoltApp>,
+    key: Option<String>,
+    value: &Value,
+    pointer: String,
+    path: String,
+    collapsed: &HashSet<String>,
+) -> Html {
+    let label = match key {
+        Some(key) => html! { <><span class="jsonkey">{key}</span>{": "}</> },
+        None => html! {},
+    };
+
+    let copy_path = display_path(&path).to_string();
+    let on_context_menu = link.callback(move |e: MouseEvent| {
+        e.prevent_default();
+        Msg::CopyJsonPath(copy_path.clone())
+    });
+
+    let copy_path = display_path(&path).to_string();
+    let copy_title = tr_args("resp.copy_path", &[&copy_path]);
+    let copy_icon = html! {
+        <span class="jsoncopy pointer" title={copy_title} onclick={link.callback(move |e: MouseEvent| {
+            e.stop_propagation();
+            Msg::CopyJsonPath(copy_path.clone())
+        })}>{"⧉"}</span>
+    };
+
+    // (label, pointer segment, path segment, value)
+    let children: Vec<(String, String, String, &Value)> = match value {
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| (format!("\"{key}\""), escape_pointer(key), key_segment(key), value))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (index.to_string(), index.to_string(), format!("[{index}]"), value))
+            .collect(),
+        scalar => {
+            return html! {
+                <div class="jsonnode" oncontextmenu={on_context_menu}>{label}{render_scalar(scalar)}{copy_icon}</div>
+            }
+        }
+    };
+
+    let (open, close, summary) = match value {
+        Value::Object(_) => ("{", "}", format!("{} keys", children.len())),
+        _ => ("[", "]", format!("{} items", children.len())),
+    };
+
+    if children.is_empty() {
+        return html! {
+            <div class="jsonnode" oncontextmenu={on_context_menu}>{label}{open}{close}{copy_icon}</div>
+        };
+    }
+
+    let is_collapsed = collapsed.contains(&pointer);
+    let toggle_pointer = pointer.clone();
+
+    html! {
+        <div class="jsonnode">
+            <span class="jsontoggle pointer" oncontextmenu={on_context_menu} onclick={link.callback(move |_| Msg::Navigation(NavigationMsg::ToggleJsonNode(toggle_pointer.clone())))}>
+                {if is_collapsed {"▸ "} else {"▾ "}}
+                {label}
+                {open}
+            </span>
+            {copy_icon}
+
+            if is_collapsed {
+                <span class="jsonsummary">{format!(" {summary} ")}</span>{close}
+            } else {
+                <div class="jsonchildren">
+                    { for children.into_iter().map(|(label, segment, path_segment, child)| {
+                        render_node(link, Some(label), child, format!("{pointer}/{segment}"), format!("{path}{path_segment}"), collapsed)
+                    })}
+                </div>
+                {close}
+            }
+        </div>
+    }
+}
+
+fn render_scalar(value: &Value) -> Html {
+    let class = match value {
+        Value::String(_) => "jsonstring",
+        Value::Number(_) => "jsonnumber",
+        Value::Bool(_) => "jsonbool",
+        _ => "jsonnull",
+    };
+
+    html! { <span class={class}>{value.to_string()}</span> }
+}
+
+// .name for keys that read as identifiers, ["odd key"] for the rest
+fn key_segment(key: &str) -> String {
+    let is_identifier = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if is_identifier {
+        format!(".{key}")
+    } else {
+        format!("[{}]", serde_json::to_string(key).unwrap())
+    }
+}
+
+// paths are built with a leading dot, the root itself has no path
+fn display_path(path: &str) -> &str {
+    match path.strip_prefix('.') {
+        Some(path) => path,
+        None if path.is_empty() => ".",
+        None => path,
+    }
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
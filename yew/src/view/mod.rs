@@ -1,10 +1,20 @@
 pub mod home;
 pub mod header;
 pub mod param;
+pub mod capture;
+pub mod variable;
 pub mod navbar;
 pub mod sidebar1;
 mod sidebar2;
 mod request;
 mod response;
+mod json_tree;
+mod components;
 mod console;
-pub mod collections;
\ No newline at end of file
+pub mod collections;
+pub mod settings;
+pub mod tabs;
+pub mod notice;pub mod runner;
+pub mod toast;
+pub mod autocomplete;
+pub mod replace;
@@ -0,0 +1,77 @@
+use crate::helpers::enums::{DeleteTarget, SaveStatus};
+use crate::BoltContext;
+use crate::DeletedItem;
+use crate::CollectionsMsg;
+use crate::Msg;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::utils::{collection_position, collection_request_position, request_position};
+use yew::{html, Html};
+
+fn target_name(bctx: &BoltContext, target: &DeleteTarget) -> String {
+    let name = match target {
+        DeleteTarget::Request(id) => request_position(&bctx.main_col.requests, *id).map(|index| bctx.main_col.requests[index].name.clone()),
+        DeleteTarget::CollectionRequest(col_id, req_id) => collection_request_position(bctx, *col_id, *req_id)
+            .map(|(col_index, req_index)| bctx.collections[col_index].requests[req_index].name.clone()),
+        DeleteTarget::Collection(id) => collection_position(bctx, *id).map(|index| bctx.collections[index].name.clone()),
+    };
+
+    name.unwrap_or_default()
+}
+
+fn deleted_name(item: &DeletedItem) -> String {
+    match item {
+        DeletedItem::Request(_, request) => request.name.clone(),
+        DeletedItem::CollectionRequest(_, _, request) => request.name.clone(),
+        DeletedItem::Collection(_, collection, _) => collection.name.clone(),
+    }
+}
+
+// stays until a save goes through, the changes are only in memory until then
+pub fn save_notice(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    match &bctx.save_status {
+        SaveStatus::Failed(err) => html! {
+            <div class="notice noticeerror">
+                <div title={err.clone()}>{tr_args("save.failed", &[err])}</div>
+                <div class="noticebtn pointer" onclick={link.callback(|_| Msg::SaveState)}>{tr("save.retry")}</div>
+            </div>
+        },
+        _ => html! {},
+    }
+}
+
+pub fn delete_notice(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    if let Some(target) = &bctx.pending_delete {
+        let question = match target {
+            DeleteTarget::Collection(_) => "notice.delete_collection",
+            _ => "notice.delete_request",
+        };
+
+        html! {
+            <div class="notice">
+                <div>{tr_args(question, &[&target_name(bctx, target)])}</div>
+                <div class="noticebtn noticebtn-danger pointer" onclick={link.callback(|_| Msg::Collections(CollectionsMsg::ConfirmDelete))}>{tr("notice.delete")}</div>
+                <div class="noticebtn pointer" onclick={link.callback(|_| Msg::Collections(CollectionsMsg::CancelDelete))}>{tr("notice.cancel")}</div>
+            </div>
+        }
+    } else if let Some(item) = &bctx.last_deleted {
+        html! {
+            <div class="notice">
+                <div>{tr_args("notice.deleted", &[&deleted_name(item)])}</div>
+                <div class="noticebtn pointer" onclick={link.callback(|_| Msg::Collections(CollectionsMsg::UndoDelete))}>{tr("notice.undo")}</div>
+            </div>
+        }
+    } else if let Some(edits) = &bctx.last_replace {
+        html! {
+            <div class="notice">
+                <div>{tr_args("notice.replaced", &[&edits.len().to_string()])}</div>
+                <div class="noticebtn pointer" onclick={link.callback(|_| Msg::UndoReplace)}>{tr("notice.undo")}</div>
+            </div>
+        }
+    } else {
+        html! {}
+    }
+}
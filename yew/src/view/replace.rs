@@ -0,0 +1,154 @@
+use crate::helpers::i18n::{tr, tr_args};
+use crate::helpers::replace::{field_text, find_ranges, FieldMatch, MatchField};
+use crate::utils::{collection_position, is_secret_header, is_secret_param, request_position};
+use crate::BoltContext;
+use crate::FindReplace;
+use crate::Msg;
+use crate::Request;
+use yew::{html, Html};
+
+// characters of context shown on each side of a match
+const SNIPPET_CONTEXT: usize = 24;
+
+fn match_request<'a>(bctx: &'a BoltContext, found: &FieldMatch) -> Option<(&'a Request, String)> {
+    let (requests, col_name) = match found.collection {
+        Some(id) => {
+            let col = &bctx.collections[collection_position(bctx, id)?];
+            (&col.requests, col.name.clone())
+        }
+        None => (&bctx.main_col.requests, tr("replace.main_list")),
+    };
+
+    Some((&requests[request_position(requests, found.request)?], col_name))
+}
+
+fn field_label(request: &Request, field: MatchField) -> String {
+    let row_name = |rows: &Vec<Vec<String>>, index: usize| rows.get(index).and_then(|row| row.first()).cloned().unwrap_or_default();
+
+    match field {
+        MatchField::Url => tr("replace.field_url"),
+        MatchField::Header(index) => tr_args("replace.field_header", &[&row_name(&request.headers, index)]),
+        MatchField::Param(index) => tr_args("replace.field_param", &[&row_name(&request.params, index)]),
+        MatchField::Body => tr("replace.field_body"),
+    }
+}
+
+// the first match with a little of the text around it, on one line
+fn snippet(bctx: &BoltContext, request: &Request, dialog: &FindReplace, field: MatchField) -> String {
+    let masked = !bctx.reveal_secrets
+        && match field {
+            MatchField::Header(index) => request.headers.get(index).is_some_and(|row| is_secret_header(request, &row[0])),
+            MatchField::Param(index) => request.params.get(index).is_some_and(|row| is_secret_param(request, &row[0])),
+            _ => false,
+        };
+
+    if masked {
+        return "••••••".to_string();
+    }
+
+    let text = match field_text(request, field) {
+        Some(text) => text,
+        None => return String::new(),
+    };
+
+    let (start, end) = match find_ranges(text, &dialog.query, dialog.options).first() {
+        Some(&range) => range,
+        None => return String::new(),
+    };
+
+    let from = text[..start].char_indices().rev().nth(SNIPPET_CONTEXT - 1).map_or(0, |(index, _)| index);
+    let to = text[end..].char_indices().nth(SNIPPET_CONTEXT).map_or(text.len(), |(index, _)| end + index);
+
+    let mut shown = String::new();
+
+    if from > 0 {
+        shown.push('…');
+    }
+
+    shown.push_str(&text[from..to].replace(['\n', '\r', '\t'], " "));
+
+    if to < text.len() {
+        shown.push('…');
+    }
+
+    shown
+}
+
+// drawn over whatever page is open, until closed
+pub fn find_replace_dialog(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let dialog = match &bctx.find_replace {
+        Some(dialog) => dialog,
+        None => return html! {},
+    };
+
+    let selected: usize = dialog
+        .matches
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !dialog.skipped.contains(index))
+        .map(|(_, found)| found.count)
+        .sum();
+
+    let mut rows = Vec::new();
+    let mut last_request = None;
+
+    for (index, found) in dialog.matches.iter().enumerate() {
+        let (request, col_name) = match match_request(bctx, found) {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        // a request's matches are next to each other, its name only goes above the first
+        if last_request != Some((found.collection, found.request)) {
+            last_request = Some((found.collection, found.request));
+
+            rows.push(html! {
+                <div class="findrequest">
+                    <span>{&request.name}</span>
+                    <span class="findcollection">{col_name}</span>
+                </div>
+            });
+        }
+
+        rows.push(html! {
+            <label class="findrow pointer">
+                <input type="checkbox" checked={!dialog.skipped.contains(&index)} onchange={link.callback(move |_| Msg::ToggleReplaceMatch(index))}/>
+                <span class="findfield">{field_label(request, found.field)}</span>
+                <span class="findcount">{format!("×{}", found.count)}</span>
+                <span class="findsnippet">{snippet(bctx, request, dialog, found.field)}</span>
+            </label>
+        });
+    }
+
+    html! {
+        <div class="findreplace" role="dialog" aria-label={tr("replace.title")}>
+            <div class="findreplacehead">
+                <div>{tr("replace.title")}</div>
+                <div class="toastclose pointer" role="button" tabindex="0" aria-label={tr("replace.close")} onclick={link.callback(|_| Msg::CloseFindReplace)}>{"✕"}</div>
+            </div>
+
+            <input id="findinput" type="text" class="findinput" placeholder={tr("replace.find")} value={dialog.query.clone()} oninput={link.callback(|_| Msg::FindReplaceChanged)}/>
+            <input id="replaceinput" type="text" class="findinput" placeholder={tr("replace.replace")} value={dialog.replacement.clone()} oninput={link.callback(|_| Msg::FindReplaceChanged)}/>
+
+            <div class="findoptions">
+                <label class="pointer"><input type="checkbox" checked={dialog.options.case_sensitive} onchange={link.callback(|_| Msg::ToggleFindCase)}/>{tr("replace.case")}</label>
+                <label class="pointer"><input type="checkbox" checked={dialog.options.whole_word} onchange={link.callback(|_| Msg::ToggleFindWholeWord)}/>{tr("replace.whole_word")}</label>
+            </div>
+
+            <div class="findresults">
+                if dialog.matches.is_empty() && !dialog.query.is_empty() {
+                    <div class="capturetitle">{tr("replace.no_matches")}</div>
+                }
+                { for rows }
+            </div>
+
+            <div class="findreplacefoot">
+                <div class={if selected > 0 { "noticebtn pointer" } else { "noticebtn disabled" }} onclick={link.callback(|_| Msg::ApplyReplace)}>
+                    {tr_args("replace.apply", &[&selected.to_string()])}
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,44 +1,121 @@
 // use web_sys::InputEvent;
-use yew::KeyboardEvent;
+use web_sys::HtmlSelectElement;
+use yew::{Event, KeyboardEvent, MouseEvent, TargetCast};
 use crate::view;
+use crate::view::components::{status_class, tab, tag_chip};
 use crate::BoltContext;
+use crate::{CollectionsMsg, EditMsg, NavigationMsg, SendMsg};
 use crate::Msg;
+use crate::helpers::i18n::{language, tr, tr_args};
+use crate::helpers::url::{query_string, validate_method, validate_url};
 use crate::Page;
 use crate::Request;
-use yew::{html, Html};
+use crate::MAX_RETRIES;
+use crate::StatusPoint;
+use crate::{DEFAULT_REPEAT_INTERVAL_MS, MIN_REPEAT_INTERVAL_MS};
+use crate::RequestPreview;
+use std::collections::BTreeSet;
+use crate::process::tabs::{secret_values, collection_variables, current_ref, has_current_request, request_variables};
+use crate::helpers::dynamic::resolved_parts;
+use crate::view::autocomplete::{autocomplete_key, is_open};
+use crate::utils::{format_timestamp, is_secret_header, is_secret_param, mask_preview, mask_secrets, SECRET_MASK, preview_request, tab_key_target, save_scroll_later, get_body_selection, format_size, get_doc_link, get_input_value, join_base_url, render_markdown};
+use yew::{html, AttrValue, Callback, Html};
 use crate::helpers::enums::{
-    RequestTabs::{self, Body, Params, Headers},
-    HttpMethod,
+    RequestTabs::{self, Body, Params, Headers, Docs, Settings, Auth, Tests, Preview},
+    AuthMode, AutocompleteAction, AutocompleteField, HttpMethod, HttpVersion, ScrollPanel, Language, RetryPolicy, SignatureEncoding, SignedContent, SigningAlgorithm, Transport,
 };
 
 pub fn request(bctx: &mut BoltContext) -> Html {
     let link = bctx.link.as_ref().unwrap();
 
-    let can_display = (bctx.page == Page::Collections
-        && !bctx.collections.is_empty()
-        && !bctx.collections[bctx.col_current[0]].requests.is_empty())
-        || (bctx.page == Page::Home && !bctx.main_col.requests.is_empty());
+    // the selection can point past the end for a moment, e.g. right after the last request went
+    let can_display = bctx.active_tab.is_some() && has_current_request(bctx);
 
-    let mut request = Request::new();
+    // borrowed, the request carries its whole response and rendering must not copy that
+    let empty = Request::new();
+    let no_base_url = None;
+    let no_variables = Vec::new();
+    let no_secrets = BTreeSet::new();
 
-    if can_display {
-        request = if bctx.page == Page::Home {
-            bctx.main_col.requests[bctx.main_current].clone()
-        } else if bctx.page == Page::Collections {
-            bctx.collections[bctx.col_current[0]].requests[bctx.col_current[1]].clone()
-        } else {
-            Request::new()
-        };
-    }
+    let (request, base_url, collection_vars, secret_vars) = match bctx.page {
+        Page::Home if can_display => (
+            &bctx.main_col.requests[bctx.main_current],
+            &bctx.main_col.base_url,
+            &bctx.main_col.variables,
+            &bctx.main_col.secret_variables,
+        ),
+        Page::Collections if can_display => {
+            let col = &bctx.collections[bctx.col_current[0]];
+            (&col.requests[bctx.col_current[1]], &col.base_url, &col.variables, &col.secret_variables)
+        }
+        _ => (&empty, &no_base_url, &no_variables, &no_secrets),
+    };
+
+    // the selected collection, when it has nothing to select in it
+    let empty_collection = Some(bctx.col_current[0])
+        .filter(|_| bctx.page == Page::Collections)
+        .filter(|col| bctx.collections.get(*col).is_some_and(|col| col.requests.is_empty()));
+
+    let effective_url = join_base_url(base_url.as_deref(), &request.url);
+
+    // {{variables}} only have a value at send time, those urls are checked then
+    let url_error = if request.url.trim().is_empty() || effective_url.contains("{{") {
+        None
+    } else {
+        validate_url(&effective_url).err()
+    };
+    let request_tab = request.req_tab;
+    let target = current_ref(bctx);
+
+    let suggesting = is_open(bctx, AutocompleteField::Url);
+
+    // the url as it goes out, shown while it has {{variables}} in it
+    let url_parts = target.filter(|_| request.url.contains("{{")).map(|target| {
+        let secrets = secret_values(bctx, &target);
+
+        resolved_parts(&effective_url, &request_variables(bctx, &target))
+            .into_iter()
+            .map(|(text, unresolved)| (mask_secrets(&text, &secrets), unresolved))
+            .collect::<Vec<_>>()
+    });
+
+    // secret values can't be edited here while they're hidden, the rows below still can
+    let query_masked = !bctx.reveal_secrets && !request.secret_params.is_empty();
+    let query = if query_masked {
+        let params: Vec<Vec<String>> = request
+            .params
+            .iter()
+            .map(|row| {
+                if is_secret_param(request, &row[0]) && !row[1].is_empty() {
+                    vec![row[0].clone(), SECRET_MASK.to_string()]
+                } else {
+                    row.clone()
+                }
+            })
+            .collect();
+
+        query_string(&params, true)
+    } else {
+        query_string(&request.params, request.raw_query)
+    };
 
     let selected_method = request.method.to_string();
+    let custom_method = match &request.method {
+        HttpMethod::Custom(name) => Some(name.clone()),
+        _ => None,
+    };
+    let method_error = validate_method(&request.method).err();
+
+    let repeat_interval = bctx.repeat.map(|repeat| repeat.interval_ms);
+
+    let (client_cert, client_key) = request.tls.client_cert.clone().unwrap_or_default();
 
     html! {
-        <div class="req">
+        <div class="req" style={format!("height: {}vh;", bctx.layout.request_ratio * 95.0)}>
         if can_display {
             <div class="requestbar">
                 <div class="">
-                    <select id="methodselect" class="methodselect pointer" onchange={link.callback(|_| Msg::MethodChanged)}>
+                    <select id="methodselect" class="methodselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::MethodChanged))}>
                         { for (0..HttpMethod::count()).map(|index| {
                             let current_method_option: HttpMethod = HttpMethod::from(index);
                             let value = current_method_option.to_string().to_lowercase();
@@ -46,58 +123,670 @@ pub fn request(bctx: &mut BoltContext) -> Html {
                                 <option value={value.clone()} selected={is_selected(&selected_method, &value)}>{current_method_option}</option>
                             }
                         })}
+                        <option value="other" selected={custom_method.is_some()}>{tr("req.other_method")}</option>
                     </select>
                 </div>
 
-                <input id="urlinput" class="urlinput" type="text" value={request.url.clone()} placeholder="http://" onkeydown={link.callback(|e: KeyboardEvent| { if e.key() == "Enter" { Msg::SendPressed } else { Msg::Nothing } })}  oninput={link.callback(|_|{ Msg::UrlChanged })} />
+                if let Some(name) = custom_method {
+                    <input id="methodinput" class={if method_error.is_some() { "methodinput invalid" } else { "methodinput" }} type="text" value={name} placeholder={tr("req.custom_method")} spellcheck="false" oninput={link.callback(|_| Msg::Edit(EditMsg::MethodChanged))} />
+                }
+
+                if let Some(err) = &method_error {
+                    <div class="urlerror" title={tr("req.invalid_method")}>{err.to_string()}</div>
+                }
+
+                <input id="urlinput" class={if url_error.is_some() { "urlinput invalid" } else { "urlinput" }} type="text" value={request.url.clone()} placeholder="http://" onkeydown={link.callback(move |e: KeyboardEvent| autocomplete_key(&e, suggesting).unwrap_or_else(|| if e.key() == "Enter" { Msg::Send(SendMsg::SendPressed) } else { Msg::Nothing }))}  oninput={link.callback(|_|{ Msg::Edit(EditMsg::UrlChanged) })} onblur={link.callback(|_| Msg::VariableAutocomplete(AutocompleteAction::Dismiss))} />
+
+                if let Some(err) = &url_error {
+                    <div class="urlerror" title={tr("req.invalid_url")}>{err.to_string()}</div>
+                }
 
-                <button class="sendbtn pointer" type="button" onclick={link.callback(|_| Msg::SendPressed)}>{"Send"}</button>
+                if request.tls.insecure_skip_verify {
+                    <div class="tlsbadge" title={tr("req.insecure_title")}>{tr("req.insecure")}</div>
+                }
+
+                <div class="repeatbtn pointer" role="button" tabindex="0" title={tr("req.check_host_title")} onclick={link.callback(|_| Msg::Send(SendMsg::CheckHost))}>{tr("req.check_host")}</div>
+
+                if let Some(interval_ms) = repeat_interval {
+                    <div class="repeatbtn pointer" role="button" tabindex="0" title={tr("req.stop_polling")} onclick={link.callback(|_| Msg::Send(SendMsg::StopRepeat))}>{tr_args("req.stop_interval", &[&(interval_ms as f64 / 1000.0).to_string()])}</div>
+                } else {
+                    <input id="repeatinterval" class="repeatinput" type="number" min={MIN_REPEAT_INTERVAL_MS.to_string()} step="500" value={DEFAULT_REPEAT_INTERVAL_MS.to_string()} title={tr("req.polling_interval")} />
+                    <div class="repeatbtn pointer" role="button" tabindex="0" title={tr("req.repeat_title")} onclick={link.callback(|_| Msg::Send(SendMsg::StartRepeat(get_input_value("repeatinterval").trim().parse().unwrap_or(DEFAULT_REPEAT_INTERVAL_MS))))}>{tr("req.repeat")}</div>
+                }
+
+                <select id="snippetselect" class="snippetselect pointer" title={tr("req.copy_as_title")} onchange={link.callback(|e: Event| {
+                    let select = e.target_unchecked_into::<HtmlSelectElement>();
+                    let value = select.value();
+                    select.set_value("");
+
+                    match value.parse::<usize>() {
+                        Ok(index) if index < Language::count() => Msg::Send(SendMsg::CopySnippet(Language::from(index))),
+                        _ => Msg::Nothing,
+                    }
+                })}>
+                    <option value="" selected=true>{tr("req.copy_as")}</option>
+                    { for (0..Language::count()).map(|index| html! {
+                        <option value={index.to_string()}>{Language::from(index)}</option>
+                    }) }
+                </select>
+
+                <button class="sendbtn pointer" type="button" onclick={link.callback(|_| Msg::Send(SendMsg::SendPressed))}>{tr("req.send")}</button>
             </div>
 
-            <div class="reqtabs">
-                <div id="req_body_tab" class={if is_tab_selected(&request.req_tab, Body) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::ReqBodyPressed)}>{"Body"}</div>
-                <div id="req_params_tab" class={if is_tab_selected(&request.req_tab, Params) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::ReqParamsPressed)}>{"Params"}</div>
-                <div id="req_headers_tab" class={if is_tab_selected(&request.req_tab, Headers) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::ReqHeadersPressed)}>{"Headers"}</div>
+            if let Some(parts) = &url_parts {
+                <div class="urlpreview" title={tr("req.resolved_url")}>
+                    { for parts.iter().map(|(text, unresolved)| html! {
+                        if *unresolved {
+                            <span class="unresolvedvar" title={tr("req.unresolved_variable")}>{text.clone()}</span>
+                        } else {
+                            <span>{text.clone()}</span>
+                        }
+                    })}
+                </div>
+            }
+
+            <div class="basebar">
+                <input id="baseurlinput" class="baseurlinput" type="text" value={base_url.clone().unwrap_or_default()} placeholder={if bctx.page == Page::Home {tr("req.base_url")} else {tr("req.collection_base_url")}} onchange={link.callback(|_| Msg::Collections(CollectionsMsg::BaseUrlChanged))} />
+
+                if effective_url != request.url {
+                    <div class="effectiveurl" title={tr("req.effective_url")}>{effective_url.clone()}</div>
+                }
+
+                <div class="requestmeta">{request_meta(request)}</div>
+            </div>
+
+            if repeat_interval.is_some() && !request.status_log.is_empty() {
+                <div class="statusstrip">
+                    { for request.status_log.iter().map(|point| html! {
+                        <div class={status_dot_class(point)} title={status_dot_title(point)}></div>
+                    }) }
+                </div>
+            }
+
+            <div class="reqtabs" role="tablist" aria-label={tr("a11y.request_tabs")} onkeydown={link.callback(move |e: KeyboardEvent| request_tab_key(&e, request_tab))}>
+                {tab(link, Body.element_id(), is_tab_selected(&request.req_tab, Body), tr("tab.body"), None, Msg::Navigation(NavigationMsg::ReqBodyPressed))}
+                {tab(link, Params.element_id(), is_tab_selected(&request.req_tab, Params), tr("tab.params"), None, Msg::Navigation(NavigationMsg::ReqParamsPressed))}
+                {tab(link, Headers.element_id(), is_tab_selected(&request.req_tab, Headers), tr("tab.headers"), None, Msg::Navigation(NavigationMsg::ReqHeadersPressed))}
+                {tab(link, Docs.element_id(), is_tab_selected(&request.req_tab, Docs), tr("tab.docs"), None, Msg::Navigation(NavigationMsg::ReqDocsPressed))}
+                {tab(link, Auth.element_id(), is_tab_selected(&request.req_tab, Auth), tr("tab.auth"), None, Msg::Navigation(NavigationMsg::ReqAuthPressed))}
+                {tab(link, Tests.element_id(), is_tab_selected(&request.req_tab, Tests), tr("tab.tests"), None, Msg::Navigation(NavigationMsg::ReqTestsPressed))}
+                {tab(link, Settings.element_id(), is_tab_selected(&request.req_tab, Settings), tr("tab.settings"), None, Msg::Navigation(NavigationMsg::ReqSettingsPressed))}
+                {tab(link, Preview.element_id(), is_tab_selected(&request.req_tab, Preview), tr("tab.preview"), Some(tr("preview.title")), Msg::Navigation(NavigationMsg::ReqPreviewPressed))}
             </div>
 
             <div class="tabcontent">
                 if is_tab_selected(&request.req_tab, Body) {
-                    <textarea id="reqbody" class="reqbody" value={request.body.clone()} placeholder="Request body" onchange={link.callback(|_| Msg::BodyChanged)}>
+                    if let Some(content_type) = request.suggested_content_type {
+                        <div class="suggestbar">
+                            <div>{tr_args("body.missing_content_type", &[content_type])}</div>
+                            <div class="tab pointer" role="button" tabindex="0" onclick={link.callback(|_| Msg::Edit(EditMsg::AcceptContentType))}>{tr("body.add_header")}</div>
+                            <div class="tab pointer" role="button" tabindex="0" onclick={link.callback(|_| Msg::Navigation(NavigationMsg::DismissContentType))}>{tr("body.dismiss")}</div>
+                        </div>
+                    }
+
+                    if matches!(request.method, HttpMethod::GET | HttpMethod::HEAD) && !request.body_note_dismissed && (!request.body.is_empty() || request.body_file.is_some()) {
+                        <div class="suggestbar">
+                            <div>{tr_args("body.ignored_on_method", &[&request.method.to_string()])}</div>
+                            <div class="tab pointer" role="button" tabindex="0" onclick={link.callback(|_| Msg::Navigation(NavigationMsg::DismissBodyNote))}>{tr("body.dismiss")}</div>
+                        </div>
+                    }
+
+                    if let Some(path) = &request.body_file {
+                        <div class="bodyfile">
+                            <div class="bodyfilename" title={path.clone()}>{file_name(path)}</div>
+                            {match &request.body_file_info {
+                                Some(Ok(size)) => html! { <div class="bodyfilesize">{format_size(*size)}</div> },
+                                Some(Err(err)) => html! { <div class="bodyfileerror" title={err.clone()}>{tr("body.file_unreadable")}</div> },
+                                None => html! { <div class="tab pointer" role="button" tabindex="0" onclick={link.callback(|_| Msg::RefreshBodyFileInfo)}>{tr("body.check_size")}</div> },
+                            }}
+                            <div class="tab pointer" role="button" tabindex="0" title={tr("body.clear_file_title")} onclick={link.callback(|_| Msg::Edit(EditMsg::ClearBodyFile))}>{tr("body.clear_file")}</div>
+                        </div>
+                        <div class="bodyfilenote">{tr("body.file_note")}</div>
+                    } else {
+                        <div class="bodyfilebar">
+                            <input id="bodyfileinput" class="bodyfileinput" type="text" placeholder={tr("body.file_placeholder")} onchange={link.callback(|_| Msg::Edit(EditMsg::BodyFileChanged))} />
+                            <div class="tab pointer" role="button" tabindex="0" title={tr("body.load_file_title")} onclick={link.callback(|_| Msg::PickBodyFile)}>{tr("body.load_file")}</div>
+                        </div>
+
+                        <textarea id="reqbody" class="reqbody" value={request.body.clone()} placeholder={tr("body.placeholder")} onchange={link.callback(|_| Msg::Edit(EditMsg::BodyChanged))}
+                            onscroll={Callback::from(move |_| if let Some(target) = target { save_scroll_later(target, ScrollPanel::RequestBody) })}
+                            onblur={link.callback(move |_| match (target, get_body_selection()) {
+                                (Some(target), Some((start, end))) => Msg::Navigation(NavigationMsg::BodySelectionChanged(target, start, end)),
+                                _ => Msg::Nothing,
+                            })}>
 
-                    </textarea>
+                        </textarea>
+                    }
                 } else if is_tab_selected(&request.req_tab, Params) {
                     <div class="reqheaders">
+                        <div class="querybar">
+                            <input id="queryinput" class="tableinput queryinput" type="text" spellcheck="false" value={query} readonly={query_masked} placeholder="key=value&key=value" title={tr("params.query_title")} onchange={link.callback(|_| Msg::Edit(EditMsg::QueryChanged))}/>
+                            <label class="pointer" title={tr("params.raw_query_title")}>
+                                <input type="checkbox" class="pointer" checked={request.raw_query} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleRawQuery))}/>
+                                {tr("params.raw_query")}
+                            </label>
+                        </div>
+                        if !request.secret_params.is_empty() {
+                            <div class="authbar">
+                                <div class="tab pointer" role="button" tabindex="0" title={tr("secret.reveal_title")} onclick={link.callback(|_| Msg::ToggleRevealSecrets)}>{if bctx.reveal_secrets { tr("secret.hide") } else { tr("secret.reveal") }}</div>
+                            </div>
+                        }
                         <table>
                             <tr>
-                                <th>{"Key"}</th>
-                                <th>{"Value"}</th>
+                                <th>{tr("params.key")}</th>
+                                <th>{tr("header.value")}</th>
                             </tr>
-                            { for request.params.iter().enumerate().map(|(index, header)| view::param::render_params(bctx, index, request.params.len(), &header[0], &header[1])) }
+                            { for request.params.iter().enumerate().map(|(index, header)| view::param::render_params(bctx, index, request.params.len(), &header[0], &header[1], is_secret_param(request, &header[0]))) }
                         </table>
                     </div>
 
                 } else if is_tab_selected(&request.req_tab, Headers) {
+                    <div class="reqheaders">
+                        if !request.secret_headers.is_empty() {
+                            <div class="authbar">
+                                <div class="tab pointer" role="button" tabindex="0" title={tr("secret.reveal_title")} onclick={link.callback(|_| Msg::ToggleRevealSecrets)}>{if bctx.reveal_secrets { tr("secret.hide") } else { tr("secret.reveal") }}</div>
+                            </div>
+                        }
+                        <table>
+                            <tr>
+                                <th>{tr("header.header")}</th>
+                                <th>{tr("header.value")}</th>
+                            </tr>
+                            { for request.headers.iter().enumerate().map(|(index, header)| view::header::render_reqheader(bctx, index, request.headers.len(), &header[0], &header[1], is_secret_header(request, &header[0]))) }
+                        </table>
+                    </div>
+                } else if is_tab_selected(&request.req_tab, Docs) {
+                    <div class="reqdocs">
+                        <div class="tagbar">
+                            { for request.tags.iter().enumerate().map(|(index, name)| html! {
+                                <span class="tagedit">
+                                    {tag_chip(name)}
+                                    <span class="pointer" role="button" tabindex="0" aria-label={tr_args("tags.remove", &[name])} onclick={link.callback(move |_| Msg::Edit(EditMsg::RemoveTag(index)))}>{"×"}</span>
+                                </span>
+                            }) }
+                            <input id="taginput" class="taginput" type="text" placeholder={tr("tags.add")} onkeydown={link.callback(|e: KeyboardEvent| if e.key() == "Enter" { Msg::Edit(EditMsg::AddTag) } else { Msg::Nothing })} />
+                        </div>
+
+                        <div class="docsbar">
+                            <div class="tab pointer" role="button" tabindex="0" onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ToggleDocsPreview))}>{if request.docs_preview { tr("docs.edit") } else { tr("docs.preview") }}</div>
+                        </div>
+
+                        if request.docs_preview {
+                            <div class="docspreview" onclick={link.callback(|e: MouseEvent| match get_doc_link(&e) { Some(url) => Msg::OpenLink(url), None => Msg::Nothing })}>
+                                {Html::from_html_unchecked(AttrValue::from(render_markdown(&request.description)))}
+                            </div>
+                        } else {
+                            <textarea id="reqdescription" class="reqbody" value={request.description.clone()} placeholder={tr("docs.placeholder")} onchange={link.callback(|_| Msg::Edit(EditMsg::DescriptionChanged))}>
+
+                            </textarea>
+                        }
+                    </div>
+                } else if is_tab_selected(&request.req_tab, Auth) {
+                    <div class="reqheaders">
+                        <div class="authbar">
+                            <select id="authmode" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::AuthModeChanged))}>
+                                { for (0..AuthMode::count()).map(|index| {
+                                    let mode = AuthMode::from(index);
+                                    html! {
+                                        <option value={index.to_string()} selected={mode == request.auth.mode}>{mode}</option>
+                                    }
+                                })}
+                            </select>
+
+                            if request.auth.mode == AuthMode::OAuth2ClientCredentials {
+                                <div class="tab pointer" role="button" tabindex="0" onclick={link.callback(|_| Msg::Send(SendMsg::FetchToken))}>{tr("auth.fetch_token")}</div>
+                            }
+                        </div>
+
+                        if request.auth.mode == AuthMode::OAuth2ClientCredentials {
+                            <table>
+                                <tr>
+                                    <th>{tr("auth.field")}</th>
+                                    <th>{tr("header.value")}</th>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.token_url")}</td>
+                                    <td><input id="authtokenurl" type="text" class="tableinput" value={request.auth.token_url.clone()} placeholder="https://auth.example.com/oauth/token" onchange={link.callback(|_| Msg::Edit(EditMsg::AuthFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.client_id")}</td>
+                                    <td><input id="authclientid" type="text" class="tableinput" value={request.auth.client_id.clone()} onchange={link.callback(|_| Msg::Edit(EditMsg::AuthFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.client_secret")}</td>
+                                    <td><input id="authclientsecret" type="password" class="tableinput" value={request.auth.client_secret.clone()} onchange={link.callback(|_| Msg::Edit(EditMsg::AuthFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.scopes")}</td>
+                                    <td><input id="authscopes" type="text" class="tableinput" value={request.auth.scopes.clone()} placeholder="read write" onchange={link.callback(|_| Msg::Edit(EditMsg::AuthFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.remember_secret")}</td>
+                                    <td><input id="authremember" type="checkbox" class="pointer" checked={request.auth.remember_secret} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleRememberSecret))}/></td>
+                                </tr>
+                            </table>
+
+                            <div class="authstatus">{token_status(request)}</div>
+
+                            if !request.auth.token_error.is_empty() && !request.auth.fetching {
+                                <pre class="autherror">{request.auth.token_error.clone()}</pre>
+                            }
+                        }
+
+                        if request.auth.mode == AuthMode::Digest {
+                            <table>
+                                <tr>
+                                    <th>{tr("auth.field")}</th>
+                                    <th>{tr("header.value")}</th>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.username")}</td>
+                                    <td><input id="authusername" type="text" class="tableinput" value={request.auth.username.clone()} onchange={link.callback(|_| Msg::Edit(EditMsg::DigestFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.password")}</td>
+                                    <td><input id="authpassword" type="password" class="tableinput" value={request.auth.password.clone()} onchange={link.callback(|_| Msg::Edit(EditMsg::DigestFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.remember_password")}</td>
+                                    <td><input id="authremember" type="checkbox" class="pointer" checked={request.auth.remember_secret} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleRememberSecret))}/></td>
+                                </tr>
+                            </table>
+                        }
+
+                        <div class="authbar signbar">
+                            <select id="signalgorithm" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::SigningAlgorithmChanged))}>
+                                { for (0..SigningAlgorithm::count()).map(|index| {
+                                    let algorithm = SigningAlgorithm::from(index);
+                                    html! {
+                                        <option value={index.to_string()} selected={algorithm == request.signing.algorithm}>{algorithm}</option>
+                                    }
+                                })}
+                            </select>
+                        </div>
+
+                        if request.signing.algorithm != SigningAlgorithm::Off {
+                            <table>
+                                <tr>
+                                    <th>{tr("auth.field")}</th>
+                                    <th>{tr("header.value")}</th>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.sign_secret")}</td>
+                                    <td><input id="signsecret" type="password" class="tableinput" value={request.signing.secret.clone()} onchange={link.callback(|_| Msg::Edit(EditMsg::SigningFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.sign_header")}</td>
+                                    <td><input id="signheader" type="text" class="tableinput" value={request.signing.header.clone()} onchange={link.callback(|_| Msg::Edit(EditMsg::SigningFieldsChanged))}/></td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.sign_content")}</td>
+                                    <td>
+                                        <select id="signcontent" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::SigningFieldsChanged))}>
+                                            { for (0..SignedContent::count()).map(|index| {
+                                                let content = SignedContent::from(index);
+                                                html! {
+                                                    <option value={index.to_string()} selected={content == request.signing.content}>{content}</option>
+                                                }
+                                            })}
+                                        </select>
+                                    </td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.sign_encoding")}</td>
+                                    <td>
+                                        <select id="signencoding" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::SigningFieldsChanged))}>
+                                            { for (0..SignatureEncoding::count()).map(|index| {
+                                                let encoding = SignatureEncoding::from(index);
+                                                html! {
+                                                    <option value={index.to_string()} selected={encoding == request.signing.encoding}>{encoding}</option>
+                                                }
+                                            })}
+                                        </select>
+                                    </td>
+                                </tr>
+                                <tr>
+                                    <td>{tr("auth.remember_secret")}</td>
+                                    <td><input id="signremember" type="checkbox" class="pointer" checked={request.signing.remember_secret} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleRememberSigningSecret))}/></td>
+                                </tr>
+                            </table>
+
+                            if request.signing.content == SignedContent::CanonicalRequest {
+                                <div class="authstatus">{tr("auth.canonical_note")}</div>
+                            }
+                        }
+                    </div>
+                } else if is_tab_selected(&request.req_tab, Preview) {
+                    {preview_view(&{
+                        let mut preview = preview_request(request, &bctx.settings, base_url.as_deref(), &target.map(|target| collection_variables(bctx, &target)).unwrap_or_default());
+                        mask_preview(&mut preview, &target.map(|target| secret_values(bctx, &target)).unwrap_or_default());
+                        preview
+                    })}
+                } else if is_tab_selected(&request.req_tab, Tests) {
+                    <div class="reqheaders capturepanel">
+                        <div class="capturetitle">{tr("tests.local_title")}</div>
+                        <table>
+                            <tr>
+                                <th>{tr("tests.variable")}</th>
+                                <th>{tr("header.value")}</th>
+                            </tr>
+                            { for request.local_variables.iter().enumerate().map(|(index, variable)| view::variable::render_local_variable(bctx, index, request.local_variables.len(), &variable[0], &variable[1])) }
+                        </table>
+                    </div>
+
+                    <div class="reqheaders capturepanel">
+                        <div class="authbar">
+                            <div class="capturetitle">{tr("tests.collection_title")}</div>
+                            if !secret_vars.is_empty() {
+                                <div class="tab pointer" role="button" tabindex="0" title={tr("secret.reveal_title")} onclick={link.callback(|_| Msg::ToggleRevealSecrets)}>{if bctx.reveal_secrets { tr("secret.hide") } else { tr("secret.reveal") }}</div>
+                            }
+                        </div>
+                        <div class="authstatus">{tr("tests.precedence")}</div>
+                        <table>
+                            <tr>
+                                <th>{tr("tests.variable")}</th>
+                                <th>{tr("header.value")}</th>
+                            </tr>
+                            { for collection_vars.iter().enumerate().map(|(index, variable)| view::variable::render_collection_variable(bctx, index, collection_vars.len(), &variable[0], &variable[1], secret_vars.contains(variable[0].trim()))) }
+                        </table>
+                    </div>
+
+                    <div class="reqheaders capturepanel">
+                        <div class="authbar">
+                            <div class="capturetitle">{tr("tests.capture_title")}</div>
+                            if !bctx.variables.is_empty() {
+                                <div class="tab pointer" role="button" tabindex="0" title={tr("tests.clear_values_title")} onclick={link.callback(|_| Msg::ClearVariables)}>{tr("tests.clear_values")}</div>
+                            }
+                        </div>
+                        <table>
+                            <tr>
+                                <th>{tr("tests.variable")}</th>
+                                <th>{tr("tests.source")}</th>
+                                <th>{tr("tests.current_value")}</th>
+                            </tr>
+                            { for request.captures.iter().enumerate().map(|(index, capture)| view::capture::render_capture(bctx, index, request.captures.len(), &capture[0], &capture[1])) }
+                        </table>
+
+                        if !bctx.variables.is_empty() {
+                            <div class="capturetitle">{tr("tests.captured")}</div>
+                            <div class="variablelist">
+                                { for bctx.variables.iter().map(|(name, value)| html! {
+                                    <div class="variablerow" title={value.clone()}>
+                                        <span class="variablename">{"{{"}{name}{"}}"}</span>
+                                        <span class="capturevalue">{value}</span>
+                                        <span class="variabletag">{tr("tests.runtime")}</span>
+                                        if request.local_variables.iter().any(|row| row[0].trim() == name) {
+                                            <span class="variabletag" title={tr("tests.overridden_local")}>{tr("tests.overridden")}</span>
+                                        } else if collection_vars.iter().any(|row| row[0].trim() == name) {
+                                            <span class="variabletag" title={tr("tests.overridden_collection")}>{tr("tests.overridden")}</span>
+                                        }
+                                    </div>
+                                }) }
+                            </div>
+                        }
+                    </div>
+
+                    <div class="reqdocs">
+                        <textarea id="reqschema" class="reqbody schemainput" value={request.response_schema.clone().unwrap_or_default()} placeholder={tr("tests.schema_placeholder")} onchange={link.callback(|_| Msg::Edit(EditMsg::SchemaChanged))}>
+
+                        </textarea>
+
+                        if let Some(violations) = &request.response.schema_violations {
+                            if violations.is_empty() {
+                                <div class="schemapass">{tr("tests.schema_pass")}</div>
+                            } else {
+                                <div class="schemaviolations">
+                                    { for violations.iter().map(|violation| html! {
+                                        <div class="schemaviolation">
+                                            <span class="schemapointer">{if violation.pointer.is_empty() {"/".to_string()} else {violation.pointer.clone()}}</span>
+                                            <span>{violation.message.clone()}</span>
+                                        </div>
+                                    })}
+                                </div>
+                            }
+                        }
+                    </div>
+                } else if is_tab_selected(&request.req_tab, Settings) {
                     <div class="reqheaders">
                         <table>
                             <tr>
-                                <th>{"Header"}</th>
-                                <th>{"Value"}</th>
+                                <th>{tr("settings.setting")}</th>
+                                <th>{tr("header.value")}</th>
+                            </tr>
+                            <tr>
+                                <td>{tr("settings.proxy")}</td>
+                                <td><input id="reqproxy" type="text" class="tableinput" value={request.proxy.clone()} placeholder={tr("reqsettings.proxy_placeholder")} onchange={link.callback(|_| Msg::Edit(EditMsg::ReqProxyChanged))}/></td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.user_agent")}</td>
+                                <td><input id="requseragent" type="text" class="tableinput" value={request.user_agent.clone()} placeholder={tr("reqsettings.user_agent_placeholder")} disabled={request.omit_user_agent} onchange={link.callback(|_| Msg::Edit(EditMsg::ReqUserAgentChanged))}/></td>
+                            </tr>
+                            <tr>
+                                <td title={tr("reqsettings.no_user_agent_title")}>{tr("reqsettings.no_user_agent")}</td>
+                                <td><input id="reqomituseragent" type="checkbox" class="pointer" checked={request.omit_user_agent} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleOmitUserAgent))}/></td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.retries")}</td>
+                                <td><input id="reqretries" type="number" min="0" max={MAX_RETRIES.to_string()} class="tableinput" value={request.retries.to_string()} onchange={link.callback(|_| Msg::Edit(EditMsg::RetriesChanged))}/></td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.retry_on")}</td>
+                                <td>
+                                    <select id="reqretryon" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::RetryPolicyChanged))}>
+                                        { for (0..RetryPolicy::count()).map(|index| {
+                                            let policy = RetryPolicy::from(index);
+                                            html! {
+                                                <option value={index.to_string()} selected={policy == request.retry_on}>{policy}</option>
+                                            }
+                                        })}
+                                    </select>
+                                </td>
+                            </tr>
+                            <tr>
+                                <td title={tr("reqsettings.transport_title")}>{tr("reqsettings.transport")}</td>
+                                <td>
+                                    <select id="reqtransport" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::TransportChanged))}>
+                                        { for (0..Transport::count()).map(|index| {
+                                            let transport = Transport::from(index);
+                                            html! {
+                                                <option value={index.to_string()} selected={transport == request.transport}>{transport}</option>
+                                            }
+                                        })}
+                                    </select>
+                                </td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.http_version")}</td>
+                                <td>
+                                    <select id="reqhttpversion" class="authselect pointer" onchange={link.callback(|_| Msg::Edit(EditMsg::HttpVersionChanged))}>
+                                        <option value="" selected={request.http_version.is_none()}>{tr("reqsettings.auto")}</option>
+                                        { for (0..HttpVersion::count()).map(|index| {
+                                            let version = HttpVersion::from(index);
+                                            html! {
+                                                <option value={index.to_string()} selected={Some(version) == request.http_version}>{version}</option>
+                                            }
+                                        })}
+                                    </select>
+                                </td>
+                            </tr>
+                            <tr>
+                                <td title={tr("reqsettings.conditional_title")}>{tr("reqsettings.conditional")}</td>
+                                <td><input id="reqconditional" type="checkbox" class="pointer" checked={request.conditional} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleConditional))}/></td>
+                            </tr>
+                            if request.etag.is_some() || request.last_modified.is_some() {
+                                <tr>
+                                    <td>{tr("reqsettings.validators")}</td>
+                                    <td class="capturetitle">
+                                        if let Some(etag) = &request.etag {
+                                            <div>{format!("ETag: {etag}")}</div>
+                                        }
+                                        if let Some(date) = &request.last_modified {
+                                            <div>{format!("Last-Modified: {date}")}</div>
+                                        }
+                                    </td>
+                                </tr>
+                            }
+                            <tr>
+                                <td>{tr("reqsettings.insecure")}</td>
+                                <td><input id="reqinsecure" type="checkbox" class="pointer" checked={request.tls.insecure_skip_verify} onchange={link.callback(|_| Msg::Edit(EditMsg::ToggleInsecureTls))}/></td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.ca_cert")}</td>
+                                <td><input id="reqcacert" type="text" class="tableinput" value={request.tls.ca_cert_path.clone().unwrap_or_default()} placeholder="/path/to/ca.pem" onchange={link.callback(|_| Msg::Edit(EditMsg::CaCertChanged))}/></td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.client_cert")}</td>
+                                <td><input id="reqclientcert" type="text" class="tableinput" value={client_cert} placeholder="/path/to/client.pem" onchange={link.callback(|_| Msg::Edit(EditMsg::ClientCertChanged))}/></td>
+                            </tr>
+                            <tr>
+                                <td>{tr("reqsettings.client_key")}</td>
+                                <td><input id="reqclientkey" type="text" class="tableinput" value={client_key} placeholder="/path/to/client-key.pem" onchange={link.callback(|_| Msg::Edit(EditMsg::ClientCertChanged))}/></td>
                             </tr>
-                            { for request.headers.iter().enumerate().map(|(index, header)| view::header::render_reqheader(bctx, index, request.headers.len(), &header[0], &header[1])) }
                         </table>
                     </div>
                 }
             </div>
+        } else if bctx.page == Page::Home && bctx.main_col.requests.is_empty() {
+            <div class="emptystate">
+                <div class="emptycard">
+                    <div class="emptytitle">{tr("empty.no_requests")}</div>
+                    <button class="sendbtn pointer" type="button" onclick={link.callback(|_| Msg::Collections(CollectionsMsg::AddRequest))}>{tr("empty.create_request")}</button>
+                </div>
+            </div>
+        } else if bctx.page == Page::Collections && bctx.collections.is_empty() {
+            <div class="emptystate">
+                <div class="emptycard">
+                    <div class="emptytitle">{tr("empty.no_collections")}</div>
+                    <button class="sendbtn pointer" type="button" onclick={link.callback(|_| Msg::Collections(CollectionsMsg::AddCollection))}>{tr("empty.create_collection")}</button>
+                </div>
+            </div>
+        } else if let Some(col_index) = empty_collection {
+            <div class="emptystate">
+                <div class="emptycard">
+                    <div class="emptytitle">{tr("empty.empty_collection")}</div>
+                    <button class="sendbtn pointer" type="button" onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::AddToCollection(col_index)))}>{tr("empty.create_request")}</button>
+                </div>
+            </div>
+        } else {
+            <div class="emptystate">{tr("empty.open_request")}</div>
         }
         </div>
 
     }
 }
 
+// the last path component, for showing a body file without its directories
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).find(|part| !part.is_empty()).unwrap_or(path)
+}
+
 fn is_selected(method: &str, option_value: &str) -> bool {
     method.to_lowercase() == option_value.to_lowercase()
 }
 
+fn status_dot_class(point: &StatusPoint) -> String {
+    format!("statusdot {}", status_class(point.status, point.failed))
+}
+
+fn status_dot_title(point: &StatusPoint) -> String {
+    let time = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(point.timestamp))
+        .to_locale_time_string("default")
+        .as_string()
+        .unwrap_or_default();
+
+    if point.failed {
+        format!("{time}: failed")
+    } else {
+        format!("{time}: {}", point.status)
+    }
+}
+
+fn token_status(request: &Request) -> String {
+    let auth = &request.auth;
+
+    if auth.fetching {
+        return tr("auth.fetching");
+    }
+
+    if !auth.token_error.is_empty() {
+        return tr("auth.token_failed");
+    }
+
+    if auth.access_token.is_empty() {
+        return tr("auth.no_token");
+    }
+
+    match auth.expires_at {
+        Some(expires_at) => {
+            let remaining = ((expires_at - js_sys::Date::now()) / 1000.0).floor();
+
+            if remaining > 0.0 {
+                tr_args("auth.token_valid_for", &[&remaining.to_string()])
+            } else {
+                tr("auth.token_expired")
+            }
+        }
+        None => tr("auth.token_valid"),
+    }
+}
+
+// when the request was created, last edited and last sent, leaving out what isn't known
+fn request_meta(request: &Request) -> String {
+    [
+        ("req.created", request.created_at),
+        ("req.modified", request.modified_at),
+        ("req.last_sent", request.last_sent_at),
+    ]
+    .into_iter()
+    .filter_map(|(key, timestamp)| timestamp.map(|timestamp| tr_args(key, &[&format_timestamp(timestamp)])))
+    .collect::<Vec<_>>()
+    .join(" · ")
+}
+
+fn preview_view(preview: &RequestPreview) -> Html {
+    html! {
+        <div class="reqheaders">
+            if !preview.unresolved.is_empty() {
+                <div class="suggestbar">{tr_args("preview.unresolved", &[&preview.unresolved.join(", ")])}</div>
+            }
+
+            <div class="sentline">
+                <span class={format!("methodchip method-{}", preview.method.to_lowercase())}>{preview.method.clone()}</span>
+                <span class="senturl">{preview.url.clone()}</span>
+            </div>
+
+            <table>
+                <tr>
+                    <th>{tr("header.header")}</th>
+                    <th>{tr("header.value")}</th>
+                </tr>
+                { for preview.headers.iter().map(|header| view::header::render_header(&header[0], &header[1])) }
+            </table>
+
+            if !preview.body.is_empty() {
+                <pre class="sentbody">{preview.body.clone()}</pre>
+            }
+
+            <div class="capturetitle">{tr("preview.note")}</div>
+        </div>
+    }
+}
+
 fn is_tab_selected(request_tab: &u8, tab: RequestTabs) -> bool {
     *request_tab == u8::from(tab)
+}
+
+fn request_tab_key(e: &KeyboardEvent, request_tab: u8) -> Msg {
+    let position = RequestTabs::ORDER
+        .iter()
+        .position(|tab| u8::from(*tab) == request_tab)
+        .unwrap_or(0);
+
+    match tab_key_target(&e.key(), position, RequestTabs::ORDER.len(), language().is_rtl()) {
+        Some(target) => {
+            e.prevent_default();
+            Msg::Navigation(NavigationMsg::ReqTabKey(RequestTabs::ORDER[target].into()))
+        }
+        None => Msg::Nothing,
+    }
 }
\ No newline at end of file
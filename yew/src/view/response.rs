@@ -1,72 +1,397 @@
 use crate::view;
 use crate::BoltContext;
+use crate::{NavigationMsg, SendMsg};
 use crate::Msg;
+use crate::helpers::i18n::{language, tr, tr_args};
+use crate::helpers::enums::{BodyFormat, ResponseTabs, ScrollPanel};
+use crate::process::tabs::{secret_values, current_ref, has_current_request};
+use crate::view::components::{sparkline, tab};
 use crate::Page;
 use crate::Request;
-use crate::ResponseType;
-use yew::{html, AttrValue, Html};
+use crate::BoltApp;
+use crate::utils::{effective_format, error_hint, format_duration, format_size, get_input_value, mask_secrets, save_scroll_later, tab_key_target, truncate_body};
+use crate::helpers::schema::Violation;
+use crate::helpers::base64_view::decode_base64_body;
+use crate::helpers::diff::{diff_headers, diff_lines, DiffLine, HeaderChange};
+use crate::{BODY_PREVIEW_LENGTH, LARGE_BODY_LIMIT};
+use yew::html::Scope;
+use yew::{html, AttrValue, Callback, Html, KeyboardEvent};
 
 pub fn response(bctx: &mut BoltContext) -> Html {
    let link = bctx.link.as_ref().unwrap();
 
-    let can_display = (bctx.page == Page::Collections
-        && !bctx.collections.is_empty()
-        && !bctx.collections[bctx.col_current[0]].requests.is_empty())
-        || (bctx.page == Page::Home && !bctx.main_col.requests.is_empty());
+    let can_display = bctx.active_tab.is_some() && has_current_request(bctx);
 
-    let mut request = Request::new();
+    // borrowed, copying the request would copy its response body and the previous one with it
+    let empty = Request::new();
 
-    if bctx.page == Page::Home && can_display {
-        request = bctx.main_col.requests[bctx.main_current].clone();
-    }
+    let request = match bctx.page {
+        Page::Home if can_display => &bctx.main_col.requests[bctx.main_current],
+        Page::Collections if can_display => &bctx.collections[bctx.col_current[0]].requests[bctx.col_current[1]],
+        _ => &empty,
+    };
 
-    if bctx.page == Page::Collections && can_display {
-        request = bctx.collections[bctx.col_current[0]].requests[bctx.col_current[1]].clone();
-    }
+    let truncate_size = bctx.settings.truncate_size;
+    let preview_length = BODY_PREVIEW_LENGTH.min(truncate_size as usize);
+    let is_truncated = request.response.size > truncate_size && !request.response.show_full;
+
+    // the tree parses the body on every render, so large bodies stay raw
+    let format = effective_format(&request.response, request.body_format);
+    let can_show_tree = format == BodyFormat::Json && request.response.size <= LARGE_BODY_LIMIT;
+
+    let response_tab = request.resp_tab;
+    // the TLS tab shows up for responses that came over TLS, or while it is the one open
+    let show_tls_tab = request.response.sent_url.starts_with("https://") || request.resp_tab == 5;
+    let target = current_ref(bctx);
+    let shown_headers = filter_headers(&request.response.headers, &request.resp_header_filter, request.sort_resp_headers);
 
     html! {
     <div class="resp">
         if can_display && !request.response.failed && !request.loading {
             <div class="respline">
-                <div class="resptabs">
-                    <div id="resp_body_tab" class={if request.resp_tab == 1  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::RespBodyPressed)}>{"Body"}</div>
-                    <div id="resp_headers_tab" class={if request.resp_tab == 2  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::RespHeadersPressed)}>{"Headers"}</div>
+                <div class="resptabs" role="tablist" aria-label={tr("a11y.response_tabs")} onkeydown={link.callback(move |e: KeyboardEvent| response_tab_key(&e, response_tab, show_tls_tab))}>
+                    {tab(link, ResponseTabs::Body.element_id(), request.resp_tab == 1, tr("tab.body"), None, Msg::Navigation(NavigationMsg::RespBodyPressed))}
+                    {tab(link, ResponseTabs::Headers.element_id(), request.resp_tab == 2, tr("tab.headers"), None, Msg::Navigation(NavigationMsg::RespHeadersPressed))}
+                    {tab(link, ResponseTabs::Sent.element_id(), request.resp_tab == 3, tr("resp.sent"), Some(tr("resp.sent_title")), Msg::Navigation(NavigationMsg::RespSentPressed))}
+                    {tab(link, ResponseTabs::Raw.element_id(), request.resp_tab == 4, tr("resp.raw_tab"), Some(tr("resp.raw_title")), Msg::Navigation(NavigationMsg::RespRawPressed))}
+                    if show_tls_tab {
+                        {tab(link, ResponseTabs::Tls.element_id(), request.resp_tab == 5, tr("resp.tls_tab"), Some(tr("resp.tls_title")), Msg::Navigation(NavigationMsg::RespTlsPressed))}
+                    }
+                    if can_show_tree && request.resp_tab == 1 && !request.diff_view {
+                        <div id="resp_tree_toggle" class="tab pointer" title={tr("resp.tree_title")} onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ToggleJsonTree))}>{if request.json_tree {tr("resp.raw")} else {tr("resp.tree")}}</div>
+                    }
+                    if request.resp_tab == 1 && !request.diff_view && !is_truncated {
+                        <div id="resp_base64_toggle" class={if request.base64_decode {"tab pointer tabSelected"} else {"tab pointer"}} title={tr("resp.base64_title")} onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ToggleBase64Decode))}>{tr("resp.base64")}</div>
+                    }
+                    if request.resp_tab == 1 && !request.diff_view {
+                        <select id="respformat" class="authselect pointer" title={tr("resp.format_title")} aria-label={tr("resp.format_title")} onchange={link.callback(|_| Msg::Navigation(NavigationMsg::BodyFormatChanged))}>
+                            { for (0..BodyFormat::count()).map(|index| {
+                                let option = BodyFormat::from(index);
+                                html! {
+                                    <option value={index.to_string()} selected={option == request.body_format}>{option}</option>
+                                }
+                            })}
+                        </select>
+                    }
+                    if request.last_response.is_some() {
+                        <div id="resp_diff_toggle" class={if request.diff_view {"tab pointer tabSelected"} else {"tab pointer"}} title={tr("resp.compare_title")} onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ToggleDiffView))}>{tr("resp.compare")}</div>
+                    }
                 </div>
     
                 <div class="respstats">
-                    <div id="status" class="respstat">{"Status: "} {request.response.status}</div>
-                    <div id="time" class="respstat">{"Time: "} {request.response.time} {" ms"}</div>
-                    <div id="size" class="respstat">{"Size: "} {request.response.size} {" B"}</div>
+                    <div id="status" class="respstat">{tr("resp.status")} {request.response.status}</div>
+                    if request.response.time_to_first_byte > 0 {
+                        <div id="ttfb" class="respstat" title={tr("resp.ttfb_title")}>{tr("resp.ttfb")} {format_duration(request.response.time_to_first_byte)}</div>
+                    }
+                    <div id="time" class="respstat" title={tr("resp.time_title")}>{tr("resp.time")} {format_duration(request.response.time)}</div>
+                    {sparkline(&request.time_history)}
+                    <div id="size" class="respstat">{tr("resp.size")} {request.response.size} {" B"}</div>
+                    if !request.response.content_encoding.is_empty() {
+                        <div id="encoding" class={if request.response.double_compressed {"respstat schemafail"} else {"respstat"}} title={if request.response.double_compressed {tr("resp.double_compressed")} else {tr("resp.encoding_title")}}>
+                            {tr_args("resp.encoding", &[&request.response.content_encoding, &format_size(request.response.encoded_size), &format_size(request.response.size)])}
+                        </div>
+                    }
+                    if request.response.via_browser {
+                        <div id="transport" class="respstat" title={tr("resp.via_browser_title")}>{tr("resp.via_browser")}</div>
+                    } else {
+                        <div id="transport" class="respstat" title={tr("resp.via_backend_title")}>{tr("resp.via_backend")}</div>
+                    }
+                    if request.response.attempts > 1 {
+                        <div id="attempts" class="respstat">{tr_args("resp.attempts", &[&request.response.attempts.to_string()])}</div>
+                    }
+                    if let Some(violations) = &request.response.schema_violations {
+                        if violations.is_empty() {
+                            <div id="schemastat" class="respstat schemaok pointer" title={tr("resp.schema_ok_title")} onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ReqTestsPressed))}>{tr("resp.schema_ok")}</div>
+                        } else {
+                            <div id="schemastat" class="respstat schemafail pointer" title={schema_summary(violations)} onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ReqTestsPressed))}>{format!("Schema: {} violations", violations.len())}</div>
+                        }
+                    }
+                    <div id="exporthttp" class="respstat pointer" title={tr("resp.export_http_title")} onclick={link.callback(|_| Msg::Send(SendMsg::ExportHttpFile))}>{tr("resp.export_http")}</div>
                 </div>
             </div>
 
             <div class="tabcontent">
-                if request.resp_tab == 1 {
-                    <div id="respbody" class="respbody" >
-                        if request.response.response_type == ResponseType::JSON {
-                            {Html::from_html_unchecked(AttrValue::from(request.response.body.clone()))}
+                if request.diff_view && request.last_response.is_some() {
+                    {diff_view(request)}
+                } else if request.resp_tab == 1 {
+                    <div id="respbody" class="respbody" onscroll={Callback::from(move |_| if let Some(target) = target { save_scroll_later(target, ScrollPanel::ResponseBody) })}>
+                        if request.response.cached_body {
+                            <div class="resptruncated">{tr("resp.not_modified")}</div>
+                        }
+
+                        if let Some(limit) = request.response.truncated_at {
+                            <div class="resptruncated">{tr_args("resp.cut_off", &[&format_size(limit)])}</div>
+                        }
+
+                        if is_truncated {
+                            <div class="resptruncated">
+                                {tr_args("resp.truncated", &[&(preview_length / 1024).to_string(), &(request.response.size / 1024).to_string()])}
+                                <div class="tab pointer" onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ShowFullBody))}>{tr("resp.show_full")}</div>
+                                <div class="tab pointer" onclick={link.callback(|_| Msg::Send(SendMsg::DownloadBody))}>{tr("resp.download")}</div>
+                            </div>
+
+                            {truncate_body(&request.response.body, preview_length).to_string()}
+                        } else if request.base64_decode {
+                            {match decode_base64_body(&request.response.body) {
+                                Ok(decoded) => html! { {decoded} },
+                                Err(err) => html! { <div class="resptruncated">{tr_args("resp.base64_failed", &[&err])}</div> },
+                            }}
+                        } else if can_show_tree && request.json_tree {
+                            {view::json_tree::json_tree(link, &request.response.body, &request.collapsed_nodes)}
+                        } else if format != BodyFormat::Text && !request.response.highlighted.is_empty() {
+                            {Html::from_html_unchecked(AttrValue::from(request.response.highlighted.clone()))}
                         } else {
                             {request.response.body.clone()}
                         }
                     </div>
                 } else if request.resp_tab == 2 {
                     <div class="respheaders">
+                        <div class="respheaderbar">
+                            <input id="respheaderfilter" class="respheaderfilter" type="text" placeholder={tr("resp.header_filter")} value={request.resp_header_filter.clone()}
+                                oninput={link.callback(|_| Msg::Navigation(NavigationMsg::FilterRespHeaders(get_input_value("respheaderfilter"))))} />
+                            <div class={if request.sort_resp_headers {"tab pointer tabSelected"} else {"tab pointer"}} role="button" tabindex="0" title={tr("resp.sort_headers_title")} onclick={link.callback(|_| Msg::Navigation(NavigationMsg::ToggleSortRespHeaders))}>{tr("resp.sort_headers")}</div>
+                        </div>
                         <table>
                             <tr>
-                                <th>{"Header"}</th>
-                                <th>{"Value"}</th>
+                                <th>{tr("header.header")}</th>
+                                <th>{tr("header.value")}</th>
                             </tr>
-                            { for request.response.headers.iter().map(|header| view::header::render_header(&header[0], &header[1])) }
+                            { for shown_headers.iter().map(|header| view::header::render_header(&header[0], &header[1])) }
                         </table>
+                        if shown_headers.is_empty() && !request.response.headers.is_empty() {
+                            <div class="capturetitle">{tr("resp.no_headers_match")}</div>
+                        }
                     </div>
+                } else if request.resp_tab == 3 {
+                    {sent_view(request, &current_ref(bctx).map(|target| secret_values(bctx, &target)).unwrap_or_default())}
+                } else if request.resp_tab == 4 {
+                    {raw_view(request, &current_ref(bctx).map(|target| secret_values(bctx, &target)).unwrap_or_default())}
+                } else if request.resp_tab == 5 {
+                    {tls_view(link, request)}
                 }
             </div>
         } else if can_display && request.loading { 
             <div class="resploading"><img src="/icon/icon.png" /></div>
         } else if request.response.failed {
-            <div class="resperror">{request.response.body.clone()}</div>
+            <div class="resperror">
+                if let Some(hint) = request.response.error_kind.as_deref().and_then(error_hint) {
+                    <div class="errorhint">{hint}</div>
+                }
+                {request.response.body.clone()}
+                // the certificate the handshake refused is worth a look
+                if request.response.error_kind.as_deref() == Some("TlsHandshake") {
+                    {tls_view(link, request)}
+                }
+            </div>
         }
         
     </div>
     }
 }
+
+// the headers to list, sorting a copy so the arrival order is still there for the raw view
+fn filter_headers<'a>(headers: &'a [Vec<String>], filter: &str, sorted: bool) -> Vec<&'a Vec<String>> {
+    let filter = filter.trim().to_lowercase();
+
+    let mut shown: Vec<&Vec<String>> = headers
+        .iter()
+        .filter(|header| filter.is_empty() || header.iter().any(|part| part.to_lowercase().contains(&filter)))
+        .collect();
+
+    if sorted {
+        // stable, repeated headers like Set-Cookie keep their order
+        shown.sort_by_key(|header| header[0].to_lowercase());
+    }
+
+    shown
+}
+
+fn sent_view(request: &Request, secrets: &[String]) -> Html {
+    let response = &request.response;
+
+    if response.sent_method.is_empty() {
+        return html! {
+            <div class="respheaders sentempty">{tr("resp.sent_empty")}</div>
+        };
+    }
+
+    html! {
+        <div class="respheaders">
+            <div class="sentline">
+                <span class={format!("methodchip method-{}", response.sent_method.to_lowercase())}>{response.sent_method.clone()}</span>
+                <span class="senturl">{mask_secrets(&response.sent_url, secrets)}</span>
+            </div>
+
+            <table>
+                <tr>
+                    <th>{tr("header.header")}</th>
+                    <th>{tr("header.value")}</th>
+                </tr>
+                { for response.sent_headers.iter().map(|header| view::header::render_header(&header[0], &mask_secrets(&header[1], secrets))) }
+            </table>
+
+            if !response.sent_body.is_empty() {
+                <pre class="sentbody">{mask_secrets(&response.sent_body, secrets)}</pre>
+            }
+        </div>
+    }
+}
+
+fn raw_view(request: &Request, secrets: &[String]) -> Html {
+    let response = &request.response;
+
+    if response.raw_response.is_empty() {
+        return html! {
+            <div class="respheaders sentempty">{tr("resp.raw_empty")}</div>
+        };
+    }
+
+    html! {
+        <div class="respheaders">
+            if !response.raw_request.is_empty() {
+                <div class="capturetitle">{tr("resp.raw_request")}</div>
+                <pre class="sentbody rawmessage">{mask_secrets(&response.raw_request, secrets)}</pre>
+            }
+
+            <div class="capturetitle">{tr("resp.raw_response")}</div>
+            <pre class="sentbody rawmessage">{mask_secrets(&response.raw_response, secrets)}</pre>
+        </div>
+    }
+}
+
+// nothing is looked up until asked, the inspection is a connection of its own and not the request's
+fn tls_view(link: &Scope<BoltApp>, request: &Request) -> Html {
+    let inspect = html! {
+        <div class="respheaders">
+            <div class="repeatbtn pointer" role="button" tabindex="0" title={tr("resp.tls_inspect_title")} onclick={link.callback(|_| Msg::Send(SendMsg::InspectTls))}>
+                {if request.inspecting_tls {tr("resp.tls_inspecting")} else {tr("resp.tls_inspect")}}
+            </div>
+            <div class="capturetitle">{tr("resp.tls_note")}</div>
+        </div>
+    };
+
+    let (url, tls) = match &request.tls_inspection {
+        Some((url, Ok(tls))) => (url, tls),
+        Some((url, Err(err))) => {
+            return html! {
+                <>
+                    {inspect}
+                    <div class="respheaders sentempty">{tr_args("resp.tls_failed", &[url, err])}</div>
+                </>
+            };
+        }
+        None => return inspect,
+    };
+
+    let expiry = if tls.days_left < 0 {
+        tr("resp.tls_expired")
+    } else {
+        tr_args("resp.tls_days_left", &[&tls.days_left.to_string()])
+    };
+
+    html! {
+        <>
+            {inspect}
+            <div class="respheaders">
+                <div class="sentline">
+                    <span class="senturl">{url.clone()}</span>
+                </div>
+                <table>
+                    <tr>
+                        <th>{tr("resp.tls_field")}</th>
+                        <th>{tr("header.value")}</th>
+                    </tr>
+                    {view::header::render_header(&tr("resp.tls_version"), &tls.version)}
+                    {view::header::render_header(&tr("resp.tls_cipher"), &tls.cipher)}
+                    {view::header::render_header(&tr("resp.tls_subject"), &tls.subject)}
+                    {view::header::render_header(&tr("resp.tls_issuer"), &tls.issuer)}
+                    {view::header::render_header(&tr("resp.tls_not_before"), &tls.not_before)}
+                    {view::header::render_header(&tr("resp.tls_not_after"), &format!("{} ({})", tls.not_after, expiry))}
+                </table>
+            </div>
+        </>
+    }
+}
+
+// one line per violation, shown when hovering the schema badge
+fn schema_summary(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("{}: {}", if violation.pointer.is_empty() { "/" } else { &violation.pointer }, violation.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn diff_view(request: &Request) -> Html {
+    let previous = request.last_response.as_ref().unwrap();
+    let current = &request.response;
+
+    let header_changes = diff_headers(&previous.headers, &current.headers);
+
+    let body_diff = if previous.size > LARGE_BODY_LIMIT || current.size > LARGE_BODY_LIMIT {
+        None
+    } else {
+        diff_lines(&previous.body, &current.body)
+    };
+
+    html! {
+        <div id="respdiff" class="respbody">
+            <div class="diffsummary">{tr_args("resp.diff_summary", &[&previous.status.to_string(), &previous.size.to_string(), &current.status.to_string(), &current.size.to_string()])}</div>
+
+            if header_changes.is_empty() {
+                <div class="diffsummary">{tr("resp.headers_identical")}</div>
+            } else {
+                <table class="diffheaders">
+                    <tr>
+                        <th>{tr("header.header")}</th>
+                        <th>{tr("resp.previous")}</th>
+                        <th>{tr("resp.current")}</th>
+                    </tr>
+                    { for header_changes.iter().map(|change| match change {
+                        HeaderChange::Added(name, value) => html! {
+                            <tr class="diffadded"><td>{name}</td><td></td><td>{value}</td></tr>
+                        },
+                        HeaderChange::Removed(name, value) => html! {
+                            <tr class="diffremoved"><td>{name}</td><td>{value}</td><td></td></tr>
+                        },
+                        HeaderChange::Changed(name, old, new) => html! {
+                            <tr class="diffchanged"><td>{name}</td><td>{old}</td><td>{new}</td></tr>
+                        },
+                    }) }
+                </table>
+            }
+
+            if previous.body == current.body {
+                <div class="diffsummary">{tr("resp.bodies_identical")}</div>
+            } else if let Some(lines) = body_diff {
+                <div class="difflines">
+                    { for lines.iter().map(|line| match line {
+                        DiffLine::Same(text) => html! { <div class="diffline">{format!("  {text}")}</div> },
+                        DiffLine::Added(text) => html! { <div class="diffline diffadded">{format!("+ {text}")}</div> },
+                        DiffLine::Removed(text) => html! { <div class="diffline diffremoved">{format!("- {text}")}</div> },
+                    }) }
+                </div>
+            } else {
+                <div class="diffsummary">{tr_args("resp.bodies_differ", &[&previous.size.to_string(), &current.size.to_string()])}</div>
+            }
+        </div>
+    }
+}
+
+fn response_tab_key(e: &KeyboardEvent, response_tab: u8, with_tls: bool) -> Msg {
+    let position = ResponseTabs::ORDER
+        .iter()
+        .position(|tab| u8::from(*tab) == response_tab)
+        .unwrap_or(0);
+
+    // Tls is last in ORDER, leaving it off skips the tab when it isn't shown
+    let count = if with_tls { ResponseTabs::ORDER.len() } else { ResponseTabs::ORDER.len() - 1 };
+
+    match tab_key_target(&e.key(), position, count, language().is_rtl()) {
+        Some(target) => {
+            e.prevent_default();
+            Msg::Navigation(NavigationMsg::RespTabKey(ResponseTabs::ORDER[target].into()))
+        }
+        None => Msg::Nothing,
+    }
+}
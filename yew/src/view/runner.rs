@@ -0,0 +1,101 @@
+use crate::process::runner::{in_flight, is_running};
+use crate::view::components::status_class;
+use crate::BoltContext;
+use crate::SendMsg;
+use crate::Msg;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::RunStep;
+use crate::utils::format_duration;
+use yew::{html, Html};
+
+// progress of the collection run, one row per request
+pub fn run_panel(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let run = match &bctx.run {
+        Some(run) => run,
+        None => return html! {},
+    };
+
+    let col = match bctx.collections.iter().find(|col| col.id == run.collection) {
+        Some(col) => col,
+        None => return html! {},
+    };
+
+    let running = is_running(run);
+    let done = run.steps.iter().filter(|step| step.result.is_some()).count();
+    let passed = run
+        .steps
+        .iter()
+        .filter_map(|step| step.result)
+        .filter(|result| !result.failed && result.status < 400 && result.schema_violations == 0)
+        .count();
+
+    let summary = if !running {
+        tr_args("run.passed", &[&col.name, &passed.to_string(), &done.to_string()])
+    } else if run.concurrency > 1 {
+        tr_args(
+            "run.running_parallel",
+            &[&col.name, &run.concurrency.to_string(), &done.to_string(), &run.steps.len().to_string()],
+        )
+    } else {
+        tr_args("run.running", &[&col.name, &done.to_string(), &run.steps.len().to_string()])
+    };
+
+    html! {
+        <div class="runpanel">
+            <div class="runbar">
+                <div class="runsummary">{summary}</div>
+
+                if let Some(finished_at) = run.finished_at {
+                    <div class="runtime">{tr_args("run.total", &[&((finished_at - run.started_at) as u64).to_string()])}</div>
+                }
+
+                if running {
+                    <div class="noticebtn pointer" onclick={link.callback(|_| Msg::Send(SendMsg::StopRun))}>{tr("run.stop")}</div>
+                } else {
+                    <div class="noticebtn pointer" onclick={link.callback(|_| Msg::Send(SendMsg::CloseRun))}>{tr("run.close")}</div>
+                }
+            </div>
+
+            <div class="runsteps">
+                { for run.steps.iter().enumerate().map(|(index, step)| {
+                    let request = col.requests.iter().find(|req| req.id == step.id);
+                    let name = request.map(|req| req.name.clone()).unwrap_or_default();
+                    let method = request.map(|req| req.method.to_string()).unwrap_or_default();
+
+                    html! {
+                        <div class="runstep">
+                            <span class={format!("methodchip method-{}", method.to_lowercase())}>{method}</span>
+                            <span class="reqname">{name}</span>
+                            {step_result(step, in_flight(run, index))}
+                        </div>
+                    }
+                }) }
+            </div>
+        </div>
+    }
+}
+
+fn step_result(step: &RunStep, in_flight: bool) -> Html {
+    let result = match step.result {
+        Some(result) => result,
+        None if in_flight => return html! { <span class="runpending">{tr("run.sending")}</span> },
+        None => return html! { <span class="runpending">{"-"}</span> },
+    };
+
+    html! {
+        <>
+            if result.schema_violations > 0 {
+                <span class="runpending" title={tr("run.schema_errors_title")}>{tr_args("run.schema_errors", &[&result.schema_violations.to_string()])}</span>
+            }
+
+            if result.failed {
+                <span class={format!("statusbadge {}", status_class(result.status, true))}>{"ERR"}</span>
+            } else {
+                <span class="runtime">{format_duration(result.time)}</span>
+                <span class={format!("statusbadge {}", status_class(result.status, false))}>{result.status}</span>
+            }
+        </>
+    }
+}
@@ -0,0 +1,153 @@
+use crate::BoltContext;
+use crate::Msg;
+use crate::helpers::enums::Lang;
+use crate::helpers::enums::RequestOrder;
+use crate::helpers::i18n::{text_direction, tr, tr_args};
+use crate::{MAX_RUN_CONCURRENCY, MAX_STATUS_LOG_LENGTH};
+use crate::view;
+use crate::style::palette::{theme_class, theme_style};
+use yew::{html, Html};
+
+pub fn settings_view(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.clone().unwrap();
+    let settings = bctx.settings.clone();
+
+    html! {
+       <body class={theme_class(bctx.theme)} style={theme_style(bctx.theme)} dir={text_direction()}>
+            {view::navbar::get_navbar(bctx)}
+
+            <div class="main">
+                {view::sidebar1::sidebar(bctx, 2)}
+
+                <div class="content settings">
+                    <h3>{tr("settings.appearance")}</h3>
+
+                    <table>
+                        <tr>
+                            <th>{tr("settings.setting")}</th>
+                            <th>{tr("settings.value")}</th>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.language")}</td>
+                            <td>
+                                <select id="langselect" class="pointer" onchange={link.callback(|_| Msg::LanguageChanged)}>
+                                    { for (0..Lang::count()).map(|index| html! {
+                                        <option value={index.to_string()} selected={Lang::from(index) == settings.language}>{Lang::from(index)}</option>
+                                    }) }
+                                </select>
+                            </td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.collection_order")}</td>
+                            <td>
+                                <select id="collectionorderselect" class="pointer" onchange={link.callback(|_| Msg::CollectionOrderChanged)}>
+                                    { for (0..RequestOrder::count()).map(|index| html! {
+                                        <option value={index.to_string()} selected={RequestOrder::from(index) == settings.collection_order}>{tr(RequestOrder::from(index).label_key())}</option>
+                                    }) }
+                                </select>
+                            </td>
+                        </tr>
+                    </table>
+
+                    <h3>{tr("settings.network")}</h3>
+
+                    <table>
+                        <tr>
+                            <th>{tr("settings.setting")}</th>
+                            <th>{tr("settings.value")}</th>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.proxy")}</td>
+                            <td><input id="proxyinput" type="text" class="tableinput" value={settings.proxy} placeholder="http://, https:// or socks5://" onchange={link.callback(|_| Msg::ProxyChanged)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.no_proxy")}</td>
+                            <td><input id="noproxyinput" type="text" class="tableinput" value={settings.no_proxy.join(", ")} placeholder="localhost, 127.0.0.1, .internal.example.com" onchange={link.callback(|_| Msg::NoProxyChanged)}/></td>
+                        </tr>
+                    </table>
+
+                    <div class="settingshead">
+                        <h3>{tr("settings.requests")}</h3>
+                        <div class="noticebtn pointer" title={tr("settings.restore_title")} onclick={link.callback(|_| Msg::RestoreDefaultSettings)}>{tr("settings.restore")}</div>
+                    </div>
+
+                    <table>
+                        <tr>
+                            <th>{tr("settings.setting")}</th>
+                            <th>{tr("settings.value")}</th>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.timeout")}</td>
+                            <td><input id="timeoutinput" type="number" min="0" class="tableinput" value={settings.timeout_ms.to_string()} onchange={link.callback(|_| Msg::TimeoutChanged)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.follow_redirects")}</td>
+                            <td><input id="followredirects" type="checkbox" class="pointer" checked={settings.follow_redirects} onchange={link.callback(|_| Msg::ToggleFollowRedirects)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.double_click_send")}</td>
+                            <td><input id="doubleclicksend" type="checkbox" class="pointer" checked={settings.double_click_send} onchange={link.callback(|_| Msg::ToggleDoubleClickSend)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{"User-Agent"}</td>
+                            <td><input id="useragentinput" type="text" class="tableinput" value={settings.user_agent} placeholder={tr("settings.user_agent_hint")} onchange={link.callback(|_| Msg::UserAgentChanged)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.run_concurrency")}</td>
+                            <td><input id="runconcurrencyinput" type="number" min="1" max={MAX_RUN_CONCURRENCY.to_string()} class="tableinput" value={settings.run_concurrency.to_string()} onchange={link.callback(|_| Msg::RunConcurrencyChanged)}/></td>
+                        </tr>
+                    </table>
+
+                    <h3>{tr("settings.default_headers")}</h3>
+                    <div class="capturetitle">{tr("settings.default_headers_hint")}</div>
+
+                    <table>
+                        <tr>
+                            <th>{tr("header.header")}</th>
+                            <th>{tr("header.value")}</th>
+                        </tr>
+                        { for settings.default_headers.iter().enumerate().map(|(index, header)| view::header::render_default_header(bctx, index, settings.default_headers.len(), &header[0], &header[1])) }
+                    </table>
+
+                    <h3>{tr("settings.responses")}</h3>
+
+                    <table>
+                        <tr>
+                            <th>{tr("settings.setting")}</th>
+                            <th>{tr("settings.value")}</th>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.status_log")}</td>
+                            <td><input id="statusloginput" type="number" min="1" max={MAX_STATUS_LOG_LENGTH.to_string()} class="tableinput" value={settings.status_log_length.to_string()} onchange={link.callback(|_| Msg::StatusLogLengthChanged)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.truncate")}</td>
+                            <td><input id="truncateinput" type="number" min="1" class="tableinput" value={(settings.truncate_size / 1024).to_string()} onchange={link.callback(|_| Msg::TruncateSizeChanged)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.max_response")}</td>
+                            <td><input id="maxresponseinput" type="number" min="0" class="tableinput" title={tr("settings.max_response_title")} value={(settings.max_response_size / (1024 * 1024)).to_string()} onchange={link.callback(|_| Msg::MaxResponseSizeChanged)}/></td>
+                        </tr>
+                    </table>
+
+                    <h3>{tr("settings.storage")}</h3>
+
+                    <table>
+                        <tr>
+                            <th>{tr("settings.setting")}</th>
+                            <th>{tr("settings.value")}</th>
+                        </tr>
+                        <tr>
+                            <td>{tr("settings.persist")}</td>
+                            <td><input id="persistresponses" type="checkbox" class="pointer" checked={settings.persist_responses} onchange={link.callback(|_| Msg::TogglePersistResponses)}/></td>
+                        </tr>
+                        <tr>
+                            <td>{tr_args("settings.recent_urls", &[&bctx.recent_urls.len().to_string()])}</td>
+                            <td><div class="noticebtn pointer" onclick={link.callback(|_| Msg::ClearRecentUrls)}>{tr("settings.clear_recent_urls")}</div></td>
+                        </tr>
+                    </table>
+                </div>
+            </div>
+       </body>
+    }
+}
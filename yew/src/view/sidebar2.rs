@@ -1,109 +1,418 @@
 use crate::BoltContext;
 use crate::Collection;
+use crate::Folder;
+use crate::process::folders::shown_in;
+use crate::{CollectionsMsg, NavigationMsg, SendMsg};
 use crate::Msg;
 use crate::Request;
-use yew::{html, Html};
+use crate::helpers::enums::DeleteTarget;
+use crate::helpers::i18n::{tr, tr_args};
+use crate::view::components::request_row;
+use crate::helpers::enums::SidebarSort;
+use crate::utils::{all_tags, ask_confirm, ask_text, group_requests, list_key_target, matches_tag_filter, reveal_element, sort_requests};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlSelectElement};
+use yew::{html, Event, Html, KeyboardEvent, MouseEvent, TargetCast};
 
 pub fn sidebar_requests(bctx: &mut BoltContext) -> Html {
     let link = bctx.link.as_ref().unwrap();
 
+    // indices stay those of main_col so selecting and deleting work the same in both sections
+    let (mut pinned, others): (Vec<_>, Vec<_>) = bctx
+        .main_col
+        .requests
+        .iter()
+        .enumerate()
+        .filter(|(_, req)| matches_tag_filter(&bctx.tag_filter, req))
+        .partition(|(_, req)| req.pinned);
+
+    let base_url = bctx.main_col.base_url.clone();
+
+    // pinned ones stay a single section, only the rest is grouped
+    sort_requests(&mut pinned, bctx.sidebar_sort, base_url.as_deref());
+    let groups = group_requests(others, bctx.sidebar_sort, base_url.as_deref());
+
+    // the arrow keys walk the rows as they are shown, not as they are stored
+    let shown: Vec<(usize, u64)> = pinned
+        .iter()
+        .chain(groups.iter().flat_map(|(_, requests)| requests.iter()))
+        .map(|(index, req)| (*index, req.id))
+        .collect();
+    let current = bctx.main_current;
+
     html! {
         <div class="sidebar2">
             <div>
-                <div class="pointer" onclick={link.callback(|_| Msg::AddRequest)}>
+                <div class="pointer" role="button" tabindex="0" title={tr("sidebar.new_request")} aria-label={tr("sidebar.new_request")} onclick={link.callback(|_| Msg::Collections(CollectionsMsg::AddRequest))}>
                     <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
                 </div>
             </div>
 
-            { for bctx.main_col.requests.iter().enumerate().map(|(index, req)| render_request(&mut bctx.clone(), bctx.main_current, index, req))}
+            {tag_filter(bctx, all_tags(bctx.main_col.requests.iter()))}
+            {sort_picker(bctx)}
+
+            <div id="sidebarlist" class="sidebarlist" tabindex="0" role="listbox" aria-label={tr("a11y.request_list")} onkeydown={link.callback(move |e: KeyboardEvent| sidebar_list_key(&e, &shown, current))}>
+            if !pinned.is_empty() {
+                <div class="sidebarsection">{tr("sidebar.pinned")}</div>
+                { for pinned.iter().map(|(index, req)| render_request(bctx, bctx.main_current, *index, req))}
+
+                <div class="sidebarsection">{tr("sidebar.requests")}</div>
+            }
+
+            { for groups.iter().map(|(heading, requests)| html! {
+                <>
+                if bctx.sidebar_sort.groups() {
+                    <div class="sidebarsection">{heading.clone().unwrap_or_else(|| tr("sidebar.no_host"))}</div>
+                }
+                { for requests.iter().map(|(index, req)| render_request(bctx, bctx.main_current, *index, req))}
+                </>
+            })}
+            </div>
 
         </div>
     }
 }
 
+// only while the list itself has the focus, the pin and delete buttons inside it keep their keys
+fn sidebar_list_key(e: &KeyboardEvent, shown: &[(usize, u64)], current: usize) -> Msg {
+    let on_list = e
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .is_some_and(|target| target.id() == "sidebarlist");
+
+    if !on_list {
+        return Msg::Nothing;
+    }
+
+    if e.key() == "Enter" {
+        e.prevent_default();
+        return Msg::Send(SendMsg::SendPressed);
+    }
+
+    let position = shown.iter().position(|(index, _)| *index == current);
+
+    match list_key_target(&e.key(), position, shown.len()) {
+        Some(target) => {
+            e.prevent_default();
+            let (index, id) = shown[target];
+            reveal_element(&format!("request{}", index));
+
+            Msg::Navigation(NavigationMsg::SelectRequest(id))
+        }
+        None => Msg::Nothing,
+    }
+}
+
 pub fn sidebar_col(bctx: &mut BoltContext) -> Html {
     let link = bctx.link.as_ref().unwrap();
 
     html! {
         <div class="sidebar2">
-            <div>
-                <div class="pointer" onclick={link.callback(|_| Msg::AddCollection)}>
+            <div class="sidebar2actions">
+                <div class="pointer" role="button" tabindex="0" title={tr("sidebar.new_collection")} aria-label={tr("sidebar.new_collection")} onclick={link.callback(|_| Msg::Collections(CollectionsMsg::AddCollection))}>
                     <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
                 </div>
+                <div class="pointer" role="button" tabindex="0" title={tr("sidebar.import_url")} aria-label={tr("sidebar.import_url")} onclick={link.callback(|_| match ask_text(&tr("import.url_prompt"), "") {
+                    Some(url) => Msg::ImportFromUrl(url),
+                    None => Msg::Nothing,
+                })}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="20px" width="20px"><path d="M3.9 12c0-1.71 1.39-3.1 3.1-3.1h4V7H7c-2.76 0-5 2.24-5 5s2.24 5 5 5h4v-1.9H7c-1.71 0-3.1-1.39-3.1-3.1zM8 13h8v-2H8v2zm9-6h-4v1.9h4c1.71 0 3.1 1.39 3.1 3.1s-1.39 3.1-3.1 3.1h-4V17h4c2.76 0 5-2.24 5-5s-2.24-5-5-5z" /></svg>
+                </div>
+                <div class="pointer" role="button" tabindex="0" title={tr("sidebar.import_openapi")} aria-label={tr("sidebar.import_openapi")} onclick={link.callback(|_| Msg::PickOpenApiSpec)}>
+                    <svg stroke="currentColor" fill="currentColor" stroke-width="0" viewBox="0 0 24 24" height="20px" width="20px" xmlns="http://www.w3.org/2000/svg"><path d="M9 16h6v-6h4l-7-7-7 7h4v6zm-4 2h14v2H5v-2z"></path></svg>
+                </div>
             </div>
 
-            { for bctx.collections.iter().enumerate().map(|(index, col)| render_collection(&mut bctx.clone(), index, bctx.col_current.clone(), col))}
+            {tag_filter(bctx, all_tags(bctx.collections.iter().flat_map(|col| col.requests.iter())))}
+            {sort_picker(bctx)}
+
+            { for bctx.collections.iter().enumerate().map(|(index, col)| render_collection(bctx, index, bctx.col_current.clone(), col))}
+
+            {trash_section(bctx)}
+
+        </div>
+    }
+}
+
+// newest first, hidden while the trash is empty
+fn trash_section(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    if bctx.trash.is_empty() {
+        return html! {};
+    }
 
+    html! {
+        <>
+        <div class="sidebarsection trashheading">
+            <div>{tr("trash.title")}</div>
+            <div class="pointer bin-col" role="button" tabindex="0" title={tr("trash.purge")} aria-label={tr("trash.purge")} onclick={link.callback(|_| if ask_confirm(&tr("trash.purge_confirm")) { Msg::Collections(CollectionsMsg::PurgeTrash) } else { Msg::Nothing })}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="15px" width="15px"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+            </div>
         </div>
+        { for bctx.trash.iter().enumerate().rev().map(|(index, item)| html! {
+            <div class="sidebar2item-child trashitem">
+                <div class="reqrow">{item.name().to_string()}</div>
+                <div class="pointer add-col" role="button" tabindex="0" title={tr("trash.restore")} aria-label={tr("trash.restore")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::RestoreFromTrash(index)))}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M13 3a9 9 0 00-9 9H1l3.89 3.89.07.14L9 12H6c0-3.87 3.13-7 7-7s7 3.13 7 7-3.13 7-7 7c-1.93 0-3.68-.79-4.94-2.06l-1.42 1.42A8.954 8.954 0 0013 21a9 9 0 000-18z" /></svg>
+                </div>
+            </div>
+        })}
+        </>
+    }
+}
+
+// picks the tag the list is narrowed to, hidden until a request has a tag
+fn tag_filter(bctx: &BoltContext, mut tags: Vec<String>) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    // the chosen tag stays selectable after its last request lost it
+    if let Some(filter) = &bctx.tag_filter {
+        if !tags.iter().any(|tag| tag.eq_ignore_ascii_case(filter)) {
+            tags.push(filter.clone());
+        }
+    }
+
+    if tags.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <select id="tagfilter" class="tagfilter pointer" aria-label={tr("sidebar.tag_filter")} onchange={link.callback(|_| Msg::TagFilterChanged)}>
+            <option value="" selected={bctx.tag_filter.is_none()}>{tr("sidebar.all_tags")}</option>
+            { for tags.into_iter().map(|tag| html! {
+                <option value={tag.clone()} selected={bctx.tag_filter.as_ref().is_some_and(|filter| filter.eq_ignore_ascii_case(&tag))}>{tag.clone()}</option>
+            }) }
+        </select>
+    }
+}
+
+fn sort_picker(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <select id="sidebarsort" class="tagfilter pointer" aria-label={tr("sidebar.sort")} onchange={link.callback(|e: Event| {
+            let index = e.target_unchecked_into::<HtmlSelectElement>().selected_index();
+
+            match usize::try_from(index) {
+                Ok(index) if index < SidebarSort::count() => Msg::SetSidebarSort(SidebarSort::from(index)),
+                _ => Msg::Nothing,
+            }
+        })}>
+            { for (0..SidebarSort::count()).map(|index| {
+                let sort = SidebarSort::from(index);
+                html! {
+                    <option selected={sort == bctx.sidebar_sort}>{tr(sort.label_key())}</option>
+                }
+            }) }
+        </select>
     }
 }
 
 fn render_collection(
-    bctx: &mut BoltContext,
+    bctx: &BoltContext,
     index: usize,
     current: Vec<usize>,
     col: &Collection,
 ) -> Html {
     let link = bctx.link.as_ref().unwrap();
+    let col_id = col.id;
+    let sort_title = tr_args("sidebar.sort_collection", &[&tr(bctx.settings.collection_order.label_key())]);
 
     html! {
         <>
         <div id={"request".to_string() + &index.to_string()} class="sidebar2item">
 
             if col.collapsed {
-                <div onclick={link.callback(move |_| Msg::ToggleCollapsed(index))} class="col-arrow pointer">{">"}</div>
+                <div onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::ToggleCollapsed(index)))} class="col-arrow pointer" role="button" tabindex="0" aria-label={tr("a11y.toggle_collection")}>{">"}</div>
             } else {
-                <div onclick={link.callback(move |_| Msg::ToggleCollapsed(index))} class="col-arrow pointer">{"⌄"}</div>
+                <div onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::ToggleCollapsed(index)))} class="col-arrow pointer" role="button" tabindex="0" aria-label={tr("a11y.toggle_collection")}>{"⌄"}</div>
             }
 
             <div>{col.name.clone()}</div>
             
             <div class="col-icons">        
-            <div class="pointer add-col" onclick={link.callback(move |_| Msg::AddToCollection(index))}>
+            if col.sample {
+                <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.dismiss_samples")} aria-label={tr("sidebar.dismiss_samples")} onclick={link.callback(|_| Msg::Collections(CollectionsMsg::DismissSamples))}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M19 6.4L17.6 5 12 10.6 6.4 5 5 6.4 10.6 12 5 17.6 6.4 19 12 13.4 17.6 19 19 17.6 13.4 12z" /></svg>
+                </div>
+            }
+
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.run")} aria-label={tr("sidebar.run")} onclick={link.callback(move |_| Msg::Send(SendMsg::RunCollection(index)))}>
+                <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M8 5v14l11-7z" /></svg>
+            </div>
+
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.run_parallel")} aria-label={tr("sidebar.run_parallel")} onclick={link.callback(move |_| Msg::Send(SendMsg::RunCollectionParallel(index)))}>
+                <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M4 5v14l8-7zM12 5v14l8-7z" /></svg>
+            </div>
+
+            if index > 0 {
+                <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.move_up")} aria-label={tr("sidebar.move_up")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::MoveCollection { from: index, to: index - 1 }))}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M12 8l-6 6 1.4 1.4 4.6-4.6 4.6 4.6L18 14z" /></svg>
+                </div>
+            }
+
+            if index + 1 < bctx.collections.len() {
+                <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.move_down")} aria-label={tr("sidebar.move_down")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::MoveCollection { from: index, to: index + 1 }))}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M12 16l6-6-1.4-1.4-4.6 4.6-4.6-4.6L6 10z" /></svg>
+                </div>
+            }
+
+            <div class="pointer add-col" role="button" tabindex="0" title={sort_title.clone()} aria-label={sort_title} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::SortCollection(index)))}>
+                <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M3 18h6v-2H3v2zM3 6v2h18V6H3zm0 7h12v-2H3v2z" /></svg>
+            </div>
+
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.duplicate")} aria-label={tr("sidebar.duplicate")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::DuplicateCollection(index)))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="15px" width="15px"><path d="M832 64H296c-4.4 0-8 3.6-8 8v56c0 4.4 3.6 8 8 8h496v688c0 4.4 3.6 8 8 8h56c4.4 0 8-3.6 8-8V96c0-17.7-14.3-32-32-32zM704 192H192c-17.7 0-32 14.3-32 32v530.7c0 8.5 3.4 16.6 9.4 22.6l173.3 173.3c2.2 2.2 4.7 4 7.4 5.5v1.9h4.2c3.5 1.3 7.2 2 11 2H704c17.7 0 32-14.3 32-32V224c0-17.7-14.3-32-32-32zM350 856.2L263.9 770H350v86.2zM664 888H414V746c0-22.1-17.9-40-40-40H232V264h432v624z" /></svg>
+            </div>
+
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.add_folder")} aria-label={tr("sidebar.add_folder")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::AddFolder(index, None)))}>
+                {folder_icon()}
+            </div>
+
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.add_request")} aria-label={tr("sidebar.add_request")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::AddToCollection(index)))}>
                 <svg viewBox="0 0 1024 1024" fill="currentColor" height="15px" width="15px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
             </div>
 
-            <div class="pointer bin-col" onclick={link.callback(move |_| Msg::RemoveCollection(index))}>
+            <div class="pointer bin-col" role="button" tabindex="0" aria-label={tr("a11y.delete_collection")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::AskDelete(DeleteTarget::Collection(col_id))))}>
                 <svg viewBox="0 0 1024 1024" fill="currentColor" height="15px" width="15px"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
             </div>
             </div>
         </div>
         if !col.collapsed {
-            { for col.requests.iter().enumerate().map(|(req_index, req)| render_col_request(bctx, req_index, index, current.clone(), req))}
+            {render_folder_items(bctx, index, None, 0, &current, col)}
         }
         
         </>
     }
 }
 
-fn render_request(bctx: &mut BoltContext, current: usize, index: usize, req: &Request) -> Html {
+fn render_request(bctx: &BoltContext, current: usize, index: usize, req: &Request) -> Html {
     let link = bctx.link.as_ref().unwrap();
+    let id = req.id;
 
     html! {
-        <div onclick={link.callback(move |_| Msg::SelectRequest(index))} id={"request".to_string() + &index.to_string()} class={if index == current { "pointer sidebar2item sidebar2item-selected" } else { "pointer sidebar2item" }} >
-            <div>{req.name.clone()}</div>
-            <div class="pointer bin-req" onclick={link.callback(move |_| Msg::RemoveRequest(index))}>
+        <div onclick={link.callback(move |_| Msg::Navigation(NavigationMsg::SelectRequest(id)))} ondblclick={link.callback(move |_| Msg::Send(SendMsg::SendFromSidebar(None, id)))} id={"request".to_string() + &index.to_string()} role="option" aria-selected={(index == current).to_string()} class={if index == current { "pointer sidebar2item sidebar2item-selected" } else { "pointer sidebar2item" }} >
+            <div class="reqrow">{request_row(req)}</div>
+            <div class="req-icons">
+            <div class={if req.pinned { "pointer pin-req pinned" } else { "pointer pin-req" }} role="button" tabindex="0" title={if req.pinned { tr("sidebar.unpin") } else { tr("sidebar.pin") }} aria-label={if req.pinned { tr("sidebar.unpin") } else { tr("sidebar.pin") }} onclick={link.callback(move |e: MouseEvent| { e.stop_propagation(); Msg::Collections(CollectionsMsg::TogglePin(id)) })}>
+                <svg viewBox="0 0 24 24" fill="currentColor" height="1em" width="1em"><path d="M12 17.27L18.18 21l-1.64-7.03L22 9.24l-7.19-.61L12 2 9.19 8.63 2 9.24l5.46 4.73L5.82 21z" /></svg>
+            </div>
+            <div class="pointer bin-req" role="button" tabindex="0" aria-label={tr("a11y.delete_request")} onclick={link.callback(move |e: MouseEvent| { e.stop_propagation(); Msg::Collections(CollectionsMsg::AskDelete(DeleteTarget::Request(id))) })}>
                 <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
             </div>
+            </div>
         </div>
     }
 }
 
+// folders first, then requests, each folder followed by its own contents one level deeper
+fn render_folder_items(
+    bctx: &BoltContext,
+    col_index: usize,
+    parent: Option<u64>,
+    depth: usize,
+    current: &[usize],
+    col: &Collection,
+) -> Html {
+    // a folder whose parent chain loops is never reached from the top, this keeps a corrupt file from recursing forever
+    if depth > col.folders.len() {
+        return html! {};
+    }
+
+    let folders = col
+        .folders
+        .iter()
+        .filter(|folder| shown_in(col, folder.parent) == parent && Some(folder.id) != parent);
+
+    let tag_filter = bctx.tag_filter.clone();
+
+    let mut requests: Vec<_> = col
+        .requests
+        .iter()
+        .enumerate()
+        .filter(|(_, req)| shown_in(col, req.folder) == parent)
+        .filter(|(_, req)| matches_tag_filter(&tag_filter, req))
+        .collect();
+
+    // inside a collection the folders already group, so the order applies without headings
+    sort_requests(&mut requests, bctx.sidebar_sort, col.base_url.as_deref());
+
+    html! {
+        <>
+        { for folders.map(|folder| html! {
+            <>
+            {render_folder(bctx, col_index, depth, folder)}
+            if !folder.collapsed {
+                {render_folder_items(bctx, col_index, Some(folder.id), depth + 1, current, col)}
+            }
+            </>
+        })}
+        { for requests.into_iter().map(|(req_index, req)| render_col_request(bctx, req_index, col_index, col.id, depth, current, req))}
+        </>
+    }
+}
+
+fn render_folder(bctx: &BoltContext, col_index: usize, depth: usize, folder: &Folder) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+    let id = folder.id;
+
+    html! {
+        <div class="sidebar2item-child sidebarfolder" style={indent(depth)}>
+            <div class="sidebarfoldername pointer" onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::ToggleFolderCollapsed(col_index, id)))}>
+                <div class="col-arrow">{if folder.collapsed {">"} else {"⌄"}}</div>
+                {folder_icon()}
+                <div>{folder.name.clone()}</div>
+            </div>
+
+            <div class="req-icons">
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.add_subfolder")} aria-label={tr("sidebar.add_subfolder")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::AddFolder(col_index, Some(id))))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em" ><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+            </div>
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.rename")} aria-label={tr("sidebar.rename")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::RenameFolder(col_index, id)))}>
+                <svg viewBox="0 0 24 24" fill="currentColor" height="1em" width="1em"><path d="M3 17.25V21h3.75L17.81 9.94l-3.75-3.75L3 17.25zM20.71 7.04a1 1 0 000-1.41l-2.34-2.34a1 1 0 00-1.41 0l-1.83 1.83 3.75 3.75 1.83-1.83z" /></svg>
+            </div>
+            <div class="pointer bin-req" role="button" tabindex="0" title={tr("sidebar.remove_folder")} aria-label={tr("sidebar.remove_folder")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::RemoveFolder(col_index, id)))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+            </div>
+            </div>
+        </div>
+    }
+}
+
+fn folder_icon() -> Html {
+    html! {
+        <svg viewBox="0 0 24 24" fill="currentColor" height="1em" width="1em"><path d="M10 4H4c-1.1 0-2 .9-2 2v12c0 1.1.9 2 2 2h16c1.1 0 2-.9 2-2V8c0-1.1-.9-2-2-2h-8l-2-2z" /></svg>
+    }
+}
+
+fn indent(depth: usize) -> String {
+    format!("margin-left: {}px;", 15 + depth * 12)
+}
+
 fn render_col_request(
-    bctx: &mut BoltContext,
+    bctx: &BoltContext,
     req_index: usize,
     col_index: usize,
-    current: Vec<usize>,
+    col_id: u64,
+    depth: usize,
+    current: &[usize],
     req: &Request,
 ) -> Html {
     let link = bctx.link.as_ref().unwrap();
+    let req_id = req.id;
 
     html! {
-        <div id={"request".to_string() + &req_index.to_string()} class={if col_index == current[0] && req_index == current[1] { "sidebar2item-child sidebar2item-selected" } else { "sidebar2item-child" }} >
-            <div class="pointer" onclick={link.callback(move |_| Msg::SelectFromCollection(col_index, req_index))}>{req.name.clone()}</div>
-            <div class="pointer bin-req" onclick={link.callback(move |_| Msg::RemoveFromCollection(col_index, req_index))}>
+        <div id={"request".to_string() + &req_index.to_string()} style={indent(depth)} class={if col_index == current[0] && req_index == current[1] { "sidebar2item-child sidebar2item-selected" } else { "sidebar2item-child" }} >
+            <div class="pointer reqrow" onclick={link.callback(move |_| Msg::Navigation(NavigationMsg::SelectFromCollection(col_id, req_id)))} ondblclick={link.callback(move |_| Msg::Send(SendMsg::SendFromSidebar(Some(col_id), req_id)))}>{request_row(req)}</div>
+            <div class="req-icons">
+            <div class="pointer add-col" role="button" tabindex="0" title={tr("sidebar.move_folder")} aria-label={tr("sidebar.move_folder")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::MoveIntoFolder(col_index, req_index)))}>
+                {folder_icon()}
+            </div>
+            <div class="pointer bin-req" role="button" tabindex="0" aria-label={tr("a11y.delete_request")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::AskDelete(DeleteTarget::CollectionRequest(col_id, req_id))))}>
                 <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
             </div>
+            </div>
         </div>
     }
 }
@@ -0,0 +1,35 @@
+use crate::BoltContext;
+use crate::NavigationMsg;
+use crate::Msg;
+use crate::helpers::i18n::tr;
+use crate::process::tabs::{find_request, request_ref};
+use yew::{html, Html, MouseEvent};
+
+fn tab_name(bctx: &BoltContext, tab: u64) -> String {
+    let request = find_request(bctx, tab).and_then(|tab| request_ref(bctx, &tab));
+
+    match request {
+        Some(request) if !request.name.is_empty() => request.name.clone(),
+        Some(_) => tr("name.untitled"),
+        None => String::new(),
+    }
+}
+
+pub fn tab_strip(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="requesttabs">
+            { for bctx.open_tabs.iter().enumerate().map(|(index, tab)| html! {
+                <div
+                    class={if bctx.active_tab == Some(index) {"requesttab pointer requesttab-selected"} else {"requesttab pointer"}}
+                    onclick={link.callback(move |_| Msg::Navigation(NavigationMsg::SelectTab(index)))}
+                    onmousedown={link.callback(move |e: MouseEvent| if e.button() == 1 { Msg::Navigation(NavigationMsg::CloseTab(index)) } else { Msg::Nothing })}
+                >
+                    <div class="requesttab-name">{tab_name(bctx, *tab)}</div>
+                    <div class="requesttab-close" role="button" tabindex="0" aria-label={tr("a11y.close_tab")} onclick={link.callback(move |e: MouseEvent| { e.stop_propagation(); Msg::Navigation(NavigationMsg::CloseTab(index)) })}>{"×"}</div>
+                </div>
+            })}
+        </div>
+    }
+}
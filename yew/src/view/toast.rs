@@ -0,0 +1,36 @@
+use crate::helpers::i18n::tr;
+use crate::BoltContext;
+use crate::Msg;
+use crate::Toast;
+use yew::{html, Html};
+
+fn toast_time(toast: &Toast) -> String {
+    js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(toast.created))
+        .to_locale_time_string("default")
+        .as_string()
+        .unwrap_or_default()
+}
+
+// drawn over whatever page is open, newest at the bottom
+pub fn toast_stack(bctx: &BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    if bctx.toasts.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="toasts" role="status">
+            { for bctx.toasts.iter().map(|toast| {
+                let id = toast.id;
+
+                html! {
+                    <div class={toast.level.class()} title={toast_time(toast)}>
+                        <div class="toasttext">{toast.text.clone()}</div>
+                        <div class="toastclose pointer" role="button" tabindex="0" title={tr("toast.dismiss")} onclick={link.callback(move |_| Msg::DismissToast(id))}>{"✕"}</div>
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
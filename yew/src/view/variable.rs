@@ -0,0 +1,67 @@
+use crate::BoltContext;
+use crate::{CollectionsMsg, EditMsg};
+use crate::Msg;
+use crate::helpers::i18n::tr;
+use yew::{html, Html};
+
+pub fn render_local_variable(
+    bctx: &BoltContext,
+    index: usize,
+    length: usize,
+    name: &String,
+    value: &String,
+) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <tr>
+            <td><input id={"localvarname".to_string() + &index.to_string()} type="text" class="tableinput" value={name.to_string()} placeholder="token" onchange={link.callback(move |_| Msg::Edit(EditMsg::LocalVariableChanged(index)))}/></td>
+            <td class="tableline">
+                <input id={"localvarvalue".to_string() + &index.to_string()} type="text" class="tableinput" value={value.to_string()} onchange={link.callback(move |_| Msg::Edit(EditMsg::LocalVariableChanged(index)))}/>
+                if index == length - 1 {
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.add_variable")} onclick={link.callback(|_| Msg::Edit(EditMsg::AddLocalVariable))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                    </div>
+                } else {
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.remove_variable")} onclick={link.callback(move |_| Msg::Edit(EditMsg::RemoveLocalVariable(index)))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                    </div>
+                }
+            </td>
+        </tr>
+    }
+}
+
+pub fn render_collection_variable(
+    bctx: &BoltContext,
+    index: usize,
+    length: usize,
+    name: &String,
+    value: &String,
+    secret: bool,
+) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let masked = secret && !bctx.reveal_secrets;
+
+    html! {
+        <tr>
+            <td><input id={"colvarname".to_string() + &index.to_string()} type="text" class="tableinput" value={name.to_string()} placeholder="token" onchange={link.callback(move |_| Msg::Collections(CollectionsMsg::CollectionVariableChanged(index)))}/></td>
+            <td class="tableline">
+                <input id={"colvarvalue".to_string() + &index.to_string()} type={if masked { "password" } else { "text" }} class="tableinput" value={value.to_string()} onchange={link.callback(move |_| Msg::Collections(CollectionsMsg::CollectionVariableChanged(index)))}/>
+                <div class={if secret { "pointer secretflag active" } else { "pointer secretflag" }} role="button" tabindex="0" title={if secret { tr("secret.plain_variable") } else { tr("secret.mark") }} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::ToggleSecretVariable(index)))}>
+                    <svg viewBox="0 0 24 24" fill="currentColor" height="1em" width="1em"><path d="M12 1a5 5 0 00-5 5v4H6a2 2 0 00-2 2v9a2 2 0 002 2h12a2 2 0 002-2v-9a2 2 0 00-2-2h-1V6a5 5 0 00-5-5zm-3 9V6a3 3 0 116 0v4H9z" /></svg>
+                </div>
+                if index == length - 1 {
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.add_variable")} onclick={link.callback(|_| Msg::Collections(CollectionsMsg::AddCollectionVariable))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                    </div>
+                } else {
+                    <div class="pointer" role="button" tabindex="0" aria-label={tr("a11y.remove_variable")} onclick={link.callback(move |_| Msg::Collections(CollectionsMsg::RemoveCollectionVariable(index)))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                    </div>
+                }
+            </td>
+        </tr>
+    }
+}